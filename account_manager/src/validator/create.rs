@@ -10,7 +10,7 @@ use eth2_wallet_manager::WalletManager;
 use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
-use types::EthSpec;
+use types::{Address, EthSpec};
 use validator_dir::Builder as ValidatorDirBuilder;
 
 pub const CMD: &str = "create";
@@ -19,6 +19,7 @@ pub const WALLET_NAME_FLAG: &str = "wallet-name";
 pub const WALLET_PASSWORD_FLAG: &str = "wallet-password";
 pub const DEPOSIT_GWEI_FLAG: &str = "deposit-gwei";
 pub const STORE_WITHDRAW_FLAG: &str = "store-withdrawal-keystore";
+pub const ETH1_WITHDRAWAL_ADDRESS_FLAG: &str = "eth1-withdrawal-address";
 pub const COUNT_FLAG: &str = "count";
 pub const AT_MOST_FLAG: &str = "at-most";
 pub const WALLET_PASSWORD_PROMPT: &str = "Enter your wallet's password:";
@@ -82,6 +83,18 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     instead generate them from the wallet seed when required.",
                 ),
         )
+        .arg(
+            Arg::with_name(ETH1_WITHDRAWAL_ADDRESS_FLAG)
+                .long(ETH1_WITHDRAWAL_ADDRESS_FLAG)
+                .value_name("ETH1_ADDRESS")
+                .help(
+                    "If present, the deposit data will use eth1 address (0x01) withdrawal \
+                    credentials for this address, instead of BLS (0x00) withdrawal credentials \
+                    from the withdrawal keypair. No withdrawal keystore is needed or stored.",
+                )
+                .conflicts_with(STORE_WITHDRAW_FLAG)
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name(COUNT_FLAG)
                 .long(COUNT_FLAG)
@@ -132,6 +145,8 @@ pub fn cli_run<T: EthSpec>(
         .unwrap_or_else(|| spec.max_effective_balance);
     let count: Option<usize> = clap_utils::parse_optional(matches, COUNT_FLAG)?;
     let at_most: Option<usize> = clap_utils::parse_optional(matches, AT_MOST_FLAG)?;
+    let eth1_withdrawal_address: Option<Address> =
+        clap_utils::parse_optional(matches, ETH1_WITHDRAWAL_ADDRESS_FLAG)?;
 
     ensure_dir_exists(&validator_dir)?;
     ensure_dir_exists(&secrets_dir)?;
@@ -186,11 +201,22 @@ pub fn cli_run<T: EthSpec>(
 
         let voting_pubkey = keystores.voting.pubkey().to_string();
 
-        ValidatorDirBuilder::new(validator_dir.clone(), secrets_dir.clone())
+        let builder = ValidatorDirBuilder::new(validator_dir.clone(), secrets_dir.clone())
             .voting_keystore(keystores.voting, voting_password.as_bytes())
-            .withdrawal_keystore(keystores.withdrawal, withdrawal_password.as_bytes())
-            .create_eth1_tx_data(deposit_gwei, &spec)
-            .store_withdrawal_keystore(matches.is_present(STORE_WITHDRAW_FLAG))
+            .create_eth1_tx_data(deposit_gwei, &spec);
+
+        // The withdrawal keystore is only required for BLS withdrawal credentials.
+        let builder = if let Some(address) = eth1_withdrawal_address {
+            builder
+                .eth1_withdrawal_address(address)
+                .store_withdrawal_keystore(false)
+        } else {
+            builder
+                .withdrawal_keystore(keystores.withdrawal, withdrawal_password.as_bytes())
+                .store_withdrawal_keystore(matches.is_present(STORE_WITHDRAW_FLAG))
+        };
+
+        builder
             .build()
             .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
 
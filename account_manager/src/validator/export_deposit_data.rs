@@ -0,0 +1,146 @@
+use crate::VALIDATOR_DIR_FLAG;
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use types::EthSpec;
+use validator_dir::{deposit_data_json::write_deposit_data_json, Manager as ValidatorManager};
+
+pub const CMD: &str = "export-deposit-data";
+pub const VALIDATOR_FLAG: &str = "validator";
+pub const OUTPUT_FLAG: &str = "output";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Exports the deposits of validators to a JSON file in the deposit_data-*.json \
+            format used by the staking launchpad, so that the deposits can be submitted via the \
+            launchpad rather than by Lighthouse. The validators must already have been created \
+            with deposit data. The fork version in the file is determined by the --testnet-dir \
+            flag on the primary Lighthouse binary.",
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path to the validator client data directory. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_FLAG)
+                .long(VALIDATOR_FLAG)
+                .value_name("VALIDATOR_NAME")
+                .help(
+                    "The name of the directory in --validator-dir to export. \
+                    Set to 'all' to export all validators in the --validator-dir.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_FLAG)
+                .long(OUTPUT_FLAG)
+                .value_name("OUTPUT_PATH")
+                .help(
+                    "The file to write the deposits to. Must not already exist. \
+                    Defaults to deposit_data-<UNIX_TIMESTAMP>.json in the current directory.",
+                )
+                .takes_value(true),
+        )
+}
+
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches<'_>, env: Environment<T>) -> Result<(), String> {
+    let spec = &env.eth2_config.spec;
+
+    let data_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let validator: String = clap_utils::parse_required(matches, VALIDATOR_FLAG)?;
+    let output: PathBuf = match clap_utils::parse_optional(matches, OUTPUT_FLAG)? {
+        Some(output) => output,
+        None => {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|e| format!("System clock is before the unix epoch: {:?}", e))?
+                .as_secs();
+            PathBuf::from(format!("deposit_data-{}.json", timestamp))
+        }
+    };
+
+    let manager = ValidatorManager::open(&data_dir)
+        .map_err(|e| format!("Unable to read --{}: {:?}", VALIDATOR_DIR_FLAG, e))?;
+
+    let validators = match validator.as_ref() {
+        "all" => manager
+            .open_all_validators()
+            .map_err(|e| format!("Unable to read all validators: {:?}", e)),
+        name => {
+            let path = manager
+                .directory_names()
+                .map_err(|e| {
+                    format!(
+                        "Unable to read --{} directory names: {:?}",
+                        VALIDATOR_DIR_FLAG, e
+                    )
+                })?
+                .get(name)
+                .ok_or_else(|| format!("Unknown validator:  {}", name))?
+                .clone();
+
+            manager
+                .open_validator(&path)
+                .map_err(|e| format!("Unable to open {}: {:?}", name, e))
+                .map(|v| vec![v])
+        }
+    }?;
+
+    let deposits = validators
+        .iter()
+        .map(|v| match v.deposit_data_json(spec) {
+            Ok(Some(deposit)) => Ok(deposit),
+            Ok(None) => Err(format!(
+                "Validator is missing deposit data file: {:?}",
+                v.dir()
+            )),
+            Err(e) => Err(format!(
+                "Unable to read deposit data for {:?}: {:?}",
+                v.dir(),
+                e
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for deposit in &deposits {
+        deposit.verify(spec).map_err(|e| {
+            format!(
+                "Deposit for {:?} fails verification, \
+                 are you using the correct testnet configuration?\nError: {:?}",
+                deposit.pubkey, e
+            )
+        })?;
+    }
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&output)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", output, e))?;
+
+    let mut writer = BufWriter::new(file);
+    write_deposit_data_json(&mut writer, &deposits)
+        .map_err(|e| format!("Unable to write {:?}: {:?}", output, e))?;
+    writer
+        .flush()
+        .map_err(|e| format!("Unable to write {:?}: {:?}", output, e))?;
+
+    eprintln!("Exported {} deposit(s) to {:?}", deposits.len(), output);
+
+    Ok(())
+}
@@ -141,7 +141,8 @@ pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
         eprintln!("");
         eprintln!(
             "If you enter the password it will be stored as plain-text in {} so that it is not \
-             required each time the validator client starts.",
+             required each time the validator client starts. Otherwise, the validator client \
+             must be started with --prompt-for-passwords to request it on each start.",
             CONFIG_FILENAME
         );
 
@@ -0,0 +1,21 @@
+pub mod rekey;
+
+use clap::{App, ArgMatches};
+
+pub const CMD: &str = "keystore";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about("Manages the EIP-2335 voting keystores of existing validators.")
+        .subcommand(rekey::cli_app())
+}
+
+pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+    match matches.subcommand() {
+        (rekey::CMD, Some(matches)) => rekey::cli_run(matches),
+        (unknown, _) => Err(format!(
+            "{} does not have a {} command. See --help",
+            CMD, unknown
+        )),
+    }
+}
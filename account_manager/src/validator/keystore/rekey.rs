@@ -0,0 +1,359 @@
+use crate::wallet::create::STDIN_INPUTS_FLAG;
+use crate::{SECRETS_DIR_FLAG, VALIDATOR_DIR_FLAG};
+use account_utils::{
+    create_with_600_perms,
+    eth2_keystore::{default_kdf, default_pbkdf2_kdf, Keystore, SALT_SIZE},
+    random_password, read_password, read_password_from_user,
+    validator_definitions::{SigningDefinition, ValidatorDefinitions, CONFIG_FILENAME},
+    PlainText, ZeroizeString,
+};
+use clap::{App, Arg, ArgMatches};
+use rand::prelude::*;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use validator_dir::VOTING_KEYSTORE_FILE;
+
+pub const CMD: &str = "rekey";
+pub const PUBKEY_FLAG: &str = "pubkey";
+pub const NEW_PASSWORD_FLAG: &str = "new-password-file";
+pub const KDF_FLAG: &str = "kdf";
+pub const SCRYPT_KDF: &str = "scrypt";
+pub const PBKDF2_KDF: &str = "pbkdf2";
+
+pub const PASSWORD_PROMPT: &str = "Enter the current keystore password:";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Re-encrypts the voting keystore of a validator with a new password and key \
+            derivation function. The UUID, path and public key of the keystore are preserved. \
+            The keystore is replaced and the password is updated wherever it was stored (a \
+            password file or validator_definitions.yml). The validator client must not be \
+            running for this validator.",
+        )
+        .arg(
+            Arg::with_name(PUBKEY_FLAG)
+                .long(PUBKEY_FLAG)
+                .value_name("PUBKEY")
+                .help("The 0x-prefixed voting public key of the validator to rekey.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(NEW_PASSWORD_FLAG)
+                .long(NEW_PASSWORD_FLAG)
+                .value_name("PASSWORD_PATH")
+                .help(
+                    "A path to a file containing the new keystore password. If not supplied, a \
+                    random password will be generated. Must be supplied if the current password \
+                    is not stored on disk, since the new password will not be stored either.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(KDF_FLAG)
+                .long(KDF_FLAG)
+                .value_name("KDF")
+                .help("The key derivation function to use for the new keystore.")
+                .takes_value(true)
+                .possible_values(&[SCRYPT_KDF, PBKDF2_KDF])
+                .default_value(SCRYPT_KDF),
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path to the validator client data directory. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(SECRETS_DIR_FLAG)
+                .long(SECRETS_DIR_FLAG)
+                .value_name("SECRETS_DIR")
+                .help(
+                    "The path where the validator keystore passwords are stored. \
+                    Defaults to ~/.lighthouse/secrets",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+/// Where the password for a voting keystore is stored.
+enum PasswordStore {
+    /// In a plain-text file (e.g., in the `--secrets-dir`).
+    File(PathBuf),
+    /// In the `voting_keystore_password` of the validator definition at this index.
+    Definition(usize),
+    /// The password is not stored, it is provided by the user each time it is required.
+    None,
+}
+
+pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+    let validator_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let secrets_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        SECRETS_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("secrets"),
+    )?;
+    let pubkey: String = clap_utils::parse_required(matches, PUBKEY_FLAG)?;
+    let new_password_path: Option<PathBuf> =
+        clap_utils::parse_optional(matches, NEW_PASSWORD_FLAG)?;
+    let kdf_name: String = clap_utils::parse_required(matches, KDF_FLAG)?;
+    let stdin_inputs = matches.is_present(STDIN_INPUTS_FLAG);
+
+    let pubkey = format!("0x{}", pubkey.trim_start_matches("0x").to_lowercase());
+
+    // Validators that have been imported are listed in the definitions file, however validators
+    // created by Lighthouse are not listed until they are discovered by the validator client.
+    let mut defs = if validator_dir.join(CONFIG_FILENAME).exists() {
+        Some(
+            ValidatorDefinitions::open(&validator_dir)
+                .map_err(|e| format!("Unable to open {}: {:?}", CONFIG_FILENAME, e))?,
+        )
+    } else {
+        None
+    };
+
+    let definition = defs.as_ref().and_then(|defs| {
+        defs.as_slice()
+            .iter()
+            .enumerate()
+            .find(|(_, def)| def.voting_public_key.to_hex_string() == pubkey)
+    });
+
+    let (keystore_path, password_store) = match definition {
+        Some((i, def)) => match &def.signing_definition {
+            // The validator client uses the password from the definition in favour of the path,
+            // so it is the password that must be updated.
+            SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                voting_keystore_password_path,
+                voting_keystore_password,
+            } => {
+                let password_store = match (voting_keystore_password_path, voting_keystore_password)
+                {
+                    (_, Some(_)) => PasswordStore::Definition(i),
+                    (Some(path), None) => PasswordStore::File(path.clone()),
+                    (None, None) => PasswordStore::None,
+                };
+                (voting_keystore_path.clone(), password_store)
+            }
+        },
+        None => {
+            let keystore_path = validator_dir.join(&pubkey).join(VOTING_KEYSTORE_FILE);
+            if !keystore_path.exists() {
+                return Err(format!(
+                    "Unknown validator {} in --{} {:?}",
+                    pubkey, VALIDATOR_DIR_FLAG, validator_dir
+                ));
+            }
+
+            let password_path = secrets_dir.join(&pubkey);
+            let password_store = if password_path.exists() {
+                PasswordStore::File(password_path)
+            } else {
+                PasswordStore::None
+            };
+            (keystore_path, password_store)
+        }
+    };
+
+    let keystore = Keystore::from_json_file(&keystore_path)
+        .map_err(|e| format!("Unable to read keystore {:?}: {:?}", keystore_path, e))?;
+
+    if format!("0x{}", keystore.pubkey()) != pubkey {
+        return Err(format!(
+            "Keystore {:?} does not have the public key {}",
+            keystore_path, pubkey
+        ));
+    }
+
+    let old_password = match &password_store {
+        PasswordStore::File(path) => read_password(path)
+            .map_err(|e| format!("Unable to read password file {:?}: {:?}", path, e))?,
+        PasswordStore::Definition(i) => match defs
+            .as_ref()
+            .map(|defs| &defs.as_slice()[*i].signing_definition)
+        {
+            Some(SigningDefinition::LocalKeystore {
+                voting_keystore_password: Some(password),
+                ..
+            }) => PlainText::from(password.as_ref().to_vec()),
+            _ => return Err(format!("Missing password in {}", CONFIG_FILENAME)),
+        },
+        PasswordStore::None => {
+            eprintln!("");
+            eprintln!("{}", PASSWORD_PROMPT);
+            PlainText::from(read_password_from_user(stdin_inputs)?.as_ref().to_vec())
+        }
+    };
+
+    let new_password = match (new_password_path, &password_store) {
+        (Some(path), _) => read_password(&path)
+            .map_err(|e| format!("Unable to read --{} {:?}: {:?}", NEW_PASSWORD_FLAG, path, e))?,
+        (None, PasswordStore::None) => {
+            return Err(format!(
+                "The password for {} is not stored on disk, --{} must be supplied",
+                pubkey, NEW_PASSWORD_FLAG
+            ))
+        }
+        (None, _) => random_password(),
+    };
+
+    let salt = rand::thread_rng().gen::<[u8; SALT_SIZE]>().to_vec();
+    let kdf = match kdf_name.as_str() {
+        SCRYPT_KDF => default_kdf(salt),
+        PBKDF2_KDF => default_pbkdf2_kdf(salt),
+        unknown => return Err(format!("Unknown --{}: {}", KDF_FLAG, unknown)),
+    };
+
+    let new_keystore = keystore
+        .rekey(old_password.as_bytes(), new_password.as_bytes(), kdf)
+        .map_err(|e| format!("Unable to rekey keystore: {:?}", e))?;
+
+    // Hold the same lockfile as the validator client, so that the keystore cannot be replaced
+    // whilst it is in use.
+    let lockfile_path = keystore_path.with_file_name(format!(
+        "{}.lock",
+        keystore_path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .ok_or_else(|| format!("Badly formatted file name: {:?}", keystore_path))?
+    ));
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&lockfile_path)
+        .map_err(|e| {
+            format!(
+                "Unable to create lockfile {:?}, the validator may be in use: {:?}",
+                lockfile_path, e
+            )
+        })?;
+
+    let result = replace_keystore_and_password(
+        &keystore_path,
+        &keystore,
+        &new_keystore,
+        &new_password,
+        &password_store,
+        defs.as_mut(),
+        &validator_dir,
+    );
+
+    fs::remove_file(&lockfile_path)
+        .map_err(|e| format!("Unable to remove lockfile {:?}: {:?}", lockfile_path, e))?;
+
+    result?;
+
+    eprintln!("Successfully rekeyed {:?}.", keystore_path);
+    match password_store {
+        PasswordStore::File(path) => eprintln!("Updated the password in {:?}.", path),
+        PasswordStore::Definition(_) => eprintln!("Updated the password in {}.", CONFIG_FILENAME),
+        PasswordStore::None => eprintln!("The new password was not stored."),
+    }
+
+    Ok(())
+}
+
+/// Replaces the keystore at `keystore_path` with `new_keystore` and then stores `new_password`
+/// in `password_store`.
+///
+/// If the password cannot be stored then `old_keystore` is restored, so the keystore is never
+/// left unreadable with the stored password.
+fn replace_keystore_and_password(
+    keystore_path: &Path,
+    old_keystore: &Keystore,
+    new_keystore: &Keystore,
+    new_password: &PlainText,
+    password_store: &PasswordStore,
+    defs: Option<&mut ValidatorDefinitions>,
+    validator_dir: &Path,
+) -> Result<(), String> {
+    // Check that the new password can be stored before modifying anything.
+    let definition_password = match password_store {
+        PasswordStore::Definition(_) => Some(
+            String::from_utf8(new_password.as_bytes().to_vec())
+                .map(ZeroizeString::from)
+                .map_err(|_| {
+                    format!(
+                        "The new password must be valid UTF-8 to be stored in {}",
+                        CONFIG_FILENAME
+                    )
+                })?,
+        ),
+        _ => None,
+    };
+
+    write_keystore_atomically(keystore_path, new_keystore)?;
+
+    let result = match (password_store, defs, definition_password) {
+        (PasswordStore::File(path), _, _) => write_file_atomically(path, new_password.as_bytes()),
+        (PasswordStore::Definition(i), Some(defs), Some(password)) => {
+            match &mut defs.as_mut_slice()[*i].signing_definition {
+                SigningDefinition::LocalKeystore {
+                    voting_keystore_password,
+                    ..
+                } => *voting_keystore_password = Some(password),
+            }
+            defs.save(validator_dir)
+                .map_err(|e| format!("Unable to save {}: {:?}", CONFIG_FILENAME, e))
+        }
+        (PasswordStore::Definition(_), _, _) => {
+            Err(format!("Missing password in {}", CONFIG_FILENAME))
+        }
+        (PasswordStore::None, _, _) => Ok(()),
+    };
+
+    if let Err(e) = result {
+        write_keystore_atomically(keystore_path, old_keystore).map_err(|restore_error| {
+            format!(
+                "{}. Unable to restore the original keystore, it is still readable with the new \
+                password: {}",
+                e, restore_error
+            )
+        })?;
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Replaces the keystore at `path` with `keystore`.
+fn write_keystore_atomically(path: &Path, keystore: &Keystore) -> Result<(), String> {
+    let json = keystore
+        .to_json_string()
+        .map_err(|e| format!("Unable to encode keystore: {:?}", e))?;
+    write_file_atomically(path, json.as_bytes())
+}
+
+/// Writes `bytes` to a temporary file alongside `path`, then renames it over `path` so that
+/// `path` is never partially written.
+fn write_file_atomically(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let temp_path = path.with_file_name(format!(
+        "{}.tmp",
+        path.file_name()
+            .and_then(|file_name| file_name.to_str())
+            .ok_or_else(|| format!("Badly formatted file name: {:?}", path))?
+    ));
+
+    if temp_path.exists() {
+        return Err(format!("Temporary file already exists: {:?}", temp_path));
+    }
+
+    create_with_600_perms(&temp_path, bytes)
+        .map_err(|e| format!("Unable to write {:?}: {:?}", temp_path, e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Unable to replace {:?}: {:?}", path, e))
+}
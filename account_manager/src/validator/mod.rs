@@ -1,8 +1,12 @@
 pub mod create;
 pub mod deposit;
+pub mod export_deposit_data;
 pub mod import;
+pub mod keystore;
 pub mod list;
 pub mod recover;
+pub mod split_key;
+pub mod vault;
 
 use crate::common::base_wallet_dir;
 use clap::{App, Arg, ArgMatches};
@@ -23,9 +27,13 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         )
         .subcommand(create::cli_app())
         .subcommand(deposit::cli_app())
+        .subcommand(export_deposit_data::cli_app())
         .subcommand(import::cli_app())
+        .subcommand(keystore::cli_app())
         .subcommand(list::cli_app())
         .subcommand(recover::cli_app())
+        .subcommand(split_key::cli_app())
+        .subcommand(vault::cli_app())
 }
 
 pub fn cli_run<T: EthSpec>(matches: &ArgMatches, env: Environment<T>) -> Result<(), String> {
@@ -34,9 +42,15 @@ pub fn cli_run<T: EthSpec>(matches: &ArgMatches, env: Environment<T>) -> Result<
     match matches.subcommand() {
         (create::CMD, Some(matches)) => create::cli_run::<T>(matches, env, base_wallet_dir),
         (deposit::CMD, Some(matches)) => deposit::cli_run::<T>(matches, env),
+        (export_deposit_data::CMD, Some(matches)) => {
+            export_deposit_data::cli_run::<T>(matches, env)
+        }
         (import::CMD, Some(matches)) => import::cli_run(matches),
+        (keystore::CMD, Some(matches)) => keystore::cli_run(matches),
         (list::CMD, Some(matches)) => list::cli_run(matches),
-        (recover::CMD, Some(matches)) => recover::cli_run(matches),
+        (recover::CMD, Some(matches)) => recover::cli_run::<T>(matches, env),
+        (split_key::CMD, Some(matches)) => split_key::cli_run(matches),
+        (vault::CMD, Some(matches)) => vault::cli_run(matches),
         (unknown, _) => Err(format!(
             "{} does not have a {} command. See --help",
             CMD, unknown
@@ -1,18 +1,24 @@
-use super::create::STORE_WITHDRAW_FLAG;
+use super::create::{DEPOSIT_GWEI_FLAG, ETH1_WITHDRAWAL_ADDRESS_FLAG, STORE_WITHDRAW_FLAG};
 use crate::common::{ensure_dir_exists, read_mnemonic_from_cli};
 use crate::validator::create::COUNT_FLAG;
 use crate::wallet::create::STDIN_INPUTS_FLAG;
 use crate::{SECRETS_DIR_FLAG, VALIDATOR_DIR_FLAG};
 use account_utils::eth2_keystore::{keypair_from_secret, Keystore, KeystoreBuilder};
 use account_utils::random_password;
+use bls::PublicKey;
 use clap::{App, Arg, ArgMatches};
+use environment::Environment;
 use eth2_wallet::bip39::Seed;
-use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType, ValidatorKeystores};
+use eth2_wallet::{recover_validator_secret_from_path, KeyType, ValidatorPath};
+use rayon::prelude::*;
 use std::path::PathBuf;
+use types::{Address, EthSpec};
 use validator_dir::Builder as ValidatorDirBuilder;
 pub const CMD: &str = "recover";
 pub const FIRST_INDEX_FLAG: &str = "first-index";
 pub const MNEMONIC_FLAG: &str = "mnemonic-path";
+pub const PATH_FLAG: &str = "path";
+pub const PUBKEYS_FLAG: &str = "pubkeys";
 
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new(CMD)
@@ -20,7 +26,8 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             "Recovers validator private keys given a BIP-39 mnemonic phrase. \
             If you did not specify a `--first-index` or count `--count`, by default this will \
             only recover the keys associated with the validator at index 0 for an HD wallet \
-            in accordance with the EIP-2333 spec.")
+            in accordance with the EIP-2333 spec. Use `--path` to recover a key from an arbitrary \
+            EIP-2334 path, or `--pubkeys` to only recover the keys with the given public keys.")
         .arg(
             Arg::with_name(FIRST_INDEX_FLAG)
                 .long(FIRST_INDEX_FLAG)
@@ -34,11 +41,36 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             Arg::with_name(COUNT_FLAG)
                 .long(COUNT_FLAG)
                 .value_name("COUNT")
-                .help("The number of validator keys you wish to recover. Counted consecutively from the provided `--first_index`.")
+                .help("The number of validator keys you wish to recover. Counted consecutively from the provided `--first_index`. \
+                    With `--pubkeys`, the number of indices to search.")
                 .takes_value(true)
                 .required(false)
                 .default_value("1"),
         )
+        .arg(
+            Arg::with_name(PATH_FLAG)
+                .long(PATH_FLAG)
+                .value_name("EIP_2334_PATH")
+                .help(
+                    "If present, recovers the single voting key at this EIP-2334 path (e.g., \
+                    m/12381/3600/0/0/0) instead of using `--first-index` and `--count`. The \
+                    withdrawal key is derived from the parent of this path.",
+                )
+                .conflicts_with(PUBKEYS_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(PUBKEYS_FLAG)
+                .long(PUBKEYS_FLAG)
+                .value_name("PUBKEYS")
+                .help(
+                    "A comma-separated list of 0x-prefixed voting public keys. If present, the \
+                    voting keys of `--count` indices from `--first-index` are searched and only \
+                    the validators with these public keys are recovered. Fails without recovering \
+                    any validator if a public key is not found.",
+                )
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name(MNEMONIC_FLAG)
                 .long(MNEMONIC_FLAG)
@@ -77,6 +109,30 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     instead generate them from the wallet seed when required.",
                 ),
         )
+        .arg(
+            Arg::with_name(ETH1_WITHDRAWAL_ADDRESS_FLAG)
+                .long(ETH1_WITHDRAWAL_ADDRESS_FLAG)
+                .value_name("ETH1_ADDRESS")
+                .help(
+                    "If present, deposit data is written for each recovered validator using eth1 \
+                    address (0x01) withdrawal credentials for this address. The withdrawal keys \
+                    are not derived and no withdrawal keystore is stored.",
+                )
+                .conflicts_with(STORE_WITHDRAW_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(DEPOSIT_GWEI_FLAG)
+                .long(DEPOSIT_GWEI_FLAG)
+                .value_name("DEPOSIT_GWEI")
+                .help(
+                    "The GWEI value of the deposit amount written with --eth1-withdrawal-address. \
+                    Defaults to the minimum amount required for an active validator \
+                    (MAX_EFFECTIVE_BALANCE)",
+                )
+                .requires(ETH1_WITHDRAWAL_ADDRESS_FLAG)
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name(STDIN_INPUTS_FLAG)
                 .long(STDIN_INPUTS_FLAG)
@@ -84,7 +140,9 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         )
 }
 
-pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches, mut env: Environment<T>) -> Result<(), String> {
+    let spec = env.core_context().eth2_config.spec;
+
     let validator_dir = clap_utils::parse_path_with_default_in_home_dir(
         matches,
         VALIDATOR_DIR_FLAG,
@@ -97,8 +155,31 @@ pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
     )?;
     let first_index: u32 = clap_utils::parse_required(matches, FIRST_INDEX_FLAG)?;
     let count: u32 = clap_utils::parse_required(matches, COUNT_FLAG)?;
+    let path: Option<ValidatorPath> = clap_utils::parse_optional(matches, PATH_FLAG)?;
+    let pubkeys = matches
+        .value_of(PUBKEYS_FLAG)
+        .map(parse_pubkeys)
+        .transpose()?;
     let mnemonic_path: Option<PathBuf> = clap_utils::parse_optional(matches, MNEMONIC_FLAG)?;
     let stdin_inputs = matches.is_present(STDIN_INPUTS_FLAG);
+    let eth1_withdrawal_address: Option<Address> =
+        clap_utils::parse_optional(matches, ETH1_WITHDRAWAL_ADDRESS_FLAG)?;
+    let deposit_gwei = clap_utils::parse_optional(matches, DEPOSIT_GWEI_FLAG)?
+        .unwrap_or_else(|| spec.max_effective_balance);
+
+    // `--first-index` and `--count` have default values, so clap cannot detect the conflict.
+    if path.is_some()
+        && (matches.occurrences_of(FIRST_INDEX_FLAG) > 0 || matches.occurrences_of(COUNT_FLAG) > 0)
+    {
+        return Err(format!(
+            "--{} cannot be used with --{} or --{}",
+            PATH_FLAG, FIRST_INDEX_FLAG, COUNT_FLAG
+        ));
+    }
+
+    let last_index = first_index
+        .checked_add(count)
+        .ok_or_else(|| format!("--{} + --{} is too large", FIRST_INDEX_FLAG, COUNT_FLAG))?;
 
     ensure_dir_exists(&validator_dir)?;
     ensure_dir_exists(&secrets_dir)?;
@@ -111,46 +192,148 @@ pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
 
     let seed = Seed::new(&mnemonic, "");
 
-    for index in first_index..first_index + count {
+    let voting_paths = if let Some(path) = path {
+        vec![path]
+    } else if let Some(pubkeys) = &pubkeys {
+        eprintln!(
+            "Searching indices {} to {} for {} public keys.",
+            first_index,
+            last_index.saturating_sub(1),
+            pubkeys.len()
+        );
+        let found = find_voting_paths(seed.as_bytes(), pubkeys, first_index..last_index)?;
+
+        // Check that every public key was found before any validator is created.
+        let missing = pubkeys
+            .iter()
+            .filter(|pubkey| {
+                !found
+                    .iter()
+                    .any(|(_, found_pubkey)| found_pubkey == *pubkey)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return Err(format!(
+                "Unable to find {} of {} public keys in indices {} to {}, try increasing --{}: {}",
+                missing.len(),
+                pubkeys.len(),
+                first_index,
+                last_index.saturating_sub(1),
+                COUNT_FLAG,
+                missing.join(", ")
+            ));
+        }
+
+        found.into_iter().map(|(path, _)| path).collect()
+    } else {
+        (first_index..last_index)
+            .map(|index| ValidatorPath::new(index, KeyType::Voting))
+            .collect()
+    };
+
+    let derive = |path: &ValidatorPath, password: &[u8]| -> Result<Keystore, String> {
+        let secret = recover_validator_secret_from_path(seed.as_bytes(), path)
+            .map_err(|e| format!("Unable to recover validator keys: {:?}", e))?;
+
+        let keypair = keypair_from_secret(secret.as_bytes())
+            .map_err(|e| format!("Unable build keystore: {:?}", e))?;
+
+        KeystoreBuilder::new(&keypair, password, format!("{}", path))
+            .map_err(|e| format!("Unable build keystore: {:?}", e))?
+            .build()
+            .map_err(|e| format!("Unable build keystore: {:?}", e))
+    };
+
+    for (i, voting_path) in voting_paths.iter().enumerate() {
         let voting_password = random_password();
         let withdrawal_password = random_password();
 
-        let derive = |key_type: KeyType, password: &[u8]| -> Result<Keystore, String> {
-            let (secret, path) =
-                recover_validator_secret_from_mnemonic(seed.as_bytes(), index, key_type)
-                    .map_err(|e| format!("Unable to recover validator keys: {:?}", e))?;
+        let voting_keystore = derive(voting_path, voting_password.as_bytes())?;
+        let voting_pubkey = voting_keystore.pubkey().to_string();
 
-            let keypair = keypair_from_secret(secret.as_bytes())
-                .map_err(|e| format!("Unable build keystore: {:?}", e))?;
-
-            KeystoreBuilder::new(&keypair, password, format!("{}", path))
-                .map_err(|e| format!("Unable build keystore: {:?}", e))?
-                .build()
-                .map_err(|e| format!("Unable build keystore: {:?}", e))
-        };
+        let builder = ValidatorDirBuilder::new(validator_dir.clone(), secrets_dir.clone())
+            .voting_keystore(voting_keystore, voting_password.as_bytes());
 
-        let keystores = ValidatorKeystores {
-            voting: derive(KeyType::Voting, voting_password.as_bytes())?,
-            withdrawal: derive(KeyType::Withdrawal, withdrawal_password.as_bytes())?,
+        // The withdrawal keys are only required for BLS withdrawal credentials.
+        let builder = if let Some(address) = eth1_withdrawal_address {
+            builder
+                .eth1_withdrawal_address(address)
+                .create_eth1_tx_data(deposit_gwei, &spec)
+                .store_withdrawal_keystore(false)
+        } else {
+            let withdrawal_path = voting_path.withdrawal_path().ok_or_else(|| {
+                format!(
+                    "The voting key path {} has no withdrawal key path, use --{}",
+                    voting_path, ETH1_WITHDRAWAL_ADDRESS_FLAG
+                )
+            })?;
+            builder
+                .withdrawal_keystore(
+                    derive(&withdrawal_path, withdrawal_password.as_bytes())?,
+                    withdrawal_password.as_bytes(),
+                )
+                .store_withdrawal_keystore(matches.is_present(STORE_WITHDRAW_FLAG))
         };
 
-        let voting_pubkey = keystores.voting.pubkey().to_string();
-
-        ValidatorDirBuilder::new(validator_dir.clone(), secrets_dir.clone())
-            .voting_keystore(keystores.voting, voting_password.as_bytes())
-            .withdrawal_keystore(keystores.withdrawal, withdrawal_password.as_bytes())
-            .store_withdrawal_keystore(matches.is_present(STORE_WITHDRAW_FLAG))
+        builder
             .build()
             .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
 
         println!(
-            "{}/{}\tIndex: {}\t0x{}",
-            index - first_index,
-            count - first_index,
-            index,
+            "{}/{}\tPath: {}\t0x{}",
+            i + 1,
+            voting_paths.len(),
+            voting_path,
             voting_pubkey
         );
     }
 
     Ok(())
 }
+
+/// Parses a comma-separated list of public keys, returning them as sorted and deduplicated
+/// lower-case, 0x-prefixed hex strings.
+fn parse_pubkeys(pubkeys: &str) -> Result<Vec<String>, String> {
+    let mut pubkeys = pubkeys
+        .split(',')
+        .map(|pubkey| {
+            let bytes = hex::decode(pubkey.trim().trim_start_matches("0x"))
+                .map_err(|e| format!("Invalid hex in --{} {:?}: {:?}", PUBKEYS_FLAG, pubkey, e))?;
+            PublicKey::deserialize(&bytes)
+                .map(|pubkey| pubkey.to_hex_string())
+                .map_err(|e| format!("Invalid --{} {:?}: {:?}", PUBKEYS_FLAG, pubkey, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    pubkeys.sort();
+    pubkeys.dedup();
+
+    Ok(pubkeys)
+}
+
+/// Returns the paths of the voting keys in `indices` which have one of the given `pubkeys`, along
+/// with their public keys.
+fn find_voting_paths(
+    seed: &[u8],
+    pubkeys: &[String],
+    indices: std::ops::Range<u32>,
+) -> Result<Vec<(ValidatorPath, String)>, String> {
+    indices
+        .into_par_iter()
+        .map(|index| {
+            let path = ValidatorPath::new(index, KeyType::Voting);
+            let secret = recover_validator_secret_from_path(seed, &path)
+                .map_err(|e| format!("Unable to recover validator keys: {:?}", e))?;
+            let keypair = keypair_from_secret(secret.as_bytes())
+                .map_err(|e| format!("Unable to derive keypair: {:?}", e))?;
+
+            Ok((path, keypair.pk.to_hex_string()))
+        })
+        .filter_map(|result: Result<_, String>| match result {
+            Ok((path, pubkey)) if pubkeys.contains(&pubkey) => Some(Ok((path, pubkey))),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect()
+}
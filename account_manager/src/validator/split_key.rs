@@ -0,0 +1,278 @@
+use crate::common::ensure_dir_exists;
+use crate::wallet::create::STDIN_INPUTS_FLAG;
+use account_utils::{
+    create_with_600_perms,
+    eth2_keystore::{Keystore, KeystoreBuilder},
+    random_password, read_password, read_password_from_user, PlainText,
+};
+use bls::{combine_signature_shares, Hash256, Keypair, PublicKey, SecretKeyShare};
+use clap::{App, Arg, ArgMatches};
+use serde_derive::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+pub const CMD: &str = "split-key";
+pub const KEYSTORE_FLAG: &str = "keystore";
+pub const PASSWORD_FLAG: &str = "password-file";
+pub const THRESHOLD_FLAG: &str = "threshold";
+pub const SHARES_FLAG: &str = "shares";
+pub const OUTPUT_DIR_FLAG: &str = "output-dir";
+
+pub const PASSWORD_PROMPT: &str = "Enter the keystore password:";
+
+/// The name of the file in each share directory which contains its `ShareDefinition`.
+pub const SHARE_DEFINITION_FILE: &str = "share.json";
+
+/// Describes a share created by `split-key`. It is stored as JSON in the share directory, so the
+/// share index can be read without parsing file names or the keystore description.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShareDefinition {
+    /// The index of the share, required to combine its signatures with those of other shares.
+    pub index: u64,
+    /// The number of shares required to produce a signature.
+    pub threshold: usize,
+    /// The total number of shares that were created.
+    pub share_count: usize,
+    /// The public key of the share keystore.
+    pub share_pubkey: PublicKey,
+    /// The public key of the keystore that was split.
+    pub pubkey: PublicKey,
+}
+
+impl ShareDefinition {
+    /// Reads a `ShareDefinition` from the JSON file at `path`.
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| format!("Unable to open {:?}: {:?}", path, e))?;
+        serde_json::from_reader(file).map_err(|e| format!("Unable to parse {:?}: {:?}", path, e))
+    }
+
+    /// Writes `self` as JSON to a new file at `path`.
+    pub fn to_json_file<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        let file =
+            File::create(path).map_err(|e| format!("Unable to create {:?}: {:?}", path, e))?;
+        serde_json::to_writer_pretty(file, self)
+            .map_err(|e| format!("Unable to write {:?}: {:?}", path, e))
+    }
+}
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Splits the secret key of an EIP-2335 keystore into shares using Shamir's secret \
+            sharing, so that signatures from any --threshold of the shares can be combined into \
+            a signature from the original key. Each share is written to its own directory in \
+            --output-dir as a keystore with a randomly generated password. The original \
+            keystore is not modified.",
+        )
+        .arg(
+            Arg::with_name(KEYSTORE_FLAG)
+                .long(KEYSTORE_FLAG)
+                .value_name("KEYSTORE_PATH")
+                .help("Path to the keystore to split.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(PASSWORD_FLAG)
+                .long(PASSWORD_FLAG)
+                .value_name("PASSWORD_PATH")
+                .help(
+                    "A path to a file containing the password of the keystore. If not supplied, \
+                    the password will be requested interactively.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(THRESHOLD_FLAG)
+                .long(THRESHOLD_FLAG)
+                .value_name("THRESHOLD")
+                .help("The number of shares required to produce a signature.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(SHARES_FLAG)
+                .long(SHARES_FLAG)
+                .value_name("SHARE_COUNT")
+                .help("The total number of shares to create.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_DIR_FLAG)
+                .long(OUTPUT_DIR_FLAG)
+                .value_name("OUTPUT_DIRECTORY")
+                .help(
+                    "The directory in which to create a share-<INDEX> directory for each share, \
+                    containing its keystore, password and a share.json file describing the \
+                    share. It will be created if it does not exist.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+/// Returns the name of the directory for the share at `index`.
+pub fn share_dir_name(index: u64) -> String {
+    format!("share-{}", index)
+}
+
+/// Returns the name of the keystore file for the share at `index`.
+pub fn share_keystore_file_name(index: u64) -> String {
+    format!("keystore-share-{}.json", index)
+}
+
+/// Returns the name of the password file for the share at `index`.
+pub fn share_password_file_name(index: u64) -> String {
+    format!("password-share-{}.txt", index)
+}
+
+pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+    let keystore_path: PathBuf = clap_utils::parse_required(matches, KEYSTORE_FLAG)?;
+    let password_path: Option<PathBuf> = clap_utils::parse_optional(matches, PASSWORD_FLAG)?;
+    let threshold: usize = clap_utils::parse_required(matches, THRESHOLD_FLAG)?;
+    let share_count: usize = clap_utils::parse_required(matches, SHARES_FLAG)?;
+    let output_dir: PathBuf = clap_utils::parse_required(matches, OUTPUT_DIR_FLAG)?;
+    let stdin_inputs = matches.is_present(STDIN_INPUTS_FLAG);
+
+    if threshold == 0 || threshold > share_count {
+        return Err(format!(
+            "--{} must be between 1 and --{} ({})",
+            THRESHOLD_FLAG, SHARES_FLAG, share_count
+        ));
+    }
+
+    let keystore = Keystore::from_json_file(&keystore_path)
+        .map_err(|e| format!("Unable to read keystore {:?}: {:?}", keystore_path, e))?;
+
+    let password = match password_path {
+        Some(path) => read_password(&path)
+            .map_err(|e| format!("Unable to read password file {:?}: {:?}", path, e))?,
+        None => {
+            eprintln!("");
+            eprintln!("{}", PASSWORD_PROMPT);
+            PlainText::from(read_password_from_user(stdin_inputs)?.as_ref().to_vec())
+        }
+    };
+
+    let keypair = keystore
+        .decrypt_keypair(password.as_bytes())
+        .map_err(|e| format!("Unable to decrypt keystore {:?}: {:?}", keystore_path, e))?;
+
+    let shares = SecretKeyShare::split(&keypair.sk, threshold, share_count)
+        .map_err(|e| format!("Unable to split secret key: {:?}", e))?;
+
+    // Check that the first and last `threshold` shares both produce a signature from the original
+    // key before writing anything to disk.
+    let msg = Hash256::from_low_u64_be(42);
+    for subset in &[&shares[..threshold], &shares[share_count - threshold..]] {
+        let signature_shares = subset
+            .iter()
+            .map(|share| share.sign(msg))
+            .collect::<Vec<_>>();
+        let signature = combine_signature_shares(&signature_shares)
+            .map_err(|e| format!("Unable to combine signature shares: {:?}", e))?;
+
+        if !signature.verify(&keypair.pk, msg) {
+            return Err(
+                "Combined signature shares do not verify against the original public \
+                 key, no shares were written"
+                    .to_string(),
+            );
+        }
+    }
+
+    ensure_dir_exists(&output_dir)?;
+
+    let share_dirs = shares
+        .iter()
+        .map(|share| output_dir.join(share_dir_name(share.index())))
+        .collect::<Vec<_>>();
+    if let Some(dir) = share_dirs.iter().find(|dir| dir.exists()) {
+        return Err(format!("Share directory {:?} already exists", dir));
+    }
+
+    eprintln!("");
+    eprintln!(
+        "Split {} into {} shares with a threshold of {}:",
+        keypair.pk.to_hex_string(),
+        share_count,
+        threshold
+    );
+
+    for (share, dir) in shares.iter().zip(share_dirs.iter()) {
+        let share_keypair =
+            Keypair::from_components(share.public_key(), share.secret_key().clone());
+        let share_password = random_password();
+
+        let share_keystore = KeystoreBuilder::new(
+            &share_keypair,
+            share_password.as_bytes(),
+            // The share is not derived from a path, so the path is left empty.
+            String::new(),
+        )
+        .and_then(|builder| {
+            builder
+                .description(format!(
+                    "Share {} of {} (threshold {}) of {}",
+                    share.index(),
+                    share_count,
+                    threshold,
+                    keypair.pk.to_hex_string()
+                ))
+                .build()
+        })
+        .map_err(|e| {
+            format!(
+                "Unable to build keystore for share {}: {:?}",
+                share.index(),
+                e
+            )
+        })?;
+        let json = share_keystore
+            .to_json_string()
+            .map_err(|e| format!("Unable to encode keystore as JSON: {:?}", e))?;
+
+        fs::create_dir(dir).map_err(|e| format!("Unable to create {:?}: {:?}", dir, e))?;
+
+        let share_keystore_path = dir.join(share_keystore_file_name(share.index()));
+        create_with_600_perms(&share_keystore_path, json.as_bytes())
+            .map_err(|e| format!("Unable to write {:?}: {:?}", share_keystore_path, e))?;
+
+        let share_password_path = dir.join(share_password_file_name(share.index()));
+        create_with_600_perms(&share_password_path, share_password.as_bytes())
+            .map_err(|e| format!("Unable to write {:?}: {:?}", share_password_path, e))?;
+
+        ShareDefinition {
+            index: share.index(),
+            threshold,
+            share_count,
+            share_pubkey: share_keypair.pk.clone(),
+            pubkey: keypair.pk.clone(),
+        }
+        .to_json_file(dir.join(SHARE_DEFINITION_FILE))?;
+
+        eprintln!(
+            "  - share {}: {} in {:?}",
+            share.index(),
+            share_keypair.pk.to_hex_string(),
+            dir
+        );
+    }
+
+    eprintln!("");
+    eprintln!(
+        "Distribute each share directory to a different machine and store the password files \
+        separately from the keystores. The original keystore can still sign on its own; store \
+        it offline or destroy it."
+    );
+
+    Ok(())
+}
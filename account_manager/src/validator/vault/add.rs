@@ -0,0 +1,173 @@
+use crate::common::ensure_dir_exists;
+use crate::wallet::create::STDIN_INPUTS_FLAG;
+use crate::SECRETS_DIR_FLAG;
+use account_utils::{
+    eth2_keystore::Keystore,
+    is_password_sufficiently_complex,
+    password_vault::{
+        default_vault_path, read_vault_passphrase, PasswordVault, PASSPHRASE_ENV_VAR,
+    },
+    read_password, read_password_from_user, PlainText,
+};
+use clap::{App, Arg, ArgMatches};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+pub const CMD: &str = "add";
+pub const KEYSTORE_FLAG: &str = "keystore";
+pub const PASSWORD_FLAG: &str = "password-file";
+pub const PASSPHRASE_FLAG: &str = "passphrase-file";
+pub const DELETE_PASSWORD_FLAG: &str = "delete-password-file";
+
+pub const PASSWORD_PROMPT: &str = "Enter the keystore password:";
+pub const RETYPE_PASSPHRASE_PROMPT: &str = "Please re-enter the new vault passphrase:";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Stores the password of a voting keystore in the password vault, creating the vault \
+            if it does not exist. The password is checked against the keystore before it is \
+            stored. The vault passphrase is read from --passphrase-file, then the \
+            LIGHTHOUSE_PASSWORD_VAULT_PASSPHRASE environment variable, then the terminal.",
+        )
+        .arg(
+            Arg::with_name(KEYSTORE_FLAG)
+                .long(KEYSTORE_FLAG)
+                .value_name("KEYSTORE_PATH")
+                .help("Path to the keystore whose password should be stored.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(PASSWORD_FLAG)
+                .long(PASSWORD_FLAG)
+                .value_name("PASSWORD_PATH")
+                .help(
+                    "A path to a file containing the password of the keystore. If not supplied, \
+                    the password will be requested interactively.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(PASSPHRASE_FLAG)
+                .long(PASSPHRASE_FLAG)
+                .value_name("PASSPHRASE_PATH")
+                .help("A path to a file containing the passphrase of the password vault.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(DELETE_PASSWORD_FLAG)
+                .long(DELETE_PASSWORD_FLAG)
+                .help(
+                    "If present, delete the --password-file once the password has been stored in \
+                    the vault.",
+                )
+                .requires(PASSWORD_FLAG),
+        )
+        .arg(
+            Arg::with_name(SECRETS_DIR_FLAG)
+                .long(SECRETS_DIR_FLAG)
+                .value_name("SECRETS_DIR")
+                .help(
+                    "The directory which contains the password vault. \
+                    Defaults to ~/.lighthouse/secrets",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+    let keystore_path: PathBuf = clap_utils::parse_required(matches, KEYSTORE_FLAG)?;
+    let password_path: Option<PathBuf> = clap_utils::parse_optional(matches, PASSWORD_FLAG)?;
+    let passphrase_path: Option<PathBuf> = clap_utils::parse_optional(matches, PASSPHRASE_FLAG)?;
+    let delete_password = matches.is_present(DELETE_PASSWORD_FLAG);
+    let secrets_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        SECRETS_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("secrets"),
+    )?;
+    let stdin_inputs = matches.is_present(STDIN_INPUTS_FLAG);
+
+    let keystore = Keystore::from_json_file(&keystore_path)
+        .map_err(|e| format!("Unable to read keystore {:?}: {:?}", keystore_path, e))?;
+
+    let password = match &password_path {
+        Some(path) => read_password(path)
+            .map_err(|e| format!("Unable to read password file {:?}: {:?}", path, e))?,
+        None => {
+            eprintln!("");
+            eprintln!("{}", PASSWORD_PROMPT);
+            PlainText::from(read_password_from_user(stdin_inputs)?.as_ref().to_vec())
+        }
+    };
+
+    let keypair = keystore
+        .decrypt_keypair(password.as_bytes())
+        .map_err(|e| format!("Unable to decrypt keystore {:?}: {:?}", keystore_path, e))?;
+
+    ensure_dir_exists(&secrets_dir)?;
+    let vault_path = default_vault_path(&secrets_dir);
+
+    let passphrase = read_vault_passphrase(passphrase_path.as_deref(), stdin_inputs)?;
+
+    let mut vault = if vault_path.exists() {
+        PasswordVault::open(&vault_path, passphrase.as_bytes())
+            .map_err(|e| format!("Unable to unlock password vault {:?}: {:?}", vault_path, e))?
+    } else {
+        is_password_sufficiently_complex(passphrase.as_bytes())?;
+
+        // Guard against typos when a new passphrase is entered interactively.
+        if passphrase_path.is_none() && env::var_os(PASSPHRASE_ENV_VAR).is_none() {
+            eprintln!("{}", RETYPE_PASSPHRASE_PROMPT);
+            let retyped_passphrase =
+                PlainText::from(read_password_from_user(stdin_inputs)?.as_ref().to_vec());
+            if retyped_passphrase != passphrase {
+                return Err("Passphrases do not match".to_string());
+            }
+        }
+
+        PasswordVault::create(&vault_path, passphrase.as_bytes())
+            .map_err(|e| format!("Unable to create password vault {:?}: {:?}", vault_path, e))?
+    };
+
+    let replaced = vault.contains(&keypair.pk);
+    vault
+        .insert(&keypair.pk, password.as_bytes())
+        .map_err(|e| format!("Unable to add password to vault: {:?}", e))?;
+    vault
+        .save()
+        .map_err(|e| format!("Unable to save password vault {:?}: {:?}", vault_path, e))?;
+
+    eprintln!("");
+    if replaced {
+        eprintln!(
+            "Replaced the password for {} in {:?}.",
+            keypair.pk.to_hex_string(),
+            vault_path
+        );
+    } else {
+        eprintln!(
+            "Added the password for {} to {:?}.",
+            keypair.pk.to_hex_string(),
+            vault_path
+        );
+    }
+
+    if let (Some(path), true) = (password_path, delete_password) {
+        fs::remove_file(&path)
+            .map_err(|e| format!("Unable to delete password file {:?}: {:?}", path, e))?;
+        eprintln!("Deleted {:?}.", path);
+    }
+
+    eprintln!(
+        "Start the validator client with --password-vault to unlock keystores with the vault."
+    );
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+pub mod add;
+
+use clap::{App, ArgMatches};
+
+pub const CMD: &str = "vault";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Manages the password vault, an encrypted store for voting keystore passwords in the \
+            secrets directory which is unlocked by a single passphrase.",
+        )
+        .subcommand(add::cli_app())
+}
+
+pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+    match matches.subcommand() {
+        (add::CMD, Some(matches)) => add::cli_run(matches),
+        (unknown, _) => Err(format!(
+            "{} does not have a {} command. See --help",
+            CMD, unknown
+        )),
+    }
+}
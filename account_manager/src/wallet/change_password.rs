@@ -0,0 +1,86 @@
+use crate::common::read_wallet_name_from_cli;
+use crate::validator::create::read_wallet_password_from_cli;
+use crate::wallet::create::{
+    read_new_wallet_password_from_cli, NAME_FLAG, PASSWORD_FLAG, STDIN_INPUTS_FLAG,
+};
+use crate::BASE_DIR_FLAG;
+use clap::{App, Arg, ArgMatches};
+use eth2_wallet_manager::WalletManager;
+use std::path::PathBuf;
+
+pub const CMD: &str = "change-password";
+pub const NEW_PASSWORD_FLAG: &str = "new-password-file";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Re-encrypts an EIP-2386 wallet with a new password. The wallet UUID, name and \
+            next account index are preserved.",
+        )
+        .arg(
+            Arg::with_name(NAME_FLAG)
+                .long(NAME_FLAG)
+                .value_name("WALLET_NAME")
+                .help("Use the wallet identified by this name")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(PASSWORD_FLAG)
+                .long(PASSWORD_FLAG)
+                .value_name("WALLET_PASSWORD_PATH")
+                .help("A path to a file containing the current password of the wallet.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(NEW_PASSWORD_FLAG)
+                .long(NEW_PASSWORD_FLAG)
+                .value_name("NEW_PASSWORD_PATH")
+                .help(
+                    "A path to a file containing the new password for the wallet. If not \
+                    supplied, the new password will be read interactively.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+pub fn cli_run(matches: &ArgMatches, base_dir: PathBuf) -> Result<(), String> {
+    let name: Option<String> = clap_utils::parse_optional(matches, NAME_FLAG)?;
+    let password_path: Option<PathBuf> = clap_utils::parse_optional(matches, PASSWORD_FLAG)?;
+    let new_password_path: Option<PathBuf> =
+        clap_utils::parse_optional(matches, NEW_PASSWORD_FLAG)?;
+    let stdin_inputs = matches.is_present(STDIN_INPUTS_FLAG);
+
+    let mgr = WalletManager::open(&base_dir)
+        .map_err(|e| format!("Unable to open --{}: {:?}", BASE_DIR_FLAG, e))?;
+
+    let wallet_name = read_wallet_name_from_cli(name, stdin_inputs)?;
+    let mut wallet = mgr
+        .wallet_by_name(&wallet_name)
+        .map_err(|e| format!("Unable to open wallet: {:?}", e))?;
+
+    let password = read_wallet_password_from_cli(password_path, stdin_inputs)?;
+
+    // Check the current password before asking for a new one.
+    wallet
+        .wallet()
+        .decrypt_seed(password.as_bytes())
+        .map_err(|e| format!("Unable to decrypt wallet: {:?}", e))?;
+
+    let new_password = read_new_wallet_password_from_cli(new_password_path, stdin_inputs)?;
+
+    wallet
+        .change_password(password.as_bytes(), new_password.as_bytes())
+        .map_err(|e| format!("Unable to change wallet password: {:?}", e))?;
+
+    eprintln!(
+        "Successfully changed the password of wallet {}.",
+        wallet_name
+    );
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use crate::common::read_wallet_name_from_cli;
+use crate::validator::create::read_wallet_password_from_cli;
+use crate::wallet::create::{NAME_FLAG, PASSWORD_FLAG, STDIN_INPUTS_FLAG};
+use crate::{BASE_DIR_FLAG, VALIDATOR_DIR_FLAG};
+use account_utils::{
+    eth2_keystore::keypair_from_secret,
+    read_input_from_user,
+    validator_definitions::{ValidatorDefinitions, CONFIG_FILENAME},
+};
+use clap::{App, Arg, ArgMatches};
+use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType};
+use eth2_wallet_manager::WalletManager;
+use std::path::{Path, PathBuf};
+
+pub const CMD: &str = "delete";
+
+pub const CONFIRMATION_PROMPT: &str = "Enter the wallet name again to confirm its deletion:";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Deletes a wallet. Refuses to delete the wallet if any validator in --validator-dir \
+            was derived from it. The validator keys derived from the wallet can only be recovered \
+            from its mnemonic after deletion.",
+        )
+        .arg(
+            Arg::with_name(NAME_FLAG)
+                .long(NAME_FLAG)
+                .value_name("WALLET_NAME")
+                .help("Use the wallet identified by this name")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(PASSWORD_FLAG)
+                .long(PASSWORD_FLAG)
+                .value_name("WALLET_PASSWORD_PATH")
+                .help(
+                    "A path to a file containing the password of the wallet. The password is \
+                    required to find the validators derived from the wallet.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path to the validator client data directory. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+pub fn cli_run(matches: &ArgMatches, base_dir: PathBuf) -> Result<(), String> {
+    let name: Option<String> = clap_utils::parse_optional(matches, NAME_FLAG)?;
+    let password_path: Option<PathBuf> = clap_utils::parse_optional(matches, PASSWORD_FLAG)?;
+    let validator_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let stdin_inputs = matches.is_present(STDIN_INPUTS_FLAG);
+
+    let mgr = WalletManager::open(&base_dir)
+        .map_err(|e| format!("Unable to open --{}: {:?}", BASE_DIR_FLAG, e))?;
+
+    let wallet_name = read_wallet_name_from_cli(name, stdin_inputs)?;
+    let wallet = mgr
+        .wallet_by_name(&wallet_name)
+        .map_err(|e| format!("Unable to open wallet: {:?}", e))?;
+
+    let password = read_wallet_password_from_cli(password_path, stdin_inputs)?;
+    let seed = wallet
+        .wallet()
+        .decrypt_seed(password.as_bytes())
+        .map_err(|e| format!("Unable to decrypt wallet: {:?}", e))?;
+
+    // Derive the voting public key of each validator that has been created by the wallet.
+    let pubkeys = (0..wallet.wallet().nextaccount())
+        .map(|index| {
+            let (secret, _) =
+                recover_validator_secret_from_mnemonic(seed.as_bytes(), index, KeyType::Voting)
+                    .map_err(|e| format!("Unable to derive validator keys: {:?}", e))?;
+            keypair_from_secret(secret.as_bytes())
+                .map(|keypair| keypair.pk.to_hex_string())
+                .map_err(|e| format!("Unable to derive validator keys: {:?}", e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let referenced = referenced_pubkeys(&validator_dir, &pubkeys)?;
+    if !referenced.is_empty() {
+        return Err(format!(
+            "Refusing to delete wallet {}, it was used to derive {} validator(s) in --{} {:?}: {}",
+            wallet_name,
+            referenced.len(),
+            VALIDATOR_DIR_FLAG,
+            validator_dir,
+            referenced.join(", ")
+        ));
+    }
+
+    eprintln!("");
+    eprintln!("{}", CONFIRMATION_PROMPT);
+    if read_input_from_user(stdin_inputs)? != wallet_name {
+        return Err("Wallet name does not match, the wallet was not deleted".to_string());
+    }
+
+    wallet
+        .delete()
+        .map_err(|e| format!("Unable to delete wallet: {:?}", e))?;
+
+    eprintln!("Successfully deleted wallet {}.", wallet_name);
+
+    Ok(())
+}
+
+/// Returns the members of `pubkeys` that are used by a validator in `validator_dir`, either as a
+/// validator directory created by Lighthouse or in the validator definitions file.
+fn referenced_pubkeys(validator_dir: &Path, pubkeys: &[String]) -> Result<Vec<String>, String> {
+    if !validator_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let defined: Vec<String> = if validator_dir.join(CONFIG_FILENAME).exists() {
+        ValidatorDefinitions::open(validator_dir)
+            .map_err(|e| format!("Unable to open {}: {:?}", CONFIG_FILENAME, e))?
+            .as_slice()
+            .iter()
+            .map(|def| def.voting_public_key.to_hex_string())
+            .collect()
+    } else {
+        vec![]
+    };
+
+    Ok(pubkeys
+        .iter()
+        .filter(|pubkey| validator_dir.join(pubkey).exists() || defined.contains(*pubkey))
+        .cloned()
+        .collect())
+}
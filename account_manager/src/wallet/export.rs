@@ -0,0 +1,68 @@
+use crate::common::read_wallet_name_from_cli;
+use crate::wallet::create::{NAME_FLAG, STDIN_INPUTS_FLAG};
+use crate::BASE_DIR_FLAG;
+use account_utils::create_with_600_perms;
+use clap::{App, Arg, ArgMatches};
+use eth2_wallet_manager::WalletManager;
+use std::path::PathBuf;
+
+pub const CMD: &str = "export";
+pub const OUTPUT_FLAG: &str = "output";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Exports a wallet to a standalone EIP-2386 JSON file. The wallet remains encrypted \
+            with its current password.",
+        )
+        .arg(
+            Arg::with_name(NAME_FLAG)
+                .long(NAME_FLAG)
+                .value_name("WALLET_NAME")
+                .help("Use the wallet identified by this name")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_FLAG)
+                .long(OUTPUT_FLAG)
+                .value_name("OUTPUT_PATH")
+                .help("The file to write the wallet JSON to. Must not already exist.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+pub fn cli_run(matches: &ArgMatches, base_dir: PathBuf) -> Result<(), String> {
+    let name: Option<String> = clap_utils::parse_optional(matches, NAME_FLAG)?;
+    let output: PathBuf = clap_utils::parse_required(matches, OUTPUT_FLAG)?;
+    let stdin_inputs = matches.is_present(STDIN_INPUTS_FLAG);
+
+    if output.exists() {
+        return Err(format!("--{} {:?} already exists", OUTPUT_FLAG, output));
+    }
+
+    let mgr = WalletManager::open(&base_dir)
+        .map_err(|e| format!("Unable to open --{}: {:?}", BASE_DIR_FLAG, e))?;
+
+    let wallet_name = read_wallet_name_from_cli(name, stdin_inputs)?;
+    let wallet = mgr
+        .wallet_by_name(&wallet_name)
+        .map_err(|e| format!("Unable to open wallet: {:?}", e))?;
+
+    let json = wallet
+        .wallet()
+        .to_json_string()
+        .map_err(|e| format!("Unable to encode wallet: {:?}", e))?;
+
+    create_with_600_perms(&output, json.as_bytes())
+        .map_err(|e| format!("Unable to write {:?}: {:?}", output, e))?;
+
+    eprintln!("Exported wallet {} to {:?}", wallet_name, output);
+
+    Ok(())
+}
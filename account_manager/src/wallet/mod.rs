@@ -1,6 +1,10 @@
+pub mod change_password;
 pub mod create;
+pub mod delete;
+pub mod export;
 pub mod list;
 pub mod recover;
+pub mod show_mnemonic;
 
 use crate::{
     common::{base_wallet_dir, ensure_dir_exists},
@@ -20,9 +24,13 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("A path containing Eth2 EIP-2386 wallets. Defaults to ~/.lighthouse/wallets")
                 .takes_value(true),
         )
+        .subcommand(change_password::cli_app())
         .subcommand(create::cli_app())
+        .subcommand(delete::cli_app())
+        .subcommand(export::cli_app())
         .subcommand(list::cli_app())
         .subcommand(recover::cli_app())
+        .subcommand(show_mnemonic::cli_app())
 }
 
 pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
@@ -30,9 +38,13 @@ pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
     ensure_dir_exists(&base_dir)?;
 
     match matches.subcommand() {
+        (change_password::CMD, Some(matches)) => change_password::cli_run(matches, base_dir),
         (create::CMD, Some(matches)) => create::cli_run(matches, base_dir),
+        (delete::CMD, Some(matches)) => delete::cli_run(matches, base_dir),
+        (export::CMD, Some(matches)) => export::cli_run(matches, base_dir),
         (list::CMD, Some(_)) => list::cli_run(base_dir),
         (recover::CMD, Some(matches)) => recover::cli_run(matches, base_dir),
+        (show_mnemonic::CMD, Some(matches)) => show_mnemonic::cli_run(matches, base_dir),
         (unknown, _) => Err(format!(
             "{} does not have a {} command. See --help",
             CMD, unknown
@@ -0,0 +1,99 @@
+use crate::common::read_wallet_name_from_cli;
+use crate::validator::create::read_wallet_password_from_cli;
+use crate::wallet::create::{NAME_FLAG, PASSWORD_FLAG, STDIN_INPUTS_FLAG};
+use crate::BASE_DIR_FLAG;
+use account_utils::read_input_from_user;
+use clap::{App, Arg, ArgMatches};
+use eth2_wallet_manager::WalletManager;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+pub const CMD: &str = "show-mnemonic";
+
+pub const CONFIRMATION: &str = "yes";
+pub const CONFIRMATION_PROMPT: &str = "Enter \"yes\" to display the mnemonic:";
+pub const CLEAR_PROMPT: &str = "Press enter to clear the terminal.";
+
+/// ANSI escape codes which clear the screen and its scrollback, then move the cursor to the top.
+pub const CLEAR_TERMINAL: &str = "\x1b[2J\x1b[3J\x1b[H";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Displays the BIP-39 mnemonic of a wallet. Only available for wallets that were \
+            created or recovered from a mnemonic by this version of Lighthouse.",
+        )
+        .arg(
+            Arg::with_name(NAME_FLAG)
+                .long(NAME_FLAG)
+                .value_name("WALLET_NAME")
+                .help("Use the wallet identified by this name")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(PASSWORD_FLAG)
+                .long(PASSWORD_FLAG)
+                .value_name("WALLET_PASSWORD_PATH")
+                .help("A path to a file containing the password of the wallet.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+pub fn cli_run(matches: &ArgMatches, base_dir: PathBuf) -> Result<(), String> {
+    let name: Option<String> = clap_utils::parse_optional(matches, NAME_FLAG)?;
+    let password_path: Option<PathBuf> = clap_utils::parse_optional(matches, PASSWORD_FLAG)?;
+    let stdin_inputs = matches.is_present(STDIN_INPUTS_FLAG);
+
+    let mgr = WalletManager::open(&base_dir)
+        .map_err(|e| format!("Unable to open --{}: {:?}", BASE_DIR_FLAG, e))?;
+
+    let wallet_name = read_wallet_name_from_cli(name, stdin_inputs)?;
+    let wallet = mgr
+        .wallet_by_name(&wallet_name)
+        .map_err(|e| format!("Unable to open wallet: {:?}", e))?;
+
+    let password = read_wallet_password_from_cli(password_path, stdin_inputs)?;
+    let mnemonic = wallet
+        .decrypt_mnemonic(password.as_bytes())
+        .map_err(|e| format!("Unable to decrypt mnemonic: {:?}", e))?
+        .ok_or_else(|| {
+            format!(
+                "Wallet {} does not store its mnemonic. Only wallets created or recovered \
+                from a mnemonic by this version of Lighthouse can display it.",
+                wallet_name
+            )
+        })?;
+
+    eprintln!();
+    eprintln!("Anyone who sees this mnemonic can take control of all the validators");
+    eprintln!(
+        "derived from wallet {}. Make sure nobody can see your screen.",
+        wallet_name
+    );
+    eprintln!();
+    eprintln!("{}", CONFIRMATION_PROMPT);
+    if read_input_from_user(stdin_inputs)? != CONFIRMATION {
+        return Err("The mnemonic was not displayed".to_string());
+    }
+
+    println!();
+    println!("Your wallet's BIP-39 mnemonic is:");
+    println!();
+    println!("\t{}", mnemonic.phrase());
+    println!();
+
+    eprintln!("{}", CLEAR_PROMPT);
+    read_input_from_user(stdin_inputs)?;
+
+    print!("{}", CLEAR_TERMINAL);
+    io::stdout()
+        .flush()
+        .map_err(|e| format!("Unable to clear the terminal: {:?}", e))?;
+
+    Ok(())
+}
@@ -34,10 +34,12 @@ use crate::{
     metrics,
     observed_attestations::ObserveOutcome,
     observed_attesters::Error as ObservedAttestersError,
+    validator_pubkey_cache::ValidatorPubkeyCache,
     BeaconChain, BeaconChainError, BeaconChainTypes,
 };
-use bls::verify_signature_sets;
+use bls::{verify_signature_sets, SignatureSet};
 use proto_array::Block as ProtoBlock;
+use rayon::prelude::*;
 use slog::debug;
 use slot_clock::SlotClock;
 use state_processing::{
@@ -52,8 +54,8 @@ use state_processing::{
 use std::borrow::Cow;
 use tree_hash::TreeHash;
 use types::{
-    Attestation, BeaconCommittee, CommitteeIndex, Epoch, EthSpec, Hash256, IndexedAttestation,
-    RelativeEpoch, SelectionProof, SignedAggregateAndProof, Slot, SubnetId,
+    Attestation, BeaconCommittee, CommitteeIndex, Epoch, EthSpec, Fork, Hash256,
+    IndexedAttestation, RelativeEpoch, SelectionProof, SignedAggregateAndProof, Slot, SubnetId,
 };
 
 /// Returned when an attestation was not successfully verified. It might not have been verified for
@@ -269,6 +271,21 @@ pub struct VerifiedUnaggregatedAttestation<T: BeaconChainTypes> {
     indexed_attestation: IndexedAttestation<T::EthSpec>,
 }
 
+/// Wraps a `SignedAggregateAndProof` that has passed all gossip checks except for signature
+/// verification.
+struct IndexedAggregatedAttestation<T: BeaconChainTypes> {
+    signed_aggregate: SignedAggregateAndProof<T::EthSpec>,
+    indexed_attestation: IndexedAttestation<T::EthSpec>,
+    attestation_root: Hash256,
+}
+
+/// Wraps an `Attestation` that has passed all gossip checks except for signature verification.
+struct IndexedUnaggregatedAttestation<T: BeaconChainTypes> {
+    attestation: Attestation<T::EthSpec>,
+    indexed_attestation: IndexedAttestation<T::EthSpec>,
+    validator_index: u64,
+}
+
 /// Custom `Clone` implementation is to avoid the restrictive trait bounds applied by the usual derive
 /// macro.
 impl<T: BeaconChainTypes> Clone for VerifiedUnaggregatedAttestation<T> {
@@ -284,24 +301,34 @@ impl<T: BeaconChainTypes> Clone for VerifiedUnaggregatedAttestation<T> {
 /// verified for application to fork choice.
 pub trait SignatureVerifiedAttestation<T: BeaconChainTypes> {
     fn indexed_attestation(&self) -> &IndexedAttestation<T::EthSpec>;
+
+    fn attestation(&self) -> &Attestation<T::EthSpec>;
 }
 
 impl<'a, T: BeaconChainTypes> SignatureVerifiedAttestation<T> for VerifiedAggregatedAttestation<T> {
     fn indexed_attestation(&self) -> &IndexedAttestation<T::EthSpec> {
         &self.indexed_attestation
     }
+
+    fn attestation(&self) -> &Attestation<T::EthSpec> {
+        &self.signed_aggregate.message.aggregate
+    }
 }
 
 impl<T: BeaconChainTypes> SignatureVerifiedAttestation<T> for VerifiedUnaggregatedAttestation<T> {
     fn indexed_attestation(&self) -> &IndexedAttestation<T::EthSpec> {
         &self.indexed_attestation
     }
+
+    fn attestation(&self) -> &Attestation<T::EthSpec> {
+        &self.attestation
+    }
 }
 
-impl<T: BeaconChainTypes> VerifiedAggregatedAttestation<T> {
-    /// Returns `Ok(Self)` if the `signed_aggregate` is valid to be (re)published on the gossip
-    /// network.
-    pub fn verify(
+impl<T: BeaconChainTypes> IndexedAggregatedAttestation<T> {
+    /// Returns `Ok(Self)` if the `signed_aggregate` passes all the checks required for
+    /// propagation on the gossip network, except for signature verification.
+    fn verify(
         signed_aggregate: SignedAggregateAndProof<T::EthSpec>,
         chain: &BeaconChain<T>,
     ) -> Result<Self, Error> {
@@ -390,11 +417,49 @@ impl<T: BeaconChainTypes> VerifiedAggregatedAttestation<T> {
                     .map_err(|e| BeaconChainError::from(e).into())
             })?;
 
+        Ok(IndexedAggregatedAttestation {
+            signed_aggregate,
+            indexed_attestation,
+            attestation_root,
+        })
+    }
+}
+
+impl<T: BeaconChainTypes> VerifiedAggregatedAttestation<T> {
+    /// Returns `Ok(Self)` if the `signed_aggregate` is valid to be (re)published on the gossip
+    /// network.
+    pub fn verify(
+        signed_aggregate: SignedAggregateAndProof<T::EthSpec>,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, Error> {
+        let indexed = IndexedAggregatedAttestation::verify(signed_aggregate, chain)?;
+
         // Ensure that all signatures are valid.
-        if !verify_signed_aggregate_signatures(chain, &signed_aggregate, &indexed_attestation)? {
+        if !verify_signed_aggregate_signatures(
+            chain,
+            &indexed.signed_aggregate,
+            &indexed.indexed_attestation,
+        )? {
             return Err(Error::InvalidSignature);
         }
 
+        Self::from_signature_verified(indexed, chain)
+    }
+
+    /// Completes the verification of an `IndexedAggregatedAttestation` whose signatures are known
+    /// to be valid, observing it so that it is not processed again.
+    fn from_signature_verified(
+        indexed: IndexedAggregatedAttestation<T>,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, Error> {
+        let IndexedAggregatedAttestation {
+            signed_aggregate,
+            indexed_attestation,
+            attestation_root,
+        } = indexed;
+        let attestation = &signed_aggregate.message.aggregate;
+        let aggregator_index = signed_aggregate.message.aggregator_index;
+
         // Observe the valid attestation so we do not re-process it.
         //
         // It's important to double check that the attestation is not already known, otherwise two
@@ -439,13 +504,13 @@ impl<T: BeaconChainTypes> VerifiedAggregatedAttestation<T> {
     }
 }
 
-impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
-    /// Returns `Ok(Self)` if the `attestation` is valid to be (re)published on the gossip
-    /// network.
+impl<T: BeaconChainTypes> IndexedUnaggregatedAttestation<T> {
+    /// Returns `Ok(Self)` if the `attestation` passes all the checks required for propagation on
+    /// the gossip network, except for signature verification.
     ///
     /// `subnet_id` is the subnet from which we received this attestation. This function will
     /// verify that it was received on the correct subnet.
-    pub fn verify(
+    fn verify(
         attestation: Attestation<T::EthSpec>,
         subnet_id: SubnetId,
         chain: &BeaconChain<T>,
@@ -521,8 +586,44 @@ impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
             });
         }
 
+        Ok(IndexedUnaggregatedAttestation {
+            attestation,
+            indexed_attestation,
+            validator_index,
+        })
+    }
+}
+
+impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
+    /// Returns `Ok(Self)` if the `attestation` is valid to be (re)published on the gossip
+    /// network.
+    ///
+    /// `subnet_id` is the subnet from which we received this attestation. This function will
+    /// verify that it was received on the correct subnet.
+    pub fn verify(
+        attestation: Attestation<T::EthSpec>,
+        subnet_id: SubnetId,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, Error> {
+        let indexed = IndexedUnaggregatedAttestation::verify(attestation, subnet_id, chain)?;
+
         // The aggregate signature of the attestation is valid.
-        verify_attestation_signature(chain, &indexed_attestation)?;
+        verify_attestation_signature(chain, &indexed.indexed_attestation)?;
+
+        Self::from_signature_verified(indexed, chain)
+    }
+
+    /// Completes the verification of an `IndexedUnaggregatedAttestation` whose signature is known
+    /// to be valid, observing the attester so that no other attestation is accepted from them.
+    fn from_signature_verified(
+        indexed: IndexedUnaggregatedAttestation<T>,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, Error> {
+        let IndexedUnaggregatedAttestation {
+            attestation,
+            indexed_attestation,
+            validator_index,
+        } = indexed;
 
         // Now that the attestation has been fully verified, store that we have received a valid
         // attestation from this validator.
@@ -754,46 +855,316 @@ pub fn verify_signed_aggregate_signatures<T: BeaconChainTypes>(
         .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
         .ok_or_else(|| BeaconChainError::ValidatorPubkeyCacheLockTimeout)?;
 
-    let aggregator_index = signed_aggregate.message.aggregator_index;
-    if aggregator_index >= pubkey_cache.len() as u64 {
-        return Err(Error::AggregatorPubkeyUnknown(aggregator_index));
-    }
-
     let fork = chain
         .canonical_head
         .try_read_for(HEAD_LOCK_TIMEOUT)
         .ok_or_else(|| BeaconChainError::CanonicalHeadLockTimeout)
         .map(|head| head.beacon_state.fork)?;
 
-    let signature_sets = vec![
+    let signature_sets = signed_aggregate_signature_sets(
+        chain,
+        &pubkey_cache,
+        &fork,
+        signed_aggregate,
+        indexed_attestation,
+    )?;
+
+    Ok(verify_signature_sets(signature_sets.iter()))
+}
+
+/// Returns the three signature sets of a `SignedAggregateAndProof` (see
+/// `verify_signed_aggregate_signatures`), without verifying them.
+fn signed_aggregate_signature_sets<'a, T: BeaconChainTypes>(
+    chain: &'a BeaconChain<T>,
+    pubkey_cache: &'a ValidatorPubkeyCache,
+    fork: &Fork,
+    signed_aggregate: &'a SignedAggregateAndProof<T::EthSpec>,
+    indexed_attestation: &'a IndexedAttestation<T::EthSpec>,
+) -> Result<Vec<SignatureSet<'a>>, Error> {
+    let aggregator_index = signed_aggregate.message.aggregator_index;
+    if aggregator_index >= pubkey_cache.len() as u64 {
+        return Err(Error::AggregatorPubkeyUnknown(aggregator_index));
+    }
+
+    Ok(vec![
         signed_aggregate_selection_proof_signature_set(
-            |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
-            &signed_aggregate,
-            &fork,
+            move |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+            signed_aggregate,
+            fork,
             chain.genesis_validators_root,
             &chain.spec,
         )
         .map_err(BeaconChainError::SignatureSetError)?,
         signed_aggregate_signature_set(
-            |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
-            &signed_aggregate,
-            &fork,
+            move |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+            signed_aggregate,
+            fork,
             chain.genesis_validators_root,
             &chain.spec,
         )
         .map_err(BeaconChainError::SignatureSetError)?,
         indexed_attestation_signature_set_from_pubkeys(
-            |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+            move |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
             &indexed_attestation.signature,
-            &indexed_attestation,
-            &fork,
+            indexed_attestation,
+            fork,
             chain.genesis_validators_root,
             &chain.spec,
         )
         .map_err(BeaconChainError::SignatureSetError)?,
-    ];
+    ])
+}
 
-    Ok(verify_signature_sets(signature_sets.iter()))
+/// Verifies a batch of `SignedAggregateAndProof` received on the gossip network, returning a result
+/// for each of the `signed_aggregates` (in the same order).
+///
+/// The outcome is the same as calling `VerifiedAggregatedAttestation::verify` on each aggregate,
+/// except that the signatures of all the aggregates that pass the preliminary checks are verified
+/// together. See `verify_signature_set_batch`.
+pub fn batch_verify_aggregated_attestations<T: BeaconChainTypes>(
+    signed_aggregates: Vec<SignedAggregateAndProof<T::EthSpec>>,
+    chain: &BeaconChain<T>,
+) -> Vec<Result<VerifiedAggregatedAttestation<T>, Error>> {
+    let indexed_results = signed_aggregates
+        .into_iter()
+        .map(|signed_aggregate| IndexedAggregatedAttestation::verify(signed_aggregate, chain))
+        .collect::<Vec<_>>();
+
+    let signature_results = match aggregated_signature_results(chain, &indexed_results) {
+        Ok(results) => results,
+        // An error that applies to the entire batch (e.g., a lock timeout) causes each aggregate to
+        // be verified individually.
+        Err(e) => {
+            debug!(
+                chain.log,
+                "Unable to batch verify aggregates";
+                "error" => format!("{:?}", e)
+            );
+            return indexed_results
+                .into_iter()
+                .map(|indexed_result| {
+                    let indexed = indexed_result?;
+                    if !verify_signed_aggregate_signatures(
+                        chain,
+                        &indexed.signed_aggregate,
+                        &indexed.indexed_attestation,
+                    )? {
+                        return Err(Error::InvalidSignature);
+                    }
+                    VerifiedAggregatedAttestation::from_signature_verified(indexed, chain)
+                })
+                .collect();
+        }
+    };
+
+    indexed_results
+        .into_iter()
+        .zip(signature_results)
+        .map(|(indexed_result, signature_result)| {
+            let indexed = indexed_result?;
+            signature_result?;
+            VerifiedAggregatedAttestation::from_signature_verified(indexed, chain)
+        })
+        .collect()
+}
+
+/// Verifies a batch of unaggregated `Attestation` received on the gossip network, returning a
+/// result for each of the `attestations` (in the same order).
+///
+/// The outcome is the same as calling `VerifiedUnaggregatedAttestation::verify` on each
+/// attestation, except that the signatures of all the attestations that pass the preliminary
+/// checks are verified together. See `verify_signature_set_batch`.
+pub fn batch_verify_unaggregated_attestations<T: BeaconChainTypes>(
+    attestations: Vec<(Attestation<T::EthSpec>, SubnetId)>,
+    chain: &BeaconChain<T>,
+) -> Vec<Result<VerifiedUnaggregatedAttestation<T>, Error>> {
+    let indexed_results = attestations
+        .into_iter()
+        .map(|(attestation, subnet_id)| {
+            IndexedUnaggregatedAttestation::verify(attestation, subnet_id, chain)
+        })
+        .collect::<Vec<_>>();
+
+    let signature_results = match unaggregated_signature_results(chain, &indexed_results) {
+        Ok(results) => results,
+        // An error that applies to the entire batch (e.g., a lock timeout) causes each attestation
+        // to be verified individually.
+        Err(e) => {
+            debug!(
+                chain.log,
+                "Unable to batch verify attestations";
+                "error" => format!("{:?}", e)
+            );
+            return indexed_results
+                .into_iter()
+                .map(|indexed_result| {
+                    let indexed = indexed_result?;
+                    verify_attestation_signature(chain, &indexed.indexed_attestation)?;
+                    VerifiedUnaggregatedAttestation::from_signature_verified(indexed, chain)
+                })
+                .collect();
+        }
+    };
+
+    indexed_results
+        .into_iter()
+        .zip(signature_results)
+        .map(|(indexed_result, signature_result)| {
+            let indexed = indexed_result?;
+            signature_result?;
+            VerifiedUnaggregatedAttestation::from_signature_verified(indexed, chain)
+        })
+        .collect()
+}
+
+/// Verifies the signatures of each `Ok` entry in `indexed_results`, returning a result for each
+/// entry. Entries that are `Err` are not verified and are given an `Ok(())` result.
+///
+/// Returns `Err` if an error prevented the setup of signature verification for the entire batch.
+fn aggregated_signature_results<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    indexed_results: &[Result<IndexedAggregatedAttestation<T>, Error>],
+) -> Result<Vec<Result<(), Error>>, Error> {
+    let signature_setup_timer =
+        metrics::start_timer(&metrics::ATTESTATION_PROCESSING_SIGNATURE_SETUP_TIMES);
+
+    let pubkey_cache = chain
+        .validator_pubkey_cache
+        .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
+        .ok_or_else(|| BeaconChainError::ValidatorPubkeyCacheLockTimeout)?;
+
+    let fork = chain
+        .canonical_head
+        .try_read_for(HEAD_LOCK_TIMEOUT)
+        .ok_or_else(|| BeaconChainError::CanonicalHeadLockTimeout)
+        .map(|head| head.beacon_state.fork)?;
+
+    let signature_sets = indexed_results
+        .iter()
+        .map(|indexed_result| match indexed_result {
+            Ok(indexed) => signed_aggregate_signature_sets(
+                chain,
+                &pubkey_cache,
+                &fork,
+                &indexed.signed_aggregate,
+                &indexed.indexed_attestation,
+            ),
+            Err(_) => Ok(vec![]),
+        })
+        .collect();
+
+    metrics::stop_timer(signature_setup_timer);
+
+    Ok(verify_signature_set_batch(signature_sets))
+}
+
+/// Verifies the signatures of each `Ok` entry in `indexed_results`, returning a result for each
+/// entry. Entries that are `Err` are not verified and are given an `Ok(())` result.
+///
+/// Returns `Err` if an error prevented the setup of signature verification for the entire batch.
+fn unaggregated_signature_results<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    indexed_results: &[Result<IndexedUnaggregatedAttestation<T>, Error>],
+) -> Result<Vec<Result<(), Error>>, Error> {
+    let signature_setup_timer =
+        metrics::start_timer(&metrics::ATTESTATION_PROCESSING_SIGNATURE_SETUP_TIMES);
+
+    let pubkey_cache = chain
+        .validator_pubkey_cache
+        .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
+        .ok_or_else(|| BeaconChainError::ValidatorPubkeyCacheLockTimeout)?;
+
+    let fork = chain
+        .canonical_head
+        .try_read_for(HEAD_LOCK_TIMEOUT)
+        .ok_or_else(|| BeaconChainError::CanonicalHeadLockTimeout)
+        .map(|head| head.beacon_state.fork)?;
+
+    let signature_sets = indexed_results
+        .iter()
+        .map(|indexed_result| match indexed_result {
+            Ok(indexed) => indexed_attestation_signature_set_from_pubkeys(
+                |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+                &indexed.indexed_attestation.signature,
+                &indexed.indexed_attestation,
+                &fork,
+                chain.genesis_validators_root,
+                &chain.spec,
+            )
+            .map(|signature_set| vec![signature_set])
+            .map_err(|e| Error::BeaconChainError(BeaconChainError::SignatureSetError(e))),
+            Err(_) => Ok(vec![]),
+        })
+        .collect();
+
+    metrics::stop_timer(signature_setup_timer);
+
+    Ok(verify_signature_set_batch(signature_sets))
+}
+
+/// Verifies the signature sets belonging to a batch of attestations, returning a result for each
+/// entry of `signature_sets`. An entry with no signature sets is considered valid.
+///
+/// All the signature sets are first verified at once, with the work split across the `rayon`
+/// thread pool. It is not possible to know which set was invalid if this fails, so the sets of
+/// each entry are then verified individually. This ensures that an invalid attestation is only
+/// attributed to the peer that sent it.
+fn verify_signature_set_batch(
+    signature_sets: Vec<Result<Vec<SignatureSet>, Error>>,
+) -> Vec<Result<(), Error>> {
+    let _signature_verification_timer =
+        metrics::start_timer(&metrics::ATTESTATION_PROCESSING_SIGNATURE_TIMES);
+
+    let all_sets = signature_sets
+        .iter()
+        .filter_map(|sets| sets.as_ref().ok())
+        .flatten()
+        .collect::<Vec<_>>();
+
+    metrics::observe(
+        &metrics::ATTESTATION_PROCESSING_BATCH_SIGNATURE_SETS,
+        all_sets.len() as f64,
+    );
+
+    let chunk_size = std::cmp::max(1, all_sets.len() / rayon::current_num_threads());
+    let batch_is_valid = all_sets
+        .par_chunks(chunk_size)
+        .map(|chunk| verify_signature_sets(chunk.iter().copied()))
+        .reduce(|| true, |current, this| current && this);
+
+    if batch_is_valid {
+        return signature_sets
+            .into_iter()
+            .map(|sets| sets.map(|_| ()))
+            .collect();
+    }
+
+    metrics::inc_counter(&metrics::ATTESTATION_PROCESSING_BATCH_SIGNATURE_FAILURES);
+
+    let entries_are_valid = signature_sets
+        .iter()
+        .map(|sets| sets.as_ref().ok())
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|sets| {
+            sets.map_or(true, |sets| {
+                sets.is_empty() || verify_signature_sets(sets.iter())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    signature_sets
+        .into_iter()
+        .zip(entries_are_valid)
+        .map(|(sets, is_valid)| {
+            sets?;
+            if is_valid {
+                Ok(())
+            } else {
+                Err(Error::InvalidSignature)
+            }
+        })
+        .collect()
 }
 
 /// Assists in readability.
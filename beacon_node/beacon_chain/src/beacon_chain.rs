@@ -1,4 +1,5 @@
 use crate::attestation_verification::{
+    batch_verify_aggregated_attestations, batch_verify_unaggregated_attestations,
     Error as AttestationError, SignatureVerifiedAttestation, VerifiedAggregatedAttestation,
     VerifiedUnaggregatedAttestation,
 };
@@ -20,6 +21,7 @@ use crate::observed_block_producers::ObservedBlockProducers;
 use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::persisted_beacon_chain::PersistedBeaconChain;
 use crate::persisted_fork_choice::PersistedForkChoice;
+use crate::reorg_history::{ReorgEvent, ReorgHistory};
 use crate::shuffling_cache::ShufflingCache;
 use crate::snapshot_cache::SnapshotCache;
 use crate::timeout_rw_lock::TimeoutRwLock;
@@ -46,7 +48,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io::prelude::*;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use store::iter::{BlockRootsIterator, ParentRootBlockIterator, StateRootsIterator};
 use store::{Error as DBError, HotColdDB, StoreOp};
 use types::*;
@@ -68,6 +70,10 @@ pub const ATTESTATION_CACHE_LOCK_TIMEOUT: Duration = Duration::from_secs(1);
 /// validator pubkey cache.
 pub const VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT: Duration = Duration::from_secs(1);
 
+/// The time-out before failure during an operation to take a read/write RwLock on the re-org
+/// history.
+pub const REORG_HISTORY_LOCK_TIMEOUT: Duration = Duration::from_secs(1);
+
 pub const BEACON_CHAIN_DB_KEY: [u8; 32] = [0; 32];
 pub const OP_POOL_DB_KEY: [u8; 32] = [0; 32];
 pub const ETH1_CACHE_DB_KEY: [u8; 32] = [0; 32];
@@ -219,6 +225,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub(crate) snapshot_cache: TimeoutRwLock<SnapshotCache<T::EthSpec>>,
     /// Caches the shuffling for a given epoch and state root.
     pub(crate) shuffling_cache: TimeoutRwLock<ShufflingCache>,
+    /// A record of the most recent re-orgs of the canonical chain.
+    pub(crate) reorg_history: TimeoutRwLock<ReorgHistory>,
     /// Caches a map of `validator_index -> validator_pubkey`.
     pub(crate) validator_pubkey_cache: TimeoutRwLock<ValidatorPubkeyCache>,
     /// A list of any hard-coded forks that have been disabled.
@@ -373,6 +381,29 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(iter.map(|result| result.map_err(Into::into)))
     }
 
+    /// Iterate through the block roots of the canonical chain from `start_slot` up to (but not
+    /// including) `end_slot`.
+    ///
+    /// If the whole range is finalized and stored in the freezer, the roots are read directly
+    /// from the freezer without loading any states. Otherwise this behaves like
+    /// `forwards_iter_block_roots`.
+    pub fn forwards_iter_block_roots_until(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<Box<dyn Iterator<Item = Result<(Hash256, Slot), Error>>>, Error> {
+        if end_slot <= self.store.get_latest_restore_point_slot() {
+            let iter =
+                HotColdDB::frozen_block_roots_iterator(self.store.clone(), start_slot, end_slot);
+            Ok(Box::new(iter.map(|result| result.map_err(Into::into))))
+        } else {
+            let iter = self.forwards_iter_block_roots(start_slot)?;
+            Ok(Box::new(iter.take_while(move |result| {
+                result.as_ref().map_or(true, |(_, slot)| *slot < end_slot)
+            })))
+        }
+    }
+
     /// Traverse backwards from `block_root` to find the block roots of its ancestors.
     ///
     /// ## Notes
@@ -386,14 +417,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         &self,
         block_root: Hash256,
     ) -> Result<impl Iterator<Item = Result<(Hash256, Slot), Error>>, Error> {
-        let block = self
-            .get_block(&block_root)?
+        let (slot, state_root) = self
+            .store
+            .get_block_slot_and_state_root(&block_root)?
             .ok_or_else(|| Error::MissingBeaconBlock(block_root))?;
         let state = self
-            .get_state(&block.state_root(), Some(block.slot()))?
-            .ok_or_else(|| Error::MissingBeaconState(block.state_root()))?;
+            .get_state(&state_root, Some(slot))?
+            .ok_or_else(|| Error::MissingBeaconState(state_root))?;
         let iter = BlockRootsIterator::owned(self.store.clone(), state);
-        Ok(std::iter::once(Ok((block_root, block.slot())))
+        Ok(std::iter::once(Ok((block_root, slot)))
             .chain(iter)
             .map(|result| result.map_err(|e| e.into())))
     }
@@ -451,10 +483,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         &self,
         slot: Slot,
     ) -> Result<Option<SignedBeaconBlock<T::EthSpec>>, Error> {
-        let root = process_results(self.rev_iter_block_roots()?, |mut iter| {
-            iter.find(|(_, this_slot)| *this_slot == slot)
-                .map(|(root, _)| root)
-        })?;
+        let root = match self.store.get_cold_block_root(slot)? {
+            Some(root) => Some(root),
+            None => process_results(self.rev_iter_block_roots()?, |mut iter| {
+                iter.find(|(_, this_slot)| *this_slot == slot)
+                    .map(|(root, _)| root)
+            })?,
+        };
 
         if let Some(block_root) = root {
             Ok(self.store.get_item(&block_root)?)
@@ -950,6 +985,48 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    /// Performs the same verification as `verify_unaggregated_attestation_for_gossip` on a batch of
+    /// attestations, verifying their signatures together. Returns a result for each attestation, in
+    /// the same order as `attestations`.
+    pub fn batch_verify_unaggregated_attestations_for_gossip(
+        &self,
+        attestations: Vec<(Attestation<T::EthSpec>, SubnetId)>,
+    ) -> Vec<Result<VerifiedUnaggregatedAttestation<T>, AttestationError>> {
+        metrics::inc_counter_by(
+            &metrics::UNAGGREGATED_ATTESTATION_PROCESSING_REQUESTS,
+            attestations.len() as i64,
+        );
+        let results = batch_verify_unaggregated_attestations(attestations, self);
+
+        metrics::inc_counter_by(
+            &metrics::UNAGGREGATED_ATTESTATION_PROCESSING_SUCCESSES,
+            results.iter().filter(|result| result.is_ok()).count() as i64,
+        );
+
+        results
+    }
+
+    /// Performs the same verification as `verify_aggregated_attestation_for_gossip` on a batch of
+    /// aggregates, verifying their signatures together. Returns a result for each aggregate, in
+    /// the same order as `signed_aggregates`.
+    pub fn batch_verify_aggregated_attestations_for_gossip(
+        &self,
+        signed_aggregates: Vec<SignedAggregateAndProof<T::EthSpec>>,
+    ) -> Vec<Result<VerifiedAggregatedAttestation<T>, AttestationError>> {
+        metrics::inc_counter_by(
+            &metrics::AGGREGATED_ATTESTATION_PROCESSING_REQUESTS,
+            signed_aggregates.len() as i64,
+        );
+        let results = batch_verify_aggregated_attestations(signed_aggregates, self);
+
+        metrics::inc_counter_by(
+            &metrics::AGGREGATED_ATTESTATION_PROCESSING_SUCCESSES,
+            results.iter().filter(|result| result.is_ok()).count() as i64,
+        );
+
+        results
+    }
+
     /// Accepts some attestation-type object and attempts to verify it in the context of fork
     /// choice. If it is valid it is applied to `self.fork_choice`.
     ///
@@ -965,8 +1042,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         self.fork_choice
             .write()
-            .on_attestation(self.slot()?, verified.indexed_attestation())
-            .map_err(Into::into)
+            .on_attestation(self.slot()?, verified.indexed_attestation())?;
+
+        let _ = self
+            .event_handler
+            .register(EventKind::BeaconAttestationImported {
+                attestation: Box::new(verified.attestation().clone()),
+            });
+
+        Ok(())
     }
 
     /// Accepts an `VerifiedUnaggregatedAttestation` and attempts to apply it to the "naive
@@ -1282,27 +1366,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
-        while let Some((_root, block)) = filtered_chain_segment.first() {
-            // Determine the epoch of the first block in the remaining segment.
-            let start_epoch = block.slot().epoch(T::EthSpec::slots_per_epoch());
-
-            // The `last_index` indicates the position of the last block that is in the current
-            // epoch of `start_epoch`.
-            let last_index = filtered_chain_segment
-                .iter()
-                .position(|(_root, block)| {
-                    block.slot().epoch(T::EthSpec::slots_per_epoch()) > start_epoch
-                })
-                .unwrap_or_else(|| filtered_chain_segment.len());
-
-            // Split off the first section blocks that are all either within the current epoch of
-            // the first block. These blocks can all be signature-verified with the same
-            // `BeaconState`.
-            let mut blocks = filtered_chain_segment.split_off(last_index);
-            std::mem::swap(&mut blocks, &mut filtered_chain_segment);
-
-            // Verify the signature of the blocks, returning early if the signature is invalid.
-            let signature_verified_blocks = match signature_verify_chain_segment(blocks, self) {
+        // Verify the signatures of all the blocks in a single batch, returning early if any
+        // signature is invalid.
+        let signature_verified_blocks =
+            match signature_verify_chain_segment(filtered_chain_segment, self) {
                 Ok(blocks) => blocks,
                 Err(error) => {
                     return ChainSegmentResult::Failed {
@@ -1312,16 +1379,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 }
             };
 
-            // Import the blocks into the chain.
-            for signature_verified_block in signature_verified_blocks {
-                match self.process_block(signature_verified_block) {
-                    Ok(_) => imported_blocks += 1,
-                    Err(error) => {
-                        return ChainSegmentResult::Failed {
-                            imported_blocks,
-                            error,
-                        };
-                    }
+        // Import the blocks into the chain.
+        for signature_verified_block in signature_verified_blocks {
+            match self.process_block(signature_verified_block) {
+                Ok(_) => imported_blocks += 1,
+                Err(error) => {
+                    return ChainSegmentResult::Failed {
+                        imported_blocks,
+                        error,
+                    };
                 }
             }
         }
@@ -1867,20 +1933,65 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             })?;
 
         // Attempt to detect if the new head is not on the same chain as the previous block
-        // (i.e., a re-org).
+        // (i.e., a re-org) by finding the common ancestor of both heads in fork choice.
         //
-        // Note: this will declare a re-org if we skip `SLOTS_PER_HISTORICAL_ROOT` blocks
-        // between calls to fork choice without swapping between chains. This seems like an
-        // extreme-enough scenario that a warning is fine.
-        let is_reorg = current_head.block_root
-            != new_head
+        // If the previous head is no longer known to fork choice (e.g., it was pruned after
+        // finalization) fall back to comparing the `block_roots` of the two head states.
+        let common_ancestor = self
+            .fork_choice
+            .read()
+            .proto_array()
+            .common_ancestor(current_head.block_root, beacon_block_root);
+        let reorg = match common_ancestor {
+            Some((ancestor_root, _)) if ancestor_root == current_head.block_root => None,
+            Some((ancestor_root, ancestor_slot)) => Some((
+                current_head
+                    .slot
+                    .as_u64()
+                    .saturating_sub(ancestor_slot.as_u64()),
+                Some((ancestor_root, ancestor_slot)),
+            )),
+            None => {
+                let depth = reorg_depth(
+                    &self
+                        .canonical_head
+                        .try_read_for(HEAD_LOCK_TIMEOUT)
+                        .ok_or_else(|| Error::CanonicalHeadLockTimeout)?
+                        .beacon_state,
+                    current_head.block_root,
+                    &new_head.beacon_state,
+                );
+
+                if depth == 0 {
+                    None
+                } else {
+                    Some((depth, None))
+                }
+            }
+        };
+        let is_reorg = reorg.is_some();
+
+        let reorg_event = if let Some((depth, common_ancestor)) = reorg {
+            let new_finalized_slot = new_head
                 .beacon_state
-                .get_block_root(current_head.slot)
-                .map(|root| *root)
-                .unwrap_or_else(|_| Hash256::random());
+                .finalized_checkpoint
+                .epoch
+                .start_slot(T::EthSpec::slots_per_epoch());
+            let distance_from_finalization = common_ancestor.map(|(_, ancestor_slot)| {
+                ancestor_slot
+                    .as_u64()
+                    .saturating_sub(new_finalized_slot.as_u64())
+            });
 
-        if is_reorg {
             metrics::inc_counter(&metrics::FORK_CHOICE_REORG_COUNT);
+            metrics::observe(&metrics::FORK_CHOICE_REORG_DEPTH, depth as f64);
+            if let Some(distance) = distance_from_finalization {
+                metrics::observe(
+                    &metrics::FORK_CHOICE_REORG_DISTANCE_FROM_FINALIZATION,
+                    distance as f64,
+                );
+            }
+
             warn!(
                 self.log,
                 "Beacon chain re-org";
@@ -1888,8 +1999,39 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 "previous_slot" => current_head.slot,
                 "new_head_parent" => format!("{}", new_head.beacon_block.parent_root()),
                 "new_head" => format!("{}", beacon_block_root),
-                "new_slot" => new_head.beacon_block.slot()
+                "new_slot" => new_head.beacon_block.slot(),
+                "reorg_depth" => depth,
+                "common_ancestor" => format!("{:?}", common_ancestor.map(|(root, _)| root)),
             );
+
+            let seen_timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0);
+
+            let reorg_event = ReorgEvent {
+                slot: new_head.beacon_block.slot(),
+                depth,
+                old_head_block: current_head.block_root,
+                old_head_slot: current_head.slot,
+                new_head_block: beacon_block_root,
+                common_ancestor: common_ancestor.map(|(root, _)| root),
+                distance_from_finalization,
+                seen_timestamp,
+            };
+            self.reorg_history
+                .try_write_for(REORG_HISTORY_LOCK_TIMEOUT)
+                .map(|mut reorg_history| reorg_history.insert(reorg_event.clone()))
+                .unwrap_or_else(|| {
+                    error!(
+                        self.log,
+                        "Failed to obtain reorg history write lock";
+                        "lock" => "reorg_history",
+                        "task" => "update head"
+                    );
+                });
+
+            Some((reorg_event, common_ancestor))
         } else {
             debug!(
                 self.log,
@@ -1901,6 +2043,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 "root" => format!("{}", beacon_block_root),
                 "slot" => new_head.beacon_block.slot(),
             );
+
+            None
         };
 
         let new_finalized_checkpoint = new_head.beacon_state.finalized_checkpoint;
@@ -1942,6 +2086,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.persist_head_and_fork_choice()?;
         }
 
+        let new_head_slot = new_head.beacon_block.slot();
+        let new_head_state_root = new_head.beacon_state_root;
+
         let update_head_timer = metrics::start_timer(&metrics::UPDATE_HEAD_TIMES);
 
         // Update the snapshot that stores the head of the chain at the time it received the
@@ -1981,6 +2128,20 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             current_head_beacon_block_root: beacon_block_root,
         });
 
+        if let Some((reorg_event, common_ancestor)) = reorg_event {
+            let _ = self.event_handler.register(EventKind::ChainReorg {
+                slot: new_head_slot,
+                depth: reorg_event.depth,
+                old_head_block: current_head.block_root,
+                new_head_block: beacon_block_root,
+                old_head_state: current_head.state_root,
+                new_head_state: new_head_state_root,
+                common_ancestor_block: common_ancestor.map(|(root, _)| root),
+                common_ancestor_slot: common_ancestor.map(|(_, slot)| slot),
+                epoch: new_head_slot.epoch(T::EthSpec::slots_per_epoch()),
+            });
+        }
+
         Ok(())
     }
 
@@ -2102,6 +2263,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Returns the most recent re-orgs of the canonical chain, oldest first.
+    pub fn reorg_history(&self) -> Result<Vec<ReorgEvent>, Error> {
+        self.reorg_history
+            .try_read_for(REORG_HISTORY_LOCK_TIMEOUT)
+            .map(|reorg_history| reorg_history.events())
+            .ok_or_else(|| Error::ReorgHistoryLockTimeout)
+    }
+
     /// Returns `true` if the given block root has not been processed.
     pub fn is_new_block_root(&self, beacon_block_root: &Hash256) -> Result<bool, Error> {
         Ok(!self
@@ -2291,6 +2460,46 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     }
 }
 
+/// Returns the number of slots between the head of `old_state` (with block root
+/// `old_head_block_root`) and the most recent slot at which it shares a block root with
+/// `new_state`.
+///
+/// This is only used when the previous head is unknown to fork choice. The search is limited to
+/// the `block_roots` of both states, so `SLOTS_PER_HISTORICAL_ROOT` is returned if no common
+/// ancestor can be found.
+fn reorg_depth<E: EthSpec>(
+    old_state: &BeaconState<E>,
+    old_head_block_root: Hash256,
+    new_state: &BeaconState<E>,
+) -> u64 {
+    let old_head_slot = old_state.slot;
+
+    for depth in 0..E::slots_per_historical_root() as u64 {
+        let slot = match old_head_slot.as_u64().checked_sub(depth) {
+            Some(slot) => Slot::new(slot),
+            None => break,
+        };
+
+        let old_root = if slot == old_head_slot {
+            old_head_block_root
+        } else {
+            match old_state.get_block_root(slot) {
+                Ok(root) => *root,
+                Err(_) => break,
+            }
+        };
+
+        match new_state.get_block_root(slot) {
+            Ok(new_root) if *new_root == old_root => return depth,
+            Ok(_) => (),
+            Err(_) if slot >= new_state.slot => (),
+            Err(_) => break,
+        }
+    }
+
+    E::slots_per_historical_root() as u64
+}
+
 impl<T: BeaconChainTypes> Drop for BeaconChain<T> {
     fn drop(&mut self) {
         let drop = || -> Result<(), Error> {
@@ -350,3 +350,15 @@ pub struct PersistedForkChoiceStore {
     justified_balances: Vec<u64>,
     best_justified_checkpoint: Checkpoint,
 }
+
+impl PersistedForkChoiceStore {
+    /// The finalized checkpoint at the time the store was persisted.
+    pub fn finalized_checkpoint(&self) -> &Checkpoint {
+        &self.finalized_checkpoint
+    }
+
+    /// The justified checkpoint at the time the store was persisted.
+    pub fn justified_checkpoint(&self) -> &Checkpoint {
+        &self.justified_checkpoint
+    }
+}
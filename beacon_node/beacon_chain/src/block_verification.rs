@@ -274,10 +274,14 @@ impl<T: EthSpec> From<DBError> for BlockError<T> {
 /// signature in the block is invalid, an `Err` is returned (it is not possible to known _which_
 /// signature was invalid).
 ///
+/// The `chain_segment` may span any number of epochs. The signatures of each epoch are read using
+/// a state from the start of that epoch (see `states_for_chain_segment`) and all signatures are
+/// then verified in a single batch.
+///
 /// ## Errors
 ///
-/// The given `chain_segment` must span no more than two epochs, otherwise an error will be
-/// returned.
+/// The given `chain_segment` must be a linear chain of blocks that descends from a known parent,
+/// otherwise an error will be returned.
 pub fn signature_verify_chain_segment<T: BeaconChainTypes>(
     mut chain_segment: Vec<(Hash256, SignedBeaconBlock<T::EthSpec>)>,
     chain: &BeaconChain<T>,
@@ -296,24 +300,73 @@ pub fn signature_verify_chain_segment<T: BeaconChainTypes>(
         .map(|(_, block)| block.slot())
         .unwrap_or_else(|| slot);
 
-    let state = cheap_state_advance_to_obtain_committees(
-        &mut parent.beacon_state,
-        highest_slot,
-        &chain.spec,
-    )?;
+    let (epoch_states, block_error) = if slot.epoch(T::EthSpec::slots_per_epoch())
+        == highest_slot.epoch(T::EthSpec::slots_per_epoch())
+    {
+        let state = cheap_state_advance_to_obtain_committees(
+            &mut parent.beacon_state,
+            highest_slot,
+            &chain.spec,
+        )?;
+        (vec![(state, chain_segment.len())], None)
+    } else {
+        let (states, block_error) = states_for_chain_segment(&parent, &chain_segment, &chain.spec)?;
+        let states = states
+            .into_iter()
+            .map(|(state, block_count)| (Cow::Owned(state), block_count))
+            .collect();
+        (states, block_error)
+    };
+
+    let signature_timer =
+        metrics::start_timer(&metrics::CHAIN_SEGMENT_SIGNATURE_VERIFICATION_TIMES);
+    metrics::observe(
+        &metrics::CHAIN_SEGMENT_SIGNATURE_VERIFICATION_BLOCKS,
+        chain_segment.len() as f64,
+    );
 
     let pubkey_cache = get_validator_pubkey_cache(chain)?;
-    let mut signature_verifier = get_signature_verifier(&state, &pubkey_cache, &chain.spec);
+    let mut signature_verifier = None;
+    let mut blocks = chain_segment.iter().enumerate();
+    let last_index = epoch_states
+        .iter()
+        .map(|(_, block_count)| block_count)
+        .sum::<usize>()
+        .saturating_sub(1);
+
+    for (state, block_count) in &epoch_states {
+        let mut epoch_verifier = get_signature_verifier(state, &pubkey_cache, &chain.spec);
+
+        for (i, (block_root, block)) in blocks.by_ref().take(*block_count) {
+            if let Err(e) = epoch_verifier.include_all_signatures(block, Some(*block_root)) {
+                // The signatures of a block that could not be applied to the state might not be
+                // readable, report the error from applying it instead.
+                if block_error.is_some() && i == last_index {
+                    break;
+                }
+                return Err(e.into());
+            }
+        }
 
-    for (block_root, block) in &chain_segment {
-        signature_verifier.include_all_signatures(block, Some(*block_root))?;
+        match signature_verifier.as_mut() {
+            Some(verifier) => verifier.append(epoch_verifier),
+            None => signature_verifier = Some(epoch_verifier),
+        }
     }
 
-    if signature_verifier.verify().is_err() {
-        return Err(BlockError::InvalidSignature);
+    if let Some(signature_verifier) = signature_verifier {
+        if signature_verifier.verify().is_err() {
+            return Err(BlockError::InvalidSignature);
+        }
     }
 
     drop(pubkey_cache);
+    metrics::stop_timer(signature_timer);
+
+    // The signatures of the invalid block and its ancestors are valid, report the invalid block.
+    if let Some(error) = block_error {
+        return Err(error);
+    }
 
     let mut signature_verified_blocks = chain_segment
         .into_iter()
@@ -1068,6 +1121,108 @@ fn cheap_state_advance_to_obtain_committees<'a, E: EthSpec>(
     }
 }
 
+/// Returns a state for each epoch spanned by the linear `chain_segment`, paired with the number of
+/// consecutive blocks in `chain_segment` that belong to that epoch.
+///
+/// Each state is at the first slot of its epoch (or at the slot of `parent`, for the epoch of
+/// `parent`) and has its previous and current committee caches built, so it can be used to read the
+/// signatures of the blocks in that epoch. The proposers and committees of an epoch depend upon
+/// the blocks of the prior epochs, so the states are obtained by applying the blocks of
+/// `chain_segment` to the state of `parent` _without verifying their signatures_.
+///
+/// The returned states are only suitable for reading signatures, they must never be imported.
+///
+/// If a block cannot be applied, the states obtained so far are returned alongside the error and
+/// the count of the last state is reduced to exclude the descendants of the invalid block. This
+/// allows the signatures of the invalid block and its ancestors to be verified, so that an invalid
+/// signature is reported in preference to any error it causes in a later block (e.g., an invalid
+/// RANDAO reveal results in a different proposer shuffling).
+#[allow(clippy::type_complexity)]
+fn states_for_chain_segment<E: EthSpec>(
+    parent: &BeaconSnapshot<E>,
+    chain_segment: &[(Hash256, SignedBeaconBlock<E>)],
+    spec: &ChainSpec,
+) -> Result<(Vec<(BeaconState<E>, usize)>, Option<BlockError<E>>), BlockError<E>> {
+    let mut state = parent
+        .beacon_state
+        .clone_with(CloneConfig::committee_caches_only());
+    // The root of `state`, if it is known and has not yet been supplied to `per_slot_processing`.
+    //
+    // Only the roots of the states produced by blocks are required: they are needed to compute
+    // the roots of the blocks in `state.block_roots`. All other state roots are left as zero since
+    // they are not required for computing shufflings or verifying block headers.
+    let mut state_root = Some(parent.beacon_block.state_root());
+    let mut states = vec![];
+    let mut remaining = chain_segment;
+
+    while let Some((_, first_block)) = remaining.first() {
+        let epoch = first_block.slot().epoch(E::slots_per_epoch());
+
+        if state.slot > first_block.slot() {
+            return Err(BlockError::BlockIsNotLaterThanParent {
+                block_slot: first_block.slot(),
+                state_slot: state.slot,
+            });
+        }
+
+        while state.current_epoch() < epoch {
+            let root = state_root.take().unwrap_or_else(Hash256::zero);
+            per_slot_processing(&mut state, Some(root), spec)?;
+        }
+
+        state.build_committee_cache(RelativeEpoch::Previous, spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, spec)?;
+
+        let block_count = remaining
+            .iter()
+            .position(|(_, block)| block.slot().epoch(E::slots_per_epoch()) > epoch)
+            .unwrap_or_else(|| remaining.len());
+        let (epoch_blocks, later_blocks) = remaining.split_at(block_count);
+        remaining = later_blocks;
+
+        if remaining.is_empty() {
+            states.push((state, block_count));
+            break;
+        }
+
+        states.push((
+            state.clone_with(CloneConfig::committee_caches_only()),
+            block_count,
+        ));
+
+        for (i, (block_root, block)) in epoch_blocks.iter().enumerate() {
+            while state.slot < block.slot() {
+                let root = state_root.take().unwrap_or_else(Hash256::zero);
+                per_slot_processing(&mut state, Some(root), spec)?;
+            }
+
+            if let Err(err) = per_block_processing(
+                &mut state,
+                block,
+                Some(*block_root),
+                // The signatures of the block are verified by the caller.
+                BlockSignatureStrategy::NoVerification,
+                spec,
+            ) {
+                let error = match err {
+                    BlockProcessingError::BeaconStateError(e) => e.into(),
+                    other => BlockError::PerBlockProcessingError(other),
+                };
+
+                if let Some((_, block_count)) = states.last_mut() {
+                    *block_count = i + 1;
+                }
+
+                return Ok((states, Some(error)));
+            }
+
+            state_root = Some(block.state_root());
+        }
+    }
+
+    Ok((states, None))
+}
+
 /// Obtains a read-locked `ValidatorPubkeyCache` from the `chain`.
 fn get_validator_pubkey_cache<T: BeaconChainTypes>(
     chain: &BeaconChain<T>,
@@ -1,12 +1,21 @@
-use bus::Bus;
 use parking_lot::Mutex;
 use serde_derive::{Deserialize, Serialize};
-use slog::{error, Logger};
+use slog::{warn, Logger};
+use std::fmt;
 use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
 use std::sync::Arc;
-use types::{Attestation, Epoch, EthSpec, Hash256, SignedBeaconBlock, SignedBeaconBlockHash};
+use types::{Attestation, Epoch, EthSpec, Hash256, SignedBeaconBlock, Slot};
 pub use websocket_server::WebSocketSender;
 
+/// The maximum number of events that may be queued for a single Server-Sent Events subscriber. A
+/// subscriber that falls this far behind is disconnected, without affecting other subscribers.
+pub const SSE_QUEUE_LEN: usize = 1_024;
+
+/// The Server-Sent Events subscribers, shared between the `BeaconChain` and the HTTP API.
+pub type EventBus<T> = Arc<EventSubscribers<T>>;
+
 pub trait EventHandler<T: EthSpec>: Sized + Send + Sync {
     fn register(&self, kind: EventKind<T>) -> Result<(), String>;
 }
@@ -15,6 +24,11 @@ pub struct NullEventHandler<T: EthSpec>(PhantomData<T>);
 
 impl<T: EthSpec> EventHandler<T> for WebSocketSender<T> {
     fn register(&self, kind: EventKind<T>) -> Result<(), String> {
+        // Avoid serializing events (e.g., every attestation) that nobody will receive.
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
         self.send_string(
             serde_json::to_string(&kind)
                 .map_err(|e| format!("Unable to serialize event: {:?}", e))?,
@@ -22,50 +36,96 @@ impl<T: EthSpec> EventHandler<T> for WebSocketSender<T> {
     }
 }
 
+/// A Server-Sent Events subscriber, with its own queue of events.
+struct Subscriber<T: EthSpec> {
+    topics: Vec<EventTopic>,
+    sender: SyncSender<EventKind<T>>,
+}
+
+/// The set of Server-Sent Events subscribers.
+///
+/// Each subscriber has its own bounded queue which only receives the events of its topics, so a
+/// slow subscriber never causes events to be dropped for the others.
+pub struct EventSubscribers<T: EthSpec> {
+    subscribers: Mutex<Vec<Subscriber<T>>>,
+}
+
+impl<T: EthSpec> Default for EventSubscribers<T> {
+    fn default() -> Self {
+        Self {
+            subscribers: Mutex::new(vec![]),
+        }
+    }
+}
+
+impl<T: EthSpec> EventSubscribers<T> {
+    /// Adds a subscriber for the events of the given `topics`, returning its queue of events.
+    ///
+    /// The subscriber is removed once the returned `Receiver` is dropped, or if it does not keep
+    /// up with its events (in which case the `Receiver` is disconnected).
+    pub fn subscribe(&self, topics: Vec<EventTopic>) -> Receiver<EventKind<T>> {
+        let (sender, receiver) = sync_channel(SSE_QUEUE_LEN);
+        self.subscribers.lock().push(Subscriber { topics, sender });
+        receiver
+    }
+
+    /// Returns the number of subscribers.
+    pub fn len(&self) -> usize {
+        self.subscribers.lock().len()
+    }
+
+    /// Returns `true` if there are no subscribers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queues `kind` for each subscriber to its topic, removing any subscriber that has
+    /// disconnected or whose queue is full.
+    fn send(&self, kind: &EventKind<T>, log: &Logger) {
+        let topic = kind.topic();
+
+        self.subscribers.lock().retain(|subscriber| {
+            if !subscriber.topics.contains(&topic) {
+                return true;
+            }
+
+            match subscriber.sender.try_send(kind.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    warn!(
+                        log,
+                        "Disconnecting slow event subscriber";
+                        "queue_len" => SSE_QUEUE_LEN,
+                        "topic" => topic.to_string(),
+                    );
+                    false
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            }
+        });
+    }
+}
+
 pub struct ServerSentEvents<T: EthSpec> {
-    // Bus<> is itself Sync + Send.  We use Mutex<> here only because of the surrounding code does
-    // not enforce mutability statically (i.e. relies on interior mutability).
-    head_changed_queue: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+    subscribers: EventBus<T>,
     log: Logger,
-    _phantom: PhantomData<T>,
 }
 
 impl<T: EthSpec> ServerSentEvents<T> {
-    pub fn new(log: Logger) -> (Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>) {
-        let bus = Bus::new(T::slots_per_epoch() as usize);
-        let mutex = Mutex::new(bus);
-        let arc = Arc::new(mutex);
+    pub fn new(log: Logger) -> (Self, EventBus<T>) {
+        let subscribers = Arc::new(EventSubscribers::default());
         let this = Self {
-            head_changed_queue: arc.clone(),
+            subscribers: subscribers.clone(),
             log,
-            _phantom: PhantomData,
         };
-        (this, arc)
+        (this, subscribers)
     }
 }
 
 impl<T: EthSpec> EventHandler<T> for ServerSentEvents<T> {
     fn register(&self, kind: EventKind<T>) -> Result<(), String> {
-        match kind {
-            EventKind::BeaconHeadChanged {
-                current_head_beacon_block_root,
-                ..
-            } => {
-                let mut guard = self.head_changed_queue.lock();
-                if guard
-                    .try_broadcast(current_head_beacon_block_root.into())
-                    .is_err()
-                {
-                    error!(
-                        self.log,
-                        "Head change streaming queue full";
-                        "dropped_change" => format!("{}", current_head_beacon_block_root),
-                    );
-                }
-                Ok(())
-            }
-            _ => Ok(()),
-        }
+        self.subscribers.send(&kind, &self.log);
+        Ok(())
     }
 }
 
@@ -78,11 +138,10 @@ pub struct TeeEventHandler<E: EthSpec> {
 }
 
 impl<E: EthSpec> TeeEventHandler<E> {
-    #[allow(clippy::type_complexity)]
     pub fn new(
         log: Logger,
         websockets_handler: WebSocketSender<E>,
-    ) -> Result<(Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>), String> {
+    ) -> Result<(Self, EventBus<E>), String> {
         let (sse_handler, bus) = ServerSentEvents::new(log);
         let result = Self {
             websockets_handler,
@@ -144,4 +203,161 @@ pub enum EventKind<T: EthSpec> {
         reason: String,
         attestation: Box<Attestation<T>>,
     },
+    ChainReorg {
+        slot: Slot,
+        depth: u64,
+        old_head_block: Hash256,
+        new_head_block: Hash256,
+        old_head_state: Hash256,
+        new_head_state: Hash256,
+        /// The most recent block shared by the old and new heads, if it is known to fork choice.
+        common_ancestor_block: Option<Hash256>,
+        common_ancestor_slot: Option<Slot>,
+        epoch: Epoch,
+    },
+}
+
+impl<T: EthSpec> EventKind<T> {
+    /// Returns the topic under which `self` is published to Server-Sent Events subscribers.
+    pub fn topic(&self) -> EventTopic {
+        match self {
+            EventKind::BeaconHeadChanged { .. } => EventTopic::Head,
+            EventKind::BeaconFinalization { .. } => EventTopic::FinalizedCheckpoint,
+            EventKind::BeaconBlockImported { .. } => EventTopic::Block,
+            EventKind::BeaconBlockRejected { .. } => EventTopic::BlockRejected,
+            EventKind::BeaconAttestationImported { .. } => EventTopic::Attestation,
+            EventKind::BeaconAttestationRejected { .. } => EventTopic::AttestationRejected,
+            EventKind::ChainReorg { .. } => EventTopic::ChainReorg,
+        }
+    }
+}
+
+/// The topics that a Server-Sent Events subscriber may filter on (e.g., `?topics=head,block`).
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum EventTopic {
+    Head,
+    Block,
+    BlockRejected,
+    Attestation,
+    AttestationRejected,
+    FinalizedCheckpoint,
+    ChainReorg,
+}
+
+impl EventTopic {
+    /// All topics, in the order they are documented.
+    pub fn all() -> Vec<EventTopic> {
+        vec![
+            EventTopic::Head,
+            EventTopic::Block,
+            EventTopic::BlockRejected,
+            EventTopic::Attestation,
+            EventTopic::AttestationRejected,
+            EventTopic::FinalizedCheckpoint,
+            EventTopic::ChainReorg,
+        ]
+    }
+}
+
+impl FromStr for EventTopic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(EventTopic::Head),
+            "block" => Ok(EventTopic::Block),
+            "block_rejected" => Ok(EventTopic::BlockRejected),
+            "attestation" => Ok(EventTopic::Attestation),
+            "attestation_rejected" => Ok(EventTopic::AttestationRejected),
+            "finalized_checkpoint" => Ok(EventTopic::FinalizedCheckpoint),
+            "chain_reorg" => Ok(EventTopic::ChainReorg),
+            other => Err(format!("Unknown event topic: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for EventTopic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            EventTopic::Head => "head",
+            EventTopic::Block => "block",
+            EventTopic::BlockRejected => "block_rejected",
+            EventTopic::Attestation => "attestation",
+            EventTopic::AttestationRejected => "attestation_rejected",
+            EventTopic::FinalizedCheckpoint => "finalized_checkpoint",
+            EventTopic::ChainReorg => "chain_reorg",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use types::MinimalEthSpec;
+
+    #[test]
+    fn topic_round_trip() {
+        for topic in EventTopic::all() {
+            assert_eq!(topic.to_string().parse::<EventTopic>(), Ok(topic));
+        }
+        assert!("cats".parse::<EventTopic>().is_err());
+    }
+
+    fn finalization(epoch: u64) -> EventKind<MinimalEthSpec> {
+        EventKind::BeaconFinalization {
+            epoch: Epoch::new(epoch),
+            root: Hash256::zero(),
+        }
+    }
+
+    #[test]
+    fn subscribers_only_receive_their_topics() {
+        let log = NullLoggerBuilder.build().expect("logger should build");
+        let (handler, subscribers) = ServerSentEvents::new(log);
+        let finalized = subscribers.subscribe(vec![EventTopic::FinalizedCheckpoint]);
+        let head = subscribers.subscribe(vec![EventTopic::Head]);
+
+        handler.register(finalization(1)).unwrap();
+
+        assert_eq!(
+            finalized.try_recv().unwrap().topic(),
+            EventTopic::FinalizedCheckpoint
+        );
+        assert!(head.try_recv().is_err());
+    }
+
+    #[test]
+    fn slow_subscriber_does_not_affect_others() {
+        let log = NullLoggerBuilder.build().expect("logger should build");
+        let (handler, subscribers) = ServerSentEvents::new(log);
+        let slow = subscribers.subscribe(EventTopic::all());
+        let fast = subscribers.subscribe(EventTopic::all());
+
+        for epoch in 0..=SSE_QUEUE_LEN as u64 {
+            handler.register(finalization(epoch)).unwrap();
+            assert!(
+                fast.try_recv().is_ok(),
+                "fast subscriber should get every event"
+            );
+        }
+
+        assert_eq!(subscribers.len(), 1, "slow subscriber should be removed");
+        assert_eq!(slow.iter().count(), SSE_QUEUE_LEN);
+
+        handler.register(finalization(0)).unwrap();
+        assert!(fast.try_recv().is_ok());
+    }
+
+    #[test]
+    fn dropped_subscriber_is_removed() {
+        let log = NullLoggerBuilder.build().expect("logger should build");
+        let (handler, subscribers) = ServerSentEvents::new(log);
+        drop(subscribers.subscribe(EventTopic::all()));
+
+        handler.register(finalization(0)).unwrap();
+
+        assert!(subscribers.is_empty());
+    }
 }
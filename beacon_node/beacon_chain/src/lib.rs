@@ -26,6 +26,7 @@ mod observed_block_producers;
 pub mod observed_operations;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
+pub mod reorg_history;
 mod shuffling_cache;
 mod snapshot_cache;
 pub mod test_utils;
@@ -34,7 +35,7 @@ mod validator_pubkey_cache;
 
 pub use self::beacon_chain::{
     AttestationProcessingOutcome, BeaconChain, BeaconChainTypes, ChainSegmentResult,
-    ForkChoiceError, StateSkipConfig,
+    ForkChoiceError, StateSkipConfig, BEACON_CHAIN_DB_KEY, FORK_CHOICE_DB_KEY,
 };
 pub use self::beacon_snapshot::BeaconSnapshot;
 pub use self::chain_config::ChainConfig;
@@ -44,8 +45,11 @@ pub use beacon_fork_choice_store::{BeaconForkChoiceStore, Error as ForkChoiceSto
 pub use block_verification::{BlockError, GossipVerifiedBlock};
 pub use eth1_chain::{Eth1Chain, Eth1ChainBackend};
 pub use events::EventHandler;
+pub use head_tracker::HeadTracker;
 pub use metrics::scrape_for_metrics;
 pub use parking_lot;
+pub use persisted_beacon_chain::PersistedBeaconChain;
+pub use persisted_fork_choice::PersistedForkChoice;
 pub use slot_clock;
 pub use state_processing::per_block_processing::errors::{
     AttestationValidationError, AttesterSlashingValidationError, DepositValidationError,
@@ -37,6 +37,15 @@ lazy_static! {
         "beacon_block_processing_signature_seconds",
         "Time spent doing signature verification for a block."
     );
+    pub static ref CHAIN_SEGMENT_SIGNATURE_VERIFICATION_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_chain_segment_signature_verification_seconds",
+        "Time spent batch verifying the signatures of all the blocks of a chain segment."
+    );
+    pub static ref CHAIN_SEGMENT_SIGNATURE_VERIFICATION_BLOCKS: Result<Histogram> = try_create_histogram_with_buckets(
+        "beacon_chain_segment_signature_verification_blocks",
+        "Number of blocks whose signatures were verified in a single batch, one batch per chain segment.",
+        linear_buckets(1.0, 4.0, 8)
+    );
     pub static ref BLOCK_PROCESSING_CORE: Result<Histogram> = try_create_histogram(
         "beacon_block_processing_core_seconds",
         "Time spent doing the core per_block_processing state processing."
@@ -171,6 +180,15 @@ lazy_static! {
         "beacon_attestation_processing_signature_seconds",
         "Time spent on the signature verification of attestation processing"
     );
+    pub static ref ATTESTATION_PROCESSING_BATCH_SIGNATURE_SETS: Result<Histogram> = try_create_histogram_with_buckets(
+        "beacon_attestation_processing_batch_signature_sets",
+        "Number of signature sets verified together when batch verifying attestations",
+        Ok(vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0, 256.0])
+    );
+    pub static ref ATTESTATION_PROCESSING_BATCH_SIGNATURE_FAILURES: Result<IntCounter> = try_create_int_counter(
+        "beacon_attestation_processing_batch_signature_failures_total",
+        "Count of attestation batches that contained an invalid signature"
+    );
 
     /*
      * Shuffling cache
@@ -218,6 +236,17 @@ lazy_static! {
         "beacon_fork_choice_reorg_total",
         "Count of occasions fork choice has switched to a different chain"
     );
+    pub static ref FORK_CHOICE_REORG_DEPTH: Result<Histogram> = try_create_histogram_with_buckets(
+        "beacon_fork_choice_reorg_depth",
+        "Number of slots between the previous head and the common ancestor of a re-org",
+        exponential_buckets(1.0, 2.0, 10)
+    );
+    pub static ref FORK_CHOICE_REORG_DISTANCE_FROM_FINALIZATION: Result<Histogram> =
+        try_create_histogram_with_buckets(
+            "beacon_fork_choice_reorg_distance_from_finalization",
+            "Number of slots between the finalized checkpoint and the common ancestor of a re-org",
+            exponential_buckets(1.0, 2.0, 12)
+        );
     pub static ref FORK_CHOICE_TIMES: Result<Histogram> =
         try_create_histogram("beacon_fork_choice_seconds", "Full runtime of fork choice");
     pub static ref FORK_CHOICE_FIND_HEAD_TIMES: Result<Histogram> =
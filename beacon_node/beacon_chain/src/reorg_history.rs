@@ -0,0 +1,112 @@
+use serde_derive::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use types::{Hash256, Slot};
+
+/// The number of re-orgs retained by the `ReorgHistory` of a `BeaconChain`.
+pub const DEFAULT_REORG_HISTORY_LEN: usize = 64;
+
+/// A record of a single occasion where fork choice switched to a head that did not descend from
+/// the previous head.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ReorgEvent {
+    /// The slot of the new head.
+    pub slot: Slot,
+    /// The number of slots between the previous head and `common_ancestor`.
+    pub depth: u64,
+    pub old_head_block: Hash256,
+    pub old_head_slot: Slot,
+    pub new_head_block: Hash256,
+    /// The most recent block shared by the old and new chains. `None` if it could not be
+    /// determined (e.g., the old head was pruned from fork choice).
+    pub common_ancestor: Option<Hash256>,
+    /// The number of slots between the finalized checkpoint of the new head and
+    /// `common_ancestor`.
+    pub distance_from_finalization: Option<u64>,
+    /// The number of seconds since the UNIX epoch at which the re-org was observed.
+    pub seen_timestamp: u64,
+}
+
+/// Retains the most recent `ReorgEvent`s, dropping the oldest once `capacity` is reached.
+#[derive(Debug)]
+pub struct ReorgHistory {
+    events: VecDeque<ReorgEvent>,
+    capacity: usize,
+}
+
+impl ReorgHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Adds `event` to the history, removing the oldest event if the history is full.
+    pub fn insert(&mut self, event: ReorgEvent) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        while self.events.len() >= self.capacity {
+            self.events.pop_front();
+        }
+
+        self.events.push_back(event);
+    }
+
+    /// Returns all events in the history, oldest first.
+    pub fn events(&self) -> Vec<ReorgEvent> {
+        self.events.iter().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl Default for ReorgHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_REORG_HISTORY_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(slot: u64) -> ReorgEvent {
+        ReorgEvent {
+            slot: Slot::new(slot),
+            depth: 1,
+            old_head_block: Hash256::from_low_u64_be(slot),
+            old_head_slot: Slot::new(slot - 1),
+            new_head_block: Hash256::from_low_u64_be(slot + 1),
+            common_ancestor: Some(Hash256::zero()),
+            distance_from_finalization: Some(slot - 2),
+            seen_timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn retains_most_recent() {
+        let mut history = ReorgHistory::new(3);
+
+        for slot in 2..7 {
+            history.insert(event(slot));
+        }
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.events(), vec![event(4), event(5), event(6)]);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut history = ReorgHistory::new(0);
+        history.insert(event(2));
+        assert!(history.is_empty());
+    }
+}
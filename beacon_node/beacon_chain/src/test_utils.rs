@@ -23,7 +23,7 @@ use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
-use store::{config::StoreConfig, BlockReplay, HotColdDB, ItemStore, LevelDB, MemoryStore};
+use store::{config::StoreConfig, BlockReplay, DiskStore, HotColdDB, ItemStore, MemoryStore};
 use tempfile::{tempdir, TempDir};
 use tree_hash::TreeHash;
 use types::{
@@ -38,6 +38,10 @@ pub use types::test_utils::generate_deterministic_keypairs;
 pub const HARNESS_GENESIS_TIME: u64 = 1_567_552_690;
 // This parameter is required by a builder but not used because we use the `TestingSlotClock`.
 pub const HARNESS_SLOT_TIME: Duration = Duration::from_secs(1);
+/// Names the database backend used by on-disk test stores, e.g. `TEST_DB_BACKEND=lmdb`.
+pub const TEST_DB_BACKEND_ENV: &str = "TEST_DB_BACKEND";
+/// Names the compression used by on-disk test stores, e.g. `TEST_DB_COMPRESSION=zstd`.
+pub const TEST_DB_COMPRESSION_ENV: &str = "TEST_DB_COMPRESSION";
 
 pub type BaseHarnessType<TStoreMigrator, TEthSpec, THotStore, TColdStore> = Witness<
     TStoreMigrator,
@@ -52,7 +56,7 @@ pub type BaseHarnessType<TStoreMigrator, TEthSpec, THotStore, TColdStore> = Witn
 pub type NullMigratorEphemeralHarnessType<E> =
     BaseHarnessType<NullMigrator, E, MemoryStore<E>, MemoryStore<E>>;
 pub type BlockingMigratorDiskHarnessType<E> =
-    BaseHarnessType<BlockingMigrator<E, LevelDB<E>, LevelDB<E>>, E, LevelDB<E>, LevelDB<E>>;
+    BaseHarnessType<BlockingMigrator<E, DiskStore<E>, DiskStore<E>>, E, DiskStore<E>, DiskStore<E>>;
 pub type BlockingMigratorEphemeralHarnessType<E> = BaseHarnessType<
     BlockingMigrator<E, MemoryStore<E>, MemoryStore<E>>,
     E,
@@ -253,11 +257,33 @@ impl<E: EthSpec> BeaconChainHarness<NullMigratorEphemeralHarnessType<E>> {
     }
 }
 
+/// Returns the default `StoreConfig`, using the database backend named by `TEST_DB_BACKEND` and
+/// the compression named by `TEST_DB_COMPRESSION`.
+///
+/// Allows the on-disk store tests to be run against each backend and compression algorithm.
+pub fn test_store_config() -> StoreConfig {
+    let default = StoreConfig::default();
+
+    StoreConfig {
+        backend: parse_test_env(TEST_DB_BACKEND_ENV).unwrap_or(default.backend),
+        compression: parse_test_env(TEST_DB_COMPRESSION_ENV).unwrap_or(default.compression),
+        ..default
+    }
+}
+
+fn parse_test_env<T: std::str::FromStr<Err = String>>(name: &str) -> Option<T> {
+    std::env::var(name).ok().map(|value| {
+        value
+            .parse()
+            .unwrap_or_else(|e| panic!("Invalid {}: {}", name, e))
+    })
+}
+
 impl<E: EthSpec> BeaconChainHarness<BlockingMigratorDiskHarnessType<E>> {
     /// Instantiate a new harness with `validator_count` initial validators.
     pub fn new_with_disk_store(
         eth_spec_instance: E,
-        store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+        store: Arc<HotColdDB<E, DiskStore<E>, DiskStore<E>>>,
         validators_keypairs: Vec<Keypair>,
     ) -> Self {
         let data_dir = tempdir().expect("should create temporary data_dir");
@@ -305,7 +331,7 @@ impl<E: EthSpec> BeaconChainHarness<BlockingMigratorDiskHarnessType<E>> {
     /// Instantiate a new harness with `validator_count` initial validators.
     pub fn resume_from_disk_store(
         eth_spec_instance: E,
-        store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+        store: Arc<HotColdDB<E, DiskStore<E>, DiskStore<E>>>,
         validators_keypairs: Vec<Keypair>,
         data_dir: TempDir,
     ) -> Self {
@@ -877,6 +877,104 @@ fn unaggregated_gossip_verification() {
     );
 }
 
+/// Tests that verifying a batch of gossip attestations gives the same results as verifying each
+/// attestation individually, including when the batch contains an invalid signature.
+#[test]
+fn batch_gossip_verification() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+
+    // Extend the chain out a few epochs so we have some chain depth to play with.
+    harness.extend_chain(
+        MainnetEthSpec::slots_per_epoch() as usize * 3 - 1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // Advance into a slot where there have not been blocks or attestations produced.
+    harness.advance_slot();
+
+    let current_slot = harness.chain.slot().expect("should get slot");
+    let head = harness.chain.head().expect("should get head");
+
+    let attestations = harness
+        .get_unaggregated_attestations(
+            &AttestationStrategy::AllValidators,
+            &head.beacon_state,
+            head.beacon_block_root,
+            current_slot,
+        )
+        .into_iter()
+        .next()
+        .expect("should have at least one committee");
+
+    assert!(
+        attestations.len() >= 4,
+        "the test requires at least four attesters"
+    );
+
+    /*
+     * Unaggregated attestations.
+     */
+
+    let (first_half, second_half) = attestations.split_at(attestations.len() / 2);
+
+    let results = harness
+        .chain
+        .batch_verify_unaggregated_attestations_for_gossip(first_half.to_vec());
+    assert_eq!(results.len(), first_half.len());
+    assert!(
+        results.iter().all(Result::is_ok),
+        "a batch of valid attestations should verify"
+    );
+
+    let mut batch = second_half.to_vec();
+    batch[0].0.signature = batch[1].0.signature.clone();
+    batch.push(first_half[0].clone());
+
+    let results = harness
+        .chain
+        .batch_verify_unaggregated_attestations_for_gossip(batch);
+    assert_eq!(results.len(), second_half.len() + 1);
+    assert!(
+        matches!(results[0], Err(AttnError::InvalidSignature)),
+        "the invalid signature should be attributed to the correct attestation"
+    );
+    assert!(
+        results[1..second_half.len()].iter().all(Result::is_ok),
+        "the valid attestations in a batch with an invalid signature should verify"
+    );
+    assert!(
+        matches!(
+            results[second_half.len()],
+            Err(AttnError::PriorAttestationKnown { .. })
+        ),
+        "an attestation that has already been seen should not verify"
+    );
+
+    /*
+     * Aggregated attestations.
+     */
+
+    let mut aggregate = attestations[0].0.clone();
+    for (attestation, _) in &attestations[1..] {
+        aggregate.aggregate(attestation);
+    }
+
+    let (valid_aggregate, _, _) = get_valid_aggregated_attestation(&harness.chain, aggregate);
+    let mut invalid_aggregate = valid_aggregate.clone();
+    invalid_aggregate.signature = invalid_aggregate.message.selection_proof.clone();
+
+    let results = harness
+        .chain
+        .batch_verify_aggregated_attestations_for_gossip(vec![invalid_aggregate, valid_aggregate]);
+    assert_eq!(results.len(), 2);
+    assert!(
+        matches!(results[0], Err(AttnError::InvalidSignature)),
+        "the invalid signature should be attributed to the correct aggregate"
+    );
+    assert!(results[1].is_ok(), "the valid aggregate should verify");
+}
+
 /// Ensures that an attestation that skips epochs can still be processed.
 ///
 /// This also checks that we can do a state lookup if we don't get a hit from the shuffling cache.
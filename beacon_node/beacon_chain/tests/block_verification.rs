@@ -7,7 +7,7 @@ use beacon_chain::{
     test_utils::{
         AttestationStrategy, BeaconChainHarness, BlockStrategy, NullMigratorEphemeralHarnessType,
     },
-    BeaconSnapshot, BlockError,
+    BeaconSnapshot, BlockError, ChainSegmentResult,
 };
 use store::config::StoreConfig;
 use types::{
@@ -275,6 +275,26 @@ fn chain_segment_non_linear_slots() {
     );
 }
 
+#[test]
+fn chain_segment_single_signature_batch() {
+    let harness = get_invalid_sigs_harness();
+    let mut blocks = chain_segment_blocks();
+    blocks.last_mut().unwrap().signature = junk_signature();
+
+    // The segment spans many epochs. If its signatures are verified as a single batch, none of
+    // the blocks will be imported.
+    match harness.chain.process_chain_segment(blocks) {
+        ChainSegmentResult::Failed {
+            imported_blocks,
+            error: BlockError::InvalidSignature,
+        } => assert_eq!(
+            imported_blocks, 0,
+            "should not import any block from a segment with an invalid signature"
+        ),
+        _ => panic!("should not import chain segment with an invalid block signature"),
+    }
+}
+
 fn assert_invalid_signature(
     harness: &BeaconChainHarness<NullMigratorEphemeralHarnessType<E>>,
     block_index: usize,
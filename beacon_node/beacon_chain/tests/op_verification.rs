@@ -7,11 +7,12 @@ extern crate lazy_static;
 
 use beacon_chain::observed_operations::ObservationOutcome;
 use beacon_chain::test_utils::{
-    AttestationStrategy, BeaconChainHarness, BlockStrategy, BlockingMigratorDiskHarnessType,
+    test_store_config, AttestationStrategy, BeaconChainHarness, BlockStrategy,
+    BlockingMigratorDiskHarnessType,
 };
 use sloggers::{null::NullLoggerBuilder, Build};
 use std::sync::Arc;
-use store::{LevelDB, StoreConfig};
+use store::DiskStore;
 use tempfile::{tempdir, TempDir};
 use types::test_utils::{
     AttesterSlashingTestTask, ProposerSlashingTestTask, TestingAttesterSlashingBuilder,
@@ -29,13 +30,13 @@ lazy_static! {
 
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<BlockingMigratorDiskHarnessType<E>>;
-type HotColdDB = store::HotColdDB<E, LevelDB<E>, LevelDB<E>>;
+type HotColdDB = store::HotColdDB<E, DiskStore<E>, DiskStore<E>>;
 
 fn get_store(db_path: &TempDir) -> Arc<HotColdDB> {
     let spec = E::default_spec();
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
-    let config = StoreConfig::default();
+    let config = test_store_config();
     let log = NullLoggerBuilder.build().expect("logger should build");
     Arc::new(
         HotColdDB::open(&hot_path, &cold_path, config, spec, log)
@@ -4,12 +4,12 @@
 extern crate lazy_static;
 
 use beacon_chain::{
-    test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy},
+    test_utils::{test_store_config, AttestationStrategy, BeaconChainHarness, BlockStrategy},
     BeaconChain, BeaconChainTypes,
 };
 use sloggers::{null::NullLoggerBuilder, Build};
 use std::sync::Arc;
-use store::{HotColdDB, LevelDB, StoreConfig};
+use store::{DiskStore, HotColdDB};
 use tempfile::{tempdir, TempDir};
 use types::{EthSpec, Keypair, MinimalEthSpec};
 
@@ -23,11 +23,11 @@ lazy_static! {
     static ref KEYPAIRS: Vec<Keypair> = types::test_utils::generate_deterministic_keypairs(VALIDATOR_COUNT);
 }
 
-fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, DiskStore<E>, DiskStore<E>>> {
     let spec = E::default_spec();
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
-    let config = StoreConfig::default();
+    let config = test_store_config();
     let log = NullLoggerBuilder.build().expect("logger should build");
     Arc::new(
         HotColdDB::open(&hot_path, &cold_path, config, spec, log)
@@ -10,7 +10,8 @@ extern crate slog_term;
 use crate::slog::Drain;
 use beacon_chain::attestation_verification::Error as AttnError;
 use beacon_chain::test_utils::{
-    AttestationStrategy, BeaconChainHarness, BlockStrategy, BlockingMigratorDiskHarnessType,
+    test_store_config, AttestationStrategy, BeaconChainHarness, BlockStrategy,
+    BlockingMigratorDiskHarnessType,
 };
 use beacon_chain::BeaconSnapshot;
 use maplit::hashset;
@@ -20,8 +21,9 @@ use std::collections::HashSet;
 use std::convert::TryInto;
 use std::sync::Arc;
 use store::{
+    hot_cold_store::CONFIG_DB_KEY,
     iter::{BlockRootsIterator, StateRootsIterator},
-    HotColdDB, LevelDB, StoreConfig,
+    DBColumn, DiskStore, HotColdDB, KeyValueStore, StoreConfig,
 };
 use tempfile::{tempdir, TempDir};
 use tree_hash::TreeHash;
@@ -40,11 +42,17 @@ lazy_static! {
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<BlockingMigratorDiskHarnessType<E>>;
 
-fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, DiskStore<E>, DiskStore<E>>> {
+    get_store_with_config(db_path, test_store_config())
+}
+
+fn get_store_with_config(
+    db_path: &TempDir,
+    config: StoreConfig,
+) -> Arc<HotColdDB<E, DiskStore<E>, DiskStore<E>>> {
     let spec = MinimalEthSpec::default_spec();
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
-    let config = StoreConfig::default();
 
     let decorator = slog_term::PlainDecorator::new(slog_term::TestStdoutWriter);
     let drain = slog_term::FullFormat::new(decorator).build();
@@ -57,7 +65,7 @@ fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
 }
 
 fn get_harness(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, DiskStore<E>, DiskStore<E>>>,
     validator_count: usize,
 ) -> TestHarness {
     let harness = BeaconChainHarness::new_with_disk_store(
@@ -262,6 +270,272 @@ fn split_slot_restore() {
     assert_eq!(store.get_split_slot(), split_slot);
 }
 
+#[test]
+fn change_slots_per_restore_point() {
+    let db_path = tempdir().unwrap();
+
+    let frozen_state_roots = |store: &HotColdDB<E, DiskStore<E>, DiskStore<E>>| {
+        (0..store.get_split_slot().as_u64())
+            .map(|slot| {
+                store
+                    .load_cold_state_by_slot(Slot::new(slot))
+                    .expect("should load frozen state")
+                    .tree_hash_root()
+            })
+            .collect::<Vec<_>>()
+    };
+    let store_with_interval = |slots_per_restore_point: Option<u64>| {
+        let mut config = test_store_config();
+        if let Some(slots_per_restore_point) = slots_per_restore_point {
+            config.slots_per_restore_point = slots_per_restore_point;
+            config.slots_per_restore_point_set_explicitly = true;
+        }
+        get_store_with_config(&db_path, config)
+    };
+
+    let state_roots = {
+        let store = store_with_interval(None);
+        let mut harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+        harness.extend_chain(
+            3 * E::slots_per_historical_root(),
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        );
+        assert!(store.get_split_slot() > 2 * store.get_slots_per_restore_point());
+        frozen_state_roots(&*store)
+    };
+
+    // Decreasing the interval reconstructs intermediate states as restore points.
+    let store = store_with_interval(Some(E::slots_per_epoch()));
+    assert_eq!(store.get_slots_per_restore_point(), E::slots_per_epoch());
+    assert_eq!(frozen_state_roots(&*store), state_roots);
+    drop(store);
+
+    // The stored interval is kept unless another is set explicitly.
+    let store = store_with_interval(None);
+    assert_eq!(store.get_slots_per_restore_point(), E::slots_per_epoch());
+    drop(store);
+
+    // Increasing the interval prunes restore points.
+    let slots_per_historical_root = E::slots_per_historical_root() as u64;
+    let store = store_with_interval(Some(slots_per_historical_root));
+    assert_eq!(
+        store.get_slots_per_restore_point(),
+        slots_per_historical_root
+    );
+    assert_eq!(frozen_state_roots(&*store), state_roots);
+}
+
+// Check that a freezer written before its configuration was stored, which only holds the
+// genesis restore point, adopts the configured slots per restore point.
+#[test]
+fn young_freezer_uses_configured_slots_per_restore_point() {
+    let db_path = tempdir().unwrap();
+
+    let frozen_state_roots = |store: &HotColdDB<E, DiskStore<E>, DiskStore<E>>| {
+        (0..store.get_split_slot().as_u64())
+            .map(|slot| {
+                store
+                    .load_cold_state_by_slot(Slot::new(slot))
+                    .expect("should load frozen state")
+                    .tree_hash_root()
+            })
+            .collect::<Vec<_>>()
+    };
+    // Remove the stored configuration, as it was before the configuration was stored.
+    let forget_config = || {
+        let cold_path = db_path.path().join("cold_db");
+        let cold_db = DiskStore::<E>::open(&cold_path, test_store_config().backend)
+            .expect("should open freezer");
+        cold_db
+            .key_delete(DBColumn::BeaconMeta.into(), CONFIG_DB_KEY.as_bytes())
+            .expect("should delete config");
+    };
+    let store_with_interval = |slots_per_restore_point: u64, set_explicitly: bool| {
+        let mut config = test_store_config();
+        config.slots_per_restore_point = slots_per_restore_point;
+        config.slots_per_restore_point_set_explicitly = set_explicitly;
+        get_store_with_config(&db_path, config)
+    };
+
+    let slots_per_historical_root = E::slots_per_historical_root() as u64;
+    let state_roots = {
+        let store = store_with_interval(slots_per_historical_root, false);
+        let mut harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+        harness.extend_chain(
+            4 * E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        );
+        assert_ne!(store.get_split_slot(), Slot::new(0));
+        assert!(store.get_split_slot() < slots_per_historical_root / 2);
+        frozen_state_roots(&*store)
+    };
+
+    // A default interval exceeding the split is adopted as-is.
+    forget_config();
+    let store = store_with_interval(slots_per_historical_root / 2, false);
+    assert_eq!(
+        store.get_slots_per_restore_point(),
+        slots_per_historical_root / 2
+    );
+    assert_eq!(frozen_state_roots(&*store), state_roots);
+    drop(store);
+
+    // The adopted interval was stored.
+    let store = store_with_interval(slots_per_historical_root, false);
+    assert_eq!(
+        store.get_slots_per_restore_point(),
+        slots_per_historical_root / 2
+    );
+    drop(store);
+
+    // An interval below the split reconstructs the restore points it needs.
+    forget_config();
+    let store = store_with_interval(E::slots_per_epoch(), true);
+    assert_eq!(store.get_slots_per_restore_point(), E::slots_per_epoch());
+    assert_eq!(frozen_state_roots(&*store), state_roots);
+}
+
+#[test]
+fn historic_state_cache() {
+    let db_path = tempdir().unwrap();
+
+    let store_with_cache_size = |historic_state_cache_size| {
+        let mut config = test_store_config();
+        config.historic_state_cache_size = historic_state_cache_size;
+        get_store_with_config(&db_path, config)
+    };
+    let load_state_roots = |store: &HotColdDB<E, DiskStore<E>, DiskStore<E>>, slots: &[u64]| {
+        slots
+            .iter()
+            .map(|slot| {
+                store
+                    .load_cold_state_by_slot(Slot::new(*slot))
+                    .expect("should load frozen state")
+                    .tree_hash_root()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let (slots, state_roots) = {
+        let store = store_with_cache_size(0);
+        let mut harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+        harness.extend_chain(
+            2 * E::slots_per_historical_root(),
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        );
+        let slots = (0..store.get_split_slot().as_u64()).collect::<Vec<_>>();
+        let state_roots = load_state_roots(&*store, &slots);
+        (slots, state_roots)
+    };
+
+    // Walk forwards, replaying from the previous state, then backwards, replaying from the
+    // cached restore points.
+    let store = store_with_cache_size(4);
+    assert_eq!(load_state_roots(&*store, &slots), state_roots);
+    let reversed_slots = slots.iter().rev().copied().collect::<Vec<_>>();
+    let reversed_state_roots = state_roots.iter().rev().copied().collect::<Vec<_>>();
+    assert_eq!(
+        load_state_roots(&*store, &reversed_slots),
+        reversed_state_roots
+    );
+    drop(store);
+
+    // Each lookup promotes the state it started from in the cache, which shows whether it was
+    // served from the cache, replayed from a cached state or replayed from a restore point.
+    let store = store_with_cache_size(4);
+    let cached_slots = |slots: &[u64]| slots.iter().copied().map(Slot::new).collect::<Vec<_>>();
+
+    // A miss replays from the restore point, caching it along with the requested state.
+    load_state_roots(&*store, &[3]);
+    assert_eq!(
+        store.get_historic_state_cache_slots(),
+        cached_slots(&[3, 0])
+    );
+
+    // A later slot replays from the closest cached state rather than the restore point.
+    load_state_roots(&*store, &[5]);
+    assert_eq!(
+        store.get_historic_state_cache_slots(),
+        cached_slots(&[5, 3, 0])
+    );
+
+    // A cached slot is served from the cache without replaying from an earlier state.
+    assert_eq!(load_state_roots(&*store, &[3]), vec![state_roots[3]]);
+    assert_eq!(
+        store.get_historic_state_cache_slots(),
+        cached_slots(&[3, 5, 0])
+    );
+}
+
+#[test]
+fn frozen_block_roots() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let mut harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        2 * E::slots_per_historical_root(),
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    let latest_restore_point_slot = store.get_latest_restore_point_slot();
+    assert!(latest_restore_point_slot > 0);
+
+    let expected = harness
+        .chain
+        .forwards_iter_block_roots(Slot::new(0))
+        .expect("should get iter")
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+
+    // The range is truncated to the slots prior to the latest restore point.
+    let frozen = HotColdDB::frozen_block_roots_iterator(
+        store.clone(),
+        Slot::new(0),
+        latest_restore_point_slot + 1,
+    )
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+    assert_eq!(frozen, expected[..latest_restore_point_slot.as_usize()]);
+
+    let reversed = HotColdDB::frozen_block_roots_iterator(
+        store.clone(),
+        Slot::new(0),
+        latest_restore_point_slot,
+    )
+    .rev()
+    .map(Result::unwrap)
+    .collect::<Vec<_>>();
+    assert_eq!(reversed, frozen.iter().rev().copied().collect::<Vec<_>>());
+
+    for (root, slot) in &expected {
+        let cold_root = store.get_cold_block_root(*slot).unwrap();
+        if *slot < latest_restore_point_slot {
+            assert_eq!(cold_root, Some(*root));
+        } else {
+            assert_eq!(cold_root, None);
+        }
+    }
+
+    // Ranges within and beyond the freezer give the same roots as the forwards iterator.
+    for end_slot in &[
+        latest_restore_point_slot,
+        harness.chain.head_info().unwrap().slot,
+    ] {
+        let start_slot = Slot::new(3);
+        let until = harness
+            .chain
+            .forwards_iter_block_roots_until(start_slot, *end_slot)
+            .expect("should get iter")
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(until, expected[3..end_slot.as_usize()]);
+    }
+}
+
 // Check attestation processing and `load_epoch_boundary_state` in the presence of a split DB.
 // This is a bit of a monster test in that it tests lots of different things, but until they're
 // tested elsewhere, this is as good a place as any.
@@ -1653,7 +1927,7 @@ fn check_finalization(harness: &TestHarness, expected_slot: u64) {
 }
 
 /// Check that the HotColdDB's split_slot is equal to the start slot of the last finalized epoch.
-fn check_split_slot(harness: &TestHarness, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) {
+fn check_split_slot(harness: &TestHarness, store: Arc<HotColdDB<E, DiskStore<E>, DiskStore<E>>>) {
     let split_slot = store.get_split_slot();
     assert_eq!(
         harness
@@ -189,6 +189,59 @@ fn chooses_fork() {
     );
 }
 
+#[test]
+fn records_reorg() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let delay = MinimalEthSpec::default_spec().min_attestation_inclusion_delay as usize;
+
+    let majority_validators: Vec<usize> = (0..two_thirds).collect();
+    let minority_validators: Vec<usize> = (two_thirds..VALIDATOR_COUNT).collect();
+
+    let initial_blocks = delay + 1;
+    let minority_fork_blocks = delay + 1;
+    let majority_fork_blocks = delay + 2;
+
+    // Build an initial chain where all validators agree.
+    let common_ancestor = harness.extend_chain(
+        initial_blocks,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // The minority fork is built on the head first, then the majority fork overtakes it.
+    let (minority_head, majority_head) = harness.generate_two_forks_by_skipping_a_block(
+        &minority_validators,
+        &majority_validators,
+        minority_fork_blocks,
+        majority_fork_blocks,
+    );
+
+    assert_eq!(
+        harness
+            .chain
+            .head()
+            .expect("should get head")
+            .beacon_block_root,
+        majority_head,
+        "the majority chain should be the canonical chain"
+    );
+
+    let reorgs = harness
+        .chain
+        .reorg_history()
+        .expect("should read reorg history");
+
+    assert_eq!(reorgs.len(), 1, "there should be a single re-org");
+    assert_eq!(reorgs[0].old_head_block, minority_head);
+    assert_eq!(reorgs[0].common_ancestor, Some(common_ancestor));
+    assert_eq!(
+        reorgs[0].depth, minority_fork_blocks as u64,
+        "the depth should be the length of the abandoned fork"
+    );
+}
+
 #[test]
 fn finalizes_with_full_participation() {
     let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
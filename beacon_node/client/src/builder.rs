@@ -1,23 +1,21 @@
 use crate::config::{ClientGenesis, Config as ClientConfig};
 use crate::notifier::spawn_notifier;
 use crate::Client;
-use beacon_chain::events::TeeEventHandler;
+use beacon_chain::events::{EventBus, TeeEventHandler};
 use beacon_chain::{
     builder::{BeaconChainBuilder, Witness},
     eth1_chain::{CachingEth1Backend, Eth1Chain},
     migrate::{BackgroundMigrator, Migrate},
     slot_clock::{SlotClock, SystemTimeSlotClock},
-    store::{HotColdDB, ItemStore, LevelDB, StoreConfig},
+    store::{DiskStore, HotColdDB, ItemStore, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, EventHandler,
 };
-use bus::Bus;
 use environment::RuntimeContext;
 use eth1::{Config as Eth1Config, Service as Eth1Service};
 use eth2_config::Eth2Config;
 use eth2_libp2p::NetworkGlobals;
 use genesis::{interop_genesis_state, Eth1GenesisService};
 use network::{NetworkConfig, NetworkMessage, NetworkService};
-use parking_lot::Mutex;
 use slog::info;
 use ssz::Decode;
 use std::net::SocketAddr;
@@ -26,10 +24,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use timer::spawn_timer;
 use tokio::sync::mpsc::UnboundedSender;
-use types::{
-    test_utils::generate_deterministic_keypairs, BeaconState, ChainSpec, EthSpec,
-    SignedBeaconBlockHash,
-};
+use types::{test_utils::generate_deterministic_keypairs, BeaconState, ChainSpec, EthSpec};
 use websocket_server::{Config as WebSocketConfig, WebSocketSender};
 
 /// Interval between polling the eth1 node for genesis information.
@@ -285,7 +280,7 @@ where
         mut self,
         client_config: &ClientConfig,
         eth2_config: &Eth2Config,
-        events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+        events: EventBus<TEthSpec>,
     ) -> Result<Self, String> {
         let beacon_chain = self
             .beacon_chain
@@ -463,12 +458,12 @@ where
     THotStore: ItemStore<TEthSpec> + 'static,
     TColdStore: ItemStore<TEthSpec> + 'static,
 {
-    #[allow(clippy::type_complexity)]
-    /// Specifies that the `BeaconChain` should publish events using the WebSocket server.
+    /// Specifies that the `BeaconChain` should publish events using the WebSocket server and to
+    /// the returned `EventBus`, from which the HTTP API serves Server-Sent Events.
     pub fn tee_event_handler(
         mut self,
         config: WebSocketConfig,
-    ) -> Result<(Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>), String> {
+    ) -> Result<(Self, EventBus<TEthSpec>), String> {
         let context = self
             .runtime_context
             .as_ref()
@@ -499,13 +494,13 @@ impl<TStoreMigrator, TSlotClock, TEth1Backend, TEthSpec, TEventHandler>
             TEth1Backend,
             TEthSpec,
             TEventHandler,
-            LevelDB<TEthSpec>,
-            LevelDB<TEthSpec>,
+            DiskStore<TEthSpec>,
+            DiskStore<TEthSpec>,
         >,
     >
 where
     TSlotClock: SlotClock + 'static,
-    TStoreMigrator: Migrate<TEthSpec, LevelDB<TEthSpec>, LevelDB<TEthSpec>> + 'static,
+    TStoreMigrator: Migrate<TEthSpec, DiskStore<TEthSpec>, DiskStore<TEthSpec>> + 'static,
     TEth1Backend: Eth1ChainBackend<TEthSpec> + 'static,
     TEthSpec: EthSpec + 'static,
     TEventHandler: EventHandler<TEthSpec> + 'static,
@@ -1,4 +1,4 @@
-use crate::peer_manager::{score::PeerAction, PeerManager, PeerManagerEvent};
+use crate::peer_manager::{score::PeerAction, PeerManager, PeerManagerEvent, RPCDirection};
 use crate::rpc::*;
 use crate::service::METADATA_FILENAME;
 use crate::types::{GossipEncoding, GossipKind, GossipTopic, SubnetDiscovery};
@@ -91,6 +91,13 @@ pub enum BehaviourEvent<TSpec: EthSpec> {
     PeerSubscribed(PeerId, TopicHash),
     /// Inform the network to send a Status to this peer.
     StatusPeer(PeerId),
+    /// A peer has reported the address it observes us on via the identify protocol.
+    ObservedAddress {
+        /// The peer that reported the address.
+        peer_id: PeerId,
+        /// The address the peer sees our connection coming from.
+        address: Multiaddr,
+    },
 }
 
 /// Builds the network behaviour that manages the core protocols of eth2.
@@ -348,8 +355,7 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
 
     /// Send a request to a peer over RPC.
     pub fn send_request(&mut self, peer_id: PeerId, request_id: RequestId, request: Request) {
-        self.eth2_rpc
-            .send_request(peer_id, request_id, request.into())
+        self.send_rpc_request(peer_id, request_id, request.into())
     }
 
     /// Send a successful response to a peer over RPC.
@@ -359,7 +365,7 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         id: PeerRequestId,
         response: Response<TSpec>,
     ) {
-        self.eth2_rpc.send_response(peer_id, id, response.into())
+        self.send_rpc_response(peer_id, id, response.into())
     }
 
     /// Inform the peer that their request produced an error.
@@ -370,8 +376,39 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         error: RPCResponseErrorCode,
         reason: String,
     ) {
-        self.eth2_rpc
-            .send_response(peer_id, id, RPCCodedResponse::Error(error, reason.into()))
+        self.send_rpc_response(peer_id, id, RPCCodedResponse::Error(error, reason.into()))
+    }
+
+    /// Accounts for and sends an RPC request.
+    fn send_rpc_request(&mut self, peer_id: PeerId, id: RequestId, request: RPCRequest<TSpec>) {
+        self.peer_manager.rpc_request(
+            &peer_id,
+            RPCDirection::Outbound,
+            request.protocol(),
+            request.ssz_bytes_len(),
+        );
+        self.eth2_rpc.send_request(peer_id, id, request)
+    }
+
+    /// Accounts for and sends an RPC response.
+    ///
+    /// Error responses are accounted for once the handler reports them.
+    fn send_rpc_response(
+        &mut self,
+        peer_id: PeerId,
+        id: PeerRequestId,
+        response: RPCCodedResponse<TSpec>,
+    ) {
+        if let RPCCodedResponse::Success(response) = &response {
+            self.peer_manager.rpc_response(
+                &peer_id,
+                RPCDirection::Inbound,
+                response.protocol(),
+                response.ssz_bytes_len(),
+                None,
+            );
+        }
+        self.eth2_rpc.send_response(peer_id, id, response)
     }
 
     /* Peer management functions */
@@ -483,8 +520,7 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         };
         trace!(self.log, "Sending Ping"; "request_id" => id, "peer_id" => peer_id.to_string());
 
-        self.eth2_rpc
-            .send_request(peer_id, id, RPCRequest::Ping(ping));
+        self.send_rpc_request(peer_id, id, RPCRequest::Ping(ping));
     }
 
     /// Sends a Pong response to the peer.
@@ -494,14 +530,13 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         };
         trace!(self.log, "Sending Pong"; "request_id" => id.1, "peer_id" => peer_id.to_string());
         let event = RPCCodedResponse::Success(RPCResponse::Pong(ping));
-        self.eth2_rpc.send_response(peer_id, id, event);
+        self.send_rpc_response(peer_id, id, event);
     }
 
     /// Sends a METADATA request to a peer.
     fn send_meta_data_request(&mut self, peer_id: PeerId) {
         let event = RPCRequest::MetaData(PhantomData);
-        self.eth2_rpc
-            .send_request(peer_id, RequestId::Behaviour, event);
+        self.send_rpc_request(peer_id, RequestId::Behaviour, event);
     }
 
     /// Sends a METADATA response to a peer.
@@ -509,7 +544,7 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         let event = RPCCodedResponse::Success(RPCResponse::MetaData(
             self.network_globals.local_metadata.read().clone(),
         ));
-        self.eth2_rpc.send_response(peer_id, id, event);
+        self.send_rpc_response(peer_id, id, event);
     }
 
     /// Returns a reference to the peer manager to allow the swarm to notify the manager of peer
@@ -596,10 +631,18 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
                         // Inform the peer manager of the error.
                         // An inbound error here means we sent an error to the peer, or the stream
                         // timed out.
+                        self.peer_manager
+                            .rpc_error(&peer_id, RPCDirection::Inbound, proto, &error);
                         self.peer_manager.handle_rpc_error(&peer_id, proto, &error);
                     }
                     HandlerErr::Outbound { id, proto, error } => {
                         // Inform the peer manager that a request we sent to the peer failed
+                        self.peer_manager.rpc_error(
+                            &peer_id,
+                            RPCDirection::Outbound,
+                            proto,
+                            &error,
+                        );
                         self.peer_manager.handle_rpc_error(&peer_id, proto, &error);
                         // inform failures of requests comming outside the behaviour
                         if !matches!(id, RequestId::Behaviour) {
@@ -610,6 +653,12 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
             }
             Ok(RPCReceived::Request(id, request)) => {
                 let peer_request_id = (handler_id, id);
+                self.peer_manager.rpc_request(
+                    &peer_id,
+                    RPCDirection::Inbound,
+                    request.protocol(),
+                    request.ssz_bytes_len(),
+                );
                 match request {
                     /* Behaviour managed protocols: Ping and Metadata */
                     RPCRequest::Ping(ping) => {
@@ -657,7 +706,14 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
                     }
                 }
             }
-            Ok(RPCReceived::Response(id, resp)) => {
+            Ok(RPCReceived::Response(id, resp, latency)) => {
+                self.peer_manager.rpc_response(
+                    &peer_id,
+                    RPCDirection::Outbound,
+                    resp.protocol(),
+                    resp.ssz_bytes_len(),
+                    latency,
+                );
                 match resp {
                     /* Behaviour managed protocols */
                     RPCResponse::Pong(ping) => self.peer_manager.pong_response(&peer_id, ping.data),
@@ -679,7 +735,11 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
                     }
                 }
             }
-            Ok(RPCReceived::EndOfStream(id, termination)) => {
+            Ok(RPCReceived::EndOfStream(id, termination, latency)) => {
+                if let Some(latency) = latency {
+                    self.peer_manager
+                        .rpc_empty_response(&peer_id, termination.protocol(), latency);
+                }
                 let response = match termination {
                     ResponseTermination::BlocksByRange => Response::BlocksByRange(None),
                     ResponseTermination::BlocksByRoot => Response::BlocksByRoot(None),
@@ -782,6 +842,10 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
                 "observed_address" => format!("{:?}", observed_addr),
                 "protocols" => format!("{:?}", info.protocols)
                 );
+                self.add_event(BehaviourEvent::ObservedAddress {
+                    peer_id,
+                    address: observed_addr,
+                });
             }
             IdentifyEvent::Sent { .. } => {}
             IdentifyEvent::Error { .. } => {}
@@ -67,7 +67,7 @@ pub struct Config {
     /// Disables the discovery protocol from starting.
     pub disable_discovery: bool,
 
-    /// Attempt to construct external port mappings with UPnP.
+    /// Attempt to construct external port mappings with UPnP, PCP or NAT-PMP.
     pub upnp_enabled: bool,
 
     /// List of extra topics to initially subscribe to as strings.
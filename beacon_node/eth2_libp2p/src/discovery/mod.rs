@@ -375,6 +375,24 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
         Ok(())
     }
 
+    /// Updates the local ENR IP address.
+    ///
+    /// This is with caution. Discovery should automatically maintain this. This should only be
+    /// used when automatic discovery is disabled.
+    pub fn update_enr_ip(&mut self, ip: IpAddr) -> Result<(), String> {
+        match ip {
+            IpAddr::V4(ip) => self.discv5.enr_insert("ip", ip.octets().into()),
+            IpAddr::V6(ip) => self.discv5.enr_insert("ip6", ip.octets().into()),
+        }
+        .map_err(|e| format!("{:?}", e))?;
+
+        // replace the global version
+        *self.network_globals.local_enr.write() = self.discv5.local_enr();
+        // persist modified enr to disk
+        enr::save_enr_to_disk(Path::new(&self.enr_dir), &self.local_enr(), &self.log);
+        Ok(())
+    }
+
     /// Updates the local ENR UDP socket.
     ///
     /// This is with caution. Discovery should automatically maintain this. This should only be
@@ -45,6 +45,26 @@ lazy_static! {
             "Failed attestation publishes per subnet",
             &["subnet"]
         );
+    pub static ref RPC_REQUESTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_rpc_requests_total",
+        "RPC requests by direction (inbound being requests from peers), protocol and client",
+        &["direction", "protocol", "client"]
+    );
+    pub static ref RPC_BYTES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_rpc_bytes_total",
+        "Uncompressed bytes of RPC requests and responses by request direction, protocol and client",
+        &["direction", "protocol", "client"]
+    );
+    pub static ref RPC_ERRORS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_rpc_errors_total",
+        "Failed RPC requests by direction, protocol, client and error response code or failure",
+        &["direction", "protocol", "client", "error"]
+    );
+    pub static ref RPC_RESPONSE_LATENCY: Result<HistogramVec> = try_create_histogram_vec(
+        "libp2p_rpc_response_latency_seconds",
+        "Time between sending an RPC request and receiving the first response, by protocol and client",
+        &["protocol", "client"]
+    );
     pub static ref FAILED_PUBLISHES_PER_MAIN_TOPIC: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "gossipsub_failed_publishes_per_main_topic",
         "Failed gossip publishes",
@@ -27,10 +27,12 @@ pub mod client;
 mod peer_info;
 mod peer_sync_status;
 mod peerdb;
+mod rpc_stats;
 pub(crate) mod score;
 
 pub use peer_info::{PeerConnectionStatus::*, PeerInfo};
 pub use peer_sync_status::{PeerSyncStatus, SyncInfo};
+pub use rpc_stats::{ProtocolStats, RPCDirection, RPCStats};
 use score::{PeerAction, ScoreState};
 use std::collections::HashMap;
 /// The time in seconds between re-status's peers.
@@ -443,6 +445,103 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         self.report_peer(peer_id, peer_action);
     }
 
+    /* RPC accounting */
+
+    /// Accounts for an RPC request sent to (outbound) or received from (inbound) a peer.
+    pub fn rpc_request(
+        &mut self,
+        peer_id: &PeerId,
+        direction: RPCDirection,
+        protocol: Protocol,
+        bytes: usize,
+    ) {
+        if let Some(client) =
+            self.update_rpc_stats(peer_id, |stats| stats.request(direction, protocol, bytes))
+        {
+            let protocol = protocol.to_string();
+            let labels: &[&str] = &[direction.as_static_str(), &protocol, &client];
+            metrics::inc_counter_vec(&metrics::RPC_REQUESTS, labels);
+            metrics::inc_counter_vec_by(&metrics::RPC_BYTES, labels, bytes as i64);
+        }
+    }
+
+    /// Accounts for a successful response chunk to a request made in `direction`.
+    ///
+    /// The `latency` is given by the RPC handler with the first chunk of a response to one of our
+    /// requests.
+    pub fn rpc_response(
+        &mut self,
+        peer_id: &PeerId,
+        direction: RPCDirection,
+        protocol: Protocol,
+        bytes: usize,
+        latency: Option<Duration>,
+    ) {
+        if let Some(client) = self.update_rpc_stats(peer_id, |stats| {
+            stats.response(direction, protocol, bytes, latency)
+        }) {
+            let protocol = protocol.to_string();
+            metrics::inc_counter_vec_by(
+                &metrics::RPC_BYTES,
+                &[direction.as_static_str(), &protocol, &client],
+                bytes as i64,
+            );
+            if let Some(latency) = latency {
+                metrics::observe_vec(
+                    &metrics::RPC_RESPONSE_LATENCY,
+                    &[&protocol, &client],
+                    latency.as_secs_f64(),
+                );
+            }
+        }
+    }
+
+    /// Accounts for the latency of one of our requests whose response stream ended without a
+    /// chunk.
+    pub fn rpc_empty_response(&mut self, peer_id: &PeerId, protocol: Protocol, latency: Duration) {
+        if let Some(client) =
+            self.update_rpc_stats(peer_id, |stats| stats.empty_response(protocol, latency))
+        {
+            metrics::observe_vec(
+                &metrics::RPC_RESPONSE_LATENCY,
+                &[&protocol.to_string(), &client],
+                latency.as_secs_f64(),
+            );
+        }
+    }
+
+    /// Accounts for a failed request made in `direction`.
+    pub fn rpc_error(
+        &mut self,
+        peer_id: &PeerId,
+        direction: RPCDirection,
+        protocol: Protocol,
+        err: &RPCError,
+    ) {
+        let error = err.as_static_str();
+        if let Some(client) =
+            self.update_rpc_stats(peer_id, |stats| stats.error(direction, protocol, error))
+        {
+            metrics::inc_counter_vec(
+                &metrics::RPC_ERRORS,
+                &[
+                    direction.as_static_str(),
+                    &protocol.to_string(),
+                    &client,
+                    error,
+                ],
+            );
+        }
+    }
+
+    /// Applies `f` to the RPC statistics of a known peer, returning the peer's client kind.
+    fn update_rpc_stats<F: FnOnce(&mut RPCStats)>(&self, peer_id: &PeerId, f: F) -> Option<String> {
+        let mut peers = self.network_globals.peers.write();
+        let peer_info = peers.peer_info_mut(peer_id)?;
+        f(&mut peer_info.rpc_stats);
+        Some(peer_info.client.kind.to_string())
+    }
+
     /// A ping request has been received.
     // NOTE: The behaviour responds with a PONG automatically
     // TODO: Update last seen
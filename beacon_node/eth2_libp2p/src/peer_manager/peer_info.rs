@@ -1,4 +1,5 @@
 use super::client::Client;
+use super::rpc_stats::RPCStats;
 use super::score::{PeerAction, Score, ScoreState};
 use super::PeerSyncStatus;
 use crate::rpc::MetaData;
@@ -43,6 +44,8 @@ pub struct PeerInfo<T: EthSpec> {
     pub min_ttl: Option<Instant>,
     /// Is the peer a trusted peer.
     pub is_trusted: bool,
+    /// The RPC requests made by and of the peer.
+    pub rpc_stats: RPCStats,
 }
 
 impl<TSpec: EthSpec> Default for PeerInfo<TSpec> {
@@ -58,6 +61,7 @@ impl<TSpec: EthSpec> Default for PeerInfo<TSpec> {
             meta_data: None,
             min_ttl: None,
             is_trusted: false,
+            rpc_stats: RPCStats::default(),
         }
     }
 }
@@ -0,0 +1,180 @@
+//! Per-peer accounting of RPC usage.
+//!
+//! Requests are accounted for in the direction they travel: `inbound` holds the requests a peer
+//! has made of us and our responses to them, `outbound` holds our requests of the peer and its
+//! responses. Byte counts are of the SSZ encoding, before compression.
+//!
+//! Response latencies are measured by the RPC handler, per outbound substream, and given with the
+//! first response to a request. Requests without a response (e.g., `Goodbye`) or which fail
+//! (e.g., time out) do not record a latency.
+
+use crate::rpc::Protocol;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// The direction of an RPC request, relative to us.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RPCDirection {
+    /// The peer made a request of us.
+    Inbound,
+    /// We made a request of the peer.
+    Outbound,
+}
+
+impl RPCDirection {
+    pub fn as_static_str(self) -> &'static str {
+        match self {
+            RPCDirection::Inbound => "inbound",
+            RPCDirection::Outbound => "outbound",
+        }
+    }
+}
+
+/// Usage of a single protocol in a single direction.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ProtocolStats {
+    /// The number of requests made.
+    pub requests: u64,
+    /// The total size of the requests.
+    pub request_bytes: u64,
+    /// The number of successful response chunks.
+    pub responses: u64,
+    /// The total size of the successful response chunks.
+    pub response_bytes: u64,
+    /// The number of failed requests, by error response code or failure kind.
+    pub errors: BTreeMap<&'static str, u64>,
+    /// The number of requests for which a response latency was recorded. Outbound only.
+    pub latency_samples: u64,
+    /// The sum of the recorded response latencies, in milliseconds.
+    pub total_latency_ms: u64,
+    /// The longest recorded response latency, in milliseconds.
+    pub max_latency_ms: u64,
+}
+
+impl ProtocolStats {
+    fn add_latency(&mut self, latency: Duration) {
+        let millis = latency.as_millis() as u64;
+        self.latency_samples += 1;
+        self.total_latency_ms += millis;
+        self.max_latency_ms = self.max_latency_ms.max(millis);
+    }
+}
+
+/// The RPC usage of a single peer.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RPCStats {
+    /// Requests made of us by the peer.
+    pub inbound: HashMap<Protocol, ProtocolStats>,
+    /// Requests we made of the peer.
+    pub outbound: HashMap<Protocol, ProtocolStats>,
+}
+
+impl RPCStats {
+    fn stats_mut(&mut self, direction: RPCDirection, protocol: Protocol) -> &mut ProtocolStats {
+        match direction {
+            RPCDirection::Inbound => self.inbound.entry(protocol).or_default(),
+            RPCDirection::Outbound => self.outbound.entry(protocol).or_default(),
+        }
+    }
+
+    /// Records a request sent in `direction`.
+    pub fn request(&mut self, direction: RPCDirection, protocol: Protocol, bytes: usize) {
+        let stats = self.stats_mut(direction, protocol);
+        stats.requests += 1;
+        stats.request_bytes += bytes as u64;
+    }
+
+    /// Records a successful response chunk to a request made in `direction`, along with the
+    /// response latency if this is the first chunk of a response to an outbound request.
+    pub fn response(
+        &mut self,
+        direction: RPCDirection,
+        protocol: Protocol,
+        bytes: usize,
+        latency: Option<Duration>,
+    ) {
+        let stats = self.stats_mut(direction, protocol);
+        stats.responses += 1;
+        stats.response_bytes += bytes as u64;
+        if let Some(latency) = latency {
+            stats.add_latency(latency);
+        }
+    }
+
+    /// Records the latency of an outbound request whose response stream ended without a chunk.
+    pub fn empty_response(&mut self, protocol: Protocol, latency: Duration) {
+        self.stats_mut(RPCDirection::Outbound, protocol)
+            .add_latency(latency);
+    }
+
+    /// Records a failed request made in `direction`, labelled by `error`.
+    pub fn error(&mut self, direction: RPCDirection, protocol: Protocol, error: &'static str) {
+        *self
+            .stats_mut(direction, protocol)
+            .errors
+            .entry(error)
+            .or_default() += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outbound_latency() {
+        let mut stats = RPCStats::default();
+
+        stats.request(RPCDirection::Outbound, Protocol::BlocksByRange, 24);
+        // The handler gives the latency with the first chunk only.
+        stats.response(
+            RPCDirection::Outbound,
+            Protocol::BlocksByRange,
+            100,
+            Some(Duration::from_millis(30)),
+        );
+        stats.response(RPCDirection::Outbound, Protocol::BlocksByRange, 100, None);
+
+        let range = &stats.outbound[&Protocol::BlocksByRange];
+        assert_eq!(range.requests, 1);
+        assert_eq!(range.request_bytes, 24);
+        assert_eq!(range.responses, 2);
+        assert_eq!(range.response_bytes, 200);
+        assert_eq!(range.latency_samples, 1);
+        assert_eq!(range.total_latency_ms, 30);
+        assert_eq!(range.max_latency_ms, 30);
+    }
+
+    #[test]
+    fn empty_response_latency() {
+        let mut stats = RPCStats::default();
+
+        stats.request(RPCDirection::Outbound, Protocol::BlocksByRoot, 32);
+        stats.empty_response(Protocol::BlocksByRoot, Duration::from_millis(5));
+        stats.request(RPCDirection::Outbound, Protocol::BlocksByRoot, 32);
+        stats.empty_response(Protocol::BlocksByRoot, Duration::from_millis(15));
+
+        let root = &stats.outbound[&Protocol::BlocksByRoot];
+        assert_eq!(root.responses, 0);
+        assert_eq!(root.latency_samples, 2);
+        assert_eq!(root.total_latency_ms, 20);
+        assert_eq!(root.max_latency_ms, 15);
+    }
+
+    #[test]
+    fn errors_by_direction() {
+        let mut stats = RPCStats::default();
+
+        stats.request(RPCDirection::Outbound, Protocol::Status, 84);
+        stats.error(RPCDirection::Outbound, Protocol::Status, "stream_timeout");
+        stats.request(RPCDirection::Inbound, Protocol::Status, 84);
+        stats.error(RPCDirection::Inbound, Protocol::Status, "rate_limited");
+        stats.error(RPCDirection::Inbound, Protocol::Status, "rate_limited");
+
+        let outbound = &stats.outbound[&Protocol::Status];
+        assert_eq!(outbound.errors["stream_timeout"], 1);
+        assert_eq!(outbound.latency_samples, 0);
+        assert_eq!(stats.inbound[&Protocol::Status].errors["rate_limited"], 2);
+    }
+}
@@ -16,8 +16,9 @@ use std::io::Cursor;
 use std::io::ErrorKind;
 use std::io::{Read, Write};
 use std::marker::PhantomData;
+use std::ops::Range;
 use tokio_util::codec::{Decoder, Encoder};
-use types::{EthSpec, SignedBeaconBlock};
+use types::{EthSpec, SignedBeaconBlock, SignedBeaconBlockView};
 use unsigned_varint::codec::Uvi;
 
 /* Inbound Codec */
@@ -210,6 +211,8 @@ pub struct SSZSnappyOutboundCodec<TSpec: EthSpec> {
     protocol: ProtocolId,
     /// Maximum bytes that can be sent in one req/resp chunked responses.
     max_packet_size: usize,
+    /// The slots of the blocks requested by a `BlocksByRange` request, if known.
+    expected_block_slots: Option<Range<u64>>,
     phantom: PhantomData<TSpec>,
 }
 
@@ -224,9 +227,21 @@ impl<TSpec: EthSpec> SSZSnappyOutboundCodec<TSpec> {
             protocol,
             max_packet_size,
             len: None,
+            expected_block_slots: None,
             phantom: PhantomData,
         }
     }
+
+    /// Reject blocks received in response to `request` which lie outside the requested slots.
+    ///
+    /// The slot of each block is read before the rest of the block is decoded.
+    pub fn with_blocks_by_range_request(mut self, request: &BlocksByRangeRequest) -> Self {
+        let end_slot = request
+            .start_slot
+            .saturating_add(request.count.saturating_mul(request.step));
+        self.expected_block_slots = Some(request.start_slot..end_slot);
+        self
+    }
 }
 
 // Encoder for outbound streams: Encodes RPC Requests to peers
@@ -315,15 +330,23 @@ impl<TSpec: EthSpec> Decoder for SSZSnappyOutboundCodec<TSpec> {
                     Protocol::Goodbye => Err(RPCError::InvalidData),
                     Protocol::BlocksByRange => match self.protocol.version {
                         Version::V1 => {
-                            if decoded_buffer.len() >= *SIGNED_BEACON_BLOCK_MIN
-                                && decoded_buffer.len() <= *SIGNED_BEACON_BLOCK_MAX
+                            if decoded_buffer.len() < *SIGNED_BEACON_BLOCK_MIN
+                                || decoded_buffer.len() > *SIGNED_BEACON_BLOCK_MAX
                             {
-                                Ok(Some(RPCResponse::BlocksByRange(Box::new(
-                                    SignedBeaconBlock::from_ssz_bytes(&decoded_buffer)?,
-                                ))))
-                            } else {
-                                Err(RPCError::InvalidData)
+                                return Err(RPCError::InvalidData);
                             }
+
+                            if let Some(expected_slots) = &self.expected_block_slots {
+                                let slot =
+                                    SignedBeaconBlockView::<TSpec>::new(&decoded_buffer)?.slot()?;
+                                if !expected_slots.contains(&slot.as_u64()) {
+                                    return Err(RPCError::InvalidData);
+                                }
+                            }
+
+                            Ok(Some(RPCResponse::BlocksByRange(Box::new(
+                                SignedBeaconBlock::from_ssz_bytes(&decoded_buffer)?,
+                            ))))
                         }
                     },
                     Protocol::BlocksByRoot => match self.protocol.version {
@@ -20,7 +20,7 @@ use std::{
     collections::hash_map::Entry,
     pin::Pin,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::time::{delay_queue, delay_until, Delay, DelayQueue, Instant as TInstant};
 use types::EthSpec;
@@ -168,6 +168,8 @@ struct OutboundInfo<TSpec: EthSpec> {
     remaining_chunks: Option<u64>,
     /// `RequestId` as given by the application that sent the request.
     req_id: RequestId,
+    /// The time the request was sent. Taken by the first response to measure the peer's latency.
+    sent_at: Option<Instant>,
 }
 
 /// State of an inbound substream connection.
@@ -438,6 +440,7 @@ where
                         proto,
                         remaining_chunks: expected_responses,
                         req_id: id,
+                        sent_at: Some(Instant::now()),
                     },
                 )
                 .is_some()
@@ -764,12 +767,15 @@ where
                         // Check what type of response we got and report it accordingly
                         let id = entry.get().req_id;
                         let proto = entry.get().proto;
+                        let latency = entry.get_mut().sent_at.take().map(|sent| sent.elapsed());
 
                         let received = match response {
                             RPCCodedResponse::StreamTermination(t) => {
-                                Ok(RPCReceived::EndOfStream(id, t))
+                                Ok(RPCReceived::EndOfStream(id, t, latency))
+                            }
+                            RPCCodedResponse::Success(resp) => {
+                                Ok(RPCReceived::Response(id, resp, latency))
                             }
-                            RPCCodedResponse::Success(resp) => Ok(RPCReceived::Response(id, resp)),
                             RPCCodedResponse::Error(ref code, ref r) => Err(HandlerErr::Outbound {
                                 id,
                                 proto,
@@ -787,13 +793,18 @@ where
                         // drop the stream
                         let delay_key = &entry.get().delay_key;
                         let request_id = entry.get().req_id;
+                        let latency = entry.get().sent_at.map(|sent| sent.elapsed());
                         self.outbound_substreams_delay.remove(delay_key);
                         entry.remove_entry();
                         // notify the application error
                         if request.expected_responses() > 1 {
                             // return an end of stream result
                             return Poll::Ready(ProtocolsHandlerEvent::Custom(Ok(
-                                RPCReceived::EndOfStream(request_id, request.stream_termination()),
+                                RPCReceived::EndOfStream(
+                                    request_id,
+                                    request.stream_termination(),
+                                    latency,
+                                ),
                             )));
                         }
 
@@ -846,7 +857,9 @@ where
 
                             if let Some(termination) = termination {
                                 return Poll::Ready(ProtocolsHandlerEvent::Custom(Ok(
-                                    RPCReceived::EndOfStream(request_id, termination),
+                                    // the latency was reported with the response that closed
+                                    // the stream, if any
+                                    RPCReceived::EndOfStream(request_id, termination, None),
                                 )));
                             }
                         }
@@ -1,8 +1,10 @@
 //! Available RPC methods types and ids.
 
+use crate::rpc::Protocol;
 use crate::types::EnrBitfield;
 use regex::bytes::Regex;
 use serde::Serialize;
+use ssz::Encode;
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
     typenum::{U1024, U256},
@@ -57,7 +59,7 @@ impl ToString for ErrorType {
 ///
 // NOTE: The handler stores the `RequestId` to inform back of responses and errors, but it's execution
 // is independent of the contents on this type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RequestId {
     Router,
     Sync(usize),
@@ -232,6 +234,30 @@ pub enum RPCResponse<T: EthSpec> {
     MetaData(MetaData<T>),
 }
 
+impl<T: EthSpec> RPCResponse<T> {
+    /// Gives the corresponding `Protocol` to this response.
+    pub fn protocol(&self) -> Protocol {
+        match self {
+            RPCResponse::Status(_) => Protocol::Status,
+            RPCResponse::BlocksByRange(_) => Protocol::BlocksByRange,
+            RPCResponse::BlocksByRoot(_) => Protocol::BlocksByRoot,
+            RPCResponse::Pong(_) => Protocol::Ping,
+            RPCResponse::MetaData(_) => Protocol::MetaData,
+        }
+    }
+
+    /// The length of the response's SSZ encoding, before compression.
+    pub fn ssz_bytes_len(&self) -> usize {
+        match self {
+            RPCResponse::Status(res) => res.ssz_bytes_len(),
+            RPCResponse::BlocksByRange(res) => res.ssz_bytes_len(),
+            RPCResponse::BlocksByRoot(res) => res.ssz_bytes_len(),
+            RPCResponse::Pong(res) => res.data.ssz_bytes_len(),
+            RPCResponse::MetaData(res) => res.ssz_bytes_len(),
+        }
+    }
+}
+
 /// Indicates which response is being terminated by a stream termination response.
 #[derive(Debug, Clone)]
 pub enum ResponseTermination {
@@ -242,6 +268,16 @@ pub enum ResponseTermination {
     BlocksByRoot,
 }
 
+impl ResponseTermination {
+    /// Gives the `Protocol` of the terminated stream.
+    pub fn protocol(&self) -> Protocol {
+        match self {
+            ResponseTermination::BlocksByRange => Protocol::BlocksByRange,
+            ResponseTermination::BlocksByRoot => Protocol::BlocksByRoot,
+        }
+    }
+}
+
 /// The structured response containing a result/code indicating success or failure
 /// and the contents of the response
 #[derive(Debug, Clone)]
@@ -318,6 +354,16 @@ impl<T: EthSpec> RPCCodedResponse<T> {
 }
 
 impl RPCResponseErrorCode {
+    /// A short label for the error code, used in metrics.
+    pub fn as_static_str(&self) -> &'static str {
+        match self {
+            RPCResponseErrorCode::InvalidRequest => "invalid_request",
+            RPCResponseErrorCode::ServerError => "server_error",
+            RPCResponseErrorCode::Unknown => "unknown",
+            RPCResponseErrorCode::RateLimited => "rate_limited",
+        }
+    }
+
     fn as_u8(&self) -> u8 {
         match self {
             RPCResponseErrorCode::InvalidRequest => 1,
@@ -65,9 +65,14 @@ pub enum RPCReceived<T: EthSpec> {
     /// The `RequestId` corresponds to the application given ID of the original request sent to the
     /// peer. The second parameter is a single chunk of a response. These go over *outbound*
     /// connections.
-    Response(RequestId, RPCResponse<T>),
+    ///
+    /// The `Duration` is the time the peer took to respond to the request. It is only given with
+    /// the first chunk of a response.
+    Response(RequestId, RPCResponse<T>, Option<Duration>),
     /// Marks a request as completed
-    EndOfStream(RequestId, ResponseTermination),
+    ///
+    /// The `Duration` is the time the peer took to respond, if the stream ended without a chunk.
+    EndOfStream(RequestId, ResponseTermination, Option<Duration>),
 }
 
 impl<T: EthSpec> std::fmt::Display for RPCSend<T> {
@@ -64,7 +64,7 @@ const TTFB_TIMEOUT: u64 = 5;
 const REQUEST_TIMEOUT: u64 = 15;
 
 /// Protocol names to be used.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Protocol {
     /// The Status protocol name.
     Status,
@@ -107,6 +107,13 @@ impl std::fmt::Display for Protocol {
     }
 }
 
+/// Serializes as the protocol name, allowing `Protocol` to be used as a map key.
+impl serde::Serialize for Protocol {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 impl std::fmt::Display for Encoding {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let repr = match self {
@@ -331,6 +338,18 @@ impl<TSpec: EthSpec> RPCRequest<TSpec> {
         }
     }
 
+    /// The length of the request's SSZ encoding, before compression.
+    pub fn ssz_bytes_len(&self) -> usize {
+        match self {
+            RPCRequest::Status(req) => req.ssz_bytes_len(),
+            RPCRequest::Goodbye(req) => req.ssz_bytes_len(),
+            RPCRequest::BlocksByRange(req) => req.ssz_bytes_len(),
+            RPCRequest::BlocksByRoot(req) => req.block_roots.ssz_bytes_len(),
+            RPCRequest::Ping(req) => req.ssz_bytes_len(),
+            RPCRequest::MetaData(_) => 0,
+        }
+    }
+
     /// Returns the `ResponseTermination` type associated with the request if a stream gets
     /// terminated.
     pub fn stream_termination(&self) -> ResponseTermination {
@@ -367,9 +386,11 @@ where
         let socket = socket.compat();
         let codec = match protocol.encoding {
             Encoding::SSZSnappy => {
-                let ssz_snappy_codec =
-                    BaseOutboundCodec::new(SSZSnappyOutboundCodec::new(protocol, MAX_RPC_SIZE));
-                OutboundCodec::SSZSnappy(ssz_snappy_codec)
+                let mut ssz_snappy_codec = SSZSnappyOutboundCodec::new(protocol, MAX_RPC_SIZE);
+                if let RPCRequest::BlocksByRange(request) = &self {
+                    ssz_snappy_codec = ssz_snappy_codec.with_blocks_by_range_request(request);
+                }
+                OutboundCodec::SSZSnappy(BaseOutboundCodec::new(ssz_snappy_codec))
             }
         };
 
@@ -453,6 +474,25 @@ impl std::fmt::Display for RPCError {
     }
 }
 
+impl RPCError {
+    /// A short label for the error, used in metrics. Error responses are labelled by their code.
+    pub fn as_static_str(&self) -> &'static str {
+        match self {
+            RPCError::SSZDecodeError(_) => "ssz_decode_error",
+            RPCError::IoError(_) => "io_error",
+            RPCError::ErrorResponse(code, _) => code.as_static_str(),
+            RPCError::StreamTimeout => "stream_timeout",
+            RPCError::UnsupportedProtocol => "unsupported_protocol",
+            RPCError::IncompleteStream => "incomplete_stream",
+            RPCError::InvalidData => "invalid_data",
+            RPCError::InternalError(_) => "internal_error",
+            RPCError::NegotiationTimeout => "negotiation_timeout",
+            RPCError::HandlerRejected => "handler_rejected",
+            RPCError::RateLimited => "rate_limited",
+        }
+    }
+}
+
 impl std::error::Error for RPCError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match *self {
@@ -6,6 +6,7 @@ use beacon_chain::{BeaconChain, BeaconChainTypes, BlockError, ChainSegmentResult
 use eth2_libp2p::PeerId;
 use slog::{debug, error, trace, warn};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use types::{Epoch, EthSpec, Hash256, SignedBeaconBlock};
 
@@ -32,7 +33,11 @@ pub fn handle_chain_segment<T: BeaconChainTypes>(
             let end_slot = downloaded_blocks.last().map(|b| b.message.slot.as_u64());
             let sent_blocks = downloaded_blocks.len();
 
-            let result = match process_blocks(chain, downloaded_blocks.iter(), &log) {
+            let processing_start = Instant::now();
+            let (imported_blocks, result) = process_blocks(chain, downloaded_blocks.iter(), &log);
+            observe_batch_throughput(imported_blocks, processing_start.elapsed());
+
+            let result = match (imported_blocks, result) {
                 (_, Ok(_)) => {
                     debug!(log, "Batch processed"; "batch_epoch" => epoch, "first_block_slot" => start_slot,
                         "last_block_slot" => end_slot, "processed_blocks" => sent_blocks, "service"=> "sync");
@@ -98,6 +103,7 @@ fn process_blocks<
     log: &slog::Logger,
 ) -> (usize, Result<(), String>) {
     let blocks = downloaded_blocks.cloned().collect::<Vec<_>>();
+    let _timer = metrics::start_timer(&metrics::BEACON_PROCESSOR_CHAIN_SEGMENT_PROCESSING_TIMES);
     match chain.process_chain_segment(blocks) {
         ChainSegmentResult::Successful { imported_blocks } => {
             metrics::inc_counter(&metrics::BEACON_PROCESSOR_CHAIN_SEGMENT_SUCCESS_TOTAL);
@@ -122,6 +128,22 @@ fn process_blocks<
     }
 }
 
+/// Updates the range sync throughput metrics after processing a batch.
+fn observe_batch_throughput(imported_blocks: usize, elapsed: Duration) {
+    metrics::inc_counter_by(
+        &metrics::BEACON_PROCESSOR_CHAIN_SEGMENT_IMPORTED_BLOCKS_TOTAL,
+        imported_blocks as i64,
+    );
+
+    let seconds = elapsed.as_secs_f64();
+    if imported_blocks > 0 && seconds > 0.0 {
+        metrics::set_float_gauge(
+            &metrics::SYNC_RANGE_BLOCKS_PER_SECOND,
+            imported_blocks as f64 / seconds,
+        );
+    }
+}
+
 /// Runs fork-choice on a given chain. This is used during block processing after one successful
 /// block import.
 fn run_fork_choice<T: BeaconChainTypes>(chain: Arc<BeaconChain<T>>, log: &slog::Logger) {
@@ -40,6 +40,7 @@ use beacon_chain::{BeaconChain, BeaconChainTypes, BlockError};
 use environment::TaskExecutor;
 use eth2_libp2p::{MessageId, NetworkGlobals, PeerId};
 use slog::{crit, debug, error, trace, warn, Logger};
+use std::cmp;
 use std::collections::VecDeque;
 use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
@@ -48,7 +49,7 @@ use types::{
     Attestation, AttesterSlashing, EthSpec, Hash256, ProposerSlashing, SignedAggregateAndProof,
     SignedBeaconBlock, SignedVoluntaryExit, SubnetId,
 };
-use worker::Worker;
+use worker::{GossipAggregatePackage, GossipAttestationPackage, Worker};
 
 mod chain_segment;
 mod worker;
@@ -98,6 +99,14 @@ const MAX_RPC_BLOCK_QUEUE_LEN: usize = 1_024;
 /// be stored before we start dropping them.
 const MAX_CHAIN_SEGMENT_QUEUE_LEN: usize = 64;
 
+/// The maximum number of queued `Attestation` objects that will be verified together in a single
+/// worker.
+const MAX_GOSSIP_ATTESTATION_BATCH_SIZE: usize = 64;
+
+/// The maximum number of queued `SignedAggregateAndProof` objects that will be verified together in
+/// a single worker.
+const MAX_GOSSIP_AGGREGATE_BATCH_SIZE: usize = 64;
+
 /// The name of the manager tokio task.
 const MANAGER_TASK_NAME: &str = "beacon_gossip_processor_manager";
 /// The name of the worker tokio tasks.
@@ -188,6 +197,11 @@ impl<T> LifoQueue<T> {
         self.queue.len() >= self.max_length
     }
 
+    /// Returns `true` if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
     /// Returns the current length of the queue.
     pub fn len(&self) -> usize {
         self.queue.len()
@@ -332,11 +346,17 @@ pub enum Work<E: EthSpec> {
         subnet_id: SubnetId,
         should_import: bool,
     },
+    GossipAttestationBatch {
+        packages: Vec<GossipAttestationPackage<E>>,
+    },
     GossipAggregate {
         message_id: MessageId,
         peer_id: PeerId,
         aggregate: Box<SignedAggregateAndProof<E>>,
     },
+    GossipAggregateBatch {
+        packages: Vec<GossipAggregatePackage<E>>,
+    },
     GossipBlock {
         message_id: MessageId,
         peer_id: PeerId,
@@ -372,7 +392,9 @@ impl<E: EthSpec> Work<E> {
     fn str_id(&self) -> &'static str {
         match self {
             Work::GossipAttestation { .. } => "gossip_attestation",
+            Work::GossipAttestationBatch { .. } => "gossip_attestation_batch",
             Work::GossipAggregate { .. } => "gossip_aggregate",
+            Work::GossipAggregateBatch { .. } => "gossip_aggregate_batch",
             Work::GossipBlock { .. } => "gossip_block",
             Work::GossipVoluntaryExit { .. } => "gossip_voluntary_exit",
             Work::GossipProposerSlashing { .. } => "gossip_proposer_slashing",
@@ -538,10 +560,81 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                         // since we assume that aggregates are more valuable to local validators
                         // and effectively give us more information with less signature
                         // verification time.
-                        } else if let Some(item) = aggregate_queue.pop() {
-                            self.spawn_worker(idle_tx.clone(), item);
-                        } else if let Some(item) = attestation_queue.pop() {
-                            self.spawn_worker(idle_tx.clone(), item);
+                        //
+                        // If more than one attestation is queued then their signatures are
+                        // verified together in a single worker.
+                        } else if !aggregate_queue.is_empty() {
+                            let batch_size =
+                                cmp::min(aggregate_queue.len(), MAX_GOSSIP_AGGREGATE_BATCH_SIZE);
+
+                            if batch_size < 2 {
+                                if let Some(item) = aggregate_queue.pop() {
+                                    self.spawn_worker(idle_tx.clone(), item);
+                                }
+                            } else {
+                                let mut packages = Vec::with_capacity(batch_size);
+                                for _ in 0..batch_size {
+                                    match aggregate_queue.pop() {
+                                        Some(Work::GossipAggregate {
+                                            message_id,
+                                            peer_id,
+                                            aggregate,
+                                        }) => packages.push(GossipAggregatePackage::new(
+                                            message_id, peer_id, aggregate,
+                                        )),
+                                        Some(_) => error!(
+                                            self.log,
+                                            "Invalid item in aggregate queue";
+                                        ),
+                                        None => break,
+                                    }
+                                }
+
+                                self.spawn_worker(
+                                    idle_tx.clone(),
+                                    Work::GossipAggregateBatch { packages },
+                                );
+                            }
+                        } else if !attestation_queue.is_empty() {
+                            let batch_size = cmp::min(
+                                attestation_queue.len(),
+                                MAX_GOSSIP_ATTESTATION_BATCH_SIZE,
+                            );
+
+                            if batch_size < 2 {
+                                if let Some(item) = attestation_queue.pop() {
+                                    self.spawn_worker(idle_tx.clone(), item);
+                                }
+                            } else {
+                                let mut packages = Vec::with_capacity(batch_size);
+                                for _ in 0..batch_size {
+                                    match attestation_queue.pop() {
+                                        Some(Work::GossipAttestation {
+                                            message_id,
+                                            peer_id,
+                                            attestation,
+                                            subnet_id,
+                                            should_import,
+                                        }) => packages.push(GossipAttestationPackage::new(
+                                            message_id,
+                                            peer_id,
+                                            attestation,
+                                            subnet_id,
+                                            should_import,
+                                        )),
+                                        Some(_) => error!(
+                                            self.log,
+                                            "Invalid item in attestation queue";
+                                        ),
+                                        None => break,
+                                    }
+                                }
+
+                                self.spawn_worker(
+                                    idle_tx.clone(),
+                                    Work::GossipAttestationBatch { packages },
+                                );
+                            }
                         // Check slashings after all other consensus messages so we prioritize
                         // following head.
                         //
@@ -590,6 +683,14 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                             _ if can_spawn => self.spawn_worker(idle_tx.clone(), work),
                             Work::GossipAttestation { .. } => attestation_queue.push(work),
                             Work::GossipAggregate { .. } => aggregate_queue.push(work),
+                            // Batches are only created by the manager, they are never received
+                            // as a work event.
+                            Work::GossipAttestationBatch { .. }
+                            | Work::GossipAggregateBatch { .. } => crit!(
+                                self.log,
+                                "Unsupported inbound work event";
+                                "work_id" => work_id
+                            ),
                             Work::GossipBlock { .. } => {
                                 gossip_block_queue.push(work, work_id, &self.log)
                             }
@@ -744,6 +845,12 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                         subnet_id,
                         should_import,
                     ),
+                    /*
+                     * Batched unaggregated attestation verification.
+                     */
+                    Work::GossipAttestationBatch { packages } => {
+                        worker.process_gossip_attestation_batch(packages)
+                    }
                     /*
                      * Aggregated attestation verification.
                      */
@@ -752,6 +859,12 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                         peer_id,
                         aggregate,
                     } => worker.process_gossip_aggregate(message_id, peer_id, *aggregate),
+                    /*
+                     * Batched aggregated attestation verification.
+                     */
+                    Work::GossipAggregateBatch { packages } => {
+                        worker.process_gossip_aggregate_batch(packages)
+                    }
                     /*
                      * Verification for beacon blocks received on gossip.
                      */
@@ -4,7 +4,10 @@ use super::{
 };
 use crate::{metrics, service::NetworkMessage, sync::SyncMessage};
 use beacon_chain::{
-    attestation_verification::Error as AttnError, observed_operations::ObservationOutcome,
+    attestation_verification::{
+        Error as AttnError, VerifiedAggregatedAttestation, VerifiedUnaggregatedAttestation,
+    },
+    observed_operations::ObservationOutcome,
     BeaconChain, BeaconChainError, BeaconChainTypes, BlockError, ForkChoiceError,
 };
 use eth2_libp2p::{MessageAcceptance, MessageId, PeerAction, PeerId};
@@ -13,10 +16,66 @@ use ssz::Encode;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use types::{
-    Attestation, AttesterSlashing, Hash256, ProposerSlashing, SignedAggregateAndProof,
+    Attestation, AttesterSlashing, EthSpec, Hash256, ProposerSlashing, SignedAggregateAndProof,
     SignedBeaconBlock, SignedVoluntaryExit, SubnetId,
 };
 
+/// An unaggregated attestation received from the gossip network, waiting to be verified as part
+/// of a batch.
+#[derive(Debug)]
+pub struct GossipAttestationPackage<E: EthSpec> {
+    message_id: MessageId,
+    peer_id: PeerId,
+    attestation: Box<Attestation<E>>,
+    subnet_id: SubnetId,
+    beacon_block_root: Hash256,
+    should_import: bool,
+}
+
+impl<E: EthSpec> GossipAttestationPackage<E> {
+    pub fn new(
+        message_id: MessageId,
+        peer_id: PeerId,
+        attestation: Box<Attestation<E>>,
+        subnet_id: SubnetId,
+        should_import: bool,
+    ) -> Self {
+        Self {
+            message_id,
+            peer_id,
+            beacon_block_root: attestation.data.beacon_block_root,
+            attestation,
+            subnet_id,
+            should_import,
+        }
+    }
+}
+
+/// An aggregated attestation received from the gossip network, waiting to be verified as part of
+/// a batch.
+#[derive(Debug)]
+pub struct GossipAggregatePackage<E: EthSpec> {
+    message_id: MessageId,
+    peer_id: PeerId,
+    aggregate: Box<SignedAggregateAndProof<E>>,
+    beacon_block_root: Hash256,
+}
+
+impl<E: EthSpec> GossipAggregatePackage<E> {
+    pub fn new(
+        message_id: MessageId,
+        peer_id: PeerId,
+        aggregate: Box<SignedAggregateAndProof<E>>,
+    ) -> Self {
+        Self {
+            message_id,
+            peer_id,
+            beacon_block_root: aggregate.message.aggregate.data.beacon_block_root,
+            aggregate,
+        }
+    }
+}
+
 /// Contains the context necessary to import blocks, attestations, etc to the beacon chain.
 pub struct Worker<T: BeaconChainTypes> {
     pub chain: Arc<BeaconChain<T>>,
@@ -43,10 +102,70 @@ impl<T: BeaconChainTypes> Worker<T> {
     ) {
         let beacon_block_root = attestation.data.beacon_block_root;
 
-        let attestation = match self
+        let result = self
+            .chain
+            .verify_unaggregated_attestation_for_gossip(attestation, subnet_id);
+
+        self.process_gossip_attestation_result(
+            result,
+            message_id,
+            peer_id,
+            beacon_block_root,
+            should_import,
+        );
+    }
+
+    /// Process a batch of unaggregated attestations received from the gossip network.
+    ///
+    /// The signatures of the attestations are verified together, otherwise each attestation is
+    /// handled exactly as in `Self::process_gossip_attestation`.
+    pub fn process_gossip_attestation_batch(
+        self,
+        packages: Vec<GossipAttestationPackage<T::EthSpec>>,
+    ) {
+        let (metadata, attestations): (Vec<_>, Vec<_>) = packages
+            .into_iter()
+            .map(|package| {
+                (
+                    (
+                        package.message_id,
+                        package.peer_id,
+                        package.beacon_block_root,
+                        package.should_import,
+                    ),
+                    (*package.attestation, package.subnet_id),
+                )
+            })
+            .unzip();
+
+        let results = self
             .chain
-            .verify_unaggregated_attestation_for_gossip(attestation, subnet_id)
+            .batch_verify_unaggregated_attestations_for_gossip(attestations);
+
+        for ((message_id, peer_id, beacon_block_root, should_import), result) in
+            metadata.into_iter().zip(results)
         {
+            self.process_gossip_attestation_result(
+                result,
+                message_id,
+                peer_id,
+                beacon_block_root,
+                should_import,
+            );
+        }
+    }
+
+    /// Handles the result of verifying an unaggregated attestation received from the gossip
+    /// network. See `Self::process_gossip_attestation`.
+    fn process_gossip_attestation_result(
+        &self,
+        result: Result<VerifiedUnaggregatedAttestation<T>, AttnError>,
+        message_id: MessageId,
+        peer_id: PeerId,
+        beacon_block_root: Hash256,
+        should_import: bool,
+    ) {
+        let attestation = match result {
             Ok(attestation) => attestation,
             Err(e) => {
                 self.handle_attestation_verification_failure(
@@ -119,10 +238,52 @@ impl<T: BeaconChainTypes> Worker<T> {
     ) {
         let beacon_block_root = aggregate.message.aggregate.data.beacon_block_root;
 
-        let aggregate = match self
+        let result = self
             .chain
-            .verify_aggregated_attestation_for_gossip(aggregate)
+            .verify_aggregated_attestation_for_gossip(aggregate);
+
+        self.process_gossip_aggregate_result(result, message_id, peer_id, beacon_block_root);
+    }
+
+    /// Process a batch of aggregated attestations received from the gossip network.
+    ///
+    /// The signatures of the aggregates are verified together, otherwise each aggregate is handled
+    /// exactly as in `Self::process_gossip_aggregate`.
+    pub fn process_gossip_aggregate_batch(self, packages: Vec<GossipAggregatePackage<T::EthSpec>>) {
+        let (metadata, aggregates): (Vec<_>, Vec<_>) = packages
+            .into_iter()
+            .map(|package| {
+                (
+                    (
+                        package.message_id,
+                        package.peer_id,
+                        package.beacon_block_root,
+                    ),
+                    *package.aggregate,
+                )
+            })
+            .unzip();
+
+        let results = self
+            .chain
+            .batch_verify_aggregated_attestations_for_gossip(aggregates);
+
+        for ((message_id, peer_id, beacon_block_root), result) in metadata.into_iter().zip(results)
         {
+            self.process_gossip_aggregate_result(result, message_id, peer_id, beacon_block_root);
+        }
+    }
+
+    /// Handles the result of verifying an aggregated attestation received from the gossip network.
+    /// See `Self::process_gossip_aggregate`.
+    fn process_gossip_aggregate_result(
+        &self,
+        result: Result<VerifiedAggregatedAttestation<T>, AttnError>,
+        message_id: MessageId,
+        peer_id: PeerId,
+        beacon_block_root: Hash256,
+    ) {
+        let aggregate = match result {
             Ok(aggregate) => aggregate,
             Err(e) => {
                 // Report the failure to gossipsub
@@ -218,6 +218,18 @@ lazy_static! {
         "beacon_processor_chain_segment_failed_total",
         "Total number of chain segments that failed processing."
     );
+    pub static ref BEACON_PROCESSOR_CHAIN_SEGMENT_IMPORTED_BLOCKS_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_processor_chain_segment_imported_blocks_total",
+        "Total number of blocks imported from chain segments."
+    );
+    pub static ref BEACON_PROCESSOR_CHAIN_SEGMENT_PROCESSING_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_processor_chain_segment_processing_seconds",
+        "Time taken to process a chain segment, including signature verification and import."
+    );
+    pub static ref SYNC_RANGE_BLOCKS_PER_SECOND: Result<Gauge> = try_create_float_gauge(
+        "sync_range_blocks_per_second",
+        "Blocks imported per second whilst processing the most recent range sync batch."
+    );
     // Unaggregated attestations.
     pub static ref BEACON_PROCESSOR_UNAGGREGATED_ATTESTATION_QUEUE_TOTAL: Result<IntGauge> = try_create_int_gauge(
         "beacon_processor_unaggregated_attestation_queue_total",
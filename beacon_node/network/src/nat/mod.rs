@@ -0,0 +1,486 @@
+//! This houses various NAT hole punching strategies.
+//!
+//! Currently supported strategies:
+//! - UPnP
+//! - PCP, falling back to NAT-PMP
+//!
+//! UPnP is attempted first. If no UPnP gateway can be found, the default gateway is asked for
+//! mappings using PCP or NAT-PMP. Mappings made with the latter expire and are renewed by the
+//! network service before they do.
+
+mod natpmp;
+pub mod reachability;
+
+use crate::{NetworkConfig, NetworkMessage};
+use get_if_addrs::get_if_addrs;
+use natpmp::{MappingRequest, Protocol, Version};
+use rand::RngCore;
+use slog::{debug, info, warn};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use types::EthSpec;
+
+/// The lifetime requested for NAT-PMP and PCP port mappings. The gateway may grant a shorter one.
+const NATPMP_MAPPING_LIFETIME: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// Configuration required to construct the port mappings.
+#[derive(Debug, Clone, Copy)]
+pub struct NatConfig {
+    /// The local tcp port.
+    tcp_port: u16,
+    /// The local udp port.
+    udp_port: u16,
+    /// Whether discovery is enabled or not.
+    disable_discovery: bool,
+}
+
+impl From<&NetworkConfig> for NatConfig {
+    fn from(config: &NetworkConfig) -> Self {
+        NatConfig {
+            tcp_port: config.libp2p_port,
+            udp_port: config.discovery_port,
+            disable_discovery: config.disable_discovery,
+        }
+    }
+}
+
+/// The protocol used to establish a set of port mappings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NatProtocol {
+    UPnP,
+    NatPmp,
+    Pcp,
+}
+
+impl fmt::Display for NatProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NatProtocol::UPnP => write!(f, "UPnP"),
+            NatProtocol::NatPmp => write!(f, "NAT-PMP"),
+            NatProtocol::Pcp => write!(f, "PCP"),
+        }
+    }
+}
+
+/// External port mappings established on the local gateway.
+#[derive(Debug, Clone)]
+pub struct PortMappings {
+    /// The protocol the mappings were established with.
+    pub protocol: NatProtocol,
+    /// The external TCP socket, if it was mapped.
+    pub tcp_socket: Option<SocketAddr>,
+    /// The external UDP socket, if it was mapped.
+    pub udp_socket: Option<SocketAddr>,
+    /// The shortest lifetime granted to the mappings. `None` if they do not expire.
+    pub lifetime: Option<Duration>,
+    config: NatConfig,
+    local_ip: Ipv4Addr,
+    /// The NAT-PMP or PCP gateway the mappings were made on.
+    gateway: Option<SocketAddrV4>,
+    /// Identifies the mappings to a PCP gateway across renewals.
+    nonce: [u8; natpmp::NONCE_LEN],
+}
+
+impl PortMappings {
+    /// The external IP address of the gateway, if known.
+    pub fn external_ip(&self) -> Option<IpAddr> {
+        self.tcp_socket
+            .or(self.udp_socket)
+            .map(|socket| socket.ip())
+    }
+
+    /// The time after which the mappings should be renewed, if they expire.
+    ///
+    /// Following RFC 6886, renewal happens half way through the lifetime.
+    pub fn renewal_delay(&self) -> Option<Duration> {
+        self.lifetime.map(|lifetime| lifetime / 2)
+    }
+}
+
+/// Attempts to construct external port mappings with UPnP, or PCP/NAT-PMP if UPnP is unavailable.
+pub fn construct_mappings<T: EthSpec>(
+    config: NatConfig,
+    network_send: mpsc::UnboundedSender<NetworkMessage<T>>,
+    log: slog::Logger,
+) {
+    let local_ip = match local_ipv4(&log) {
+        Some(ip) => ip,
+        None => return,
+    };
+
+    let mappings = construct_upnp_mappings(config, local_ip, &log)
+        .or_else(|| construct_natpmp_mappings(config, local_ip, None, &log));
+
+    match mappings {
+        // report any updates to the network service.
+        Some(mappings) => send_mappings(mappings, &network_send, &log),
+        None => debug!(log, "No NAT port mappings could be established"),
+    }
+}
+
+/// Renews port mappings that are about to expire.
+pub fn renew_mappings<T: EthSpec>(
+    mappings: PortMappings,
+    network_send: mpsc::UnboundedSender<NetworkMessage<T>>,
+    log: slog::Logger,
+) {
+    if mappings.protocol == NatProtocol::UPnP {
+        // UPnP mappings are constructed without a lease and don't expire.
+        return;
+    }
+
+    match construct_natpmp_mappings(mappings.config, mappings.local_ip, Some(&mappings), &log) {
+        Some(renewed) => send_mappings(renewed, &network_send, &log),
+        None => warn!(log, "Failed to renew NAT port mappings"; "protocol" => %mappings.protocol),
+    }
+}
+
+fn send_mappings<T: EthSpec>(
+    mappings: PortMappings,
+    network_send: &mpsc::UnboundedSender<NetworkMessage<T>>,
+    log: &slog::Logger,
+) {
+    network_send
+        .send(NetworkMessage::PortMappingsEstablished(mappings))
+        .unwrap_or_else(
+            |e| warn!(log, "Could not send message to the network service"; "error" => %e),
+        );
+}
+
+/// Finds the local IPv4 address to point port mappings at.
+fn local_ipv4(log: &slog::Logger) -> Option<Ipv4Addr> {
+    // Need to find the local listening address matched with the router subnet
+    let interfaces = match get_if_addrs() {
+        Ok(v) => v,
+        Err(e) => {
+            debug!(log, "NAT failed to get local interfaces"; "error" => %e);
+            return None;
+        }
+    };
+
+    // Just use the first IPv4 address of the first interface that is not a loopback
+    let local_ip = interfaces
+        .iter()
+        .filter(|interface| !interface.is_loopback())
+        .find_map(|interface| match interface.ip() {
+            IpAddr::V4(ip) => Some(ip),
+            IpAddr::V6(_) => None,
+        });
+
+    if local_ip.is_none() {
+        debug!(
+            log,
+            "NAT failed to find local IPv4 address. IPv6 not supported"
+        );
+    }
+    local_ip
+}
+
+/// Attempts to construct external port mappings with UPnP.
+fn construct_upnp_mappings(
+    config: NatConfig,
+    local_ip: Ipv4Addr,
+    log: &slog::Logger,
+) -> Option<PortMappings> {
+    debug!(log, "UPnP Initialising routes");
+    let gateway = match igd::search_gateway(Default::default()) {
+        Ok(gateway) => gateway,
+        Err(e) => {
+            debug!(log, "UPnP not available"; "error" => %e);
+            return None;
+        }
+    };
+
+    let external_ip = gateway.get_external_ip();
+    let external_socket = |port| {
+        external_ip
+            .as_ref()
+            .map(|ip| SocketAddr::new((*ip).into(), port))
+            .ok()
+    };
+
+    // We add specific port mappings rather than getting the router to arbitrary assign
+    // one.
+    // I've found this to be more reliable. If multiple users are behind a single
+    // router, they should ideally try to set different port numbers.
+    let libp2p_socket = SocketAddrV4::new(local_ip, config.tcp_port);
+    let tcp_socket = match gateway.add_port(
+        igd::PortMappingProtocol::TCP,
+        libp2p_socket.port(),
+        libp2p_socket,
+        0,
+        "lighthouse-tcp",
+    ) {
+        Err(e) => {
+            debug!(log, "UPnP could not construct libp2p port route"; "error" => %e);
+            None
+        }
+        Ok(_) => {
+            let socket = external_socket(config.tcp_port);
+            info!(log, "UPnP TCP route established"; "external_socket" => socket_string(socket));
+            socket
+        }
+    };
+
+    let udp_socket = if !config.disable_discovery {
+        let discovery_socket = SocketAddrV4::new(local_ip, config.udp_port);
+        match gateway.add_port(
+            igd::PortMappingProtocol::UDP,
+            discovery_socket.port(),
+            discovery_socket,
+            0,
+            "lighthouse-udp",
+        ) {
+            Err(e) => {
+                debug!(log, "UPnP could not construct discovery port route"; "error" => %e);
+                None
+            }
+            Ok(_) => {
+                let socket = external_socket(config.udp_port);
+                info!(log, "UPnP UDP route established"; "external_socket" => socket_string(socket));
+                socket
+            }
+        }
+    } else {
+        None
+    };
+
+    if tcp_socket.is_none() && udp_socket.is_none() {
+        return None;
+    }
+
+    Some(PortMappings {
+        protocol: NatProtocol::UPnP,
+        tcp_socket,
+        udp_socket,
+        lifetime: None,
+        config,
+        local_ip,
+        gateway: None,
+        nonce: [0; natpmp::NONCE_LEN],
+    })
+}
+
+/// Attempts to construct, or renew if `previous` is given, port mappings with PCP or NAT-PMP.
+fn construct_natpmp_mappings(
+    config: NatConfig,
+    local_ip: Ipv4Addr,
+    previous: Option<&PortMappings>,
+    log: &slog::Logger,
+) -> Option<PortMappings> {
+    let gateway = match previous.and_then(|mappings| mappings.gateway) {
+        Some(gateway) => gateway,
+        None => match natpmp::default_gateway() {
+            Some(ip) => SocketAddrV4::new(ip, natpmp::SERVER_PORT),
+            None => {
+                debug!(log, "NAT-PMP failed to find the default gateway");
+                return None;
+            }
+        },
+    };
+
+    let client = match natpmp::Client::new(gateway) {
+        Ok(client) => client,
+        Err(e) => {
+            debug!(log, "NAT-PMP could not create socket"; "error" => %e);
+            return None;
+        }
+    };
+
+    let nonce = previous.map_or_else(
+        || {
+            let mut nonce = [0; natpmp::NONCE_LEN];
+            rand::thread_rng().fill_bytes(&mut nonce);
+            nonce
+        },
+        |mappings| mappings.nonce,
+    );
+    // When renewing, ask for the external ports the gateway granted previously, which may differ
+    // from the local ports if the gateway reassigned them.
+    let request = |protocol, internal_port, previous_socket: Option<SocketAddr>| MappingRequest {
+        protocol,
+        local_ip,
+        internal_port,
+        external_port: previous_socket.map_or(internal_port, |socket| socket.port()),
+        lifetime: NATPMP_MAPPING_LIFETIME,
+        nonce,
+    };
+
+    // Negotiate the protocol version with the TCP mapping, unless it is already known.
+    let tcp_request = request(
+        Protocol::Tcp,
+        config.tcp_port,
+        previous.and_then(|mappings| mappings.tcp_socket),
+    );
+    let (version, tcp_mapping) = match previous.map(|mappings| mappings.protocol) {
+        Some(NatProtocol::NatPmp) => (Version::NatPmp, client.map(Version::NatPmp, &tcp_request)),
+        Some(NatProtocol::Pcp) => (Version::Pcp, client.map(Version::Pcp, &tcp_request)),
+        _ => match client.map_any(&tcp_request) {
+            Ok((version, mapping)) => (version, Ok(mapping)),
+            Err(e) => {
+                debug!(log, "NAT-PMP and PCP not available"; "gateway" => %gateway, "error" => %e);
+                return None;
+            }
+        },
+    };
+    let protocol = match version {
+        Version::NatPmp => NatProtocol::NatPmp,
+        Version::Pcp => NatProtocol::Pcp,
+    };
+
+    let tcp_mapping = tcp_mapping
+        .map_err(|e| debug!(log, "Could not construct libp2p port route"; "protocol" => %protocol, "error" => %e))
+        .ok();
+
+    let udp_mapping = if !config.disable_discovery {
+        client
+            .map(
+                version,
+                &request(
+                    Protocol::Udp,
+                    config.udp_port,
+                    previous.and_then(|mappings| mappings.udp_socket),
+                ),
+            )
+            .map_err(|e| debug!(log, "Could not construct discovery port route"; "protocol" => %protocol, "error" => %e))
+            .ok()
+    } else {
+        None
+    };
+
+    // Bail if neither mapping could be made.
+    let lifetime = tcp_mapping
+        .iter()
+        .chain(udp_mapping.iter())
+        .map(|mapping| mapping.lifetime)
+        .min()?;
+
+    // PCP reports the external address with each mapping, NAT-PMP requires a separate request.
+    let external_ip = match tcp_mapping
+        .iter()
+        .chain(udp_mapping.iter())
+        .find_map(|mapping| mapping.external_ip)
+        .filter(|ip| !ip.is_unspecified())
+    {
+        Some(ip) => ip,
+        None => match client.external_address() {
+            Ok(ip) => ip,
+            Err(e) => {
+                debug!(log, "Could not determine external address"; "protocol" => %protocol, "error" => %e);
+                return None;
+            }
+        },
+    };
+
+    let tcp_socket = tcp_mapping.map(|m| SocketAddr::new(external_ip.into(), m.external_port));
+    let udp_socket = udp_mapping.map(|m| SocketAddr::new(external_ip.into(), m.external_port));
+
+    if previous.is_some() {
+        debug!(log, "Port mappings renewed"; "protocol" => %protocol, "lifetime_secs" => lifetime.as_secs());
+    } else {
+        info!(
+            log,
+            "Port mappings established";
+            "protocol" => %protocol,
+            "external_tcp_socket" => socket_string(tcp_socket),
+            "external_udp_socket" => socket_string(udp_socket),
+            "lifetime_secs" => lifetime.as_secs(),
+        );
+    }
+
+    Some(PortMappings {
+        protocol,
+        tcp_socket,
+        udp_socket,
+        lifetime: Some(lifetime),
+        config,
+        local_ip,
+        gateway: Some(gateway),
+        nonce,
+    })
+}
+
+/// Formats an optional socket for logging.
+fn socket_string(socket: Option<SocketAddr>) -> String {
+    socket.map_or_else(|| "unknown".into(), |socket| socket.to_string())
+}
+
+/// Removes the specified TCP and UDP port mappings.
+pub fn remove_mappings(mappings: &PortMappings, log: &slog::Logger) {
+    debug!(log, "Removing port mappings"; "protocol" => %mappings.protocol);
+    let tcp_port = mappings.tcp_socket.map(|_| mappings.config.tcp_port);
+    let udp_port = mappings.udp_socket.map(|_| mappings.config.udp_port);
+
+    match mappings.gateway {
+        None => remove_upnp_mappings(tcp_port, udp_port, log),
+        Some(gateway) => {
+            let version = match mappings.protocol {
+                NatProtocol::Pcp => Version::Pcp,
+                _ => Version::NatPmp,
+            };
+            let client = match natpmp::Client::new(gateway) {
+                Ok(client) => client,
+                Err(e) => {
+                    debug!(log, "Failed to remove port mappings"; "error" => %e);
+                    return;
+                }
+            };
+            let ports = mappings
+                .tcp_socket
+                .map(|socket| (Protocol::Tcp, mappings.config.tcp_port, socket.port()))
+                .into_iter()
+                .chain(
+                    mappings
+                        .udp_socket
+                        .map(|socket| (Protocol::Udp, mappings.config.udp_port, socket.port())),
+                );
+            for (protocol, port, external_port) in ports {
+                let request = MappingRequest {
+                    protocol,
+                    local_ip: mappings.local_ip,
+                    internal_port: port,
+                    external_port,
+                    lifetime: NATPMP_MAPPING_LIFETIME,
+                    nonce: mappings.nonce,
+                };
+                match client.remove(version, &request) {
+                    Ok(()) => {
+                        debug!(log, "Removed port mapping"; "protocol" => format!("{:?}", protocol), "port" => port)
+                    }
+                    Err(e) => {
+                        debug!(log, "Failed to remove port mapping"; "protocol" => format!("{:?}", protocol), "port" => port, "error" => %e)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Removes the specified UPnP TCP and UDP port mappings.
+fn remove_upnp_mappings(tcp_port: Option<u16>, udp_port: Option<u16>, log: &slog::Logger) {
+    if tcp_port.is_some() || udp_port.is_some() {
+        match igd::search_gateway(Default::default()) {
+            Ok(gateway) => {
+                if let Some(tcp_port) = tcp_port {
+                    match gateway.remove_port(igd::PortMappingProtocol::TCP, tcp_port) {
+                        Ok(()) => debug!(log, "UPnP Removed TCP port mapping"; "port" => tcp_port),
+                        Err(e) => {
+                            debug!(log, "UPnP Failed to remove TCP port mapping"; "port" => tcp_port, "error" => %e)
+                        }
+                    }
+                }
+                if let Some(udp_port) = udp_port {
+                    match gateway.remove_port(igd::PortMappingProtocol::UDP, udp_port) {
+                        Ok(()) => debug!(log, "UPnP Removed UDP port mapping"; "port" => udp_port),
+                        Err(e) => {
+                            debug!(log, "UPnP Failed to remove UDP port mapping"; "port" => udp_port, "error" => %e)
+                        }
+                    }
+                }
+            }
+            Err(e) => debug!(log, "UPnP failed to remove mappings"; "error" => %e),
+        }
+    }
+}
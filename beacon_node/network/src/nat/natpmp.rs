@@ -0,0 +1,500 @@
+//! Minimal NAT-PMP (RFC 6886) and PCP (RFC 6887) clients.
+//!
+//! Both protocols are spoken to the default gateway over UDP port 5351. PCP is the successor of
+//! NAT-PMP and gateways that only understand NAT-PMP reply to a PCP request with an "unsupported
+//! version" error, in which case the NAT-PMP equivalent of the request is used instead.
+
+use std::fmt;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddrV4, UdpSocket};
+use std::time::Duration;
+
+/// The port on which NAT-PMP and PCP servers listen.
+pub const SERVER_PORT: u16 = 5351;
+
+/// The time to wait for the first response. Doubled after each retransmission.
+const INITIAL_TIMEOUT: Duration = Duration::from_millis(250);
+/// The number of times a request is sent before giving up.
+///
+/// RFC 6886 suggests 9 attempts (over a minute), which is far too long to block start-up on when
+/// the gateway simply doesn't support the protocol.
+const MAX_ATTEMPTS: u32 = 4;
+
+const NATPMP_VERSION: u8 = 0;
+const PCP_VERSION: u8 = 2;
+/// Set on the opcode of all responses.
+const RESPONSE_BIT: u8 = 0x80;
+
+const NATPMP_OP_EXTERNAL_ADDRESS: u8 = 0;
+const NATPMP_OP_MAP_UDP: u8 = 1;
+const NATPMP_OP_MAP_TCP: u8 = 2;
+const PCP_OP_MAP: u8 = 1;
+
+/// The result code both protocols use to reject a request with an unknown version.
+const RESULT_UNSUPPORTED_VERSION: u16 = 1;
+
+const NATPMP_EXTERNAL_ADDRESS_RESPONSE_LEN: usize = 12;
+const NATPMP_MAP_REQUEST_LEN: usize = 12;
+const NATPMP_MAP_RESPONSE_LEN: usize = 16;
+const PCP_HEADER_LEN: usize = 24;
+const PCP_MAP_LEN: usize = PCP_HEADER_LEN + 36;
+
+/// The length of the nonce identifying a PCP mapping.
+pub const NONCE_LEN: usize = 12;
+
+/// The transport protocol of a port mapping.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+impl Protocol {
+    /// The IANA protocol number, as used by PCP.
+    fn number(self) -> u8 {
+        match self {
+            Protocol::Tcp => 6,
+            Protocol::Udp => 17,
+        }
+    }
+
+    /// The NAT-PMP opcode used to map this protocol.
+    fn natpmp_opcode(self) -> u8 {
+        match self {
+            Protocol::Tcp => NATPMP_OP_MAP_TCP,
+            Protocol::Udp => NATPMP_OP_MAP_UDP,
+        }
+    }
+}
+
+/// The protocol version spoken to the gateway.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Version {
+    NatPmp,
+    Pcp,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// Sending or receiving from the gateway failed, or it didn't respond in time.
+    Io(io::Error),
+    /// The gateway does not support the protocol version of the request.
+    UnsupportedVersion,
+    /// The gateway refused the request with the given result code.
+    ResultCode(u16),
+    /// The gateway responded with a malformed or unexpected message.
+    InvalidResponse(&'static str),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::UnsupportedVersion => write!(f, "unsupported protocol version"),
+            Error::ResultCode(code) => write!(f, "gateway returned result code {}", code),
+            Error::InvalidResponse(reason) => write!(f, "invalid response: {}", reason),
+        }
+    }
+}
+
+/// The parameters of a port mapping request.
+#[derive(Debug, Clone)]
+pub struct MappingRequest {
+    pub protocol: Protocol,
+    /// The address of the local interface the mapping points at. Only used by PCP.
+    pub local_ip: Ipv4Addr,
+    pub internal_port: u16,
+    /// The external port we would like. The gateway may assign a different one.
+    pub external_port: u16,
+    pub lifetime: Duration,
+    /// Identifies the mapping to a PCP server. Must be the same when renewing a mapping.
+    pub nonce: [u8; NONCE_LEN],
+}
+
+/// A port mapping granted by the gateway.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mapping {
+    /// The external address, if the protocol reports it with the mapping (PCP only).
+    pub external_ip: Option<Ipv4Addr>,
+    pub external_port: u16,
+    pub lifetime: Duration,
+}
+
+/// A client for a single NAT-PMP or PCP gateway.
+pub struct Client {
+    socket: UdpSocket,
+}
+
+impl Client {
+    /// Creates a client sending requests to `gateway`.
+    pub fn new(gateway: SocketAddrV4) -> Result<Self, Error> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        // Only accept responses from the gateway.
+        socket.connect(gateway)?;
+        Ok(Client { socket })
+    }
+
+    /// Requests the external IPv4 address of the gateway using NAT-PMP.
+    pub fn external_address(&self) -> Result<Ipv4Addr, Error> {
+        let request = [NATPMP_VERSION, NATPMP_OP_EXTERNAL_ADDRESS];
+        let response = self.request(&request, NATPMP_OP_EXTERNAL_ADDRESS)?;
+        check_natpmp_response(&response, NATPMP_EXTERNAL_ADDRESS_RESPONSE_LEN)?;
+
+        Ok(Ipv4Addr::new(
+            response[8],
+            response[9],
+            response[10],
+            response[11],
+        ))
+    }
+
+    /// Requests a port mapping using PCP, falling back to NAT-PMP if the gateway doesn't
+    /// support it. Returns the version that succeeded so that it can be used for renewals.
+    pub fn map_any(&self, request: &MappingRequest) -> Result<(Version, Mapping), Error> {
+        match self.map(Version::Pcp, request) {
+            Err(Error::UnsupportedVersion) => self
+                .map(Version::NatPmp, request)
+                .map(|mapping| (Version::NatPmp, mapping)),
+            result => result.map(|mapping| (Version::Pcp, mapping)),
+        }
+    }
+
+    /// Requests a port mapping, or renews it if it already exists.
+    pub fn map(&self, version: Version, request: &MappingRequest) -> Result<Mapping, Error> {
+        match version {
+            Version::NatPmp => self.map_natpmp(request),
+            Version::Pcp => self.map_pcp(request),
+        }
+    }
+
+    /// Deletes a port mapping previously created with `map`.
+    pub fn remove(&self, version: Version, request: &MappingRequest) -> Result<(), Error> {
+        let request = MappingRequest {
+            external_port: 0,
+            lifetime: Duration::from_secs(0),
+            ..request.clone()
+        };
+        self.map(version, &request).map(|_| ())
+    }
+
+    fn map_natpmp(&self, request: &MappingRequest) -> Result<Mapping, Error> {
+        let opcode = request.protocol.natpmp_opcode();
+
+        let mut message = [0; NATPMP_MAP_REQUEST_LEN];
+        message[0] = NATPMP_VERSION;
+        message[1] = opcode;
+        message[4..6].copy_from_slice(&request.internal_port.to_be_bytes());
+        message[6..8].copy_from_slice(&request.external_port.to_be_bytes());
+        message[8..12].copy_from_slice(&lifetime_secs(request.lifetime).to_be_bytes());
+
+        let response = self.request(&message, opcode)?;
+        check_natpmp_response(&response, NATPMP_MAP_RESPONSE_LEN)?;
+
+        if read_u16(&response[8..10]) != request.internal_port {
+            return Err(Error::InvalidResponse("internal port mismatch"));
+        }
+
+        Ok(Mapping {
+            external_ip: None,
+            external_port: read_u16(&response[10..12]),
+            lifetime: Duration::from_secs(read_u32(&response[12..16]).into()),
+        })
+    }
+
+    fn map_pcp(&self, request: &MappingRequest) -> Result<Mapping, Error> {
+        let mut message = [0; PCP_MAP_LEN];
+        // Common request header.
+        message[0] = PCP_VERSION;
+        message[1] = PCP_OP_MAP;
+        message[4..8].copy_from_slice(&lifetime_secs(request.lifetime).to_be_bytes());
+        message[8..24].copy_from_slice(&request.local_ip.to_ipv6_mapped().octets());
+        // MAP opcode payload.
+        message[24..36].copy_from_slice(&request.nonce);
+        message[36] = request.protocol.number();
+        message[40..42].copy_from_slice(&request.internal_port.to_be_bytes());
+        message[42..44].copy_from_slice(&request.external_port.to_be_bytes());
+        // Express no preference for the external address with the IPv4-mapped `0.0.0.0`.
+        message[44..60].copy_from_slice(&Ipv4Addr::UNSPECIFIED.to_ipv6_mapped().octets());
+
+        let response = self.request(&message, PCP_OP_MAP)?;
+
+        // A NAT-PMP only gateway answers with its own version.
+        if response[0] != PCP_VERSION {
+            return Err(Error::UnsupportedVersion);
+        }
+        if response.len() < PCP_HEADER_LEN {
+            return Err(Error::InvalidResponse("short response"));
+        }
+        match u16::from(response[3]) {
+            0 => {}
+            RESULT_UNSUPPORTED_VERSION => return Err(Error::UnsupportedVersion),
+            code => return Err(Error::ResultCode(code)),
+        }
+        if response.len() < PCP_MAP_LEN {
+            return Err(Error::InvalidResponse("short response"));
+        }
+        if response[24..36] != request.nonce {
+            return Err(Error::InvalidResponse("nonce mismatch"));
+        }
+
+        let mut external_ip = [0; 16];
+        external_ip.copy_from_slice(&response[44..60]);
+        let external_ip = match IpAddr::from(external_ip) {
+            IpAddr::V6(ip) => ip.to_ipv4(),
+            IpAddr::V4(ip) => Some(ip),
+        };
+
+        Ok(Mapping {
+            external_ip,
+            external_port: read_u16(&response[42..44]),
+            lifetime: Duration::from_secs(read_u32(&response[4..8]).into()),
+        })
+    }
+
+    /// Sends `message` to the gateway, retransmitting with an exponential back-off until a
+    /// response to `opcode` is received.
+    fn request(&self, message: &[u8], opcode: u8) -> Result<Vec<u8>, Error> {
+        let mut buf = [0; 1100];
+        let mut timeout = INITIAL_TIMEOUT;
+
+        for _ in 0..MAX_ATTEMPTS {
+            self.socket.set_read_timeout(Some(timeout))?;
+            self.socket.send(message)?;
+
+            loop {
+                match self.socket.recv(&mut buf) {
+                    // Ignore stray responses to earlier requests of another type.
+                    Ok(len) if len >= 2 && buf[1] == opcode | RESPONSE_BIT => {
+                        return Ok(buf[..len].to_vec())
+                    }
+                    Ok(_) => continue,
+                    Err(e)
+                        if e.kind() == io::ErrorKind::WouldBlock
+                            || e.kind() == io::ErrorKind::TimedOut =>
+                    {
+                        break
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+
+            timeout *= 2;
+        }
+
+        Err(io::Error::new(io::ErrorKind::TimedOut, "gateway did not respond").into())
+    }
+}
+
+/// Returns the IPv4 default gateway of this machine, if it can be determined.
+///
+/// Only Linux is currently supported, where the routing table is read from `/proc/net/route`.
+pub fn default_gateway() -> Option<Ipv4Addr> {
+    std::fs::read_to_string("/proc/net/route")
+        .ok()
+        .and_then(|table| parse_route_table(&table))
+}
+
+/// Finds the gateway of the default route in the contents of `/proc/net/route`.
+fn parse_route_table(table: &str) -> Option<Ipv4Addr> {
+    table.lines().skip(1).find_map(|line| {
+        let mut columns = line.split_whitespace().skip(1);
+        let destination = columns.next()?;
+        let gateway = columns.next()?;
+        if destination != "00000000" {
+            return None;
+        }
+        // Addresses are printed as a hex integer in host byte order.
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        Some(Ipv4Addr::from(gateway.to_ne_bytes())).filter(|ip| !ip.is_unspecified())
+    })
+}
+
+/// Checks the header of a NAT-PMP response.
+fn check_natpmp_response(response: &[u8], expected_len: usize) -> Result<(), Error> {
+    if response.len() < 4 || response[0] != NATPMP_VERSION {
+        return Err(Error::InvalidResponse("not a NAT-PMP response"));
+    }
+    match read_u16(&response[2..4]) {
+        0 => {}
+        RESULT_UNSUPPORTED_VERSION => return Err(Error::UnsupportedVersion),
+        code => return Err(Error::ResultCode(code)),
+    }
+    if response.len() < expected_len {
+        return Err(Error::InvalidResponse("short response"));
+    }
+    Ok(())
+}
+
+fn lifetime_secs(lifetime: Duration) -> u32 {
+    lifetime.as_secs().min(u32::max_value().into()) as u32
+}
+
+fn read_u16(bytes: &[u8]) -> u16 {
+    u16::from_be_bytes([bytes[0], bytes[1]])
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    const EXTERNAL_IP: Ipv4Addr = Ipv4Addr::new(203, 0, 113, 7);
+    /// The stand-in gateway always assigns an external port this much higher than requested.
+    const PORT_OFFSET: u16 = 1000;
+
+    /// A local stand-in for a gateway, answering `requests` messages before exiting.
+    fn spawn_gateway(supports_pcp: bool, requests: usize) -> SocketAddrV4 {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = match socket.local_addr().unwrap() {
+            std::net::SocketAddr::V4(addr) => addr,
+            _ => unreachable!(),
+        };
+
+        thread::spawn(move || {
+            let mut buf = [0; 1100];
+            for _ in 0..requests {
+                let (len, peer) = socket.recv_from(&mut buf).unwrap();
+                let response = respond(&buf[..len], supports_pcp);
+                socket.send_to(&response, peer).unwrap();
+            }
+        });
+
+        addr
+    }
+
+    fn respond(request: &[u8], supports_pcp: bool) -> Vec<u8> {
+        match (request[0], request[1]) {
+            (PCP_VERSION, PCP_OP_MAP) if supports_pcp => {
+                let mut response = vec![0; PCP_MAP_LEN];
+                response[0] = PCP_VERSION;
+                response[1] = PCP_OP_MAP | RESPONSE_BIT;
+                response[4..8].copy_from_slice(&request[4..8]);
+                response[24..42].copy_from_slice(&request[24..42]);
+                let external_port = read_u16(&request[40..42]) + PORT_OFFSET;
+                response[42..44].copy_from_slice(&external_port.to_be_bytes());
+                response[44..60].copy_from_slice(&EXTERNAL_IP.to_ipv6_mapped().octets());
+                response
+            }
+            (NATPMP_VERSION, NATPMP_OP_EXTERNAL_ADDRESS) => {
+                let mut response = vec![0; NATPMP_EXTERNAL_ADDRESS_RESPONSE_LEN];
+                response[1] = NATPMP_OP_EXTERNAL_ADDRESS | RESPONSE_BIT;
+                response[8..12].copy_from_slice(&EXTERNAL_IP.octets());
+                response
+            }
+            (NATPMP_VERSION, opcode) => {
+                let mut response = vec![0; NATPMP_MAP_RESPONSE_LEN];
+                response[1] = opcode | RESPONSE_BIT;
+                response[8..10].copy_from_slice(&request[4..6]);
+                let external_port = read_u16(&request[4..6]) + PORT_OFFSET;
+                response[10..12].copy_from_slice(&external_port.to_be_bytes());
+                response[12..16].copy_from_slice(&request[8..12]);
+                response
+            }
+            // A NAT-PMP server rejects other versions with its own version number.
+            (_, opcode) => {
+                let mut response = vec![0; 4];
+                response[1] = opcode | RESPONSE_BIT;
+                response[2..4].copy_from_slice(&RESULT_UNSUPPORTED_VERSION.to_be_bytes());
+                response
+            }
+        }
+    }
+
+    fn mapping_request(protocol: Protocol) -> MappingRequest {
+        MappingRequest {
+            protocol,
+            local_ip: Ipv4Addr::new(192, 168, 1, 10),
+            internal_port: 9000,
+            external_port: 9000,
+            lifetime: Duration::from_secs(7200),
+            nonce: [42; NONCE_LEN],
+        }
+    }
+
+    #[test]
+    fn pcp_mapping() {
+        let client = Client::new(spawn_gateway(true, 1)).unwrap();
+
+        let (version, mapping) = client.map_any(&mapping_request(Protocol::Udp)).unwrap();
+
+        assert_eq!(version, Version::Pcp);
+        assert_eq!(
+            mapping,
+            Mapping {
+                external_ip: Some(EXTERNAL_IP),
+                external_port: 9000 + PORT_OFFSET,
+                lifetime: Duration::from_secs(7200),
+            }
+        );
+    }
+
+    #[test]
+    fn natpmp_fallback() {
+        let client = Client::new(spawn_gateway(false, 3)).unwrap();
+
+        let (version, mapping) = client.map_any(&mapping_request(Protocol::Tcp)).unwrap();
+
+        assert_eq!(version, Version::NatPmp);
+        assert_eq!(
+            mapping,
+            Mapping {
+                external_ip: None,
+                external_port: 9000 + PORT_OFFSET,
+                lifetime: Duration::from_secs(7200),
+            }
+        );
+        assert_eq!(client.external_address().unwrap(), EXTERNAL_IP);
+    }
+
+    #[test]
+    fn remove_mapping() {
+        let client = Client::new(spawn_gateway(false, 1)).unwrap();
+
+        client
+            .remove(Version::NatPmp, &mapping_request(Protocol::Udp))
+            .unwrap();
+    }
+
+    #[test]
+    fn no_gateway() {
+        // Bind and drop a socket to find a port that nothing is listening on.
+        let addr = match UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap()
+        {
+            std::net::SocketAddr::V4(addr) => addr,
+            _ => unreachable!(),
+        };
+        let client = Client::new(addr).unwrap();
+
+        assert!(client.map_any(&mapping_request(Protocol::Tcp)).is_err());
+    }
+
+    #[test]
+    fn route_table() {
+        let gateway = u32::from_ne_bytes([192, 168, 1, 1]);
+        let table = format!(
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n\
+             eth0\t0001A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\t0\t0\t0\n\
+             eth0\t00000000\t{:08X}\t0003\t0\t0\t0\t00000000\t0\t0\t0\n",
+            gateway
+        );
+
+        assert_eq!(
+            parse_route_table(&table),
+            Some(Ipv4Addr::new(192, 168, 1, 1))
+        );
+        assert_eq!(parse_route_table("Iface\tDestination\tGateway\n"), None);
+    }
+}
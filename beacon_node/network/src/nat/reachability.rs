@@ -0,0 +1,237 @@
+//! Tracks whether the local node can be reached from outside its network.
+//!
+//! Peers report the address they observe our connections coming from via the libp2p identify
+//! protocol. Once enough distinct peers agree on an address it is used as our external address
+//! and compared with the one advertised in our ENR. Separately, if no peer manages to connect to
+//! us within a grace period the node is considered unreachable.
+
+use eth2_libp2p::PeerId;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// The number of distinct peers that must report the same address before it is trusted.
+const OBSERVED_ADDRESS_THRESHOLD: usize = 3;
+/// The maximum number of distinct observed addresses that are tracked.
+const MAX_TRACKED_ADDRESSES: usize = 16;
+/// The time we allow for a peer to connect to us before the node is considered unreachable.
+pub const UNREACHABLE_GRACE_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// A change in our believed reachability.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reachability {
+    /// No peer has connected to us since start-up.
+    Unreachable,
+    /// A peer has connected to us after we reported being unreachable.
+    Reachable,
+}
+
+pub struct ReachabilityTracker {
+    /// The peers that have reported each observed address.
+    votes: HashMap<IpAddr, HashSet<PeerId>>,
+    /// The address that a sufficient number of peers agree on.
+    observed_ip: Option<IpAddr>,
+    /// Whether any peer has connected to us.
+    inbound_connection: bool,
+    /// The time after which the node is considered unreachable if no peer has connected.
+    deadline: Instant,
+    /// Whether `Reachability::Unreachable` has been reported.
+    reported_unreachable: bool,
+}
+
+impl ReachabilityTracker {
+    pub fn new(now: Instant) -> Self {
+        ReachabilityTracker {
+            votes: HashMap::new(),
+            observed_ip: None,
+            inbound_connection: false,
+            deadline: now + UNREACHABLE_GRACE_PERIOD,
+            reported_unreachable: false,
+        }
+    }
+
+    /// The external address agreed on by our peers, if any.
+    pub fn observed_ip(&self) -> Option<IpAddr> {
+        self.observed_ip
+    }
+
+    /// Records the address `peer_id` observes us on.
+    ///
+    /// Returns the address if it has just become the agreed external address. Private and
+    /// loopback addresses are ignored as they are only observed by peers on the local network.
+    pub fn observed_address(&mut self, peer_id: &PeerId, ip: IpAddr) -> Option<IpAddr> {
+        if !is_global(&ip) {
+            return None;
+        }
+
+        // Each peer only votes for its most recent observation.
+        for (other_ip, voters) in self.votes.iter_mut() {
+            if *other_ip != ip {
+                voters.remove(peer_id);
+            }
+        }
+        self.votes.retain(|_, voters| !voters.is_empty());
+
+        self.votes.entry(ip).or_default().insert(peer_id.clone());
+
+        if self.votes.len() > MAX_TRACKED_ADDRESSES {
+            if let Some(least_voted) = self
+                .votes
+                .iter()
+                .filter(|(other_ip, _)| **other_ip != ip)
+                .min_by_key(|(_, voters)| voters.len())
+                .map(|(other_ip, _)| *other_ip)
+            {
+                self.votes.remove(&least_voted);
+            }
+        }
+
+        let votes = self.votes.get(&ip).map_or(0, HashSet::len);
+        if votes >= OBSERVED_ADDRESS_THRESHOLD && self.observed_ip != Some(ip) {
+            self.observed_ip = Some(ip);
+            Some(ip)
+        } else {
+            None
+        }
+    }
+
+    /// Records that a peer has connected to us.
+    ///
+    /// Returns `Reachability::Reachable` if the node was previously reported as unreachable.
+    pub fn inbound_connection(&mut self) -> Option<Reachability> {
+        self.inbound_connection = true;
+        if self.reported_unreachable {
+            self.reported_unreachable = false;
+            Some(Reachability::Reachable)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `Reachability::Unreachable` once, if no peer has connected to us by the end of the
+    /// grace period.
+    pub fn poll(&mut self, now: Instant) -> Option<Reachability> {
+        if !self.inbound_connection && !self.reported_unreachable && now >= self.deadline {
+            self.reported_unreachable = true;
+            Some(Reachability::Unreachable)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns `true` if `ip` may be routable on the public internet.
+fn is_global(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // Carrier-grade NAT, 100.64.0.0/10.
+                || (ip.octets()[0] == 100 && ip.octets()[1] & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => {
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                // Unique local, fc00::/7.
+                || ip.segments()[0] & 0xfe00 == 0xfc00
+                // Link local, fe80::/10.
+                || ip.segments()[0] & 0xffc0 == 0xfe80)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn observed_address_threshold() {
+        let mut tracker = ReachabilityTracker::new(Instant::now());
+        let peers: Vec<_> = (0..OBSERVED_ADDRESS_THRESHOLD)
+            .map(|_| PeerId::random())
+            .collect();
+
+        for peer in &peers[..OBSERVED_ADDRESS_THRESHOLD - 1] {
+            assert_eq!(tracker.observed_address(peer, ip("198.51.100.1")), None);
+            // Repeated reports from the same peer don't count twice.
+            assert_eq!(tracker.observed_address(peer, ip("198.51.100.1")), None);
+        }
+        assert_eq!(
+            tracker.observed_address(&peers[OBSERVED_ADDRESS_THRESHOLD - 1], ip("198.51.100.1")),
+            Some(ip("198.51.100.1"))
+        );
+        assert_eq!(tracker.observed_ip(), Some(ip("198.51.100.1")));
+
+        // Peers changing their minds move their votes.
+        for peer in &peers {
+            tracker.observed_address(peer, ip("198.51.100.2"));
+        }
+        assert_eq!(tracker.observed_ip(), Some(ip("198.51.100.2")));
+        assert!(!tracker.votes.contains_key(&ip("198.51.100.1")));
+    }
+
+    #[test]
+    fn local_addresses_ignored() {
+        let mut tracker = ReachabilityTracker::new(Instant::now());
+
+        for address in &[
+            "127.0.0.1",
+            "192.168.1.10",
+            "100.64.0.1",
+            "fe80::1",
+            "fd00::1",
+        ] {
+            for _ in 0..OBSERVED_ADDRESS_THRESHOLD {
+                assert_eq!(
+                    tracker.observed_address(&PeerId::random(), ip(address)),
+                    None
+                );
+            }
+        }
+        assert_eq!(tracker.observed_ip(), None);
+    }
+
+    #[test]
+    fn tracked_addresses_bounded() {
+        let mut tracker = ReachabilityTracker::new(Instant::now());
+
+        for i in 0..MAX_TRACKED_ADDRESSES * 2 {
+            tracker.observed_address(&PeerId::random(), ip(&format!("198.51.100.{}", i)));
+        }
+        assert_eq!(tracker.votes.len(), MAX_TRACKED_ADDRESSES);
+    }
+
+    #[test]
+    fn unreachable_after_grace_period() {
+        let start = Instant::now();
+        let mut tracker = ReachabilityTracker::new(start);
+
+        assert_eq!(tracker.poll(start), None);
+        assert_eq!(
+            tracker.poll(start + UNREACHABLE_GRACE_PERIOD),
+            Some(Reachability::Unreachable)
+        );
+        // Only reported once.
+        assert_eq!(tracker.poll(start + UNREACHABLE_GRACE_PERIOD * 2), None);
+
+        assert_eq!(tracker.inbound_connection(), Some(Reachability::Reachable));
+        assert_eq!(tracker.inbound_connection(), None);
+        assert_eq!(tracker.poll(start + UNREACHABLE_GRACE_PERIOD * 3), None);
+    }
+
+    #[test]
+    fn reachable_within_grace_period() {
+        let start = Instant::now();
+        let mut tracker = ReachabilityTracker::new(start);
+
+        assert_eq!(tracker.inbound_connection(), None);
+        assert_eq!(tracker.poll(start + UNREACHABLE_GRACE_PERIOD), None);
+    }
+}
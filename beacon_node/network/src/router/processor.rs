@@ -361,9 +361,12 @@ impl<T: BeaconChainTypes> Processor<T> {
             return;
         }
 
+        // NOTE: We don't mind if req.count * req.step overflows as it just ends the iterator early and
+        // the peer will get less blocks.
+        let end_slot = req.start_slot.saturating_add(req.count * req.step);
         let forwards_block_root_iter = match self
             .chain
-            .forwards_iter_block_roots(Slot::from(req.start_slot))
+            .forwards_iter_block_roots_until(Slot::from(req.start_slot), Slot::from(end_slot))
         {
             Ok(iter) => iter,
             Err(e) => {
@@ -377,17 +380,12 @@ impl<T: BeaconChainTypes> Processor<T> {
 
         // Pick out the required blocks, ignoring skip-slots and stepping by the step parameter.
         //
-        // NOTE: We don't mind if req.count * req.step overflows as it just ends the iterator early and
-        // the peer will get less blocks.
         // The step parameter is quadratically weighted in the filter, so large values should be
         // prevented before reaching this point.
         let mut last_block_root = None;
         let maybe_block_roots = process_results(forwards_block_root_iter, |iter| {
-            iter.take_while(|(_, slot)| {
-                slot.as_u64() < req.start_slot.saturating_add(req.count * req.step)
-            })
             // map skip slots to None
-            .map(|(root, _)| {
+            iter.map(|(root, _)| {
                 let result = if Some(root) == last_block_root {
                     None
                 } else {
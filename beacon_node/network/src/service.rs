@@ -1,3 +1,7 @@
+use crate::nat::{
+    reachability::{Reachability, ReachabilityTracker},
+    NatConfig, PortMappings,
+};
 use crate::persisted_dht::{load_dht, persist_dht};
 use crate::router::{Router, RouterMessage};
 use crate::{
@@ -7,17 +11,23 @@ use crate::{
 use crate::{error, metrics};
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use eth2_libp2p::{
-    rpc::{GoodbyeReason, RPCResponseErrorCode, RequestId},
-    Libp2pEvent, PeerAction, PeerRequestId, PubsubMessage, Request, Response,
+    multiaddr::Protocol, types::GossipKind, BehaviourEvent, GossipTopic, MessageId, Multiaddr,
+    NetworkGlobals, PeerId, TopicHash,
 };
 use eth2_libp2p::{
-    types::GossipKind, BehaviourEvent, GossipTopic, MessageId, NetworkGlobals, PeerId, TopicHash,
+    rpc::{GoodbyeReason, RPCResponseErrorCode, RequestId},
+    Libp2pEvent, PeerAction, PeerRequestId, PubsubMessage, Request, Response,
 };
 use eth2_libp2p::{MessageAcceptance, Service as LibP2PService};
 use futures::prelude::*;
 use rest_types::ValidatorSubscription;
 use slog::{debug, error, info, o, trace, warn};
-use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use store::HotColdDB;
 use tokio::sync::mpsc;
 use tokio::time::Delay;
@@ -27,6 +37,8 @@ mod tests;
 
 /// The interval (in seconds) that various network metrics will update.
 const METRIC_UPDATE_INTERVAL: u64 = 1;
+/// The time (in seconds) to wait before retrying a failed renewal of the NAT port mappings.
+const NAT_RENEWAL_RETRY_INTERVAL: u64 = 300;
 
 /// Types of messages that the network service can receive.
 #[derive(Debug)]
@@ -70,13 +82,8 @@ pub enum NetworkMessage<T: EthSpec> {
         /// The result of the validation
         validation_result: MessageAcceptance,
     },
-    /// Called if a known external TCP socket address has been updated.
-    UPnPMappingEstablished {
-        /// The external TCP address has been updated.
-        tcp_socket: Option<SocketAddr>,
-        /// The external UDP address has been updated.
-        udp_socket: Option<SocketAddr>,
-    },
+    /// Called if external port mappings have been established or renewed on the local gateway.
+    PortMappingsEstablished(PortMappings),
     /// Reports a peer to the peer manager for performing an action.
     ReportPeer { peer_id: PeerId, action: PeerAction },
     /// Disconnect an ban a peer, providing a reason.
@@ -103,12 +110,17 @@ pub struct NetworkService<T: BeaconChainTypes> {
     store: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
     /// A collection of global variables, accessible outside of the network service.
     network_globals: Arc<NetworkGlobals<T::EthSpec>>,
-    /// Stores potentially created UPnP mappings to be removed on shutdown. (TCP port and UDP
-    /// port).
-    upnp_mappings: (Option<u16>, Option<u16>),
+    /// Stores potentially created NAT port mappings to be renewed and removed on shutdown.
+    nat_mappings: Option<PortMappings>,
+    /// A delay that expires when the NAT port mappings need to be renewed.
+    nat_renewal: Option<Delay>,
+    /// Tracks our external address as observed by peers and whether peers can connect to us.
+    reachability: ReachabilityTracker,
     /// Keeps track of if discovery is auto-updating or not. This is used to inform us if we should
-    /// update the UDP socket of discovery if the UPnP mappings get established.
+    /// update the UDP socket of discovery if the port mappings get established.
     discovery_auto_update: bool,
+    /// A channel to the network service, used by NAT port mapping renewals.
+    network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
     /// A delay that expires when a new fork takes place.
     next_fork_update: Option<Delay>,
     /// A timer for updating various network metrics.
@@ -131,16 +143,14 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         // build the network channel
         let (network_send, network_recv) = mpsc::unbounded_channel::<NetworkMessage<T::EthSpec>>();
 
-        // try and construct UPnP port mappings if required.
-        let upnp_config = crate::nat::UPnPConfig::from(config);
-        let upnp_log = network_log.new(o!("service" => "UPnP"));
-        let upnp_network_send = network_send.clone();
+        // try and construct NAT port mappings if required.
+        let nat_config = NatConfig::from(config);
+        let nat_log = network_log.new(o!("service" => "NAT"));
+        let nat_network_send = network_send.clone();
         if config.upnp_enabled {
             executor.spawn_blocking(
-                move || {
-                    crate::nat::construct_upnp_mappings(upnp_config, upnp_network_send, upnp_log)
-                },
-                "UPnP",
+                move || crate::nat::construct_mappings(nat_config, nat_network_send, nat_log),
+                "NAT",
             );
         }
 
@@ -194,8 +204,11 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             router_send,
             store,
             network_globals: network_globals.clone(),
-            upnp_mappings: (None, None),
+            nat_mappings: None,
+            nat_renewal: None,
+            reachability: ReachabilityTracker::new(Instant::now()),
             discovery_auto_update: config.discv5_config.enr_update,
+            network_send: network_send.clone(),
             next_fork_update,
             metrics_update,
             log: network_log,
@@ -213,6 +226,7 @@ fn spawn_service<T: BeaconChainTypes>(
 ) -> error::Result<()> {
     let mut exit_rx = executor.exit();
     let mut shutdown_sender = executor.shutdown_sender();
+    let nat_executor = executor.clone();
 
     // spawn on the current executor
     executor.spawn_without_exit(async move {
@@ -243,7 +257,9 @@ fn spawn_service<T: BeaconChainTypes>(
                     }
 
                     // attempt to remove port mappings
-                    crate::nat::remove_mappings(service.upnp_mappings.0, service.upnp_mappings.1, &service.log);
+                    if let Some(mappings) = &service.nat_mappings {
+                        crate::nat::remove_mappings(mappings, &service.log);
+                    }
 
                     info!(service.log, "Network service shutdown");
                     return;
@@ -258,6 +274,10 @@ fn spawn_service<T: BeaconChainTypes>(
                             .map(|gauge| gauge.reset());
                     }
                     update_gossip_metrics::<T::EthSpec>(&service.libp2p.swarm.gs());
+
+                    if let Some(reachability) = service.reachability.poll(Instant::now()) {
+                        log_reachability(&service, reachability);
+                    }
                 }
                 // handle a message sent to the network
                 Some(message) = service.network_recv.recv() => {
@@ -271,23 +291,35 @@ fn spawn_service<T: BeaconChainTypes>(
                         NetworkMessage::SendError{ peer_id, error, id, reason } => {
                             service.libp2p.respond_with_error(peer_id, id, error, reason);
                         }
-                        NetworkMessage::UPnPMappingEstablished { tcp_socket, udp_socket} => {
-                            service.upnp_mappings = (tcp_socket.map(|s| s.port()), udp_socket.map(|s| s.port()));
+                        NetworkMessage::PortMappingsEstablished(mappings) => {
                             // If there is an external TCP port update, modify our local ENR.
-                            if let Some(tcp_socket) = tcp_socket {
+                            if let Some(tcp_socket) = mappings.tcp_socket {
                                 if let Err(e) = service.libp2p.swarm.peer_manager().discovery_mut().update_enr_tcp_port(tcp_socket.port()) {
                                     warn!(service.log, "Failed to update ENR"; "error" => e);
                                 }
                             }
                             // if the discovery service is not auto-updating, update it with the
-                            // UPnP mappings
+                            // port mappings
                             if !service.discovery_auto_update {
-                                if let Some(udp_socket) = udp_socket {
+                                if let Some(udp_socket) = mappings.udp_socket {
                                     if let Err(e) = service.libp2p.swarm.peer_manager().discovery_mut().update_enr_udp_socket(udp_socket) {
                                     warn!(service.log, "Failed to update ENR"; "error" => e);
                                 }
                                 }
                             }
+                            // peers observing a different address than the gateway reports
+                            // suggests there is another NAT between us and the internet.
+                            if let (Some(mapped_ip), Some(observed_ip)) = (mappings.external_ip(), service.reachability.observed_ip()) {
+                                if mapped_ip != observed_ip {
+                                    warn!(service.log, "Port mappings may be ineffective";
+                                        "reason" => "peers observe a different address than the gateway reports, there may be multiple NATs",
+                                        "protocol" => %mappings.protocol,
+                                        "gateway_external_ip" => %mapped_ip,
+                                        "observed_ip" => %observed_ip);
+                                }
+                            }
+                            service.nat_renewal = mappings.renewal_delay().map(tokio::time::delay_for);
+                            service.nat_mappings = Some(mappings);
                         },
                         NetworkMessage::ValidationResult {
                             propagation_source,
@@ -381,8 +413,10 @@ fn spawn_service<T: BeaconChainTypes>(
                                             debug!(service.log, "Failed to send peer dialed to router"); });
                             },
                             BehaviourEvent::PeerConnected(_peer_id) => {
-                                // A peer has connected to us
-                                // We currently do not perform any action here.
+                                // A peer has connected to us, so we are reachable.
+                                if let Some(reachability) = service.reachability.inbound_connection() {
+                                    log_reachability(&service, reachability);
+                                }
                             },
                             BehaviourEvent::PeerDisconnected(peer_id) => {
                             let _ = service
@@ -464,6 +498,13 @@ fn spawn_service<T: BeaconChainTypes>(
                                 }
                             }
                             BehaviourEvent::PeerSubscribed(_, _) => {},
+                            BehaviourEvent::ObservedAddress { peer_id, address } => {
+                                if let Some(ip) = ip_from_multiaddr(&address) {
+                                    if let Some(observed_ip) = service.reachability.observed_address(&peer_id, ip) {
+                                        reconcile_observed_ip(&mut service, observed_ip);
+                                    }
+                                }
+                            }
                         }
                         Libp2pEvent::NewListenAddr(multiaddr) => {
                             service.network_globals.listen_multiaddrs.write().push(multiaddr);
@@ -487,6 +528,21 @@ fn spawn_service<T: BeaconChainTypes>(
                     service.next_fork_update = next_fork_delay(&service.beacon_chain);
                 }
             }
+
+            if let Some(delay) = &service.nat_renewal {
+                if delay.is_elapsed() {
+                    // Retry later if the renewal fails. A successful renewal replaces this delay.
+                    service.nat_renewal = Some(tokio::time::delay_for(Duration::from_secs(NAT_RENEWAL_RETRY_INTERVAL)));
+                    if let Some(mappings) = service.nat_mappings.clone() {
+                        let network_send = service.network_send.clone();
+                        let log = service.log.new(o!("service" => "NAT"));
+                        nat_executor.spawn_blocking(
+                            move || crate::nat::renew_mappings(mappings, network_send, log),
+                            "NAT renewal",
+                        );
+                    }
+                }
+            }
         }
     }, "network");
 
@@ -505,6 +561,81 @@ fn next_fork_delay<T: BeaconChainTypes>(
     })
 }
 
+/// Returns the IP address contained in `address`, if any.
+fn ip_from_multiaddr(address: &Multiaddr) -> Option<IpAddr> {
+    address.iter().find_map(|protocol| match protocol {
+        Protocol::Ip4(ip) => Some(ip.into()),
+        Protocol::Ip6(ip) => Some(ip.into()),
+        _ => None,
+    })
+}
+
+/// Compares the external address our peers agree on with the one advertised in our ENR.
+///
+/// If discovery isn't maintaining the ENR address and none is set, the observed address is
+/// advertised. Otherwise differences are logged, as peers may be unable to reach us through the
+/// address in our ENR.
+fn reconcile_observed_ip<T: BeaconChainTypes>(
+    service: &mut NetworkService<T>,
+    observed_ip: IpAddr,
+) {
+    let enr = service.network_globals.local_enr();
+    let enr_ip = match observed_ip {
+        IpAddr::V4(_) => enr.ip().map(IpAddr::from),
+        IpAddr::V6(_) => enr.ip6().map(IpAddr::from),
+    };
+
+    match enr_ip {
+        Some(enr_ip) if enr_ip == observed_ip => {
+            debug!(service.log, "Peers observe the address advertised in the ENR"; "ip" => %observed_ip);
+        }
+        None if !service.discovery_auto_update => {
+            info!(service.log, "Updating ENR address to the address observed by peers"; "ip" => %observed_ip);
+            if let Err(e) = service
+                .libp2p
+                .swarm
+                .peer_manager()
+                .discovery_mut()
+                .update_enr_ip(observed_ip)
+            {
+                warn!(service.log, "Failed to update ENR"; "error" => e);
+            }
+        }
+        enr_ip => {
+            warn!(
+                service.log,
+                "Peers observe a different address than the ENR advertises";
+                "enr_ip" => enr_ip.map_or_else(|| "none".into(), |ip| ip.to_string()),
+                "observed_ip" => %observed_ip,
+                "auto_update" => service.discovery_auto_update,
+            );
+        }
+    }
+}
+
+/// Logs a change in whether peers are able to connect to us.
+fn log_reachability<T: BeaconChainTypes>(service: &NetworkService<T>, reachability: Reachability) {
+    match reachability {
+        Reachability::Unreachable => warn!(
+            service.log,
+            "No peers have connected to this node, it appears unreachable from outside";
+            "advice" => "forward the TCP and UDP ports on your router, or enable UPnP, NAT-PMP or PCP",
+            "tcp_port" => service.network_globals.listen_port_tcp(),
+            "udp_port" => service.network_globals.listen_port_udp(),
+            "port_mappings" => service
+                .nat_mappings
+                .as_ref()
+                .map_or_else(|| "none".into(), |mappings| mappings.protocol.to_string()),
+        ),
+        Reachability::Reachable => {
+            info!(
+                service.log,
+                "A peer has connected to this node, it is reachable from outside"
+            )
+        }
+    }
+}
+
 /// Inspects the `messages` that were being sent to the network and updates Prometheus metrics.
 fn expose_publish_metrics<T: EthSpec>(messages: &[PubsubMessage<T>]) {
     for message in messages {
@@ -24,6 +24,11 @@ pub const EPOCHS_PER_BATCH: u64 = 8;
 /// The maximum number of batches to queue before requesting more.
 const BATCH_BUFFER_SIZE: u8 = 5;
 
+/// The maximum number of batches that may be downloaded concurrently from a single peer. Allowing
+/// more than one keeps the download pipeline full whilst batches are being processed, even when
+/// the chain only has a few peers.
+const MAX_BATCHES_PER_PEER: usize = 2;
+
 /// A return type for functions that act on a `Chain` which informs the caller whether the chain
 /// has been completed and should be removed or to be kept if further processing is
 /// required.
@@ -767,8 +772,8 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             debug!(self.log, "Peer added to non-syncing chain"; "peer" => %peer_id)
         }
         // add the peer without overwriting its active requests
-        if self.peers.entry(peer_id).or_default().is_empty() {
-            // Either new or not, this peer is idle, try to request more batches
+        if self.peers.entry(peer_id).or_default().len() < MAX_BATCHES_PER_PEER {
+            // Either new or not, this peer has capacity, try to request more batches
             self.request_batches(network)
         } else {
             ProcessingResult::KeepChain
@@ -907,7 +912,8 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
     }
 
     /// Attempts to request the next required batches from the peer pool if the chain is syncing. It will exhaust the peer
-    /// pool and left over batches until the batch buffer is reached or all peers are exhausted.
+    /// pool and left over batches until the batch buffer is reached or all peers have
+    /// `MAX_BATCHES_PER_PEER` active requests.
     fn request_batches(
         &mut self,
         network: &mut SyncNetworkContext<T::EthSpec>,
@@ -916,28 +922,14 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             return ProcessingResult::KeepChain;
         }
 
-        // find the next pending batch and request it from the peer
-
         // randomize the peers for load balancing
         let mut rng = rand::thread_rng();
-        let mut idle_peers = self
-            .peers
-            .iter()
-            .filter_map(|(peer, requests)| {
-                if requests.is_empty() {
-                    Some(peer.clone())
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>();
-        idle_peers.shuffle(&mut rng);
 
         // check if we have the batch for our optimistic start. If not, request it first.
         // We wait for this batch before requesting any other batches.
         if let Some(epoch) = self.optimistic_start {
             if !self.batches.contains_key(&epoch) {
-                if let Some(peer) = idle_peers.pop() {
+                if let Some(peer) = least_loaded_peer(&self.peers, &mut rng) {
                     let optimistic_batch = BatchInfo::new(&epoch, EPOCHS_PER_BATCH);
                     self.batches.insert(epoch, optimistic_batch);
                     if let ProcessingResult::RemoveChain = self.send_batch(network, epoch, peer) {
@@ -948,7 +940,9 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             return ProcessingResult::KeepChain;
         }
 
-        while let Some(peer) = idle_peers.pop() {
+        // find the next pending batch and request it from the peer with the fewest active
+        // requests, until either the batch buffer is full or all peers are at capacity.
+        while let Some(peer) = least_loaded_peer(&self.peers, &mut rng) {
             if let Some(batch_id) = self.include_next_batch() {
                 // send the batch
                 if let ProcessingResult::RemoveChain = self.send_batch(network, batch_id, peer) {
@@ -1047,3 +1041,92 @@ impl<T: BeaconChainTypes> slog::KV for SyncingChain<T> {
         slog::Result::Ok(())
     }
 }
+
+/// Returns a peer with the fewest active batch requests, provided it has fewer than
+/// `MAX_BATCHES_PER_PEER`. Ties are broken randomly for load balancing.
+fn least_loaded_peer<R: rand::Rng>(
+    peers: &FnvHashMap<PeerId, HashSet<BatchId>>,
+    rng: &mut R,
+) -> Option<PeerId> {
+    let min_requests = peers
+        .values()
+        .map(|requests| requests.len())
+        .filter(|requests| *requests < MAX_BATCHES_PER_PEER)
+        .min()?;
+
+    let candidates = peers
+        .iter()
+        .filter(|(_, requests)| requests.len() == min_requests)
+        .map(|(peer, _)| peer)
+        .collect::<Vec<_>>();
+
+    candidates.choose(rng).map(|peer| (*peer).clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Assigns batches the same way as `SyncingChain::request_batches`, returning the number of
+    /// batches that were assigned.
+    fn assign_batches(peers: &mut FnvHashMap<PeerId, HashSet<BatchId>>, next: &mut u64) -> usize {
+        let mut rng = rand::thread_rng();
+        let mut assigned = 0;
+        while let Some(peer) = least_loaded_peer(peers, &mut rng) {
+            peers
+                .get_mut(&peer)
+                .expect("peer is known")
+                .insert(Epoch::new(*next));
+            *next += EPOCHS_PER_BATCH;
+            assigned += 1;
+        }
+        assigned
+    }
+
+    #[test]
+    fn peers_are_filled_to_capacity() {
+        let mut peers = FnvHashMap::default();
+        for _ in 0..3 {
+            peers.insert(PeerId::random(), HashSet::new());
+        }
+        let mut next = 0;
+
+        // Each peer receives `MAX_BATCHES_PER_PEER` batches, then the loop stops.
+        assert_eq!(
+            assign_batches(&mut peers, &mut next),
+            3 * MAX_BATCHES_PER_PEER
+        );
+        assert!(peers
+            .values()
+            .all(|requests| requests.len() == MAX_BATCHES_PER_PEER));
+        assert_eq!(least_loaded_peer(&peers, &mut rand::thread_rng()), None);
+
+        // A completed batch frees capacity on exactly that peer.
+        let peer = peers.keys().next().cloned().unwrap();
+        peers.get_mut(&peer).unwrap().clear();
+        assert_eq!(assign_batches(&mut peers, &mut next), MAX_BATCHES_PER_PEER);
+        assert_eq!(peers[&peer].len(), MAX_BATCHES_PER_PEER);
+    }
+
+    #[test]
+    fn least_loaded_peer_is_preferred() {
+        let busy = PeerId::random();
+        let idle = PeerId::random();
+        let mut peers = FnvHashMap::default();
+        peers.insert(busy.clone(), vec![Epoch::new(0)].into_iter().collect());
+        peers.insert(idle.clone(), HashSet::new());
+
+        for _ in 0..16 {
+            assert_eq!(
+                least_loaded_peer(&peers, &mut rand::thread_rng()),
+                Some(idle.clone())
+            );
+        }
+
+        // The idle peer catches up first, then both peers are filled to capacity.
+        let mut next = EPOCHS_PER_BATCH;
+        assert_eq!(assign_batches(&mut peers, &mut next), 3);
+        assert_eq!(peers[&busy].len(), MAX_BATCHES_PER_PEER);
+        assert_eq!(peers[&idle].len(), MAX_BATCHES_PER_PEER);
+    }
+}
@@ -1,12 +1,13 @@
+use crate::events;
 use crate::helpers::*;
 use crate::validator::get_state_for_epoch;
 use crate::Context;
 use crate::{ApiError, UrlQuery};
 use beacon_chain::{
-    observed_operations::ObservationOutcome, BeaconChain, BeaconChainTypes, StateSkipConfig,
+    events::{EventKind, EventTopic},
+    observed_operations::ObservationOutcome,
+    BeaconChain, BeaconChainTypes, StateSkipConfig,
 };
-use futures::executor::block_on;
-use hyper::body::Bytes;
 use hyper::{Body, Request};
 use rest_types::{
     BlockResponse, CanonicalHeadResponse, Committee, HeadBeaconBlock, StateResponse,
@@ -14,11 +15,9 @@ use rest_types::{
 };
 use std::io::Write;
 use std::sync::Arc;
-
-use slog::error;
 use types::{
     AttesterSlashing, BeaconState, EthSpec, Hash256, ProposerSlashing, PublicKeyBytes,
-    RelativeEpoch, SignedBeaconBlockHash, Slot,
+    RelativeEpoch, Slot,
 };
 
 /// Returns a summary of the head of the beacon chain.
@@ -118,38 +117,19 @@ pub fn get_block_root<T: BeaconChainTypes>(
     })
 }
 
-fn make_sse_response_chunk(new_head_hash: SignedBeaconBlockHash) -> std::io::Result<Bytes> {
-    let mut buffer = Vec::new();
-    {
-        let mut sse_message = uhttp_sse::SseMessage::new(&mut buffer);
-        let untyped_hash: Hash256 = new_head_hash.into();
-        write!(sse_message.data()?, "{:?}", untyped_hash)?;
-    }
-    let bytes: Bytes = buffer.into();
-    Ok(bytes)
-}
-
+/// Returns a stream of the block roots of each new head of the canonical chain.
 pub fn stream_forks<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<Body, ApiError> {
-    let mut events = ctx.events.lock().add_rx();
-    let (mut sender, body) = Body::channel();
-    std::thread::spawn(move || {
-        while let Ok(new_head_hash) = events.recv() {
-            let chunk = match make_sse_response_chunk(new_head_hash) {
-                Ok(chunk) => chunk,
-                Err(e) => {
-                    error!(ctx.log, "Failed to make SSE chunk"; "error" => e.to_string());
-                    sender.abort();
-                    break;
-                }
-            };
-            match block_on(sender.send_data(chunk)) {
-                Err(e) if e.is_closed() => break,
-                Err(e) => error!(ctx.log, "Couldn't stream piece {:?}", e),
-                Ok(_) => (),
-            }
+    events::stream(ctx, vec![EventTopic::Head], |event, buffer| match event {
+        EventKind::BeaconHeadChanged {
+            current_head_beacon_block_root,
+            ..
+        } => {
+            let mut sse_message = uhttp_sse::SseMessage::new(buffer);
+            write!(sse_message.data()?, "{:?}", current_head_beacon_block_root)?;
+            Ok(true)
         }
-    });
-    Ok(body)
+        _ => Ok(false),
+    })
 }
 
 /// HTTP handler to which accepts a query string of a list of validator pubkeys and maps it to a
@@ -0,0 +1,97 @@
+//! Server-Sent Events streams of `BeaconChain` events.
+
+use crate::{ApiError, Context, UrlQuery};
+use beacon_chain::events::{EventKind, EventTopic};
+use beacon_chain::BeaconChainTypes;
+use futures::executor::block_on;
+use hyper::body::{Bytes, Sender};
+use hyper::{Body, Request};
+use slog::error;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Returns a stream of all events whose topic is listed in the `topics` query parameter.
+///
+/// Topics may be supplied as a comma-separated list (`?topics=head,block`) and/or as repeated
+/// parameters (`?topics=head&topics=block`). If no `topics` are supplied, all events are streamed.
+pub fn stream_events<T: BeaconChainTypes>(
+    req: Request<()>,
+    ctx: Arc<Context<T>>,
+) -> Result<Body, ApiError> {
+    let topics = parse_topics(&req)?;
+
+    stream(ctx, topics, |event, buffer| {
+        let data = serde_json::to_string(&event)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        let mut sse_message = uhttp_sse::SseMessage::new(buffer);
+        write!(sse_message.event()?, "{}", event.topic())?;
+        write!(sse_message.data()?, "{}", data)?;
+        Ok(true)
+    })
+}
+
+/// Subscribes to the events of `topics` in `ctx` and sends each event to a new `Body`, using
+/// `write_chunk` to encode it.
+///
+/// `write_chunk` should return `Ok(false)` if the event should not be sent to this subscriber.
+///
+/// The stream ends if the subscriber is too slow to keep up with its events.
+pub fn stream<T, F>(
+    ctx: Arc<Context<T>>,
+    topics: Vec<EventTopic>,
+    write_chunk: F,
+) -> Result<Body, ApiError>
+where
+    T: BeaconChainTypes,
+    F: Fn(&EventKind<T::EthSpec>, &mut Vec<u8>) -> std::io::Result<bool> + Send + 'static,
+{
+    let events = ctx.events.subscribe(topics);
+    let (mut sender, body) = Body::channel();
+    std::thread::spawn(move || {
+        while let Ok(event) = events.recv() {
+            let mut buffer = Vec::new();
+            match write_chunk(&event, &mut buffer) {
+                Ok(true) => (),
+                Ok(false) => continue,
+                Err(e) => {
+                    error!(ctx.log, "Failed to make SSE chunk"; "error" => e.to_string());
+                    sender.abort();
+                    break;
+                }
+            }
+
+            if !send_chunk(&ctx, &mut sender, buffer.into()) {
+                break;
+            }
+        }
+    });
+    Ok(body)
+}
+
+/// Sends `chunk` to `sender`, returning `false` if the subscriber has disconnected.
+fn send_chunk<T: BeaconChainTypes>(ctx: &Context<T>, sender: &mut Sender, chunk: Bytes) -> bool {
+    match block_on(sender.send_data(chunk)) {
+        Err(e) if e.is_closed() => false,
+        Err(e) => {
+            error!(ctx.log, "Couldn't stream piece {:?}", e);
+            true
+        }
+        Ok(_) => true,
+    }
+}
+
+/// Parses the `topics` query parameter, returning all topics if it is absent.
+fn parse_topics<T>(req: &Request<T>) -> Result<Vec<EventTopic>, ApiError> {
+    let values = UrlQuery::from_request(req)?.all_of("topics")?;
+
+    if values.is_empty() {
+        return Ok(EventTopic::all());
+    }
+
+    values
+        .iter()
+        .flat_map(|value| value.split(','))
+        .filter(|topic| !topic.is_empty())
+        .map(|topic| topic.parse().map_err(ApiError::BadRequest))
+        .collect()
+}
@@ -101,6 +101,11 @@ pub fn block_root_at_slot<T: BeaconChainTypes>(
     beacon_chain: &BeaconChain<T>,
     target: Slot,
 ) -> Result<Option<Hash256>, ApiError> {
+    // Finalized roots can be read from the freezer without loading any states.
+    if let Some(root) = beacon_chain.store.get_cold_block_root(target)? {
+        return Ok(Some(root));
+    }
+
     Ok(process_results(
         beacon_chain.rev_iter_block_roots()?,
         |iter| {
@@ -6,6 +6,7 @@ extern crate network as client_network;
 mod beacon;
 pub mod config;
 mod consensus;
+mod events;
 mod helpers;
 mod lighthouse;
 mod metrics;
@@ -13,8 +14,7 @@ mod node;
 mod url_query;
 mod validator;
 
-use beacon_chain::{BeaconChain, BeaconChainTypes};
-use bus::Bus;
+use beacon_chain::{events::EventBus, BeaconChain, BeaconChainTypes};
 use client_network::NetworkMessage;
 pub use config::ApiEncodingFormat;
 use eth2_config::Eth2Config;
@@ -23,14 +23,12 @@ use futures::future::TryFutureExt;
 use hyper::server::conn::AddrStream;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Request, Server};
-use parking_lot::Mutex;
 use rest_types::ApiError;
 use slog::{info, warn};
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::mpsc;
-use types::SignedBeaconBlockHash;
 use url_query::UrlQuery;
 
 pub use crate::helpers::parse_pubkey_bytes;
@@ -54,7 +52,7 @@ pub fn start_server<T: BeaconChainTypes>(
     db_path: PathBuf,
     freezer_db_path: PathBuf,
     eth2_config: Eth2Config,
-    events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+    events: EventBus<T::EthSpec>,
 ) -> Result<SocketAddr, hyper::Error> {
     let log = executor.log();
     let eth2_config = Arc::new(eth2_config);
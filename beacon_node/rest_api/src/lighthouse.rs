@@ -1,7 +1,7 @@
 //! This contains a collection of lighthouse specific HTTP endpoints.
 
 use crate::{ApiError, Context};
-use beacon_chain::BeaconChainTypes;
+use beacon_chain::{reorg_history::ReorgEvent, BeaconChainTypes};
 use eth2_libp2p::PeerInfo;
 use serde::Serialize;
 use std::sync::Arc;
@@ -37,6 +37,11 @@ pub fn connected_peers<T: BeaconChainTypes>(
         .collect())
 }
 
+/// Returns the most recent re-orgs of the canonical chain, oldest first.
+pub fn reorgs<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<Vec<ReorgEvent>, ApiError> {
+    ctx.beacon_chain.reorg_history().map_err(Into::into)
+}
+
 /// Information returned by `peers` and `connected_peers`.
 #[derive(Clone, Debug, Serialize)]
 #[serde(bound = "T: EthSpec")]
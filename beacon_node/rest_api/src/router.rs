@@ -1,8 +1,7 @@
 use crate::{
-    beacon, config::Config, consensus, lighthouse, metrics, node, validator, NetworkChannel,
+    beacon, config::Config, consensus, events, lighthouse, metrics, node, validator, NetworkChannel,
 };
-use beacon_chain::{BeaconChain, BeaconChainTypes};
-use bus::Bus;
+use beacon_chain::{events::EventBus, BeaconChain, BeaconChainTypes};
 use environment::TaskExecutor;
 use eth2_config::Eth2Config;
 use eth2_libp2p::{NetworkGlobals, PeerId};
@@ -10,13 +9,12 @@ use hyper::header::HeaderValue;
 use hyper::{Body, Method, Request, Response};
 use lighthouse_version::version_with_platform;
 use operation_pool::PersistedOperationPool;
-use parking_lot::Mutex;
 use rest_types::{ApiError, Handler, Health};
 use slog::debug;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
-use types::{EthSpec, SignedBeaconBlockHash};
+use types::EthSpec;
 
 pub struct Context<T: BeaconChainTypes> {
     pub executor: TaskExecutor,
@@ -28,7 +26,7 @@ pub struct Context<T: BeaconChainTypes> {
     pub log: slog::Logger,
     pub db_path: PathBuf,
     pub freezer_db_path: PathBuf,
-    pub events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+    pub events: EventBus<T::EthSpec>,
 }
 
 pub async fn on_http_request<T: BeaconChainTypes>(
@@ -159,6 +157,7 @@ async fn route<T: BeaconChainTypes>(
         (Method::GET, "/beacon/fork/stream") => {
             handler.sse_stream(|_, ctx| beacon::stream_forks(ctx)).await
         }
+        (Method::GET, "/events") => handler.sse_stream(events::stream_events).await,
         (Method::GET, "/beacon/genesis_time") => handler
             .in_blocking_task(|_, ctx| Ok(ctx.beacon_chain.head_info()?.genesis_time))
             .await?
@@ -187,7 +186,7 @@ async fn route<T: BeaconChainTypes>(
         (Method::GET, "/beacon/state") => handler
             .in_blocking_task(beacon::get_state)
             .await?
-            .all_encodings(),
+            .streamed_encodings(),
         (Method::GET, "/beacon/state_root") => handler
             .in_blocking_task(beacon::get_state_root)
             .await?
@@ -195,7 +194,7 @@ async fn route<T: BeaconChainTypes>(
         (Method::GET, "/beacon/state/genesis") => handler
             .in_blocking_task(|_, ctx| beacon::get_genesis_state(ctx))
             .await?
-            .all_encodings(),
+            .streamed_encodings(),
         (Method::GET, "/beacon/committees") => handler
             .in_blocking_task(beacon::get_committees)
             .await?
@@ -315,6 +314,10 @@ async fn route<T: BeaconChainTypes>(
             .in_blocking_task(|_, ctx| lighthouse::connected_peers(ctx))
             .await?
             .serde_encodings(),
+        (Method::GET, "/lighthouse/reorgs") => handler
+            .in_blocking_task(|_, ctx| lighthouse::reorgs(ctx))
+            .await?
+            .serde_encodings(),
         _ => Err(ApiError::NotFound(
             "Request path and/or method not found.".to_owned(),
         )),
@@ -11,7 +11,8 @@ use node_test_rig::{
 use remote_beacon_node::{
     Committee, HeadBeaconBlock, PersistedOperationPool, PublishStatus, ValidatorResponse,
 };
-use rest_types::ValidatorDutyBytes;
+use rest_types::{StateResponse, ValidatorDutyBytes};
+use ssz::Decode;
 use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -651,6 +652,68 @@ fn beacon_state() {
     );
 }
 
+#[test]
+fn beacon_state_ssz() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("node should have http server");
+
+    let request = hyper::Request::get(format!("http://{}/beacon/state?slot=0", socket_addr))
+        .header(hyper::header::ACCEPT, "application/ssz")
+        .body(hyper::Body::empty())
+        .expect("should build request");
+
+    let (content_length, bytes) = env.runtime().block_on(async {
+        let response = hyper::Client::new()
+            .request(request)
+            .await
+            .expect("should fetch state from http api");
+        assert_eq!(
+            response.headers()[hyper::header::CONTENT_TYPE],
+            "application/ssz"
+        );
+        let content_length: usize = response.headers()[hyper::header::CONTENT_LENGTH]
+            .to_str()
+            .expect("content-length should be a string")
+            .parse()
+            .expect("content-length should be a number");
+        let bytes = hyper::body::to_bytes(response.into_body())
+            .await
+            .expect("should read streamed body");
+        (content_length, bytes)
+    });
+
+    assert_eq!(
+        content_length,
+        bytes.len(),
+        "content-length should match the streamed body"
+    );
+
+    let response = StateResponse::<E>::from_ssz_bytes(&bytes).expect("should decode ssz state");
+
+    let mut db_state = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain")
+        .state_at_slot(Slot::new(0), StateSkipConfig::WithStateRoots)
+        .expect("should find state");
+    db_state.drop_all_caches();
+
+    assert_eq!(
+        response.root,
+        db_state.canonical_root(),
+        "root from ssz response should match that from the DB"
+    );
+    assert_eq!(
+        response.beacon_state, db_state,
+        "genesis state from ssz response should match that from the DB"
+    );
+}
+
 #[test]
 fn beacon_block() {
     let mut env = build_env();
@@ -78,7 +78,7 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .arg(
             Arg::with_name("disable-upnp")
                 .long("disable-upnp")
-                .help("Disables UPnP support. Setting this will prevent Lighthouse from attempting to automatically establish external port mappings.")
+                .help("Disables UPnP, NAT-PMP and PCP support. Setting this will prevent Lighthouse from attempting to automatically establish external port mappings.")
                 .takes_value(false),
         )
         .arg(
@@ -241,7 +241,9 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .long("slots-per-restore-point")
                 .value_name("SLOT_COUNT")
                 .help("Specifies how often a freezer DB restore point should be stored. \
-                       DO NOT DECREASE AFTER INITIALIZATION. [default: 2048 (mainnet) or 64 (minimal)]")
+                       Changing it for an existing database reconstructs the freezer's restore \
+                       points on startup, which may take some time. If not set, an existing \
+                       database keeps its interval. [default: 2048 (mainnet) or 64 (minimal)]")
                 .takes_value(true)
         )
         .arg(
@@ -251,6 +253,36 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Specifies how many blocks the database should cache in memory [default: 5]")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("historic-state-cache-size")
+                .long("historic-state-cache-size")
+                .value_name("SIZE")
+                .help("Specifies how many reconstructed finalized states the database should \
+                       cache in memory. Loading a finalized state replays blocks from the closest \
+                       cached state, so a larger cache speeds up queries over ranges of old \
+                       states. [default: 1]")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("db-backend")
+                .long("db-backend")
+                .value_name("BACKEND")
+                .help("Specifies the key-value store used for new databases. Existing databases \
+                       must be converted with `lighthouse db convert` to change backend. \
+                       [default: leveldb]")
+                .possible_values(&["leveldb", "lmdb"])
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("db-compression")
+                .long("db-compression")
+                .value_name("ALGORITHM")
+                .help("Specifies the algorithm used to compress newly written blocks, states and \
+                       freezer chunks. Values written with any algorithm remain readable. \
+                       [default: none]")
+                .possible_values(&["none", "snappy", "zstd"])
+                .takes_value(true)
+        )
 
         /*
          * Purge.
@@ -183,6 +183,7 @@ pub fn get_config<E: EthSpec>(
         client_config.store.slots_per_restore_point = slots_per_restore_point
             .parse()
             .map_err(|_| "slots-per-restore-point is not a valid integer".to_string())?;
+        client_config.store.slots_per_restore_point_set_explicitly = true;
     } else {
         client_config.store.slots_per_restore_point = std::cmp::min(
             E::slots_per_historical_root() as u64,
@@ -196,6 +197,20 @@ pub fn get_config<E: EthSpec>(
             .map_err(|_| "block-cache-size is not a valid integer".to_string())?;
     }
 
+    if let Some(historic_state_cache_size) =
+        clap_utils::parse_optional(cli_args, "historic-state-cache-size")?
+    {
+        client_config.store.historic_state_cache_size = historic_state_cache_size;
+    }
+
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "db-backend")? {
+        client_config.store.backend = backend;
+    }
+
+    if let Some(compression) = clap_utils::parse_optional(cli_args, "db-compression")? {
+        client_config.store.compression = compression;
+    }
+
     if spec_constants != client_config.spec_constants {
         crit!(log, "Specification constants do not match.";
               "client_config" => client_config.spec_constants,
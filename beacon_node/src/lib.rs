@@ -12,7 +12,7 @@ pub use eth2_config::Eth2Config;
 
 use beacon_chain::events::TeeEventHandler;
 use beacon_chain::migrate::BackgroundMigrator;
-use beacon_chain::store::LevelDB;
+use beacon_chain::store::DiskStore;
 use beacon_chain::{
     builder::Witness, eth1_chain::CachingEth1Backend, slot_clock::SystemTimeSlotClock,
 };
@@ -26,13 +26,13 @@ use types::EthSpec;
 /// A type-alias to the tighten the definition of a production-intended `Client`.
 pub type ProductionClient<E> = Client<
     Witness<
-        BackgroundMigrator<E, LevelDB<E>, LevelDB<E>>,
+        BackgroundMigrator<E, DiskStore<E>, DiskStore<E>>,
         SystemTimeSlotClock,
         CachingEth1Backend<E>,
         E,
         TeeEventHandler<E>,
-        LevelDB<E>,
-        LevelDB<E>,
+        DiskStore<E>,
+        DiskStore<E>,
     >,
 >;
 
@@ -1,5 +1,5 @@
-use crate::chunked_vector::{chunk_key, Chunk, Field};
-use crate::{HotColdDB, ItemStore};
+use crate::chunked_vector::{chunk_key, Chunk, ChunkError, Field};
+use crate::{Error, HotColdDB, ItemStore};
 use slog::error;
 use std::sync::Arc;
 use types::{ChainSpec, EthSpec, Slot};
@@ -122,3 +122,125 @@ where
         }
     }
 }
+
+/// Double-ended iterator over a range of the values of a `BeaconState` vector field.
+///
+/// Like `ChunkedVectorIter` the values are read directly from the freezer DB's chunks, but errors
+/// are returned to the caller rather than logged, and the range may be traversed from either end.
+/// The most recently loaded chunk at each end of the range is kept, so each chunk is read at most
+/// once per direction.
+pub struct ChunkedVectorRangeIter<F, E, Hot, Cold>
+where
+    F: Field<E>,
+    E: EthSpec,
+    Hot: ItemStore<E>,
+    Cold: ItemStore<E>,
+{
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    start_vindex: usize,
+    end_vindex: usize,
+    front_chunk: Option<(usize, Chunk<F::Value>)>,
+    back_chunk: Option<(usize, Chunk<F::Value>)>,
+}
+
+impl<F, E, Hot, Cold> ChunkedVectorRangeIter<F, E, Hot, Cold>
+where
+    F: Field<E>,
+    E: EthSpec,
+    Hot: ItemStore<E>,
+    Cold: ItemStore<E>,
+{
+    /// Create a new iterator over the values from `start_vindex` up to (but not including)
+    /// `end_vindex`.
+    ///
+    /// The caller is responsible for ensuring that every value in the range is stored in the
+    /// freezer DB, e.g. by bounding it with `HotColdDB::get_latest_restore_point_slot`.
+    pub fn new(
+        store: Arc<HotColdDB<E, Hot, Cold>>,
+        start_vindex: usize,
+        end_vindex: usize,
+    ) -> Self {
+        Self {
+            store,
+            start_vindex,
+            end_vindex,
+            front_chunk: None,
+            back_chunk: None,
+        }
+    }
+
+    /// Load the value at `vindex`, reusing `cached_chunk` if it contains `vindex`.
+    fn load_value(
+        store: &HotColdDB<E, Hot, Cold>,
+        cached_chunk: &mut Option<(usize, Chunk<F::Value>)>,
+        vindex: usize,
+    ) -> Result<F::Value, Error> {
+        let chunk_size = F::chunk_size();
+        let cindex = vindex / chunk_size;
+
+        let chunk = match cached_chunk.take() {
+            Some((chunk_index, chunk)) if chunk_index == cindex => chunk,
+            _ => Chunk::load(&store.cold_db, F::column(), &chunk_key(cindex as u64))?.ok_or(
+                ChunkError::Missing {
+                    chunk_index: cindex,
+                },
+            )?,
+        };
+
+        let value = chunk
+            .values
+            .get(vindex % chunk_size)
+            .cloned()
+            .ok_or_else(|| {
+                ChunkError::InvalidSize {
+                    chunk_index: cindex,
+                    expected: vindex % chunk_size + 1,
+                    actual: chunk.values.len(),
+                }
+                .into()
+            });
+        *cached_chunk = Some((cindex, chunk));
+        value
+    }
+}
+
+impl<F, E, Hot, Cold> Iterator for ChunkedVectorRangeIter<F, E, Hot, Cold>
+where
+    F: Field<E>,
+    E: EthSpec,
+    Hot: ItemStore<E>,
+    Cold: ItemStore<E>,
+{
+    type Item = Result<(usize, F::Value), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start_vindex >= self.end_vindex {
+            return None;
+        }
+        let vindex = self.start_vindex;
+        self.start_vindex += 1;
+        Some(Self::load_value(&self.store, &mut self.front_chunk, vindex).map(|val| (vindex, val)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end_vindex.saturating_sub(self.start_vindex);
+        (len, Some(len))
+    }
+}
+
+impl<F, E, Hot, Cold> DoubleEndedIterator for ChunkedVectorRangeIter<F, E, Hot, Cold>
+where
+    F: Field<E>,
+    E: EthSpec,
+    Hot: ItemStore<E>,
+    Cold: ItemStore<E>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start_vindex >= self.end_vindex {
+            return None;
+        }
+        self.end_vindex -= 1;
+        let vindex = self.end_vindex;
+        Some(Self::load_value(&self.store, &mut self.back_chunk, vindex).map(|val| (vindex, val)))
+    }
+}
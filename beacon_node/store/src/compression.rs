@@ -0,0 +1,327 @@
+//! Optional compression of the largest database values: blocks, states and the freezer's
+//! chunked vectors.
+//!
+//! Each compressed-column value is written with a leading format tag, so values written with
+//! different `CompressionAlgorithm`s (including none) can be read back regardless of the current
+//! configuration. Values written before tags were introduced are read as-is: in the hot database
+//! their SSZ encoding can never begin with a tag byte, while a freezer database that already
+//! held untagged values is never tagged (see `CompressedStore::new`).
+use crate::config::CompressionAlgorithm;
+use crate::{metrics, DBColumn, Error, KeyValueStore, KeyValueStoreOp};
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+use types::EthSpec;
+
+/// The zstd compression level, favouring speed as values are compressed on the write path.
+const ZSTD_LEVEL: i32 = 3;
+
+/// The tag of a value stored without compression.
+const TAG_UNCOMPRESSED: u8 = 0xf0;
+/// The tag of a value compressed with snappy.
+const TAG_SNAPPY: u8 = 0xf1;
+/// The tag of a value compressed with zstd.
+const TAG_ZSTD: u8 = 0xf2;
+
+/// Returns the metrics label of a column whose values are compressed, or `None` if values of the
+/// column are stored unmodified.
+///
+/// `column` may be a full database key: all column names are three bytes long, so a key's first
+/// three bytes identify its column.
+fn compressed_column(column: &[u8]) -> Option<&'static str> {
+    let column = column.get(..3)?;
+    [
+        DBColumn::BeaconBlock,
+        DBColumn::BeaconState,
+        DBColumn::BeaconBlockRoots,
+        DBColumn::BeaconStateRoots,
+        DBColumn::BeaconHistoricalRoots,
+        DBColumn::BeaconRandaoMixes,
+    ]
+    .iter()
+    .map(|&column| -> &'static str { column.into() })
+    .find(|name| name.as_bytes() == column)
+}
+
+/// A key-value store which compresses the values of large columns.
+///
+/// Values of other columns are passed through unmodified.
+#[derive(Debug)]
+pub struct CompressedStore<E: EthSpec, S: KeyValueStore<E>> {
+    db: S,
+    algorithm: CompressionAlgorithm,
+    /// Whether values are written with a format tag. Compression requires tags.
+    tagged: bool,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec, S: KeyValueStore<E>> CompressedStore<E, S> {
+    /// Wrap `db`, compressing new values with `algorithm`.
+    ///
+    /// `tagged` must only be `true` if every existing value that could begin with a tag byte
+    /// was written by a tagged store. If it is `false` values are always written uncompressed.
+    pub fn new(db: S, algorithm: CompressionAlgorithm, tagged: bool) -> Self {
+        Self {
+            db,
+            algorithm,
+            tagged,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The algorithm actually applied to new values.
+    pub fn algorithm(&self) -> CompressionAlgorithm {
+        if self.tagged {
+            self.algorithm
+        } else {
+            CompressionAlgorithm::None
+        }
+    }
+
+    fn encode(&self, label: &str, bytes: &[u8]) -> Result<Vec<u8>, Error> {
+        if !self.tagged {
+            return Ok(bytes.to_vec());
+        }
+
+        let (tag, compressed) = {
+            let _timer = metrics::start_timer_vec(&metrics::COMPRESSION_TIMES, &[label]);
+            match self.algorithm {
+                CompressionAlgorithm::None => (TAG_UNCOMPRESSED, None),
+                CompressionAlgorithm::Snappy => (
+                    TAG_SNAPPY,
+                    Some(
+                        snap::raw::Encoder::new()
+                            .compress_vec(bytes)
+                            .map_err(|e| compression_error("snappy", e))?,
+                    ),
+                ),
+                CompressionAlgorithm::Zstd => (
+                    TAG_ZSTD,
+                    Some(
+                        zstd::stream::encode_all(bytes, ZSTD_LEVEL)
+                            .map_err(|e| compression_error("zstd", e))?,
+                    ),
+                ),
+            }
+        };
+        let payload = compressed.as_deref().unwrap_or(bytes);
+
+        if compressed.is_some() {
+            metrics::inc_counter_vec_by(
+                &metrics::COMPRESSION_INPUT_BYTES,
+                &[label],
+                bytes.len() as i64,
+            );
+            metrics::inc_counter_vec_by(
+                &metrics::COMPRESSION_OUTPUT_BYTES,
+                &[label],
+                payload.len() as i64,
+            );
+            if !bytes.is_empty() {
+                metrics::observe_vec(
+                    &metrics::COMPRESSION_RATIO,
+                    &[label],
+                    payload.len() as f64 / bytes.len() as f64,
+                );
+            }
+        }
+
+        let mut value = Vec::with_capacity(1 + payload.len());
+        value.push(tag);
+        value.extend_from_slice(payload);
+        Ok(value)
+    }
+
+    fn decode<'a>(&self, label: &str, bytes: &'a [u8]) -> Result<Cow<'a, [u8]>, Error> {
+        let (tag, payload) = match bytes.split_first() {
+            Some((tag, payload)) if self.tagged => (*tag, payload),
+            _ => return Ok(Cow::Borrowed(bytes)),
+        };
+
+        let _timer = metrics::start_timer_vec(&metrics::DECOMPRESSION_TIMES, &[label]);
+        match tag {
+            TAG_UNCOMPRESSED => Ok(Cow::Borrowed(payload)),
+            TAG_SNAPPY => snap::raw::Decoder::new()
+                .decompress_vec(payload)
+                .map(Cow::Owned)
+                .map_err(|e| compression_error("snappy", e)),
+            TAG_ZSTD => zstd::stream::decode_all(payload)
+                .map(Cow::Owned)
+                .map_err(|e| compression_error("zstd", e)),
+            // An untagged value written before tags were introduced.
+            _ => Ok(Cow::Borrowed(bytes)),
+        }
+    }
+}
+
+fn compression_error(algorithm: &str, e: impl fmt::Debug) -> Error {
+    Error::DBError {
+        message: format!("{} compression error: {:?}", algorithm, e),
+    }
+}
+
+impl<E: EthSpec, S: KeyValueStore<E>> KeyValueStore<E> for CompressedStore<E, S> {
+    fn get_bytes(&self, col: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let bytes = self.db.get_bytes(col, key)?;
+        match (bytes, compressed_column(col.as_bytes())) {
+            (Some(bytes), Some(label)) => Ok(Some(self.decode(label, &bytes)?.into_owned())),
+            (bytes, _) => Ok(bytes),
+        }
+    }
+
+    fn put_bytes(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        match compressed_column(col.as_bytes()) {
+            Some(label) => self.db.put_bytes(col, key, &self.encode(label, val)?),
+            None => self.db.put_bytes(col, key, val),
+        }
+    }
+
+    fn put_bytes_sync(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        match compressed_column(col.as_bytes()) {
+            Some(label) => self.db.put_bytes_sync(col, key, &self.encode(label, val)?),
+            None => self.db.put_bytes_sync(col, key, val),
+        }
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        self.db.sync()
+    }
+
+    fn key_exists(&self, col: &str, key: &[u8]) -> Result<bool, Error> {
+        self.db.key_exists(col, key)
+    }
+
+    fn key_delete(&self, col: &str, key: &[u8]) -> Result<(), Error> {
+        self.db.key_delete(col, key)
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        let batch = batch
+            .into_iter()
+            .map(|op| match op {
+                KeyValueStoreOp::PutKeyValue(key, value) => match compressed_column(&key) {
+                    Some(label) => {
+                        let value = self.encode(label, &value)?;
+                        Ok(KeyValueStoreOp::PutKeyValue(key, value))
+                    }
+                    None => Ok(KeyValueStoreOp::PutKeyValue(key, value)),
+                },
+                op => Ok(op),
+            })
+            .collect::<Result<_, Error>>()?;
+        self.db.do_atomically(batch)
+    }
+
+    fn for_each_key_value<F>(&self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<(), Error>,
+    {
+        self.db
+            .for_each_key_value(|key, value| match compressed_column(key) {
+                Some(label) => f(key, self.decode(label, value)?.as_ref()),
+                None => f(key, value),
+            })
+    }
+}
+
+impl<E: EthSpec, S: crate::ItemStore<E>> crate::ItemStore<E> for CompressedStore<E, S> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_key_for_col, MemoryStore};
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    fn block_column() -> &'static str {
+        DBColumn::BeaconBlock.into()
+    }
+
+    fn compressible_value() -> Vec<u8> {
+        (0..4096u32).map(|i| (i % 7) as u8).collect()
+    }
+
+    #[test]
+    fn round_trip_all_algorithms() {
+        for algorithm in &[
+            CompressionAlgorithm::None,
+            CompressionAlgorithm::Snappy,
+            CompressionAlgorithm::Zstd,
+        ] {
+            let store = CompressedStore::new(MemoryStore::<E>::open(), *algorithm, true);
+            let value = compressible_value();
+
+            store.put_bytes(block_column(), b"key", &value).unwrap();
+            assert_eq!(
+                store.get_bytes(block_column(), b"key").unwrap(),
+                Some(value.clone())
+            );
+
+            let raw = store.db.get_bytes(block_column(), b"key").unwrap().unwrap();
+            if *algorithm == CompressionAlgorithm::None {
+                assert_eq!(raw.len(), value.len() + 1);
+            } else {
+                assert!(raw.len() < value.len());
+            }
+        }
+    }
+
+    #[test]
+    fn formats_coexist() {
+        let db = MemoryStore::<E>::open();
+        let value = compressible_value();
+
+        // A value written before tags were introduced, and values in each format.
+        db.put_bytes(block_column(), b"legacy", &[0x64, 0, 0, 0, 1, 2, 3])
+            .unwrap();
+        let db = {
+            let store = CompressedStore::new(db, CompressionAlgorithm::Snappy, true);
+            store.put_bytes(block_column(), b"snappy", &value).unwrap();
+            store.db
+        };
+        let store = CompressedStore::new(db, CompressionAlgorithm::Zstd, true);
+        store
+            .do_atomically(vec![KeyValueStoreOp::PutKeyValue(
+                get_key_for_col(block_column(), b"zstd"),
+                value.clone(),
+            )])
+            .unwrap();
+
+        assert_eq!(
+            store.get_bytes(block_column(), b"legacy").unwrap(),
+            Some(vec![0x64, 0, 0, 0, 1, 2, 3])
+        );
+        assert_eq!(
+            store.get_bytes(block_column(), b"snappy").unwrap(),
+            Some(value.clone())
+        );
+        assert_eq!(
+            store.get_bytes(block_column(), b"zstd").unwrap(),
+            Some(value)
+        );
+    }
+
+    #[test]
+    fn untagged_and_uncompressed_columns_unmodified() {
+        let value = compressible_value();
+        let meta_column: &str = DBColumn::BeaconMeta.into();
+
+        let store =
+            CompressedStore::new(MemoryStore::<E>::open(), CompressionAlgorithm::Zstd, true);
+        store.put_bytes(meta_column, b"key", &value).unwrap();
+        assert_eq!(
+            store.db.get_bytes(meta_column, b"key").unwrap(),
+            Some(value.clone())
+        );
+
+        let store =
+            CompressedStore::new(MemoryStore::<E>::open(), CompressionAlgorithm::Zstd, false);
+        assert_eq!(store.algorithm(), CompressionAlgorithm::None);
+        store.put_bytes(block_column(), b"key", &value).unwrap();
+        assert_eq!(
+            store.db.get_bytes(block_column(), b"key").unwrap(),
+            Some(value)
+        );
+    }
+}
@@ -1,16 +1,131 @@
+use crate::{DBColumn, Error, StoreItem};
 use serde_derive::{Deserialize, Serialize};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::fmt;
+use std::str::FromStr;
 use types::{EthSpec, MinimalEthSpec};
 
 pub const DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 2048;
 pub const DEFAULT_BLOCK_CACHE_SIZE: usize = 5;
+pub const DEFAULT_HISTORIC_STATE_CACHE_SIZE: usize = 1;
 
 /// Database configuration parameters.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StoreConfig {
     /// Number of slots to wait between storing restore points in the freezer database.
     pub slots_per_restore_point: u64,
+    /// Whether `slots_per_restore_point` was chosen by the user, in which case an existing freezer
+    /// database written with a different value is reconstructed rather than read as it is.
+    pub slots_per_restore_point_set_explicitly: bool,
     /// Maximum number of blocks to store in the in-memory block cache.
     pub block_cache_size: usize,
+    /// Maximum number of reconstructed frozen states to store in the in-memory state cache.
+    pub historic_state_cache_size: usize,
+    /// The key-value store used for new on-disk databases.
+    pub backend: DatabaseBackend,
+    /// The algorithm used to compress new blocks, states and freezer chunks.
+    pub compression: CompressionAlgorithm,
+}
+
+/// The parts of the `StoreConfig` which determine how the freezer database is laid out.
+///
+/// Stored in the freezer database, so that it is always read with the configuration it was
+/// written with.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct OnDiskStoreConfig {
+    pub slots_per_restore_point: u64,
+}
+
+impl StoreItem for OnDiskStoreConfig {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+/// The key-value stores available for the on-disk databases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DatabaseBackend {
+    LevelDb,
+    Lmdb,
+}
+
+impl Default for DatabaseBackend {
+    fn default() -> Self {
+        DatabaseBackend::LevelDb
+    }
+}
+
+impl FromStr for DatabaseBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "leveldb" => Ok(DatabaseBackend::LevelDb),
+            "lmdb" => Ok(DatabaseBackend::Lmdb),
+            other => Err(format!(
+                "Unknown database backend \"{}\", expected leveldb or lmdb",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DatabaseBackend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DatabaseBackend::LevelDb => write!(f, "leveldb"),
+            DatabaseBackend::Lmdb => write!(f, "lmdb"),
+        }
+    }
+}
+
+/// The algorithms available for compressing blocks, states and freezer chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompressionAlgorithm {
+    None,
+    Snappy,
+    Zstd,
+}
+
+impl Default for CompressionAlgorithm {
+    fn default() -> Self {
+        CompressionAlgorithm::None
+    }
+}
+
+impl FromStr for CompressionAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(CompressionAlgorithm::None),
+            "snappy" => Ok(CompressionAlgorithm::Snappy),
+            "zstd" => Ok(CompressionAlgorithm::Zstd),
+            other => Err(format!(
+                "Unknown compression algorithm \"{}\", expected none, snappy or zstd",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CompressionAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompressionAlgorithm::None => write!(f, "none"),
+            CompressionAlgorithm::Snappy => write!(f, "snappy"),
+            CompressionAlgorithm::Zstd => write!(f, "zstd"),
+        }
+    }
 }
 
 impl Default for StoreConfig {
@@ -18,7 +133,11 @@ impl Default for StoreConfig {
         Self {
             // Safe default for tests, shouldn't ever be read by a CLI node.
             slots_per_restore_point: MinimalEthSpec::slots_per_historical_root() as u64,
+            slots_per_restore_point_set_explicitly: false,
             block_cache_size: DEFAULT_BLOCK_CACHE_SIZE,
+            historic_state_cache_size: DEFAULT_HISTORIC_STATE_CACHE_SIZE,
+            backend: DatabaseBackend::default(),
+            compression: CompressionAlgorithm::default(),
         }
     }
 }
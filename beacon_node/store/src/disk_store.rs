@@ -0,0 +1,135 @@
+use crate::config::DatabaseBackend;
+use crate::leveldb_store::LevelDB;
+use crate::lmdb_store::{Lmdb, LMDB_DATA_FILENAME};
+use crate::{Error, ItemStore, KeyValueStore, KeyValueStoreOp};
+use std::path::Path;
+use types::EthSpec;
+
+/// The file every leveldb database directory contains.
+const LEVELDB_CURRENT_FILENAME: &str = "CURRENT";
+
+/// An on-disk key-value store, using the backend chosen at runtime.
+pub enum DiskStore<E: EthSpec> {
+    LevelDb(LevelDB<E>),
+    Lmdb(Lmdb<E>),
+}
+
+impl<E: EthSpec> DiskStore<E> {
+    /// Open the database at `path`, creating a new `backend` database if none exists.
+    ///
+    /// Returns an error if an existing database uses a different backend.
+    pub fn open(path: &Path, backend: DatabaseBackend) -> Result<Self, Error> {
+        if let Some(existing) = detect_backend(path) {
+            if existing != backend {
+                return Err(Error::DBError {
+                    message: format!(
+                        "The database at {:?} uses the {} backend, not {}. Use --db-backend {} \
+                         or convert it with `lighthouse db convert`",
+                        path, existing, backend, existing
+                    ),
+                });
+            }
+        }
+
+        match backend {
+            DatabaseBackend::LevelDb => LevelDB::open(path).map(DiskStore::LevelDb),
+            DatabaseBackend::Lmdb => Lmdb::open(path).map(DiskStore::Lmdb),
+        }
+    }
+
+    /// Open the existing database at `path` with the backend it was written with, without
+    /// creating or writing to it.
+    ///
+    /// Returns an error if there is no database at `path`.
+    pub fn open_read_only(path: &Path) -> Result<Self, Error> {
+        match detect_backend(path) {
+            Some(DatabaseBackend::LevelDb) => LevelDB::open_existing(path).map(DiskStore::LevelDb),
+            Some(DatabaseBackend::Lmdb) => Lmdb::open_read_only(path).map(DiskStore::Lmdb),
+            None => Err(Error::DBError {
+                message: format!("No database found at {:?}", path),
+            }),
+        }
+    }
+
+    /// The backend of this database.
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            DiskStore::LevelDb(_) => DatabaseBackend::LevelDb,
+            DiskStore::Lmdb(_) => DatabaseBackend::Lmdb,
+        }
+    }
+}
+
+/// Returns the backend of the database at `path`, or `None` if there is no database there.
+pub fn detect_backend(path: &Path) -> Option<DatabaseBackend> {
+    if path.join(LMDB_DATA_FILENAME).exists() {
+        Some(DatabaseBackend::Lmdb)
+    } else if path.join(LEVELDB_CURRENT_FILENAME).exists() {
+        Some(DatabaseBackend::LevelDb)
+    } else {
+        None
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for DiskStore<E> {
+    fn get_bytes(&self, col: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        match self {
+            DiskStore::LevelDb(db) => db.get_bytes(col, key),
+            DiskStore::Lmdb(db) => db.get_bytes(col, key),
+        }
+    }
+
+    fn put_bytes(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        match self {
+            DiskStore::LevelDb(db) => db.put_bytes(col, key, val),
+            DiskStore::Lmdb(db) => db.put_bytes(col, key, val),
+        }
+    }
+
+    fn put_bytes_sync(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        match self {
+            DiskStore::LevelDb(db) => db.put_bytes_sync(col, key, val),
+            DiskStore::Lmdb(db) => db.put_bytes_sync(col, key, val),
+        }
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        match self {
+            DiskStore::LevelDb(db) => db.sync(),
+            DiskStore::Lmdb(db) => db.sync(),
+        }
+    }
+
+    fn key_exists(&self, col: &str, key: &[u8]) -> Result<bool, Error> {
+        match self {
+            DiskStore::LevelDb(db) => db.key_exists(col, key),
+            DiskStore::Lmdb(db) => db.key_exists(col, key),
+        }
+    }
+
+    fn key_delete(&self, col: &str, key: &[u8]) -> Result<(), Error> {
+        match self {
+            DiskStore::LevelDb(db) => db.key_delete(col, key),
+            DiskStore::Lmdb(db) => db.key_delete(col, key),
+        }
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        match self {
+            DiskStore::LevelDb(db) => db.do_atomically(batch),
+            DiskStore::Lmdb(db) => db.do_atomically(batch),
+        }
+    }
+
+    fn for_each_key_value<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<(), Error>,
+    {
+        match self {
+            DiskStore::LevelDb(db) => db.for_each_key_value(f),
+            DiskStore::Lmdb(db) => db.for_each_key_value(f),
+        }
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for DiskStore<E> {}
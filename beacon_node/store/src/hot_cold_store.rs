@@ -1,16 +1,19 @@
+use crate::chunked_iter::ChunkedVectorRangeIter;
 use crate::chunked_vector::{
-    store_updated_vector, BlockRoots, HistoricalRoots, RandaoMixes, StateRoots,
+    chunk_key, store_updated_vector, BlockRoots, Chunk, ChunkError, Field, HistoricalRoots,
+    RandaoMixes, StateRoots,
 };
-use crate::config::StoreConfig;
+use crate::compression::CompressedStore;
+use crate::config::{CompressionAlgorithm, OnDiskStoreConfig, StoreConfig};
+use crate::disk_store::DiskStore;
 use crate::forwards_iter::HybridForwardsBlockRootsIterator;
 use crate::impls::beacon_state::{get_full_state, store_full_state};
 use crate::iter::{ParentRootBlockIterator, StateRootsIterator};
-use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metrics;
 use crate::{
-    get_key_for_col, DBColumn, Error, ItemStore, KeyValueStoreOp, PartialBeaconState, StoreItem,
-    StoreOp,
+    get_key_for_col, DBColumn, Error, ItemStore, KeyValueStore, KeyValueStoreOp,
+    PartialBeaconState, StoreItem, StoreOp,
 };
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
@@ -29,6 +32,10 @@ use types::*;
 
 /// 32-byte key for accessing the `split` of the freezer DB.
 pub const SPLIT_DB_KEY: &str = "FREEZERDBSPLITFREEZERDBSPLITFREE";
+/// 32-byte key for accessing the `OnDiskStoreConfig` of the freezer DB.
+pub const CONFIG_DB_KEY: &str = "FREEZERDBCONFIGFREEZERDBCONFIGFR";
+/// 32-byte key marking a freezer DB whose values are all written with a compression format tag.
+pub const FREEZER_TAGGED_DB_KEY: &str = "FREEZERDBTAGGEDFREEZERDBTAGGEDFR";
 
 /// Defines how blocks should be replayed on states.
 #[derive(PartialEq)]
@@ -53,13 +60,16 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     split: RwLock<Split>,
     config: StoreConfig,
     /// Cold database containing compact historical data.
-    pub(crate) cold_db: Cold,
+    pub(crate) cold_db: CompressedStore<E, Cold>,
     /// Hot database containing duplicated but quick-to-access recent data.
     ///
     /// The hot database also contains all blocks.
-    pub(crate) hot_db: Hot,
+    pub(crate) hot_db: CompressedStore<E, Hot>,
     /// LRU cache of deserialized blocks. Updated whenever a block is loaded.
     block_cache: Mutex<LruCache<Hash256, SignedBeaconBlock<E>>>,
+    /// LRU cache of frozen states, keyed by slot. Updated whenever a state is reconstructed
+    /// between restore points, along with the restore point it was reconstructed from.
+    historic_state_cache: Mutex<LruCache<Slot, BeaconState<E>>>,
     /// Chain spec.
     spec: ChainSpec,
     /// Logger.
@@ -95,6 +105,11 @@ pub enum HotColdDBError {
         slots_per_epoch: u64,
     },
     RestorePointBlockHashError(BeaconStateError),
+    ReconstructedStateRootMismatch {
+        slot: Slot,
+        expected: Hash256,
+        computed: Hash256,
+    },
 }
 
 impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
@@ -107,9 +122,10 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
 
         let db = HotColdDB {
             split: RwLock::new(Split::default()),
-            cold_db: MemoryStore::open(),
-            hot_db: MemoryStore::open(),
+            cold_db: CompressedStore::new(MemoryStore::open(), config.compression, true),
+            hot_db: CompressedStore::new(MemoryStore::open(), config.compression, true),
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
+            historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
             config,
             spec,
             log,
@@ -120,24 +136,47 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
     }
 }
 
-impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
+impl<E: EthSpec> HotColdDB<E, DiskStore<E>, DiskStore<E>> {
     /// Open a new or existing database, with the given paths to the hot and cold DBs.
     ///
     /// The `slots_per_restore_point` parameter must be a divisor of `SLOTS_PER_HISTORICAL_ROOT`.
+    /// New databases are created with `config.backend`, existing databases must match it.
+    ///
+    /// New values are compressed with `config.compression`, except in a freezer DB created
+    /// before compression was supported, see `Self::freezer_is_tagged`.
+    ///
+    /// If the freezer DB was written with a different `slots_per_restore_point`, it is read with
+    /// the interval it was written with, unless `config.slots_per_restore_point_set_explicitly`,
+    /// in which case its restore points are first regenerated at the configured interval, see
+    /// `Self::reconstruct_restore_points`.
     pub fn open(
         hot_path: &Path,
         cold_path: &Path,
         config: StoreConfig,
         spec: ChainSpec,
         log: Logger,
-    ) -> Result<HotColdDB<E, LevelDB<E>, LevelDB<E>>, Error> {
+    ) -> Result<HotColdDB<E, DiskStore<E>, DiskStore<E>>, Error> {
         Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
 
-        let db = HotColdDB {
+        let hot_db = DiskStore::open(hot_path, config.backend)?;
+        let cold_db = DiskStore::open(cold_path, config.backend)?;
+        let cold_tagged = Self::freezer_is_tagged(&hot_db, &cold_db)?;
+
+        if !cold_tagged && config.compression != CompressionAlgorithm::None {
+            warn!(
+                log,
+                "Freezer DB will not be compressed";
+                "reason" => "created before database compression was supported",
+                "compression" => config.compression.to_string()
+            );
+        }
+
+        let mut db = HotColdDB {
             split: RwLock::new(Split::default()),
-            cold_db: LevelDB::open(cold_path)?,
-            hot_db: LevelDB::open(hot_path)?,
+            cold_db: CompressedStore::new(cold_db, config.compression, cold_tagged),
+            hot_db: CompressedStore::new(hot_db, config.compression, true),
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
+            historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
             config,
             spec,
             log,
@@ -150,13 +189,128 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             info!(
                 db.log,
                 "Hot-Cold DB initialized";
+                "backend" => db.config.backend.to_string(),
+                "compression" => db.config.compression.to_string(),
                 "split_slot" => split.slot,
                 "split_state" => format!("{:?}", split.state_root)
             );
             *db.split.write() = split;
         }
+
+        let slots_per_restore_point = db.config.slots_per_restore_point;
+        match Self::load_freezer_slots_per_restore_point(&db.hot_db, &db.cold_db)? {
+            Some(stored) if stored != slots_per_restore_point => {
+                db.config.slots_per_restore_point = stored;
+                if db.config.slots_per_restore_point_set_explicitly {
+                    db.reconstruct_restore_points(slots_per_restore_point)?;
+                } else {
+                    info!(
+                        db.log,
+                        "Using the freezer's slots per restore point";
+                        "slots_per_restore_point" => stored,
+                        "help" => "set --slots-per-restore-point to change it"
+                    );
+                    db.store_on_disk_config(&mut vec![])?;
+                }
+            }
+            // A freezer holding only the genesis restore point is readable with any interval
+            // exceeding its frozen slots, such as the largest valid one. Reconstruct the restore
+            // points the configured interval expects below the split.
+            None if db.split.read().slot > slots_per_restore_point => {
+                db.config.slots_per_restore_point = E::slots_per_historical_root() as u64;
+                db.reconstruct_restore_points(slots_per_restore_point)?;
+            }
+            _ => db.store_on_disk_config(&mut vec![])?,
+        }
+
         Ok(db)
     }
+
+    /// Open the existing databases at the given paths to the hot and cold DBs, without creating
+    /// or writing to them.
+    ///
+    /// Both databases are opened with the backend they were written with, and the freezer DB is
+    /// read with the slots per restore point it was written with. Returns an error if either
+    /// database does not exist.
+    pub fn open_read_only(
+        hot_path: &Path,
+        cold_path: &Path,
+        mut config: StoreConfig,
+        spec: ChainSpec,
+        log: Logger,
+    ) -> Result<HotColdDB<E, DiskStore<E>, DiskStore<E>>, Error> {
+        let hot_db = DiskStore::open_read_only(hot_path)?;
+        let cold_db = DiskStore::open_read_only(cold_path)?;
+        config.backend = hot_db.backend();
+
+        let cold_tagged = cold_db.key_exists(
+            DBColumn::BeaconMeta.into(),
+            FREEZER_TAGGED_DB_KEY.as_bytes(),
+        )?;
+        let hot_db = CompressedStore::new(hot_db, config.compression, true);
+        let cold_db = CompressedStore::new(cold_db, config.compression, cold_tagged);
+
+        let split = hot_db
+            .get::<Split>(&Hash256::from_slice(SPLIT_DB_KEY.as_bytes()))?
+            .unwrap_or_default();
+        // A freezer holding only the genesis restore point is readable with the largest valid
+        // interval, but not necessarily the configured one.
+        config.slots_per_restore_point =
+            match Self::load_freezer_slots_per_restore_point(&hot_db, &cold_db)? {
+                Some(stored) => stored,
+                None if split.slot > config.slots_per_restore_point => {
+                    E::slots_per_historical_root() as u64
+                }
+                None => config.slots_per_restore_point,
+            };
+        Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
+
+        info!(
+            log,
+            "Hot-Cold DB opened read-only";
+            "backend" => config.backend.to_string(),
+            "split_slot" => split.slot,
+            "slots_per_restore_point" => config.slots_per_restore_point
+        );
+
+        Ok(HotColdDB {
+            split: RwLock::new(split),
+            cold_db,
+            hot_db,
+            block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
+            historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            config,
+            spec,
+            log,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Determine whether the values of the freezer DB carry compression format tags, marking a
+    /// new freezer DB as tagged.
+    ///
+    /// Untagged freezer values are raw SSZ which may begin with any byte, so a freezer DB that
+    /// already holds them must continue to be written without tags.
+    fn freezer_is_tagged(hot_db: &DiskStore<E>, cold_db: &DiskStore<E>) -> Result<bool, Error> {
+        let meta_column = DBColumn::BeaconMeta.into();
+        let marker_key = FREEZER_TAGGED_DB_KEY.as_bytes();
+
+        if cold_db.key_exists(meta_column, marker_key)? {
+            return Ok(true);
+        }
+
+        let has_split = hot_db.key_exists(meta_column, SPLIT_DB_KEY.as_bytes())?;
+        let has_restore_point = cold_db.key_exists(
+            DBColumn::BeaconRestorePoint.into(),
+            Self::restore_point_key(0).as_bytes(),
+        )?;
+        if has_split || has_restore_point {
+            return Ok(false);
+        }
+
+        cold_db.put_bytes_sync(meta_column, marker_key, &[])?;
+        Ok(true)
+    }
 }
 
 impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
@@ -196,6 +350,27 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
     }
 
+    /// Fetch the slot and state root of a block, without decoding the rest of the block.
+    pub fn get_block_slot_and_state_root(
+        &self,
+        block_root: &Hash256,
+    ) -> Result<Option<(Slot, Hash256)>, Error> {
+        if let Some(block) = self.block_cache.lock().get(block_root) {
+            return Ok(Some((block.slot(), block.state_root())));
+        }
+
+        match self
+            .hot_db
+            .get_bytes(DBColumn::BeaconBlock.into(), block_root.as_bytes())?
+        {
+            Some(bytes) => {
+                let view = SignedBeaconBlockView::<E>::new(&bytes)?;
+                Ok(Some((view.slot()?, view.state_root()?)))
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Delete a block from the store and the block cache.
     pub fn delete_block(&self, block_root: &Hash256) -> Result<(), Error> {
         self.block_cache.lock().pop(block_root);
@@ -310,6 +485,52 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         HybridForwardsBlockRootsIterator::new(store, start_slot, end_state, end_block_root, spec)
     }
 
+    /// Iterate forwards or backwards through the canonical block roots from `start_slot` up to
+    /// (but not including) `end_slot`, reading them directly from the freezer DB.
+    ///
+    /// No states are loaded. The range is truncated to the slots whose roots are stored in the
+    /// freezer, i.e. those prior to the latest restore point. As in `BeaconState::block_roots`,
+    /// skipped slots contain the root of the closest prior non-skipped slot.
+    pub fn frozen_block_roots_iterator(
+        store: Arc<Self>,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> impl DoubleEndedIterator<Item = Result<(Hash256, Slot), Error>> {
+        let end_slot = std::cmp::min(end_slot, store.get_latest_restore_point_slot());
+        let start_slot = std::cmp::min(start_slot, end_slot);
+        ChunkedVectorRangeIter::<BlockRoots, _, _, _>::new(
+            store,
+            start_slot.as_usize(),
+            end_slot.as_usize(),
+        )
+        .map(|result| result.map(|(vindex, root)| (root, Slot::from(vindex))))
+    }
+
+    /// Fetch the root of the canonical block at `slot` from the freezer DB, without loading any
+    /// states.
+    ///
+    /// Returns `None` if `slot` is not prior to the latest restore point, as only those roots are
+    /// stored in the freezer. If `slot` was skipped, the root of the closest prior block is
+    /// returned.
+    pub fn get_cold_block_root(&self, slot: Slot) -> Result<Option<Hash256>, Error> {
+        if slot >= self.get_latest_restore_point_slot() {
+            return Ok(None);
+        }
+
+        let chunk_size = <BlockRoots as Field<E>>::chunk_size();
+        let cindex = slot.as_usize() / chunk_size;
+        let chunk = Chunk::<Hash256>::load(
+            &self.cold_db,
+            DBColumn::BeaconBlockRoots,
+            &chunk_key(cindex as u64),
+        )?
+        .ok_or(ChunkError::Missing {
+            chunk_index: cindex,
+        })?;
+
+        Ok(chunk.values.get(slot.as_usize() % chunk_size).copied())
+    }
+
     /// Load an epoch boundary state by using the hot state summary look-up.
     ///
     /// Will fall back to the cold DB if a hot state summary is not found.
@@ -586,7 +807,15 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Load a frozen state that lies between restore points.
+    ///
+    /// Blocks are replayed from the closest preceding state in the historic state cache, or from
+    /// the preceding restore point if none is cached.
     fn load_cold_intermediate_state(&self, slot: Slot) -> Result<BeaconState<E>, Error> {
+        if let Some(state) = self.historic_state_cache.lock().get(&slot) {
+            metrics::inc_counter(&metrics::BEACON_HISTORIC_STATE_CACHE_HIT_COUNT);
+            return Ok(state.clone());
+        }
+
         // 1. Load the restore points either side of the intermediate state.
         let low_restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
         let high_restore_point_idx = low_restore_point_idx + 1;
@@ -594,7 +823,20 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         // Acquire the read lock, so that the split can't change while this is happening.
         let split = self.split.read();
 
-        let low_restore_point = self.load_restore_point_by_index(low_restore_point_idx)?;
+        let low_restore_point_slot =
+            Slot::new(low_restore_point_idx * self.config.slots_per_restore_point);
+        let base_state = match self.get_historic_state_base(low_restore_point_slot, slot) {
+            Some(state) => {
+                metrics::inc_counter(&metrics::BEACON_HISTORIC_STATE_CACHE_BASE_COUNT);
+                state
+            }
+            None => {
+                metrics::inc_counter(&metrics::BEACON_HISTORIC_STATE_CACHE_MISS_COUNT);
+                let low_restore_point = self.load_restore_point_by_index(low_restore_point_idx)?;
+                self.put_historic_state(&low_restore_point);
+                low_restore_point
+            }
+        };
         // If the slot of the high point lies outside the freezer, use the split state
         // as the upper restore point.
         let high_restore_point = if high_restore_point_idx * self.config.slots_per_restore_point
@@ -606,15 +848,36 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             self.load_restore_point_by_index(high_restore_point_idx)?
         };
 
-        // 2. Load the blocks from the high restore point back to the low restore point.
+        // 2. Load the blocks from the high restore point back to the base state.
         let blocks = self.load_blocks_to_replay(
-            low_restore_point.slot,
+            base_state.slot,
             slot,
             self.get_high_restore_point_block_root(&high_restore_point, slot)?,
         )?;
 
-        // 3. Replay the blocks on top of the low restore point.
-        self.replay_blocks(low_restore_point, blocks, slot, BlockReplay::Accurate)
+        // 3. Replay the blocks on top of the base state.
+        let state = self.replay_blocks(base_state, blocks, slot, BlockReplay::Accurate)?;
+        self.put_historic_state(&state);
+        Ok(state)
+    }
+
+    /// Return a copy of the cached frozen state with the highest slot in `[start_slot, end_slot)`.
+    fn get_historic_state_base(&self, start_slot: Slot, end_slot: Slot) -> Option<BeaconState<E>> {
+        let mut cache = self.historic_state_cache.lock();
+        let base_slot = cache
+            .iter()
+            .map(|(slot, _)| *slot)
+            .filter(|slot| *slot >= start_slot && *slot < end_slot)
+            .max()?;
+        cache.get(&base_slot).cloned()
+    }
+
+    /// Add a copy of the frozen `state` to the historic state cache, if it is enabled.
+    fn put_historic_state(&self, state: &BeaconState<E>) {
+        if self.config.historic_state_cache_size > 0 {
+            let state = state.clone();
+            self.historic_state_cache.lock().put(state.slot, state);
+        }
     }
 
     /// Get a suitable block root for backtracking from `high_restore_point` to the state at `slot`.
@@ -738,12 +1001,237 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.split.read().slot
     }
 
+    /// Fetch the slots of the states in the historic state cache, most recently used first.
+    pub fn get_historic_state_cache_slots(&self) -> Vec<Slot> {
+        self.historic_state_cache
+            .lock()
+            .iter()
+            .map(|(slot, _)| *slot)
+            .collect()
+    }
+
     /// Fetch the slot of the most recently stored restore point.
     pub fn get_latest_restore_point_slot(&self) -> Slot {
         (self.get_split_slot() - 1) / self.config.slots_per_restore_point
             * self.config.slots_per_restore_point
     }
 
+    /// Fetch the slots per restore point of the freezer, as configured when opening the store.
+    pub fn get_slots_per_restore_point(&self) -> u64 {
+        self.config.slots_per_restore_point
+    }
+
+    /// Return the roots of all hot states at `slot`, which may lie on different forks.
+    pub fn get_hot_state_roots_at_slot(&self, slot: Slot) -> Result<Vec<Hash256>, Error> {
+        let column: &str = DBColumn::BeaconStateSummary.into();
+        let mut state_roots = vec![];
+        self.for_each_hot_key_value(|key, value| {
+            if key.starts_with(column.as_bytes())
+                && HotStateSummary::from_store_bytes(value)?.slot == slot
+            {
+                state_roots.push(Hash256::from_slice(&key[column.len()..]));
+            }
+            Ok(())
+        })?;
+        Ok(state_roots)
+    }
+
+    /// Call `f` with every key and value in the hot database.
+    ///
+    /// Keys are prefixed with their column and values are decompressed.
+    pub fn for_each_hot_key_value<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<(), Error>,
+    {
+        self.hot_db.for_each_key_value(f)
+    }
+
+    /// Call `f` with every key and value in the freezer database.
+    ///
+    /// Keys are prefixed with their column and values are decompressed.
+    pub fn for_each_cold_key_value<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<(), Error>,
+    {
+        self.cold_db.for_each_key_value(f)
+    }
+
+    /// Determine the slots per restore point that the freezer DB was written with.
+    ///
+    /// Freezers written before their configuration was stored are inferred from the slot of
+    /// their second restore point. Returns `None` if the freezer DB is empty or only holds the
+    /// genesis restore point, in which case it was written with an interval exceeding its frozen
+    /// slots and the configured interval may be adopted.
+    fn load_freezer_slots_per_restore_point<H: ItemStore<E>, C: ItemStore<E>>(
+        hot_db: &H,
+        cold_db: &C,
+    ) -> Result<Option<u64>, Error> {
+        let config: Option<OnDiskStoreConfig> =
+            cold_db.get(&Hash256::from_slice(CONFIG_DB_KEY.as_bytes()))?;
+        if let Some(config) = config {
+            return Ok(Some(config.slots_per_restore_point));
+        }
+
+        let split: Option<Split> = hot_db.get(&Hash256::from_slice(SPLIT_DB_KEY.as_bytes()))?;
+        if split.map_or(true, |split| split.slot == 0) {
+            return Ok(None);
+        }
+
+        let restore_point: Option<RestorePointHash> = cold_db.get(&Self::restore_point_key(1))?;
+        match restore_point {
+            Some(restore_point) => {
+                let summary: ColdStateSummary = cold_db.get(&restore_point.state_root)?.ok_or(
+                    HotColdDBError::MissingColdStateSummary(restore_point.state_root),
+                )?;
+                Ok(Some(summary.slot.as_u64()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Push an op storing the current `OnDiskStoreConfig` to `ops`, writing `ops` to the freezer.
+    fn store_on_disk_config(&self, ops: &mut Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        let config = OnDiskStoreConfig {
+            slots_per_restore_point: self.config.slots_per_restore_point,
+        };
+        ops.push(config.as_kv_store_op(Hash256::from_slice(CONFIG_DB_KEY.as_bytes())));
+        self.cold_db.do_atomically(std::mem::take(ops))
+    }
+
+    /// Regenerate the restore points of the freezer DB every `slots_per_restore_point` slots,
+    /// replaying frozen blocks to reconstruct states that were not previously restore points.
+    ///
+    /// New restore point states are written as they are reconstructed. The restore point index,
+    /// the deletion of superseded restore points and the new configuration are then written in a
+    /// single batch, so an interrupted reconstruction leaves the freezer readable with its
+    /// previous interval.
+    pub fn reconstruct_restore_points(
+        &mut self,
+        slots_per_restore_point: u64,
+    ) -> Result<(), Error> {
+        Self::verify_slots_per_restore_point(slots_per_restore_point)?;
+
+        let old_slots_per_restore_point = self.config.slots_per_restore_point;
+        let split = *self.split.read();
+
+        info!(
+            self.log,
+            "Reconstructing restore points";
+            "split_slot" => split.slot,
+            "old_slots_per_restore_point" => old_slots_per_restore_point,
+            "new_slots_per_restore_point" => slots_per_restore_point
+        );
+
+        // The most recent restore point, and the next state above it with a full `state_roots`
+        // and `block_roots` history, from which blocks are backtracked.
+        let mut state: Option<BeaconState<E>> = None;
+        let mut high_state: Option<BeaconState<E>> = None;
+        let mut restore_point_roots = vec![];
+
+        let mut slot = Slot::new(0);
+        while slot < split.slot {
+            let state_root = if slot % old_slots_per_restore_point == 0 {
+                let state_root =
+                    self.load_restore_point_hash(slot.as_u64() / old_slots_per_restore_point)?;
+                state = Some(self.load_restore_point(&state_root)?);
+                state_root
+            } else {
+                let high_slot = std::cmp::min(
+                    (slot / old_slots_per_restore_point + 1) * old_slots_per_restore_point,
+                    split.slot,
+                );
+                let high = match high_state.take() {
+                    Some(high) if high.slot == high_slot => high,
+                    _ if high_slot == split.slot => {
+                        self.get_state(&split.state_root, Some(split.slot))?.ok_or(
+                            HotColdDBError::MissingSplitState(split.state_root, split.slot),
+                        )?
+                    }
+                    _ => self.load_restore_point_by_index(
+                        (high_slot / old_slots_per_restore_point).as_u64(),
+                    )?,
+                };
+                let low = state
+                    .take()
+                    .ok_or(HotColdDBError::MissingRestorePointHash(0))?;
+
+                let blocks = self.load_blocks_to_replay(
+                    low.slot,
+                    slot,
+                    self.get_high_restore_point_block_root(&high, slot)?,
+                )?;
+                let mut reconstructed =
+                    self.replay_blocks(low, blocks, slot, BlockReplay::Accurate)?;
+
+                let expected = *high
+                    .get_state_root(slot)
+                    .map_err(HotColdDBError::RestorePointBlockHashError)?;
+                let computed = reconstructed.update_tree_hash_cache()?;
+                if computed != expected {
+                    return Err(HotColdDBError::ReconstructedStateRootMismatch {
+                        slot,
+                        expected,
+                        computed,
+                    }
+                    .into());
+                }
+                high_state = Some(high);
+
+                let partial_state = PartialBeaconState::from_state_forgetful(&reconstructed);
+                self.cold_db
+                    .do_atomically(vec![partial_state.as_kv_store_op(computed)])?;
+                state = Some(reconstructed);
+                computed
+            };
+
+            debug!(
+                self.log,
+                "Reconstructed restore point";
+                "slot" => slot,
+                "state_root" => format!("{:?}", state_root)
+            );
+            restore_point_roots.push(state_root);
+            slot += slots_per_restore_point;
+        }
+
+        let mut ops = vec![];
+
+        // Remove the old restore point index and the states which are no longer restore points.
+        let mut old_slot = Slot::new(0);
+        while old_slot < split.slot {
+            let restore_point_index = old_slot.as_u64() / old_slots_per_restore_point;
+            let restore_point_key = Self::restore_point_key(restore_point_index);
+            if old_slot % slots_per_restore_point != 0 {
+                let state_root = self.load_restore_point_hash(restore_point_index)?;
+                ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::BeaconState.into(),
+                    state_root.as_bytes(),
+                )));
+            }
+            ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                DBColumn::BeaconRestorePoint.into(),
+                restore_point_key.as_bytes(),
+            )));
+            old_slot += old_slots_per_restore_point;
+        }
+
+        for (restore_point_index, state_root) in restore_point_roots.iter().enumerate() {
+            self.store_restore_point_hash(restore_point_index as u64, *state_root, &mut ops);
+        }
+
+        self.config.slots_per_restore_point = slots_per_restore_point;
+        self.store_on_disk_config(&mut ops)?;
+
+        info!(
+            self.log,
+            "Reconstructed restore points";
+            "restore_points" => restore_point_roots.len(),
+            "slots_per_restore_point" => slots_per_restore_point
+        );
+
+        Ok(())
+    }
+
     /// Load the split point from disk.
     fn load_split(&self) -> Result<Option<Split>, Error> {
         let key = Hash256::from_slice(SPLIT_DB_KEY.as_bytes());
@@ -1,7 +1,8 @@
 use crate::*;
-use ssz::{Decode, DecodeError, Encode};
+use ssz::{Decode, DecodeError, Encode, SszWriter, BYTES_PER_LENGTH_OFFSET};
 use ssz_derive::{Decode, Encode};
 use std::convert::TryInto;
+use std::io;
 use types::beacon_state::{CloneConfig, CommitteeCache, CACHED_EPOCHS};
 
 pub fn store_full_state<E: EthSpec>(
@@ -11,7 +12,11 @@ pub fn store_full_state<E: EthSpec>(
 ) -> Result<(), Error> {
     let bytes = {
         let _overhead_timer = metrics::start_timer(&metrics::BEACON_STATE_WRITE_OVERHEAD_TIMES);
-        StorageContainer::new(state).as_ssz_bytes()
+        let mut bytes = vec![];
+        StorageContainer::write_state(state, &mut bytes).map_err(|e| Error::DBError {
+            message: format!("Unable to encode state: {:?}", e),
+        })?;
+        bytes
     };
     metrics::inc_counter_by(&metrics::BEACON_STATE_WRITE_BYTES, bytes.len() as i64);
     metrics::inc_counter(&metrics::BEACON_STATE_WRITE_COUNT);
@@ -58,6 +63,17 @@ impl<T: EthSpec> StorageContainer<T> {
             committee_caches: state.committee_caches.to_vec(),
         }
     }
+
+    /// Writes the SSZ encoding of `Self::new(state)` to `writer`, without cloning `state`.
+    pub fn write_state<W: io::Write>(state: &BeaconState<T>, writer: &mut W) -> io::Result<()> {
+        let committee_caches = state.committee_caches.to_vec();
+
+        let mut container = SszWriter::container(writer, 2 * BYTES_PER_LENGTH_OFFSET);
+        container.write_fixed_part(state)?;
+        container.write_fixed_part(&committee_caches)?;
+        container.write_variable_part(state)?;
+        container.write_variable_part(&committee_caches)
+    }
 }
 
 impl<T: EthSpec> TryInto<BeaconState<T>> for StorageContainer<T> {
@@ -79,3 +95,27 @@ impl<T: EthSpec> TryInto<BeaconState<T>> for StorageContainer<T> {
         Ok(state)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{test_utils::TestingBeaconStateBuilder, Keypair, MinimalEthSpec};
+
+    #[test]
+    fn write_state_matches_container_encoding() {
+        let spec = MinimalEthSpec::default_spec();
+        let (mut state, _keypairs) =
+            TestingBeaconStateBuilder::<MinimalEthSpec>::from_single_keypair(
+                4,
+                &Keypair::random(),
+                &spec,
+            )
+            .build();
+        state.build_all_committee_caches(&spec).unwrap();
+
+        let mut written = vec![];
+        StorageContainer::write_state(&state, &mut written).unwrap();
+
+        assert_eq!(written, StorageContainer::new(&state).as_ssz_bytes());
+    }
+}
@@ -178,12 +178,12 @@ impl<'a, T: EthSpec, Hot: ItemStore<T>, Cold: ItemStore<T>> RootsIterator<'a, T,
         store: Arc<HotColdDB<T, Hot, Cold>>,
         block_hash: Hash256,
     ) -> Result<Self, Error> {
-        let block = store
-            .get_block(&block_hash)?
+        let (slot, state_root) = store
+            .get_block_slot_and_state_root(&block_hash)?
             .ok_or_else(|| BeaconStateError::MissingBeaconBlock(block_hash.into()))?;
         let state = store
-            .get_state(&block.state_root(), Some(block.slot()))?
-            .ok_or_else(|| BeaconStateError::MissingBeaconState(block.state_root().into()))?;
+            .get_state(&state_root, Some(slot))?
+            .ok_or_else(|| BeaconStateError::MissingBeaconState(state_root.into()))?;
         Ok(Self::owned(store, state))
     }
 
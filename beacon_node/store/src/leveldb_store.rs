@@ -5,6 +5,7 @@ use leveldb::database::batch::{Batch, Writebatch};
 use leveldb::database::kv::KV;
 use leveldb::database::Database;
 use leveldb::error::Error as LevelDBError;
+use leveldb::iterator::Iterable;
 use leveldb::options::{Options, ReadOptions, WriteOptions};
 use std::marker::PhantomData;
 use std::path::Path;
@@ -30,6 +31,22 @@ impl<E: EthSpec> LevelDB<E> {
         })
     }
 
+    /// Open an existing database at `path`, returning an error if there is none.
+    ///
+    /// leveldb has no read-only mode, so callers must not write to the database.
+    pub fn open_existing(path: &Path) -> Result<Self, Error> {
+        let mut options = Options::new();
+
+        options.create_if_missing = false;
+
+        let db = Database::open(path, options)?;
+
+        Ok(Self {
+            db,
+            _phantom: PhantomData,
+        })
+    }
+
     fn read_options(&self) -> ReadOptions<BytesKey> {
         ReadOptions::new()
     }
@@ -138,6 +155,16 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
         self.db.write(self.write_options(), &leveldb_batch)?;
         Ok(())
     }
+
+    fn for_each_key_value<F>(&self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<(), Error>,
+    {
+        for (key, value) in self.db.iter(self.read_options()) {
+            f(&key.key, &value)?;
+        }
+        Ok(())
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for LevelDB<E> {}
@@ -2,7 +2,7 @@
 //!
 //! Provides the following stores:
 //!
-//! - `HotColdDB`: an on-disk store backed by leveldb. Used in production.
+//! - `HotColdDB`: an on-disk store backed by leveldb or LMDB. Used in production.
 //! - `MemoryStore`: an in-memory store backed by a hash-map. Used for testing.
 //!
 //! Provides a simple API for storing/retrieving all types that sometimes needs type-hints. See
@@ -12,12 +12,15 @@ extern crate lazy_static;
 
 pub mod chunked_iter;
 pub mod chunked_vector;
+mod compression;
 pub mod config;
+mod disk_store;
 pub mod errors;
 mod forwards_iter;
 pub mod hot_cold_store;
 mod impls;
 mod leveldb_store;
+mod lmdb_store;
 mod memory_store;
 mod metrics;
 mod partial_beacon_state;
@@ -26,9 +29,12 @@ pub mod iter;
 
 use std::borrow::Cow;
 
-pub use self::config::StoreConfig;
+pub use self::compression::CompressedStore;
+pub use self::config::{CompressionAlgorithm, DatabaseBackend, StoreConfig};
+pub use self::disk_store::{detect_backend, DiskStore};
 pub use self::hot_cold_store::{BlockReplay, HotColdDB, HotStateSummary, Split};
 pub use self::leveldb_store::LevelDB;
+pub use self::lmdb_store::Lmdb;
 pub use self::memory_store::MemoryStore;
 pub use self::partial_beacon_state::PartialBeaconState;
 pub use errors::Error;
@@ -59,6 +65,13 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
 
     /// Execute either all of the operations in `batch` or none at all, returning an error.
     fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error>;
+
+    /// Call `f` with every raw (column-prefixed) key and value in the database.
+    ///
+    /// Stops at, and returns, the first error returned by `f`.
+    fn for_each_key_value<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<(), Error>;
 }
 
 pub fn get_key_for_col(column: &str, key: &[u8]) -> Vec<u8> {
@@ -152,6 +165,45 @@ pub enum DBColumn {
     DhtEnrs,
 }
 
+impl DBColumn {
+    /// Every column, in the order they are declared.
+    pub const ALL: [DBColumn; 14] = [
+        DBColumn::BeaconMeta,
+        DBColumn::BeaconBlock,
+        DBColumn::BeaconState,
+        DBColumn::BeaconChain,
+        DBColumn::OpPool,
+        DBColumn::Eth1Cache,
+        DBColumn::ForkChoice,
+        DBColumn::BeaconRestorePoint,
+        DBColumn::BeaconStateSummary,
+        DBColumn::BeaconBlockRoots,
+        DBColumn::BeaconStateRoots,
+        DBColumn::BeaconHistoricalRoots,
+        DBColumn::BeaconRandaoMixes,
+        DBColumn::DhtEnrs,
+    ];
+
+    /// Returns the column of a database `key`, which begins with the column's name.
+    pub fn from_key(key: &[u8]) -> Option<DBColumn> {
+        DBColumn::ALL.iter().copied().find(|column| {
+            let name: &str = (*column).into();
+            key.starts_with(name.as_bytes())
+        })
+    }
+}
+
+impl std::str::FromStr for DBColumn {
+    type Err = String;
+
+    /// Parses a column from its name in the database, e.g. `blk`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DBColumn::from_key(s.as_bytes())
+            .filter(|_| s.len() == 3)
+            .ok_or_else(|| format!("Unknown database column \"{}\"", s))
+    }
+}
+
 impl Into<&'static str> for DBColumn {
     /// Returns a `&str` that can be used for keying a key-value data base.
     fn into(self) -> &'static str {
@@ -240,6 +292,47 @@ mod tests {
         assert_eq!(store.get::<StorableThing>(&key).unwrap(), None);
     }
 
+    fn test_batch_impl(store: impl ItemStore<MinimalEthSpec>) {
+        let keep = Hash256::random();
+        let delete = Hash256::random();
+        let item = StorableThing { a: 1, b: 42 };
+
+        store.put(&delete, &item).unwrap();
+        store
+            .do_atomically(vec![
+                item.as_kv_store_op(keep),
+                KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    StorableThing::db_column().into(),
+                    delete.as_bytes(),
+                )),
+                // Deleting an absent key is not an error.
+                KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    StorableThing::db_column().into(),
+                    Hash256::random().as_bytes(),
+                )),
+            ])
+            .unwrap();
+        store.sync().unwrap();
+
+        assert_eq!(store.get(&keep).unwrap(), Some(item));
+        assert_eq!(store.exists::<StorableThing>(&delete).unwrap(), false);
+
+        let mut entries = vec![];
+        store
+            .for_each_key_value(|key, value| {
+                entries.push((key.to_vec(), value.to_vec()));
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(
+            entries,
+            vec![(
+                get_key_for_col(StorableThing::db_column().into(), keep.as_bytes()),
+                StorableThing { a: 1, b: 42 }.as_store_bytes()
+            )]
+        );
+    }
+
     #[test]
     fn simplediskdb() {
         let dir = tempdir().unwrap();
@@ -249,6 +342,14 @@ mod tests {
         test_impl(store);
     }
 
+    #[test]
+    fn lmdb() {
+        let dir = tempdir().unwrap();
+        let store = Lmdb::open(dir.path()).unwrap();
+
+        test_impl(store);
+    }
+
     #[test]
     fn memorydb() {
         let store = MemoryStore::open();
@@ -256,6 +357,32 @@ mod tests {
         test_impl(store);
     }
 
+    #[test]
+    fn batches() {
+        let leveldb_dir = tempdir().unwrap();
+        test_batch_impl(LevelDB::open(leveldb_dir.path()).unwrap());
+
+        let lmdb_dir = tempdir().unwrap();
+        test_batch_impl(Lmdb::open(lmdb_dir.path()).unwrap());
+
+        test_batch_impl(MemoryStore::open());
+    }
+
+    #[test]
+    fn disk_store_backend_mismatch() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("db");
+
+        assert_eq!(detect_backend(&path), None);
+        let store = DiskStore::<MinimalEthSpec>::open(&path, DatabaseBackend::Lmdb).unwrap();
+        assert_eq!(store.backend(), DatabaseBackend::Lmdb);
+        drop(store);
+
+        assert_eq!(detect_backend(&path), Some(DatabaseBackend::Lmdb));
+        assert!(DiskStore::<MinimalEthSpec>::open(&path, DatabaseBackend::LevelDb).is_err());
+        assert!(DiskStore::<MinimalEthSpec>::open(&path, DatabaseBackend::Lmdb).is_ok());
+    }
+
     #[test]
     fn exists() {
         let store = MemoryStore::<MinimalEthSpec>::open();
@@ -0,0 +1,208 @@
+use super::*;
+use crate::metrics;
+use lmdb::{
+    Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, Error as LmdbError,
+    Transaction, WriteFlags,
+};
+use std::fs;
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// The maximum size the database may grow to.
+///
+/// This only reserves address space, the file on disk grows as data is written.
+pub const LMDB_MAP_SIZE: usize = 1 << 40;
+/// The maximum number of concurrent read transactions, one per reading thread.
+const LMDB_MAX_READERS: u32 = 1024;
+/// The name of the data file LMDB creates within the database directory.
+pub const LMDB_DATA_FILENAME: &str = "data.mdb";
+
+/// A wrapped LMDB database.
+///
+/// All columns share a single LMDB database, keyed by `get_key_for_col`, so that the batches
+/// produced by `StoreItem::as_kv_store_op` apply unchanged.
+pub struct Lmdb<E: EthSpec> {
+    env: Environment,
+    db: Database,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Lmdb<E> {
+    /// Open a database at `path`, creating a new database if one does not already exist.
+    ///
+    /// Commits are written without syncing the meta page, so a crash may lose the most recent
+    /// transactions but never leaves the database inconsistent. `sync` makes all prior commits
+    /// durable.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        fs::create_dir_all(path).map_err(|e| Error::DBError {
+            message: format!("Unable to create {:?}: {:?}", path, e),
+        })?;
+
+        let env = Environment::new()
+            .set_flags(EnvironmentFlags::NO_META_SYNC | EnvironmentFlags::NO_TLS)
+            .set_map_size(LMDB_MAP_SIZE)
+            .set_max_readers(LMDB_MAX_READERS)
+            .open(path)?;
+        let db = env.create_db(None, DatabaseFlags::empty())?;
+
+        Ok(Self {
+            env,
+            db,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Open an existing database at `path` in read-only mode, returning an error if there is
+    /// none. All writes to the database fail.
+    pub fn open_read_only(path: &Path) -> Result<Self, Error> {
+        let env = Environment::new()
+            .set_flags(EnvironmentFlags::READ_ONLY | EnvironmentFlags::NO_TLS)
+            .set_map_size(LMDB_MAP_SIZE)
+            .set_max_readers(LMDB_MAX_READERS)
+            .open(path)?;
+        let db = env.open_db(None)?;
+
+        Ok(Self {
+            env,
+            db,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn put_bytes_with_sync(
+        &self,
+        col: &str,
+        key: &[u8],
+        val: &[u8],
+        sync: bool,
+    ) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_WRITE_COUNT);
+        metrics::inc_counter_by(&metrics::DISK_DB_WRITE_BYTES, val.len() as i64);
+        let timer = metrics::start_timer(&metrics::DISK_DB_WRITE_TIMES);
+
+        let mut txn = self.env.begin_rw_txn()?;
+        txn.put(self.db, &column_key, &val, WriteFlags::empty())?;
+        txn.commit()?;
+
+        if sync {
+            self.sync()?;
+        }
+
+        metrics::stop_timer(timer);
+        Ok(())
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for Lmdb<E> {
+    /// Store some `value` in `column`, indexed with `key`.
+    fn put_bytes(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_sync(col, key, val, false)
+    }
+
+    fn put_bytes_sync(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_sync(col, key, val, true)
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        self.env.sync(true).map_err(Into::into)
+    }
+
+    /// Retrieve some bytes in `column` with `key`.
+    fn get_bytes(&self, col: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_READ_COUNT);
+        let timer = metrics::start_timer(&metrics::DISK_DB_READ_TIMES);
+
+        let txn = self.env.begin_ro_txn()?;
+        match txn.get(self.db, &column_key) {
+            Ok(bytes) => {
+                metrics::inc_counter_by(&metrics::DISK_DB_READ_BYTES, bytes.len() as i64);
+                metrics::stop_timer(timer);
+                Ok(Some(bytes.to_vec()))
+            }
+            Err(LmdbError::NotFound) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Return `true` if `key` exists in `column`.
+    fn key_exists(&self, col: &str, key: &[u8]) -> Result<bool, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_EXISTS_COUNT);
+
+        let txn = self.env.begin_ro_txn()?;
+        match txn.get(self.db, &column_key) {
+            Ok(_) => Ok(true),
+            Err(LmdbError::NotFound) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Removes `key` from `column`.
+    fn key_delete(&self, col: &str, key: &[u8]) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_DELETE_COUNT);
+
+        let mut txn = self.env.begin_rw_txn()?;
+        delete_if_present(&mut txn, self.db, &column_key)?;
+        txn.commit().map_err(Into::into)
+    }
+
+    /// Apply all of the operations in `ops_batch` in a single write transaction.
+    ///
+    /// The transaction is aborted when dropped, so none of the operations apply if any fails.
+    fn do_atomically(&self, ops_batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        let mut txn = self.env.begin_rw_txn()?;
+        for op in ops_batch {
+            match op {
+                KeyValueStoreOp::PutKeyValue(key, value) => {
+                    txn.put(self.db, &key, &value, WriteFlags::empty())?;
+                }
+
+                KeyValueStoreOp::DeleteKey(key) => {
+                    delete_if_present(&mut txn, self.db, &key)?;
+                }
+            }
+        }
+        txn.commit().map_err(Into::into)
+    }
+
+    fn for_each_key_value<F>(&self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<(), Error>,
+    {
+        let txn = self.env.begin_ro_txn()?;
+        let mut cursor = txn.open_ro_cursor(self.db)?;
+        for (key, value) in cursor.iter_start() {
+            f(key, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for Lmdb<E> {}
+
+/// Deletes `key`, treating an absent key as success as LevelDB does.
+fn delete_if_present(
+    txn: &mut lmdb::RwTransaction,
+    db: Database,
+    key: &[u8],
+) -> Result<(), LmdbError> {
+    match txn.del(db, &key, None) {
+        Ok(()) | Err(LmdbError::NotFound) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+impl From<LmdbError> for Error {
+    fn from(e: LmdbError) -> Error {
+        Error::DBError {
+            message: format!("{:?}", e),
+        }
+    }
+}
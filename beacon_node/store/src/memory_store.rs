@@ -74,19 +74,31 @@ impl<E: EthSpec> KeyValueStore<E> for MemoryStore<E> {
     }
 
     fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        // Hold the lock for the whole batch so that readers never observe part of it.
+        let mut db = self.db.write();
         for op in batch {
             match op {
                 KeyValueStoreOp::PutKeyValue(key, value) => {
-                    self.db.write().insert(key, value);
+                    db.insert(key, value);
                 }
 
                 KeyValueStoreOp::DeleteKey(hash) => {
-                    self.db.write().remove(&hash);
+                    db.remove(&hash);
                 }
             }
         }
         Ok(())
     }
+
+    fn for_each_key_value<F>(&self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<(), Error>,
+    {
+        for (key, value) in self.db.read().iter() {
+            f(key, value)?;
+        }
+        Ok(())
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for MemoryStore<E> {}
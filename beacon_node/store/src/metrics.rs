@@ -62,6 +62,18 @@ lazy_static! {
         "store_beacon_state_cache_clone_time",
         "Time to load a beacon block from the block cache"
     );
+    pub static ref BEACON_HISTORIC_STATE_CACHE_HIT_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_historic_state_cache_hit_total",
+        "Number of frozen states served from the historic state cache without replaying blocks"
+    );
+    pub static ref BEACON_HISTORIC_STATE_CACHE_BASE_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_historic_state_cache_base_total",
+        "Number of frozen state reconstructions which replayed blocks from a cached state"
+    );
+    pub static ref BEACON_HISTORIC_STATE_CACHE_MISS_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_historic_state_cache_miss_total",
+        "Number of frozen state reconstructions which replayed blocks from a restore point"
+    );
     pub static ref BEACON_STATE_READ_TIMES: Result<Histogram> = try_create_histogram(
         "store_beacon_state_read_seconds",
         "Total time required to read a BeaconState from the database"
@@ -125,6 +137,35 @@ lazy_static! {
         "store_beacon_block_write_bytes_total",
         "Total number of beacon block bytes written to the DB"
     );
+    /*
+     * Compression
+     */
+    pub static ref COMPRESSION_RATIO: Result<HistogramVec> = try_create_histogram_vec_with_buckets(
+        "store_compression_ratio",
+        "Size of each compressed value relative to its uncompressed size, by column",
+        &["column"],
+        linear_buckets(0.1, 0.1, 10)
+    );
+    pub static ref COMPRESSION_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "store_compression_seconds",
+        "Time taken to compress a value, by column",
+        &["column"]
+    );
+    pub static ref DECOMPRESSION_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "store_decompression_seconds",
+        "Time taken to decompress a value, by column",
+        &["column"]
+    );
+    pub static ref COMPRESSION_INPUT_BYTES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "store_compression_input_bytes_total",
+        "Total size of values before compression, by column",
+        &["column"]
+    );
+    pub static ref COMPRESSION_OUTPUT_BYTES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "store_compression_output_bytes_total",
+        "Total size of values after compression, by column",
+        &["column"]
+    );
 }
 
 /// Updates the global metrics registry with store-related information.
@@ -22,6 +22,11 @@ impl<T: EthSpec> WebSocketSender<T> {
         }
     }
 
+    /// Returns `true` if this sender is connected to a running websocket server.
+    pub fn is_enabled(&self) -> bool {
+        self.sender.is_some()
+    }
+
     pub fn send_string(&self, string: String) -> Result<(), String> {
         if let Some(sender) = &self.sender {
             sender
@@ -12,6 +12,7 @@ use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 use zeroize::Zeroize;
 
+pub mod password_vault;
 pub mod validator_definitions;
 
 pub use eth2_keystore;
@@ -0,0 +1,392 @@
+//! Provides an encrypted store for the passwords of voting keystores, as an alternative to storing
+//! them in plain-text in the `--secrets-dir` or `validator_definitions.yml`.
+//!
+//! The vault is a single JSON file which is unlocked with a passphrase. An AES-256-GCM key is
+//! derived from the passphrase using scrypt and each password is encrypted with that key, using
+//! the 0x-prefixed voting public key of its validator as the additional authenticated data. This
+//! prevents an entry from being moved to another validator without detection.
+
+use crate::{create_with_600_perms, read_password, read_password_from_user, PlainText};
+use aes_gcm::aead::{generic_array::GenericArray, Aead, NewAead, Payload};
+use aes_gcm::Aes256Gcm;
+use eth2_keystore::{
+    default_kdf, derive_key,
+    json_keystore::{EmptyString, HexBytes, Kdf, KdfModule},
+    DerivedKey, SALT_SIZE,
+};
+use rand::prelude::*;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use types::PublicKey;
+
+/// The name of the vault file in the secrets directory.
+pub const VAULT_FILENAME: &str = "password_vault.json";
+/// The environment variable which may contain the vault passphrase.
+pub const PASSPHRASE_ENV_VAR: &str = "LIGHTHOUSE_PASSWORD_VAULT_PASSPHRASE";
+/// The prompt displayed when reading the vault passphrase from the user.
+pub const PASSPHRASE_PROMPT: &str = "Enter the password vault passphrase:";
+
+/// The only vault version that is presently supported.
+const VERSION: u32 = 1;
+/// The byte-length of an AES-GCM nonce.
+const NONCE_SIZE: usize = 12;
+/// The additional authenticated data of the entry which is used to check the passphrase.
+const CHECK_AAD: &[u8] = b"lighthouse_password_vault";
+
+#[derive(Debug)]
+pub enum Error {
+    /// There was a filesystem error when reading the vault.
+    UnableToOpenVault(io::Error),
+    /// The vault could not be parsed, it is likely bad JSON.
+    UnableToParseVault(serde_json::Error),
+    /// The vault could not be serialized to JSON.
+    UnableToEncodeVault(serde_json::Error),
+    /// There was a filesystem error when writing the vault.
+    UnableToSaveVault(io::Error),
+    /// Refused to overwrite an existing vault.
+    VaultAlreadyExists(PathBuf),
+    /// The vault has a version that is not supported.
+    UnsupportedVersion(u32),
+    /// The passphrase must not be empty.
+    EmptyPassphrase,
+    /// A key could not be derived from the passphrase (e.g., bad scrypt params).
+    UnableToDeriveKey(eth2_keystore::Error),
+    /// The passphrase does not unlock the vault.
+    InvalidPassphrase,
+    /// A password could not be encrypted.
+    UnableToEncrypt,
+    /// The entry for the given public key could not be decrypted, the vault has been tampered
+    /// with.
+    UnableToDecrypt(String),
+}
+
+/// The JSON representation of an encrypted message.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonEntry {
+    nonce: HexBytes,
+    cipher_text: HexBytes,
+}
+
+/// The JSON representation of the vault.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonVault {
+    version: u32,
+    kdf: KdfModule,
+    /// An empty message, used to check the passphrase before decrypting any passwords.
+    check: JsonEntry,
+    /// Maps a 0x-prefixed voting public key to its encrypted keystore password.
+    passwords: BTreeMap<String, JsonEntry>,
+}
+
+/// An unlocked password vault.
+///
+/// Changes made with `Self::insert` and `Self::remove` are only written to disk by `Self::save`.
+pub struct PasswordVault {
+    path: PathBuf,
+    json: JsonVault,
+    key: DerivedKey,
+}
+
+impl PasswordVault {
+    /// Creates a new, empty vault at `path` which is unlocked by `passphrase`.
+    ///
+    /// ## Errors
+    ///
+    /// - `path` already exists.
+    /// - `passphrase` is empty.
+    /// - The vault cannot be written to `path`.
+    pub fn create<P: AsRef<Path>>(path: P, passphrase: &[u8]) -> Result<Self, Error> {
+        let salt = rand::thread_rng().gen::<[u8; SALT_SIZE]>();
+        Self::create_with_kdf(path, passphrase, default_kdf(salt.to_vec()))
+    }
+
+    fn create_with_kdf<P: AsRef<Path>>(
+        path: P,
+        passphrase: &[u8],
+        kdf: Kdf,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        if path.exists() {
+            return Err(Error::VaultAlreadyExists(path.into()));
+        }
+
+        if passphrase.is_empty() {
+            return Err(Error::EmptyPassphrase);
+        }
+
+        let key = derive_key(passphrase, &kdf).map_err(Error::UnableToDeriveKey)?;
+        let check = encrypt_entry(&key, CHECK_AAD, &[])?;
+
+        let vault = Self {
+            path: path.into(),
+            json: JsonVault {
+                version: VERSION,
+                kdf: KdfModule {
+                    function: kdf.function(),
+                    params: kdf,
+                    message: EmptyString,
+                },
+                check,
+                passwords: BTreeMap::new(),
+            },
+            key,
+        };
+        vault.save()?;
+
+        Ok(vault)
+    }
+
+    /// Opens the existing vault at `path`, unlocking it with `passphrase`.
+    ///
+    /// ## Errors
+    ///
+    /// - The vault cannot be read or parsed.
+    /// - `passphrase` does not unlock the vault.
+    pub fn open<P: AsRef<Path>>(path: P, passphrase: &[u8]) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        let file = File::open(path).map_err(Error::UnableToOpenVault)?;
+        let json: JsonVault = serde_json::from_reader(file).map_err(Error::UnableToParseVault)?;
+
+        if json.version != VERSION {
+            return Err(Error::UnsupportedVersion(json.version));
+        }
+
+        let key = derive_key(passphrase, &json.kdf.params).map_err(Error::UnableToDeriveKey)?;
+        decrypt_entry(&key, CHECK_AAD, &json.check).ok_or(Error::InvalidPassphrase)?;
+
+        Ok(Self {
+            path: path.into(),
+            json,
+            key,
+        })
+    }
+
+    /// Opens the vault at `path` if it exists, otherwise creates it.
+    pub fn open_or_create<P: AsRef<Path>>(path: P, passphrase: &[u8]) -> Result<Self, Error> {
+        if path.as_ref().exists() {
+            Self::open(path, passphrase)
+        } else {
+            Self::create(path, passphrase)
+        }
+    }
+
+    /// Returns the path of the vault file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if the vault contains a password for `pubkey`.
+    pub fn contains(&self, pubkey: &PublicKey) -> bool {
+        self.json.passwords.contains_key(&pubkey.to_hex_string())
+    }
+
+    /// Returns the password for `pubkey`, if any.
+    pub fn get(&self, pubkey: &PublicKey) -> Result<Option<PlainText>, Error> {
+        let pubkey = pubkey.to_hex_string();
+
+        self.json
+            .passwords
+            .get(&pubkey)
+            .map(|entry| {
+                decrypt_entry(&self.key, pubkey.as_bytes(), entry)
+                    .ok_or_else(|| Error::UnableToDecrypt(pubkey.clone()))
+            })
+            .transpose()
+    }
+
+    /// Stores `password` for `pubkey`, replacing any existing password.
+    pub fn insert(&mut self, pubkey: &PublicKey, password: &[u8]) -> Result<(), Error> {
+        let pubkey = pubkey.to_hex_string();
+        let entry = encrypt_entry(&self.key, pubkey.as_bytes(), password)?;
+        self.json.passwords.insert(pubkey, entry);
+        Ok(())
+    }
+
+    /// Removes the password for `pubkey`, returning `true` if it was present.
+    pub fn remove(&mut self, pubkey: &PublicKey) -> bool {
+        self.json
+            .passwords
+            .remove(&pubkey.to_hex_string())
+            .is_some()
+    }
+
+    /// Writes the vault to `self.path()`.
+    ///
+    /// The vault is written to a temporary file which is then renamed over the existing vault, so
+    /// that the vault is never partially written.
+    pub fn save(&self) -> Result<(), Error> {
+        let bytes = serde_json::to_vec_pretty(&self.json).map_err(Error::UnableToEncodeVault)?;
+
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+
+        create_with_600_perms(&temp_path, &bytes).map_err(Error::UnableToSaveVault)?;
+        fs::rename(&temp_path, &self.path).map_err(Error::UnableToSaveVault)
+    }
+}
+
+/// Returns the path of the vault in `secrets_dir`.
+pub fn default_vault_path<P: AsRef<Path>>(secrets_dir: P) -> PathBuf {
+    secrets_dir.as_ref().join(VAULT_FILENAME)
+}
+
+/// Reads the vault passphrase from the first of the following sources that is available:
+///
+/// 1. The file at `passphrase_path`, if supplied.
+/// 2. The `PASSPHRASE_ENV_VAR` environment variable.
+/// 3. The user, via TTY or stdin if `use_stdin == true`.
+pub fn read_vault_passphrase(
+    passphrase_path: Option<&Path>,
+    use_stdin: bool,
+) -> Result<PlainText, String> {
+    if let Some(path) = passphrase_path {
+        return read_password(path)
+            .map_err(|e| format!("Unable to read passphrase file {:?}: {:?}", path, e));
+    }
+
+    if let Some(passphrase) = env::var_os(PASSPHRASE_ENV_VAR) {
+        return passphrase
+            .into_string()
+            .map(|passphrase| PlainText::from(passphrase.into_bytes()))
+            .map_err(|_| format!("{} must be valid UTF-8", PASSPHRASE_ENV_VAR));
+    }
+
+    eprintln!("");
+    eprintln!("{}", PASSPHRASE_PROMPT);
+    read_password_from_user(use_stdin).map(|passphrase| passphrase.as_ref().to_vec().into())
+}
+
+/// Encrypts `msg` with `key`, using a random nonce.
+fn encrypt_entry(key: &DerivedKey, aad: &[u8], msg: &[u8]) -> Result<JsonEntry, Error> {
+    let cipher = Aes256Gcm::new(GenericArray::from_slice(key.as_bytes()));
+    let nonce = rand::thread_rng().gen::<[u8; NONCE_SIZE]>();
+
+    let cipher_text = cipher
+        .encrypt(GenericArray::from_slice(&nonce), Payload { msg, aad })
+        .map_err(|_| Error::UnableToEncrypt)?;
+
+    Ok(JsonEntry {
+        nonce: nonce.to_vec().into(),
+        cipher_text: cipher_text.into(),
+    })
+}
+
+/// Decrypts `entry` with `key`, returning `None` if the entry is malformed or was not encrypted
+/// with `key` and `aad`.
+fn decrypt_entry(key: &DerivedKey, aad: &[u8], entry: &JsonEntry) -> Option<PlainText> {
+    if entry.nonce.len() != NONCE_SIZE {
+        return None;
+    }
+
+    let cipher = Aes256Gcm::new(GenericArray::from_slice(key.as_bytes()));
+    cipher
+        .decrypt(
+            GenericArray::from_slice(entry.nonce.as_bytes()),
+            Payload {
+                msg: entry.cipher_text.as_bytes(),
+                aad,
+            },
+        )
+        .ok()
+        .map(PlainText::from)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use eth2_keystore::{json_keystore::Scrypt, DKLEN};
+    use tempfile::tempdir;
+    use types::Keypair;
+
+    const PASSPHRASE: &[u8] = b"correct horse battery staple";
+
+    /// A cheap KDF, so that the tests run quickly.
+    fn test_kdf() -> Kdf {
+        Kdf::Scrypt(Scrypt {
+            dklen: DKLEN,
+            n: 16,
+            p: 1,
+            r: 8,
+            salt: vec![42; SALT_SIZE].into(),
+        })
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = tempdir().unwrap();
+        let path = default_vault_path(dir.path());
+        let (a, b) = (Keypair::random().pk, Keypair::random().pk);
+
+        let mut vault = PasswordVault::create_with_kdf(&path, PASSPHRASE, test_kdf()).unwrap();
+        vault.insert(&a, b"a password").unwrap();
+        vault.insert(&b, b"b password").unwrap();
+        vault.insert(&b, b"new b password").unwrap();
+        vault.save().unwrap();
+
+        let mut vault = PasswordVault::open(&path, PASSPHRASE).unwrap();
+        assert_eq!(vault.get(&a).unwrap().unwrap().as_bytes(), b"a password");
+        assert_eq!(
+            vault.get(&b).unwrap().unwrap().as_bytes(),
+            b"new b password"
+        );
+
+        assert!(vault.remove(&a));
+        assert!(!vault.remove(&a));
+        vault.save().unwrap();
+
+        let vault = PasswordVault::open(&path, PASSPHRASE).unwrap();
+        assert!(!vault.contains(&a));
+        assert!(vault.get(&a).unwrap().is_none());
+        assert!(vault.contains(&b));
+
+        assert!(
+            matches!(
+                PasswordVault::create(&path, PASSPHRASE),
+                Err(Error::VaultAlreadyExists(_))
+            ),
+            "should not overwrite an existing vault"
+        );
+    }
+
+    #[test]
+    fn invalid_passphrase() {
+        let dir = tempdir().unwrap();
+        let path = default_vault_path(dir.path());
+
+        assert!(matches!(
+            PasswordVault::create_with_kdf(&path, b"", test_kdf()),
+            Err(Error::EmptyPassphrase)
+        ));
+
+        PasswordVault::create_with_kdf(&path, PASSPHRASE, test_kdf()).unwrap();
+
+        assert!(matches!(
+            PasswordVault::open(&path, b"incorrect horse battery staple"),
+            Err(Error::InvalidPassphrase)
+        ));
+    }
+
+    #[test]
+    fn entries_are_bound_to_pubkeys() {
+        let dir = tempdir().unwrap();
+        let path = default_vault_path(dir.path());
+        let (a, b) = (Keypair::random().pk, Keypair::random().pk);
+
+        let mut vault = PasswordVault::create_with_kdf(&path, PASSPHRASE, test_kdf()).unwrap();
+        vault.insert(&a, b"a password").unwrap();
+
+        // Move the entry for `a` to `b`.
+        let entry = vault.json.passwords[&a.to_hex_string()].clone();
+        vault.json.passwords.insert(b.to_hex_string(), entry);
+
+        assert!(matches!(vault.get(&b), Err(Error::UnableToDecrypt(_))));
+    }
+}
@@ -12,7 +12,7 @@ use eth2_config::{include_altona_file, include_medalla_file, include_spadina_fil
 use enr::{CombinedKey, Enr};
 use ssz::{Decode, Encode};
 use std::fs::{create_dir_all, File};
-use std::io::{Read, Write};
+use std::io::{BufWriter, Read, Write};
 use std::path::PathBuf;
 use types::{Address, BeaconState, EthSpec, YamlConfig};
 
@@ -170,8 +170,11 @@ impl<E: EthSpec> Eth2TestnetConfig<E> {
 
             File::create(&file)
                 .map_err(|e| format!("Unable to create {:?}: {:?}", file, e))
-                .and_then(|mut file| {
-                    file.write_all(&genesis_state.as_ssz_bytes())
+                .and_then(|f| {
+                    let mut writer = BufWriter::new(f);
+                    genesis_state
+                        .ssz_write(&mut writer)
+                        .and_then(|_| writer.flush())
                         .map_err(|e| format!("Unable to write {:?}: {:?}", file, e))
                 })?;
         }
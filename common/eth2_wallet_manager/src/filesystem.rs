@@ -1,11 +1,14 @@
 //! Provides some CRUD functions for wallets on the filesystem.
 
 use eth2_wallet::Error as WalletError;
-use eth2_wallet::{Uuid, Wallet};
-use std::fs::{copy as copy_file, remove_file, OpenOptions};
+use eth2_wallet::{EncryptedMnemonic, Uuid, Wallet};
+use std::fs::{copy as copy_file, remove_file, rename, OpenOptions};
 use std::io;
 use std::path::{Path, PathBuf};
 
+/// The file in a wallet directory that contains the `EncryptedMnemonic` of the wallet.
+pub const MNEMONIC_FILE: &str = "mnemonic.json";
+
 #[derive(Debug)]
 pub enum Error {
     WalletAlreadyExists(PathBuf),
@@ -18,6 +21,10 @@ pub enum Error {
     UnableToReadWallet(io::Error),
     JsonWriteError(WalletError),
     JsonReadError(WalletError),
+    UnableToCreateMnemonic(io::Error),
+    UnableToReadMnemonic(io::Error),
+    UnableToReplaceMnemonic(io::Error),
+    UnableToRemoveMnemonic(io::Error),
 }
 
 /// Read a wallet with the given `uuid` from the `wallet_dir`.
@@ -88,6 +95,68 @@ pub fn create<P: AsRef<Path>>(wallet_dir: P, wallet: &Wallet) -> Result<(), Erro
     }
 }
 
+/// Read the `EncryptedMnemonic` from the `wallet_dir`, returning `None` if the wallet does not have
+/// a stored mnemonic (e.g., it was created by an earlier version of Lighthouse).
+pub fn read_mnemonic<P: AsRef<Path>>(wallet_dir: P) -> Result<Option<EncryptedMnemonic>, Error> {
+    let path = wallet_dir.as_ref().join(MNEMONIC_FILE);
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    OpenOptions::new()
+        .read(true)
+        .create(false)
+        .open(path)
+        .map_err(Error::UnableToReadMnemonic)
+        .and_then(|f| EncryptedMnemonic::from_json_reader(f).map_err(Error::JsonReadError))
+        .map(Some)
+}
+
+/// Writes the `mnemonic` into the `wallet_dir`, returning an error if it already exists.
+pub fn create_mnemonic<P: AsRef<Path>>(
+    wallet_dir: P,
+    mnemonic: &EncryptedMnemonic,
+) -> Result<(), Error> {
+    write_mnemonic(wallet_dir.as_ref().join(MNEMONIC_FILE), mnemonic)
+}
+
+/// Replaces the mnemonic in the `wallet_dir` with `mnemonic`.
+///
+/// The new mnemonic is written to a temporary file which is then renamed over the existing file,
+/// so the mnemonic is never lost if the write fails.
+pub fn update_mnemonic<P: AsRef<Path>>(
+    wallet_dir: P,
+    mnemonic: &EncryptedMnemonic,
+) -> Result<(), Error> {
+    let path = wallet_dir.as_ref().join(MNEMONIC_FILE);
+    let temp_path = wallet_dir.as_ref().join(format!("{}.tmp", MNEMONIC_FILE));
+
+    write_mnemonic(&temp_path, mnemonic)?;
+
+    rename(temp_path, path).map_err(Error::UnableToReplaceMnemonic)
+}
+
+/// Removes the mnemonic from the `wallet_dir`, if it exists.
+pub fn remove_mnemonic<P: AsRef<Path>>(wallet_dir: P) -> Result<(), Error> {
+    let path = wallet_dir.as_ref().join(MNEMONIC_FILE);
+
+    if path.exists() {
+        remove_file(path).map_err(Error::UnableToRemoveMnemonic)
+    } else {
+        Ok(())
+    }
+}
+
+fn write_mnemonic<P: AsRef<Path>>(path: P, mnemonic: &EncryptedMnemonic) -> Result<(), Error> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(Error::UnableToCreateMnemonic)
+        .and_then(|f| mnemonic.to_json_writer(f).map_err(Error::JsonWriteError))
+}
+
 fn wallet_json_backup_path<P: AsRef<Path>>(wallet_dir: P, uuid: &Uuid) -> PathBuf {
     wallet_dir.as_ref().join(format!("{}.backup", uuid))
 }
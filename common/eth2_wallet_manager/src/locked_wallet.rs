@@ -1,9 +1,9 @@
 use crate::{
-    filesystem::{read, update},
+    filesystem::{read, read_mnemonic, remove_mnemonic, update, update_mnemonic},
     Error,
 };
-use eth2_wallet::{Uuid, ValidatorKeystores, Wallet};
-use std::fs::{remove_file, OpenOptions};
+use eth2_wallet::{bip39::Mnemonic, Uuid, ValidatorKeystores, Wallet};
+use std::fs::{remove_dir, remove_file, OpenOptions};
 use std::path::{Path, PathBuf};
 
 pub const LOCK_FILE: &str = ".lock";
@@ -16,6 +16,7 @@ pub const LOCK_FILE: &str = ".lock";
 /// <wallet_dir>
 /// └── .lock
 /// └── <wallet-json>
+/// └── mnemonic.json (optional)
 /// ```
 ///
 /// Provides the following functionality:
@@ -98,6 +99,75 @@ impl LockedWallet {
 
         Ok(keystores)
     }
+
+    /// Returns the mnemonic of the wallet, decrypted with `password`.
+    ///
+    /// Returns `Ok(None)` if the mnemonic of the wallet was not stored (e.g., the wallet was
+    /// created by an earlier version of Lighthouse).
+    ///
+    /// ## Errors
+    ///
+    /// - If `password` is incorrect.
+    /// - If the stored mnemonic does not belong to the wallet.
+    /// - If there is a file-system or parsing error.
+    pub fn decrypt_mnemonic(&self, password: &[u8]) -> Result<Option<Mnemonic>, Error> {
+        read_mnemonic(&self.wallet_dir)?
+            .map(|mnemonic| self.wallet.decrypt_mnemonic(&mnemonic, password))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Calls `Wallet::change_password` on the underlying `wallet`, replacing it with the
+    /// re-encrypted wallet. The stored mnemonic, if any, is also re-encrypted.
+    ///
+    /// Ensures that the wallet JSON file is updated.
+    ///
+    /// ## Errors
+    ///
+    /// - If `old_password` is incorrect or `new_password` is empty.
+    /// - If there is a file-system error.
+    pub fn change_password(
+        &mut self,
+        old_password: &[u8],
+        new_password: &[u8],
+    ) -> Result<(), Error> {
+        let wallet = self.wallet.change_password(old_password, new_password)?;
+        let mnemonic = read_mnemonic(&self.wallet_dir)?
+            .map(|mnemonic| mnemonic.change_password(old_password, new_password))
+            .transpose()?;
+
+        update(&self.wallet_dir, &wallet)?;
+        if let Some(mnemonic) = mnemonic {
+            update_mnemonic(&self.wallet_dir, &mnemonic)?;
+        }
+
+        self.wallet = wallet;
+
+        Ok(())
+    }
+
+    /// Deletes the wallet JSON file, the stored mnemonic and then the wallet directory, consuming
+    /// `self`.
+    ///
+    /// The wallet directory is only removed if it is empty once the lockfile has been removed, so
+    /// unexpected files (e.g., a backup left by a failed update) are never deleted.
+    ///
+    /// ## Errors
+    ///
+    /// If there is a file-system error.
+    pub fn delete(self) -> Result<(), Error> {
+        let wallet_dir = self.wallet_dir.clone();
+
+        remove_mnemonic(&wallet_dir)?;
+        remove_file(wallet_dir.join(format!("{}", self.wallet.uuid())))?;
+
+        // Remove the lockfile.
+        drop(self);
+
+        remove_dir(&wallet_dir)?;
+
+        Ok(())
+    }
 }
 
 impl Drop for LockedWallet {
@@ -1,5 +1,5 @@
 use crate::{
-    filesystem::{create, Error as FilesystemError},
+    filesystem::{create, create_mnemonic, Error as FilesystemError},
     LockedWallet,
 };
 use eth2_wallet::{bip39::Mnemonic, Error as WalletError, Uuid, Wallet, WalletBuilder};
@@ -58,6 +58,9 @@ pub enum WalletType {
 /// Each wallet is stored in a directory with the name of the wallet UUID. Inside each directory a
 /// EIP-2386 JSON wallet is also stored using the UUID as the filename.
 ///
+/// Wallets created from a mnemonic also store an `EncryptedMnemonic` in `mnemonic.json`, so the
+/// mnemonic can be displayed again.
+///
 /// In each wallet directory an optional `.lock` exists to prevent concurrent reads and writes from
 /// the same wallet.
 ///
@@ -67,9 +70,11 @@ pub enum WalletType {
 /// wallets
 /// ├── 35c07717-c6f3-45e8-976f-ef5d267e86c9
 /// │   └── 35c07717-c6f3-45e8-976f-ef5d267e86c9
+/// │   └── mnemonic.json
 /// └── 747ad9dc-e1a1-4804-ada4-0dc124e46c49
 ///     └── .lock
 ///     └── 747ad9dc-e1a1-4804-ada4-0dc124e46c49
+///     └── mnemonic.json
 /// ```
 pub struct WalletManager {
     dir: PathBuf,
@@ -105,7 +110,7 @@ impl WalletManager {
     }
 
     /// Creates a new wallet with the given `name` in `self.dir` with the given `mnemonic` as a
-    /// seed, encrypted with `password`.
+    /// seed, encrypted with `password`. The `mnemonic` is also stored, encrypted with `password`.
     ///
     /// ## Errors
     ///
@@ -123,6 +128,7 @@ impl WalletManager {
         }
 
         let wallet = WalletBuilder::from_mnemonic(mnemonic, password, name)?.build()?;
+        let encrypted_mnemonic = wallet.encrypt_mnemonic(mnemonic, password)?;
         let uuid = *wallet.uuid();
 
         let wallet_dir = self.dir.join(format!("{}", uuid));
@@ -134,6 +140,7 @@ impl WalletManager {
         create_dir_all(&wallet_dir)?;
 
         create(&wallet_dir, &wallet)?;
+        create_mnemonic(&wallet_dir, &encrypted_mnemonic)?;
 
         drop(wallet);
 
@@ -225,6 +232,15 @@ mod tests {
             lockfile_path(&mgr.dir, wallet.wallet().uuid()).exists(),
             "should have created lockfile"
         );
+        assert_eq!(
+            wallet
+                .decrypt_mnemonic(WALLET_PASSWORD)
+                .expect("should decrypt mnemonic")
+                .expect("should have stored mnemonic")
+                .phrase(),
+            MNEMONIC,
+            "should have stored the mnemonic"
+        );
 
         wallet
     }
@@ -377,4 +393,71 @@ mod tests {
         LockedWallet::open(&base_dir, &uuid_b)
             .expect("should open wallet a after previous instance is dropped");
     }
+
+    #[test]
+    fn change_password() {
+        let dir = tempdir().unwrap();
+        let base_dir = dir.path();
+        let mgr = WalletManager::open(base_dir).unwrap();
+        let new_password: &[u8] = &[44; 44];
+
+        let mut wallet = create_wallet(&mgr, 0);
+        let uuid = *wallet.wallet().uuid();
+
+        wallet
+            .change_password(WALLET_PASSWORD, new_password)
+            .expect("should change password");
+        wallet
+            .wallet()
+            .decrypt_seed(new_password)
+            .expect("in-memory wallet should use new password");
+
+        drop(wallet);
+
+        let raw = load_wallet_raw(&base_dir, &uuid);
+        raw.decrypt_seed(new_password)
+            .expect("persisted wallet should use new password");
+        raw.decrypt_seed(WALLET_PASSWORD)
+            .expect_err("persisted wallet should not use old password");
+
+        let wallet = LockedWallet::open(&base_dir, &uuid).unwrap();
+        assert_eq!(
+            wallet
+                .decrypt_mnemonic(new_password)
+                .expect("persisted mnemonic should use new password")
+                .expect("should have stored mnemonic")
+                .phrase(),
+            MNEMONIC
+        );
+        wallet
+            .decrypt_mnemonic(WALLET_PASSWORD)
+            .expect_err("persisted mnemonic should not use old password");
+    }
+
+    #[test]
+    fn delete() {
+        let dir = tempdir().unwrap();
+        let base_dir = dir.path();
+        let mgr = WalletManager::open(base_dir).unwrap();
+
+        let uuid_a = *create_wallet(&mgr, 0).wallet().uuid();
+        let uuid_b = *create_wallet(&mgr, 1).wallet().uuid();
+
+        mgr.wallet_by_name("0")
+            .unwrap()
+            .delete()
+            .expect("should delete wallet");
+
+        assert!(
+            !wallet_dir_path(&base_dir, &uuid_a).exists(),
+            "wallet dir should be removed"
+        );
+        assert!(
+            wallet_dir_path(&base_dir, &uuid_b).exists(),
+            "other wallet dir should remain"
+        );
+
+        let wallets = mgr.wallets().unwrap().into_iter().collect::<Vec<_>>();
+        assert_eq!(wallets, vec![("1".to_string(), uuid_b)]);
+    }
 }
@@ -57,8 +57,8 @@
 use prometheus::{HistogramOpts, HistogramTimer, Opts};
 
 pub use prometheus::{
-    Encoder, Gauge, GaugeVec, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge,
-    IntGaugeVec, Result, TextEncoder,
+    exponential_buckets, linear_buckets, Encoder, Gauge, GaugeVec, Histogram, HistogramVec,
+    IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Result, TextEncoder,
 };
 
 /// Collect all the metrics for reporting.
@@ -102,6 +102,19 @@ pub fn try_create_histogram(name: &str, help: &str) -> Result<Histogram> {
     Ok(histogram)
 }
 
+/// Attempts to create a `Histogram` with the given `buckets`, returning `Err` if the registry does
+/// not accept the counter (potentially due to naming conflict) or `buckets` is `Err`.
+pub fn try_create_histogram_with_buckets(
+    name: &str,
+    help: &str,
+    buckets: Result<Vec<f64>>,
+) -> Result<Histogram> {
+    let opts = HistogramOpts::new(name, help).buckets(buckets?);
+    let histogram = Histogram::with_opts(opts)?;
+    prometheus::register(Box::new(histogram.clone()))?;
+    Ok(histogram)
+}
+
 /// Attempts to create a `HistogramVec`, returning `Err` if the registry does not accept the counter
 /// (potentially due to naming conflict).
 pub fn try_create_histogram_vec(
@@ -115,6 +128,20 @@ pub fn try_create_histogram_vec(
     Ok(histogram_vec)
 }
 
+/// Attempts to create a `HistogramVec` with the given `buckets`, returning `Err` if the registry
+/// does not accept the counter (potentially due to naming conflict) or `buckets` is `Err`.
+pub fn try_create_histogram_vec_with_buckets(
+    name: &str,
+    help: &str,
+    label_names: &[&str],
+    buckets: Result<Vec<f64>>,
+) -> Result<HistogramVec> {
+    let opts = HistogramOpts::new(name, help).buckets(buckets?);
+    let histogram_vec = HistogramVec::new(opts, label_names)?;
+    prometheus::register(Box::new(histogram_vec.clone()))?;
+    Ok(histogram_vec)
+}
+
 /// Attempts to create a `IntGaugeVec`, returning `Err` if the registry does not accept the gauge
 /// (potentially due to naming conflict).
 pub fn try_create_int_gauge_vec(
@@ -198,6 +225,13 @@ pub fn inc_counter_vec(int_counter_vec: &Result<IntCounterVec>, name: &[&str]) {
     }
 }
 
+/// Increments the `int_counter_vec` with the given `name` by `value`.
+pub fn inc_counter_vec_by(int_counter_vec: &Result<IntCounterVec>, name: &[&str], value: i64) {
+    if let Some(counter) = get_int_counter(int_counter_vec, name) {
+        counter.inc_by(value)
+    }
+}
+
 /// If `histogram_vec.is_ok()`, returns a histogram with the given `name`.
 pub fn get_histogram(histogram_vec: &Result<HistogramVec>, name: &[&str]) -> Option<Histogram> {
     if let Ok(histogram_vec) = histogram_vec {
@@ -207,6 +241,13 @@ pub fn get_histogram(histogram_vec: &Result<HistogramVec>, name: &[&str]) -> Opt
     }
 }
 
+/// Sets the value of the histogram in `vec` with the given `name` manually.
+pub fn observe_vec(vec: &Result<HistogramVec>, name: &[&str], value: f64) {
+    if let Some(h) = get_histogram(vec, name) {
+        h.observe(value)
+    }
+}
+
 /// Starts a timer on `vec` with the given `name`.
 pub fn start_timer_vec(vec: &Result<HistogramVec>, name: &[&str]) -> Option<HistogramTimer> {
     get_histogram(vec, name).map(|h| h.start_timer())
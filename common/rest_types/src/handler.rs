@@ -1,10 +1,21 @@
 use crate::{ApiError, ApiResult};
 use environment::TaskExecutor;
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::SinkExt;
 use hyper::header;
 use hyper::{Body, Request, Response, StatusCode};
 use serde::Deserialize;
 use serde::Serialize;
 use ssz::Encode;
+use std::io::{self, Write};
+
+/// The size of the chunks in which a streamed response body is sent.
+const STREAM_CHUNK_BYTES: usize = 64 * 1024;
+
+/// The number of chunks of a streamed response body that may be buffered before the encoder
+/// waits for them to be sent.
+const STREAM_CHANNEL_CAPACITY: usize = 4;
 
 /// Defines the encoding for the API.
 #[derive(Clone, Serialize, Deserialize, Copy)]
@@ -89,6 +100,7 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
         Ok(HandledRequest {
             value,
             encoding: self.encoding,
+            executor: self.executor,
         })
     }
 
@@ -109,6 +121,7 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
         Ok(HandledRequest {
             value,
             encoding: self.encoding,
+            executor: self.executor,
         })
     }
 
@@ -141,6 +154,7 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
         Ok(HandledRequest {
             value,
             encoding: self.encoding,
+            executor: self.executor,
         })
     }
 
@@ -182,6 +196,7 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
 pub struct HandledRequest<V> {
     encoding: ApiEncodingFormat,
     value: V,
+    executor: TaskExecutor,
 }
 
 impl HandledRequest<String> {
@@ -209,6 +224,82 @@ impl<V: Serialize + Encode> HandledRequest<V> {
     }
 }
 
+impl<V: Serialize + Encode + Send + 'static> HandledRequest<V> {
+    /// Suitable for large items which implement `serde` and `ssz`.
+    ///
+    /// The SSZ encoding is written to the response body as it is produced, on the blocking
+    /// executor, rather than being collected in memory first.
+    pub fn streamed_encodings(self) -> ApiResult {
+        match self.encoding {
+            ApiEncodingFormat::SSZ => {
+                let content_length = self.value.ssz_bytes_len();
+                let (sender, receiver) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+                let value = self.value;
+
+                self.executor.spawn_blocking(
+                    move || {
+                        let mut writer = BodyWriter::new(sender);
+                        if let Err(e) = value.ssz_write(&mut writer).and_then(|_| writer.flush()) {
+                            // Abort the response, unless it was the client that went away.
+                            if e.kind() != io::ErrorKind::BrokenPipe {
+                                let _ = block_on(writer.sender.send(Err(e)));
+                            }
+                        }
+                    },
+                    "http_ssz_stream",
+                );
+
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header("content-type", "application/ssz")
+                    .header("content-length", content_length)
+                    .body(Body::wrap_stream(receiver))
+                    .map_err(|e| {
+                        ApiError::ServerError(format!("Failed to build response: {:?}", e))
+                    })
+            }
+            _ => self.serde_encodings(),
+        }
+    }
+}
+
+/// Sends the bytes written to it to a response body, in chunks of `STREAM_CHUNK_BYTES`.
+///
+/// Writes block whilst the channel to the body is full.
+struct BodyWriter {
+    sender: mpsc::Sender<Result<Vec<u8>, io::Error>>,
+    buf: Vec<u8>,
+}
+
+impl BodyWriter {
+    fn new(sender: mpsc::Sender<Result<Vec<u8>, io::Error>>) -> Self {
+        Self {
+            sender,
+            buf: Vec::with_capacity(STREAM_CHUNK_BYTES),
+        }
+    }
+}
+
+impl Write for BodyWriter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(bytes);
+        if self.buf.len() >= STREAM_CHUNK_BYTES {
+            self.flush()?;
+        }
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+
+        let chunk = std::mem::replace(&mut self.buf, Vec::with_capacity(STREAM_CHUNK_BYTES));
+        block_on(self.sender.send(Ok(chunk)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "Response body was dropped"))
+    }
+}
+
 impl<V: Serialize> HandledRequest<V> {
     /// Suitable for items which only implement `serde`.
     pub fn serde_encodings(self) -> ApiResult {
@@ -1,5 +1,5 @@
 use crate::{Error as DirError, ValidatorDir};
-use bls::get_withdrawal_credentials;
+use bls::{get_eth1_withdrawal_credentials, get_withdrawal_credentials};
 use deposit_contract::{encode_eth1_tx_data, Error as DepositError};
 use eth2_keystore::{Error as KeystoreError, Keystore, KeystoreBuilder, PlainText};
 use rand::{distributions::Alphanumeric, Rng};
@@ -7,7 +7,7 @@ use std::fs::{create_dir_all, File, OpenOptions};
 use std::io::{self, Write};
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
-use types::{ChainSpec, DepositData, Hash256, Keypair, Signature};
+use types::{Address, ChainSpec, DepositData, Hash256, Keypair, Signature};
 
 /// The `Alphanumeric` crate only generates a-z, A-Z, 0-9, therefore it has a range of 62
 /// characters.
@@ -55,6 +55,7 @@ pub struct Builder<'a> {
     pub(crate) voting_keystore: Option<(Keystore, PlainText)>,
     pub(crate) withdrawal_keystore: Option<(Keystore, PlainText)>,
     store_withdrawal_keystore: bool,
+    eth1_withdrawal_address: Option<Address>,
     deposit_info: Option<(u64, &'a ChainSpec)>,
 }
 
@@ -67,6 +68,7 @@ impl<'a> Builder<'a> {
             voting_keystore: None,
             withdrawal_keystore: None,
             store_withdrawal_keystore: true,
+            eth1_withdrawal_address: None,
             deposit_info: None,
         }
     }
@@ -109,6 +111,17 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Use eth1 address (`0x01`) withdrawal credentials for `address` in the deposit created by
+    /// `Self::create_eth1_tx_data`, instead of BLS (`0x00`) credentials from the withdrawal
+    /// keystore.
+    ///
+    /// A withdrawal keystore is not required to create the deposit, however one will still be
+    /// stored if it is supplied and `Self::store_withdrawal_keystore(true)`.
+    pub fn eth1_withdrawal_address(mut self, address: Address) -> Self {
+        self.eth1_withdrawal_address = Some(address);
+        self
+    }
+
     /// If `should_store == true`, the validator keystore will be saved in the `ValidatorDir` (and
     /// the password to it stored in the `password_dir`). If `should_store == false`, the
     /// withdrawal keystore will be dropped after `Self::build`.
@@ -144,27 +157,43 @@ impl<'a> Builder<'a> {
         }
 
         // The withdrawal keystore must be initialized in order to store it or create an eth1
-        // deposit.
-        if (self.store_withdrawal_keystore || self.deposit_info.is_some())
-            && self.withdrawal_keystore.is_none()
-        {
+        // deposit with BLS withdrawal credentials.
+        let requires_withdrawal_keystore = self.store_withdrawal_keystore
+            || (self.deposit_info.is_some() && self.eth1_withdrawal_address.is_none());
+        if requires_withdrawal_keystore && self.withdrawal_keystore.is_none() {
             return Err(Error::UninitializedWithdrawalKeystore);
         };
 
-        if let Some((withdrawal_keystore, withdrawal_password)) = self.withdrawal_keystore {
+        // Attempt to decrypt the withdrawal keypair.
+        let withdrawal_keypair = match &self.withdrawal_keystore {
+            Some((withdrawal_keystore, withdrawal_password)) => {
+                Some(withdrawal_keystore.decrypt_keypair(withdrawal_password.as_bytes())?)
+            }
+            None => None,
+        };
+
+        if withdrawal_keypair.is_some() || self.deposit_info.is_some() {
             // Attempt to decrypt the voting keypair.
             let voting_keypair = voting_keystore.decrypt_keypair(voting_password.as_bytes())?;
 
-            // Attempt to decrypt the withdrawal keypair.
-            let withdrawal_keypair =
-                withdrawal_keystore.decrypt_keypair(withdrawal_password.as_bytes())?;
-
             // If a deposit amount was specified, create a deposit.
             if let Some((amount, spec)) = self.deposit_info {
-                let withdrawal_credentials = Hash256::from_slice(&get_withdrawal_credentials(
-                    &withdrawal_keypair.pk,
-                    spec.bls_withdrawal_prefix_byte,
-                ));
+                let withdrawal_credentials =
+                    match (self.eth1_withdrawal_address, &withdrawal_keypair) {
+                        (Some(address), _) => {
+                            Hash256::from_slice(&get_eth1_withdrawal_credentials(
+                                address.as_fixed_bytes(),
+                                spec.eth1_address_withdrawal_prefix_byte,
+                            ))
+                        }
+                        (None, Some(withdrawal_keypair)) => {
+                            Hash256::from_slice(&get_withdrawal_credentials(
+                                &withdrawal_keypair.pk,
+                                spec.bls_withdrawal_prefix_byte,
+                            ))
+                        }
+                        (None, None) => return Err(Error::UninitializedWithdrawalKeystore),
+                    };
 
                 let mut deposit_data = DepositData {
                     pubkey: voting_keypair.pk.clone().into(),
@@ -214,19 +243,23 @@ impl<'a> Builder<'a> {
                         .map_err(Error::UnableToSaveDepositAmount)?
                 }
             }
+        }
 
-            // Only the withdrawal keystore if explicitly required.
-            if self.store_withdrawal_keystore {
-                // Write the withdrawal password to file.
-                write_password_to_file(
-                    self.password_dir
-                        .join(withdrawal_keypair.pk.to_hex_string()),
-                    withdrawal_password.as_bytes(),
-                )?;
-
-                // Write the withdrawal keystore to file.
-                write_keystore_to_file(dir.join(WITHDRAWAL_KEYSTORE_FILE), &withdrawal_keystore)?;
-            }
+        // Only the withdrawal keystore if explicitly required.
+        if let (true, Some((withdrawal_keystore, withdrawal_password)), Some(withdrawal_keypair)) = (
+            self.store_withdrawal_keystore,
+            self.withdrawal_keystore,
+            withdrawal_keypair,
+        ) {
+            // Write the withdrawal password to file.
+            write_password_to_file(
+                self.password_dir
+                    .join(withdrawal_keypair.pk.to_hex_string()),
+                withdrawal_password.as_bytes(),
+            )?;
+
+            // Write the withdrawal keystore to file.
+            write_keystore_to_file(dir.join(WITHDRAWAL_KEYSTORE_FILE), &withdrawal_keystore)?;
         }
 
         // Write the voting password to file.
@@ -0,0 +1,317 @@
+//! Provides reading and writing of deposits in the `deposit_data-*.json` format, as produced by
+//! the `eth2.0-deposit-cli` and consumed by the staking launchpad.
+
+use serde_derive::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use tree_hash::TreeHash;
+use types::{ChainSpec, DepositData, Domain, Hash256, PublicKeyBytes, SignatureBytes, SignedRoot};
+
+#[derive(Debug)]
+pub enum Error {
+    UnableToWriteJson(serde_json::Error),
+    UnableToReadJson(serde_json::Error),
+    /// The `deposit_message_root` does not match the root computed from the other fields.
+    DepositMessageRootMismatch {
+        expected: Hash256,
+        found: Hash256,
+    },
+    /// The `deposit_data_root` does not match the root computed from the other fields.
+    DepositDataRootMismatch {
+        expected: Hash256,
+        found: Hash256,
+    },
+    /// The `fork_version` of the deposit is not the genesis fork version of the network.
+    ForkVersionMismatch {
+        expected: [u8; 4],
+        found: [u8; 4],
+    },
+    /// The withdrawal credentials have neither a BLS nor a well-formed eth1 address prefix.
+    InvalidWithdrawalCredentials(Hash256),
+    InvalidPublicKey(bls::Error),
+    InvalidSignatureBytes(bls::Error),
+    /// The signature is not valid for the `fork_version` of the deposit.
+    InvalidSignature,
+}
+
+/// A single deposit, as found in the JSON array of a `deposit_data-*.json` file.
+///
+/// All byte values are hex-encoded without a `0x` prefix.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DepositDataJson {
+    #[serde(with = "unprefixed_hex")]
+    pub pubkey: PublicKeyBytes,
+    #[serde(with = "unprefixed_hex")]
+    pub withdrawal_credentials: Hash256,
+    pub amount: u64,
+    #[serde(with = "unprefixed_hex")]
+    pub signature: SignatureBytes,
+    #[serde(with = "unprefixed_hex")]
+    pub deposit_message_root: Hash256,
+    #[serde(with = "unprefixed_hex")]
+    pub deposit_data_root: Hash256,
+    #[serde(with = "unprefixed_hex")]
+    pub fork_version: [u8; 4],
+}
+
+impl DepositDataJson {
+    /// Instantiate from a signed `deposit_data`, for the genesis fork version of `spec`.
+    pub fn new(deposit_data: &DepositData, spec: &ChainSpec) -> Self {
+        Self {
+            pubkey: deposit_data.pubkey.clone(),
+            withdrawal_credentials: deposit_data.withdrawal_credentials,
+            amount: deposit_data.amount,
+            signature: deposit_data.signature.clone(),
+            deposit_message_root: deposit_data.as_deposit_message().tree_hash_root(),
+            deposit_data_root: deposit_data.tree_hash_root(),
+            fork_version: spec.genesis_fork_version,
+        }
+    }
+
+    /// Returns the `DepositData` that would be submitted to the deposit contract.
+    pub fn deposit_data(&self) -> DepositData {
+        DepositData {
+            pubkey: self.pubkey.clone(),
+            withdrawal_credentials: self.withdrawal_credentials,
+            amount: self.amount,
+            signature: self.signature.clone(),
+        }
+    }
+
+    /// Checks that `self` is a deposit for the network of `spec`: its `fork_version` must be
+    /// `spec.genesis_fork_version`, the roots must match its other fields, the withdrawal
+    /// credentials must be well-formed and the signature must be valid for the fork version.
+    pub fn verify(&self, spec: &ChainSpec) -> Result<(), Error> {
+        if self.fork_version != spec.genesis_fork_version {
+            return Err(Error::ForkVersionMismatch {
+                expected: spec.genesis_fork_version,
+                found: self.fork_version,
+            });
+        }
+
+        let deposit_data = self.deposit_data();
+        let deposit_message = deposit_data.as_deposit_message();
+
+        let deposit_message_root = deposit_message.tree_hash_root();
+        if deposit_message_root != self.deposit_message_root {
+            return Err(Error::DepositMessageRootMismatch {
+                expected: deposit_message_root,
+                found: self.deposit_message_root,
+            });
+        }
+
+        let deposit_data_root = deposit_data.tree_hash_root();
+        if deposit_data_root != self.deposit_data_root {
+            return Err(Error::DepositDataRootMismatch {
+                expected: deposit_data_root,
+                found: self.deposit_data_root,
+            });
+        }
+
+        verify_withdrawal_credentials(&self.withdrawal_credentials, spec)?;
+
+        let pubkey = self.pubkey.decompress().map_err(Error::InvalidPublicKey)?;
+        let signature = self
+            .signature
+            .decompress()
+            .map_err(Error::InvalidSignatureBytes)?;
+        let domain = spec.compute_domain(Domain::Deposit, self.fork_version, Hash256::zero());
+
+        if signature.verify(&pubkey, deposit_message.signing_root(domain)) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+}
+
+/// Checks that `withdrawal_credentials` are either BLS withdrawal credentials or eth1 address
+/// withdrawal credentials, as defined by the prefix bytes in `spec`.
+///
+/// For eth1 address credentials, the 11 bytes between the prefix and the address must be zero.
+pub fn verify_withdrawal_credentials(
+    withdrawal_credentials: &Hash256,
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    let bytes = withdrawal_credentials.as_bytes();
+
+    if bytes[0] == spec.bls_withdrawal_prefix_byte
+        || (bytes[0] == spec.eth1_address_withdrawal_prefix_byte
+            && bytes[1..12].iter().all(|byte| *byte == 0))
+    {
+        Ok(())
+    } else {
+        Err(Error::InvalidWithdrawalCredentials(*withdrawal_credentials))
+    }
+}
+
+/// Writes `deposits` to `writer` as a JSON array, in the format of a `deposit_data-*.json` file.
+pub fn write_deposit_data_json<W: Write>(
+    writer: W,
+    deposits: &[DepositDataJson],
+) -> Result<(), Error> {
+    serde_json::to_writer(writer, deposits).map_err(Error::UnableToWriteJson)
+}
+
+/// Reads the JSON array of a `deposit_data-*.json` file from `reader`.
+///
+/// The deposits are not verified.
+pub fn read_deposit_data_json<R: Read>(reader: R) -> Result<Vec<DepositDataJson>, Error> {
+    serde_json::from_reader(reader).map_err(Error::UnableToReadJson)
+}
+
+/// Serde (de)serialization of values with fixed-length SSZ encodings (e.g., public keys, roots and
+/// fork versions) as hex strings without a `0x` prefix.
+mod unprefixed_hex {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use ssz::{Decode, Encode};
+
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Encode,
+    {
+        serializer.serialize_str(&hex::encode(value.as_ssz_bytes()))
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Decode,
+    {
+        let string = String::deserialize(deserializer)?;
+        let bytes = hex::decode(string.trim_start_matches("0x"))
+            .map_err(|e| D::Error::custom(format!("invalid hex: {:?}", e)))?;
+        T::from_ssz_bytes(&bytes).map_err(|e| D::Error::custom(format!("invalid bytes: {:?}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{EthSpec, Keypair, MainnetEthSpec, Signature};
+
+    fn deposit(spec: &ChainSpec) -> DepositData {
+        let mut withdrawal_credentials = Hash256::repeat_byte(42);
+        withdrawal_credentials.as_bytes_mut()[0] = spec.bls_withdrawal_prefix_byte;
+        deposit_with_credentials(withdrawal_credentials, spec)
+    }
+
+    fn deposit_with_credentials(withdrawal_credentials: Hash256, spec: &ChainSpec) -> DepositData {
+        let keypair = Keypair::random();
+        let mut deposit_data = DepositData {
+            pubkey: keypair.pk.clone().into(),
+            withdrawal_credentials,
+            amount: 32_000_000_000,
+            signature: Signature::empty().into(),
+        };
+        deposit_data.signature = deposit_data.create_signature(&keypair.sk, spec);
+        deposit_data
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let spec = &MainnetEthSpec::default_spec();
+        let deposits = vec![
+            DepositDataJson::new(&deposit(spec), spec),
+            DepositDataJson::new(&deposit(spec), spec),
+        ];
+
+        let mut json = vec![];
+        write_deposit_data_json(&mut json, &deposits).unwrap();
+        let json = String::from_utf8(json).unwrap();
+
+        assert!(json.starts_with("[{\"pubkey\":\""));
+        assert!(!json.contains("0x"));
+        assert!(json.contains("\"fork_version\":\"00000000\""));
+        assert!(json.contains("\"amount\":32000000000"));
+
+        let read = read_deposit_data_json(json.as_bytes()).unwrap();
+        assert_eq!(read, deposits);
+        for deposit in &read {
+            deposit.verify(spec).unwrap();
+        }
+    }
+
+    #[test]
+    fn verify_detects_inconsistencies() {
+        let spec = &MainnetEthSpec::default_spec();
+        let deposit = DepositDataJson::new(&deposit(spec), spec);
+
+        let mut wrong_amount = deposit.clone();
+        wrong_amount.amount += 1;
+        assert!(matches!(
+            wrong_amount.verify(spec),
+            Err(Error::DepositMessageRootMismatch { .. })
+        ));
+
+        let mut wrong_root = deposit.clone();
+        wrong_root.deposit_data_root = Hash256::zero();
+        assert!(matches!(
+            wrong_root.verify(spec),
+            Err(Error::DepositDataRootMismatch { .. })
+        ));
+
+        let mut wrong_fork = deposit;
+        wrong_fork.fork_version = [1, 2, 3, 4];
+        assert!(matches!(
+            wrong_fork.verify(spec),
+            Err(Error::ForkVersionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn verify_checks_network() {
+        let spec = &MainnetEthSpec::default_spec();
+        let mut other_spec = spec.clone();
+        other_spec.genesis_fork_version = [1, 2, 3, 4];
+
+        // A correctly signed deposit for another network is rejected.
+        let other_deposit = DepositDataJson::new(&deposit(&other_spec), &other_spec);
+        other_deposit.verify(&other_spec).unwrap();
+        assert!(matches!(
+            other_deposit.verify(spec),
+            Err(Error::ForkVersionMismatch { expected, found })
+                if expected == spec.genesis_fork_version
+                    && found == other_spec.genesis_fork_version
+        ));
+
+        // Relabelling it with this network's fork version leaves the signature invalid.
+        let mut relabelled = other_deposit;
+        relabelled.fork_version = spec.genesis_fork_version;
+        assert!(matches!(
+            relabelled.verify(spec),
+            Err(Error::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn verify_withdrawal_credentials_prefix() {
+        let spec = &MainnetEthSpec::default_spec();
+        let address = [7; 20];
+
+        let eth1_credentials = Hash256::from_slice(&bls::get_eth1_withdrawal_credentials(
+            &address,
+            spec.eth1_address_withdrawal_prefix_byte,
+        ));
+        DepositDataJson::new(&deposit_with_credentials(eth1_credentials, spec), spec)
+            .verify(spec)
+            .unwrap();
+
+        let mut unknown_prefix = eth1_credentials;
+        unknown_prefix.as_bytes_mut()[0] = 42;
+        assert!(matches!(
+            DepositDataJson::new(&deposit_with_credentials(unknown_prefix, spec), spec)
+                .verify(spec),
+            Err(Error::InvalidWithdrawalCredentials(_))
+        ));
+
+        let mut nonzero_padding = eth1_credentials;
+        nonzero_padding.as_bytes_mut()[5] = 1;
+        assert!(matches!(
+            DepositDataJson::new(&deposit_with_credentials(nonzero_padding, spec), spec)
+                .verify(spec),
+            Err(Error::InvalidWithdrawalCredentials(_))
+        ));
+    }
+}
@@ -8,10 +8,12 @@
 //! client to load those validators.
 
 mod builder;
+pub mod deposit_data_json;
 pub mod insecure_keys;
 mod manager;
 mod validator_dir;
 
+pub use crate::deposit_data_json::DepositDataJson;
 pub use crate::validator_dir::{Error, Eth1DepositData, ValidatorDir, ETH1_DEPOSIT_TX_HASH_FILE};
 pub use builder::{
     Builder, Error as BuilderError, ETH1_DEPOSIT_DATA_FILE, VOTING_KEYSTORE_FILE,
@@ -2,13 +2,14 @@ use crate::builder::{
     ETH1_DEPOSIT_AMOUNT_FILE, ETH1_DEPOSIT_DATA_FILE, VOTING_KEYSTORE_FILE,
     WITHDRAWAL_KEYSTORE_FILE,
 };
+use crate::deposit_data_json::DepositDataJson;
 use deposit_contract::decode_eth1_tx_data;
 use eth2_keystore::{Error as KeystoreError, Keystore, PlainText};
 use std::fs::{read, remove_file, write, OpenOptions};
 use std::io;
 use std::path::{Path, PathBuf};
 use tree_hash::TreeHash;
-use types::{DepositData, Hash256, Keypair};
+use types::{ChainSpec, DepositData, Hash256, Keypair};
 
 /// The file used for indicating if a directory is in-use by another process.
 const LOCK_FILE: &str = ".lock";
@@ -231,6 +232,19 @@ impl ValidatorDir {
             root,
         }))
     }
+
+    /// Attempts to read the deposit files in `self.dir` (see `Self::eth1_deposit_data`) and return
+    /// the deposit in the format used by the staking launchpad, for the genesis fork of `spec`.
+    ///
+    /// ## Errors
+    ///
+    /// If there is a file-system error, not all required files exist or the files are
+    /// inconsistent.
+    pub fn deposit_data_json(&self, spec: &ChainSpec) -> Result<Option<DepositDataJson>, Error> {
+        Ok(self
+            .eth1_deposit_data()?
+            .map(|eth1_deposit_data| DepositDataJson::new(&eth1_deposit_data.deposit_data, spec)))
+    }
 }
 
 impl Drop for ValidatorDir {
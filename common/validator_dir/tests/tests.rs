@@ -4,7 +4,9 @@ use eth2_keystore::{Keystore, KeystoreBuilder, PlainText};
 use std::fs::{self, File};
 use std::path::Path;
 use tempfile::{tempdir, TempDir};
-use types::{test_utils::generate_deterministic_keypair, EthSpec, Keypair, MainnetEthSpec};
+use types::{
+    test_utils::generate_deterministic_keypair, Address, EthSpec, Keypair, MainnetEthSpec,
+};
 use validator_dir::{
     Builder, BuilderError, ValidatorDir, ETH1_DEPOSIT_DATA_FILE, ETH1_DEPOSIT_TX_HASH_FILE,
     VOTING_KEYSTORE_FILE, WITHDRAWAL_KEYSTORE_FILE,
@@ -154,9 +156,16 @@ impl Harness {
                     .unwrap();
             assert!(hex.starts_with("0x"), "deposit data should have 0x prefix");
             hex::decode(&hex[2..]).unwrap();
+
+            // Check that the launchpad deposit data is consistent and correctly signed.
+            let json = validator.deposit_data_json(&spec).unwrap().unwrap();
+            assert_eq!(json.deposit_data(), data.deposit_data);
+            assert_eq!(json.deposit_data_root, data.root);
+            json.verify(&spec).unwrap();
         } else {
             // If there was no deposit then we should return `Ok(None)`.
             assert!(validator.eth1_deposit_data().unwrap().is_none());
+            assert!(validator.deposit_data_json(&spec).unwrap().is_none());
         }
 
         let tx_hash_path = validator.dir().join(ETH1_DEPOSIT_TX_HASH_FILE);
@@ -385,3 +394,38 @@ fn store_withdrawal_keystore_with_eth1_data() {
 
     harness.create_and_test(&config);
 }
+
+#[test]
+fn eth1_withdrawal_address() {
+    let harness = Harness::new();
+    let spec = &MainnetEthSpec::default_spec();
+    let address = Address::repeat_byte(42);
+
+    // Should build eth1 data without a withdrawal keystore.
+    let validator = Builder::new(
+        harness.validators_dir.path().into(),
+        harness.password_dir.path().into(),
+    )
+    .random_voting_keystore()
+    .unwrap()
+    .eth1_withdrawal_address(address)
+    .create_eth1_tx_data(32000000000, spec)
+    .store_withdrawal_keystore(false)
+    .build()
+    .unwrap();
+
+    assert!(!validator.dir().join(WITHDRAWAL_KEYSTORE_FILE).exists());
+
+    let data = validator.eth1_deposit_data().unwrap().unwrap();
+    let credentials = data.deposit_data.withdrawal_credentials;
+    assert_eq!(
+        credentials.as_bytes()[0],
+        spec.eth1_address_withdrawal_prefix_byte
+    );
+    assert_eq!(&credentials.as_bytes()[1..12], &[0; 11]);
+    assert_eq!(&credentials.as_bytes()[12..], address.as_bytes());
+
+    let json = validator.deposit_data_json(spec).unwrap().unwrap();
+    assert_eq!(json.deposit_data(), data.deposit_data);
+    json.verify(spec).unwrap();
+}
@@ -1,7 +1,8 @@
 use crate::cache_arena;
 use crate::SmallVec8;
 use crate::{Error, Hash256};
-use eth2_hashing::{hash32_concat, ZERO_HASHES};
+use eth2_hashing::{hash_2to1, ZERO_HASHES};
+use rayon::prelude::*;
 use smallvec::smallvec;
 use ssz_derive::{Decode, Encode};
 use tree_hash::BYTES_PER_CHUNK;
@@ -9,6 +10,12 @@ use tree_hash::BYTES_PER_CHUNK;
 type CacheArena = cache_arena::CacheArena<Hash256>;
 type CacheArenaAllocation = cache_arena::CacheArenaAllocation<Hash256>;
 
+/// The minimum number of dirty nodes in a layer of the tree for that layer to be hashed in
+/// parallel.
+///
+/// Below this, the overhead of distributing the work across threads outweighs the time saved.
+pub const PARALLEL_HASHING_THRESHOLD: usize = 1_024;
+
 /// Sparse Merkle tree suitable for tree hashing vectors and lists.
 #[derive(Debug, PartialEq, Clone, Default, Encode, Decode)]
 pub struct TreeHashCache {
@@ -109,31 +116,23 @@ impl TreeHashCache {
         while depth > 0 {
             let new_dirty_indices = lift_dirty(&dirty_indices);
 
-            for &idx in &new_dirty_indices {
-                let left_idx = 2 * idx;
-                let right_idx = left_idx + 1;
-
-                let left = self.layers[depth]
-                    .get(arena, left_idx)?
-                    .ok_or_else(|| Error::MissingLeftIdx(left_idx))?;
-                let right = self.layers[depth]
-                    .get(arena, right_idx)?
-                    .copied()
-                    .unwrap_or_else(|| Hash256::from_slice(&ZERO_HASHES[self.depth - depth]));
-
-                let new_hash = hash32_concat(left.as_bytes(), right.as_bytes());
-
-                match self.layers[depth - 1].get_mut(arena, idx)? {
-                    Some(hash) => {
-                        hash.assign_from_slice(&new_hash);
-                    }
-                    None => {
-                        // Parent layer should already contain nodes for all non-dirty indices
-                        if idx != self.layers[depth - 1].len(arena)? {
-                            return Err(Error::CacheInconsistent);
-                        }
-                        self.layers[depth - 1].push(arena, Hash256::from_slice(&new_hash))?;
-                    }
+            if new_dirty_indices.len() >= PARALLEL_HASHING_THRESHOLD {
+                // Hash the layer in parallel, reading from the arena, then store the new nodes.
+                let new_hashes = {
+                    let arena = &*arena;
+                    new_dirty_indices
+                        .par_iter()
+                        .map(|&idx| self.hash_children(arena, depth, idx))
+                        .collect::<Result<Vec<_>, _>>()?
+                };
+
+                for (&idx, new_hash) in new_dirty_indices.iter().zip(new_hashes) {
+                    self.set_node(arena, depth - 1, idx, new_hash)?;
+                }
+            } else {
+                for &idx in &new_dirty_indices {
+                    let new_hash = self.hash_children(arena, depth, idx)?;
+                    self.set_node(arena, depth - 1, idx, new_hash)?;
                 }
             }
 
@@ -146,6 +145,55 @@ impl TreeHashCache {
         Ok(self.root(arena))
     }
 
+    /// Compute the parent of the nodes at `2 * idx` and `2 * idx + 1` in the layer at `depth`.
+    fn hash_children(
+        &self,
+        arena: &CacheArena,
+        depth: usize,
+        idx: usize,
+    ) -> Result<Hash256, Error> {
+        let left_idx = 2 * idx;
+        let right_idx = left_idx + 1;
+
+        let left = self.layers[depth]
+            .get(arena, left_idx)?
+            .ok_or_else(|| Error::MissingLeftIdx(left_idx))?;
+        let right = self.layers[depth]
+            .get(arena, right_idx)?
+            .copied()
+            .unwrap_or_else(|| Hash256::from_slice(&ZERO_HASHES[self.depth - depth]));
+
+        Ok(Hash256::from(hash_2to1(
+            left.as_fixed_bytes(),
+            right.as_fixed_bytes(),
+        )))
+    }
+
+    /// Set the node at `idx` in the layer at `depth` to `hash`, appending it if it is the next
+    /// node in the layer.
+    fn set_node(
+        &self,
+        arena: &mut CacheArena,
+        depth: usize,
+        idx: usize,
+        hash: Hash256,
+    ) -> Result<(), Error> {
+        match self.layers[depth].get_mut(arena, idx)? {
+            Some(node) => {
+                *node = hash;
+            }
+            None => {
+                // Parent layer should already contain nodes for all non-dirty indices
+                if idx != self.layers[depth].len(arena)? {
+                    return Err(Error::CacheInconsistent);
+                }
+                self.layers[depth].push(arena, hash)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get the root of this cache, without doing any updates/computation.
     pub fn root(&self, arena: &CacheArena) -> Hash256 {
         self.layers[0]
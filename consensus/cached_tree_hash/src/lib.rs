@@ -8,7 +8,7 @@ use smallvec::SmallVec;
 type SmallVec8<T> = SmallVec<[T; 8]>;
 pub type CacheArena = cache_arena::CacheArena<Hash256>;
 
-pub use crate::cache::TreeHashCache;
+pub use crate::cache::{TreeHashCache, PARALLEL_HASHING_THRESHOLD};
 pub use crate::impls::int_log;
 use ethereum_types::H256 as Hash256;
 use tree_hash::TreeHash;
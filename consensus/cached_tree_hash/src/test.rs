@@ -1,9 +1,11 @@
 use crate::impls::hash256_iter;
-use crate::{CacheArena, CachedTreeHash, Error, Hash256, TreeHashCache};
+use crate::{
+    CacheArena, CachedTreeHash, Error, Hash256, TreeHashCache, PARALLEL_HASHING_THRESHOLD,
+};
 use eth2_hashing::ZERO_HASHES;
 use quickcheck_macros::quickcheck;
 use ssz_types::{
-    typenum::{Unsigned, U16, U255, U256, U257},
+    typenum::{Unsigned, U16, U255, U256, U257, U8192},
     FixedVector, VariableList,
 };
 use tree_hash::TreeHash;
@@ -109,6 +111,34 @@ fn variable_list_hash256() {
     );
 }
 
+#[test]
+fn variable_list_hash256_parallel() {
+    type List8192 = VariableList<Hash256, U8192>;
+
+    let arena = &mut CacheArena::default();
+    // Enough leaves for the lowest layers of the tree to be hashed in parallel.
+    let len = 4 * PARALLEL_HASHING_THRESHOLD as u64 + 3;
+
+    let mut list = List8192::new(int_hashes(0, len)).unwrap();
+    let mut cache = list.new_tree_hash_cache(arena);
+    assert_eq!(
+        list.recalculate_tree_hash_root(arena, &mut cache).unwrap(),
+        list.tree_hash_root()
+    );
+
+    // Update a large range of existing leaves, and append some more.
+    for (i, leaf) in list.iter_mut().enumerate().skip(7).step_by(2) {
+        *leaf = Hash256::from_low_u64_be(i as u64);
+    }
+    for i in 0..PARALLEL_HASHING_THRESHOLD as u64 {
+        list.push(Hash256::from_low_u64_le(len + i)).unwrap();
+    }
+    assert_eq!(
+        list.recalculate_tree_hash_root(arena, &mut cache).unwrap(),
+        list.tree_hash_root()
+    );
+}
+
 #[quickcheck]
 fn quickcheck_variable_list_h256_256(leaves_and_skips: Vec<(u64, bool)>) -> bool {
     variable_list_h256_test::<U256>(leaves_and_skips)
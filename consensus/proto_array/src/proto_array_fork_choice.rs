@@ -220,6 +220,32 @@ impl ProtoArrayForkChoice {
             .unwrap_or(false)
     }
 
+    /// Returns the root and slot of the most recent block which is an ancestor of both `a_root`
+    /// and `b_root`. Returns `None` if either input root is unknown or if the two chains do not
+    /// share an ancestor in `self`.
+    ///
+    /// ## Notes
+    ///
+    /// A block is considered to be its own ancestor, so if `a_root` is an ancestor of `b_root`
+    /// then `a_root` is returned.
+    pub fn common_ancestor(&self, a_root: Hash256, b_root: Hash256) -> Option<(Hash256, Slot)> {
+        let mut a_iter = self.proto_array.iter_block_roots(&a_root).peekable();
+        let mut b_iter = self.proto_array.iter_block_roots(&b_root).peekable();
+
+        loop {
+            let (a, a_slot) = *a_iter.peek()?;
+            let (b, b_slot) = *b_iter.peek()?;
+
+            if a == b {
+                return Some((a, a_slot));
+            } else if a_slot >= b_slot {
+                a_iter.next();
+            } else {
+                b_iter.next();
+            }
+        }
+    }
+
     pub fn latest_message(&self, validator_index: usize) -> Option<(Hash256, Epoch)> {
         if validator_index < self.votes.0.len() {
             let vote = &self.votes.0[validator_index];
@@ -398,6 +424,57 @@ mod test_compute_deltas {
         assert!(!fc.is_descendant(not_finalized_desc, unknown));
     }
 
+    #[test]
+    fn common_ancestor() {
+        let genesis_slot = Slot::new(0);
+        let genesis_epoch = Epoch::new(0);
+
+        let state_root = Hash256::from_low_u64_be(0);
+        let finalized_root = Hash256::from_low_u64_be(1);
+        let a_1 = Hash256::from_low_u64_be(2);
+        let a_2 = Hash256::from_low_u64_be(3);
+        let b_3 = Hash256::from_low_u64_be(4);
+        let unknown = Hash256::from_low_u64_be(5);
+
+        let mut fc = ProtoArrayForkChoice::new(
+            genesis_slot,
+            state_root,
+            genesis_epoch,
+            genesis_epoch,
+            finalized_root,
+        )
+        .unwrap();
+
+        // Build the following tree, where `b_3` skips slot 2:
+        //
+        //  finalized_root -> a_1 -> a_2
+        //                       \-> b_3
+        for (slot, root, parent_root) in &[(1, a_1, finalized_root), (2, a_2, a_1), (3, b_3, a_1)] {
+            fc.proto_array
+                .on_block(Block {
+                    slot: Slot::new(*slot),
+                    root: *root,
+                    parent_root: Some(*parent_root),
+                    state_root,
+                    target_root: finalized_root,
+                    justified_epoch: genesis_epoch,
+                    finalized_epoch: genesis_epoch,
+                })
+                .unwrap();
+        }
+
+        assert_eq!(fc.common_ancestor(a_2, b_3), Some((a_1, Slot::new(1))));
+        assert_eq!(fc.common_ancestor(b_3, a_2), Some((a_1, Slot::new(1))));
+        assert_eq!(fc.common_ancestor(a_1, a_2), Some((a_1, Slot::new(1))));
+        assert_eq!(fc.common_ancestor(a_2, a_2), Some((a_2, Slot::new(2))));
+        assert_eq!(
+            fc.common_ancestor(finalized_root, b_3),
+            Some((finalized_root, genesis_slot))
+        );
+        assert_eq!(fc.common_ancestor(unknown, a_2), None);
+        assert_eq!(fc.common_ancestor(a_2, unknown), None);
+    }
+
     #[test]
     fn zero_hash() {
         let validator_count: usize = 16;
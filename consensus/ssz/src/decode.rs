@@ -2,7 +2,7 @@ use super::*;
 use smallvec::{smallvec, SmallVec};
 use std::cmp::Ordering;
 
-type SmallVec8<T> = SmallVec<[T; 8]>;
+pub(crate) type SmallVec8<T> = SmallVec<[T; 8]>;
 
 pub mod impls;
 
@@ -48,6 +48,9 @@ pub enum DecodeError {
     ZeroLengthItem,
     /// The given bytes were invalid for some application-level reason.
     BytesInvalid(String),
+    /// The union selector is greater than `MAX_UNION_SELECTOR` or does not correspond to a
+    /// variant of the union.
+    UnionSelectorInvalid(u8),
 }
 
 /// Performs checks on the `offset` based upon the other parameters provided.
@@ -223,6 +226,14 @@ impl<'a> SszDecoderBuilder<'a> {
 
         Ok(SszDecoder { items: self.items })
     }
+
+    /// Finalizes the builder, returning a `ContainerView` that may be used to read individual
+    /// fields without decoding the others.
+    pub fn build_view(mut self) -> Result<ContainerView<'a>, DecodeError> {
+        self.finalize()?;
+
+        Ok(ContainerView::new(self.items))
+    }
 }
 
 /// Decodes some slices of SSZ into object instances. Should be instantiated using
@@ -279,6 +290,24 @@ impl<'a> SszDecoder<'a> {
     }
 }
 
+/// Splits the SSZ encoding of a union into its selector and the bytes of its value.
+///
+/// Returns an error if `bytes` is empty or the selector is greater than `MAX_UNION_SELECTOR`.
+pub fn split_union_bytes(bytes: &[u8]) -> Result<(u8, &[u8]), DecodeError> {
+    let (selector, body) = bytes
+        .split_first()
+        .ok_or_else(|| DecodeError::InvalidByteLength {
+            len: bytes.len(),
+            expected: BYTES_PER_UNION_SELECTOR,
+        })?;
+
+    if *selector > MAX_UNION_SELECTOR {
+        return Err(DecodeError::UnionSelectorInvalid(*selector));
+    }
+
+    Ok((*selector, body))
+}
+
 /// Reads a `BYTES_PER_LENGTH_OFFSET`-byte union index from `bytes`, where `bytes.len() >=
 /// BYTES_PER_LENGTH_OFFSET`.
 pub fn read_union_index(bytes: &[u8]) -> Result<usize, DecodeError> {
@@ -287,7 +316,7 @@ pub fn read_union_index(bytes: &[u8]) -> Result<usize, DecodeError> {
 
 /// Reads a `BYTES_PER_LENGTH_OFFSET`-byte length from `bytes`, where `bytes.len() >=
 /// BYTES_PER_LENGTH_OFFSET`.
-fn read_offset(bytes: &[u8]) -> Result<usize, DecodeError> {
+pub(crate) fn read_offset(bytes: &[u8]) -> Result<usize, DecodeError> {
     decode_offset(bytes.get(0..BYTES_PER_LENGTH_OFFSET).ok_or_else(|| {
         DecodeError::InvalidLengthPrefix {
             len: bytes.len(),
@@ -1,7 +1,12 @@
 use super::*;
+use std::io;
 
 mod impls;
 
+/// The number of bytes of fixed-length list items that are encoded in memory before being written
+/// out by `ssz_write_list`.
+const WRITE_BATCH_BYTES: usize = 4096;
+
 /// Provides SSZ encoding (serialization) via the `as_ssz_bytes(&self)` method.
 ///
 /// See `examples/` for manual implementations or the crate root for implementations using
@@ -43,6 +48,16 @@ pub trait Encode {
 
         buf
     }
+
+    /// Writes the full-form encoding of this object to `writer`, producing the same bytes as
+    /// `self.as_ssz_bytes()`.
+    ///
+    /// The default implementation encodes the whole object in memory before writing it. Lists and
+    /// containers override this to write each of their items in turn, so that large objects can
+    /// be written to a file or socket without holding their entire encoding in memory.
+    fn ssz_write<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.as_ssz_bytes())
+    }
 }
 
 /// Allow for encoding an ordered series of distinct or indistinct objects as SSZ bytes.
@@ -125,6 +140,112 @@ impl<'a> SszEncoder<'a> {
     }
 }
 
+/// Allow for writing the SSZ encoding of a container to an `io::Write`, without first encoding it
+/// in memory.
+///
+/// Unlike `SszEncoder`, the fixed-length portion of each field must be written (in order) before
+/// the variable-length portion of any field, since the variable-length bytes are not buffered.
+///
+/// ## Example
+///
+/// Use `SszWriter` to produce identical output to `foo.as_ssz_bytes()`:
+///
+/// ```rust
+/// use ssz_derive::{Encode, Decode};
+/// use ssz::{Encode, SszWriter};
+///
+/// #[derive(PartialEq, Debug, Encode, Decode)]
+/// struct Foo {
+///     a: u64,
+///     b: Vec<u16>,
+/// }
+///
+/// let foo = Foo {
+///     a: 42,
+///     b: vec![1, 3, 3, 7]
+/// };
+///
+/// let mut buf: Vec<u8> = vec![];
+/// let offset = <u64 as Encode>::ssz_fixed_len() + <Vec<u16> as Encode>::ssz_fixed_len();
+///
+/// let mut writer = SszWriter::container(&mut buf, offset);
+///
+/// writer.write_fixed_part(&foo.a).unwrap();
+/// writer.write_fixed_part(&foo.b).unwrap();
+/// writer.write_variable_part(&foo.a).unwrap();
+/// writer.write_variable_part(&foo.b).unwrap();
+///
+/// assert_eq!(foo.as_ssz_bytes(), buf);
+/// ```
+pub struct SszWriter<'a, W> {
+    offset: usize,
+    writer: &'a mut W,
+}
+
+impl<'a, W: io::Write> SszWriter<'a, W> {
+    /// Instantiate a new writer for writing a SSZ container.
+    pub fn container(writer: &'a mut W, num_fixed_bytes: usize) -> Self {
+        Self {
+            offset: num_fixed_bytes,
+            writer,
+        }
+    }
+
+    /// Write the fixed-length portion of `item`: either its encoding or, for variable-length
+    /// items, the offset to its encoding.
+    pub fn write_fixed_part<T: Encode>(&mut self, item: &T) -> io::Result<()> {
+        if T::is_ssz_fixed_len() {
+            item.ssz_write(self.writer)
+        } else {
+            self.writer.write_all(&encode_length(self.offset))?;
+            self.offset += item.ssz_bytes_len();
+            Ok(())
+        }
+    }
+
+    /// Write the variable-length portion of `item`, which is empty for fixed-length items.
+    pub fn write_variable_part<T: Encode>(&mut self, item: &T) -> io::Result<()> {
+        if T::is_ssz_fixed_len() {
+            Ok(())
+        } else {
+            item.ssz_write(self.writer)
+        }
+    }
+}
+
+/// Write the SSZ encoding of a list (or vector) of `items` to `writer`.
+///
+/// Produces the same bytes as `items.to_vec().as_ssz_bytes()`.
+pub fn ssz_write_list<T: Encode, W: io::Write>(items: &[T], writer: &mut W) -> io::Result<()> {
+    if T::is_ssz_fixed_len() {
+        // Encode small items in batches, rather than writing each one individually.
+        let mut buf = Vec::with_capacity(WRITE_BATCH_BYTES);
+
+        for item in items {
+            item.ssz_append(&mut buf);
+
+            if buf.len() >= WRITE_BATCH_BYTES {
+                writer.write_all(&buf)?;
+                buf.clear();
+            }
+        }
+
+        writer.write_all(&buf)
+    } else {
+        let mut list = SszWriter::container(writer, items.len() * BYTES_PER_LENGTH_OFFSET);
+
+        for item in items {
+            list.write_fixed_part(item)?;
+        }
+
+        for item in items {
+            list.write_variable_part(item)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Encode `index` as a little-endian byte array of `BYTES_PER_LENGTH_OFFSET` length.
 ///
 /// If `len` is larger than `2 ^ BYTES_PER_LENGTH_OFFSET`, a `debug_assert` is raised.
@@ -229,6 +229,16 @@ impl<T: Encode> Encode for Option<T> {
             }
         }
     }
+
+    fn ssz_write<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        match self {
+            None => writer.write_all(&encode_union_index(0)),
+            Some(t) => {
+                writer.write_all(&encode_union_index(1))?;
+                t.ssz_write(writer)
+            }
+        }
+    }
 }
 
 macro_rules! impl_for_vec {
@@ -266,6 +276,10 @@ macro_rules! impl_for_vec {
                     encoder.finalize();
                 }
             }
+
+            fn ssz_write<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+                ssz_write_list(self, writer)
+            }
         }
     };
 }
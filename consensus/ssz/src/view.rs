@@ -0,0 +1,245 @@
+//! Read-only views of SSZ bytes, which read individual values without decoding the whole object.
+//!
+//! The offsets of a view are validated once when it is created, after which each value can be
+//! located without any allocation. Values are only decoded when they are read, so errors in the
+//! encoding of a value are only detected if that value is read.
+use super::*;
+use crate::decode::{read_offset, sanitize_offset, SmallVec8};
+use std::marker::PhantomData;
+
+/// A view of the SSZ encoding of a container, giving access to each of its fields.
+///
+/// Should be instantiated using
+/// [`SszDecoderBuilder::build_view`](struct.SszDecoderBuilder.html#method.build_view).
+///
+/// ## Example
+///
+/// ```rust
+/// use ssz_derive::{Encode, Decode};
+/// use ssz::{Encode, SszDecoderBuilder};
+///
+/// #[derive(PartialEq, Debug, Encode, Decode)]
+/// struct Foo {
+///     a: u64,
+///     b: Vec<u16>,
+/// }
+///
+/// let foo = Foo {
+///     a: 42,
+///     b: vec![1, 3, 3, 7]
+/// };
+/// let bytes = foo.as_ssz_bytes();
+///
+/// let mut builder = SszDecoderBuilder::new(&bytes);
+/// builder.register_type::<u64>().unwrap();
+/// builder.register_type::<Vec<u16>>().unwrap();
+/// let view = builder.build_view().unwrap();
+///
+/// assert_eq!(view.decode_field::<u64>(0), Ok(42));
+/// assert_eq!(view.list_field::<u16>(1).unwrap().get(3), Some(Ok(7)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContainerView<'a> {
+    items: SmallVec8<&'a [u8]>,
+}
+
+impl<'a> ContainerView<'a> {
+    pub(crate) fn new(items: SmallVec8<&'a [u8]>) -> Self {
+        Self { items }
+    }
+
+    /// The number of fields in the container.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the container has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the SSZ bytes of the field at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the number of registered fields.
+    pub fn field_bytes(&self, index: usize) -> &'a [u8] {
+        self.items[index]
+    }
+
+    /// Decodes the field at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the number of registered fields.
+    pub fn decode_field<T: Decode>(&self, index: usize) -> Result<T, DecodeError> {
+        T::from_ssz_bytes(self.field_bytes(index))
+    }
+
+    /// Returns a view of the field at `index`, which must be a list or vector of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the number of registered fields.
+    pub fn list_field<T: Decode>(&self, index: usize) -> Result<ListView<'a, T>, DecodeError> {
+        ListView::new(self.field_bytes(index))
+    }
+}
+
+/// A view of the SSZ encoding of a list or vector of `T`, giving access to each of its items.
+#[derive(Debug, Clone)]
+pub struct ListView<'a, T> {
+    bytes: &'a [u8],
+    len: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T: Decode> ListView<'a, T> {
+    /// Create a view of `bytes`, checking that they hold a whole number of fixed-length items or,
+    /// for variable-length items, that every offset is valid.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let len = if bytes.is_empty() {
+            0
+        } else if T::is_ssz_fixed_len() {
+            let item_len = T::ssz_fixed_len();
+            if item_len == 0 {
+                return Err(DecodeError::ZeroLengthItem);
+            }
+            if bytes.len() % item_len != 0 {
+                return Err(DecodeError::InvalidByteLength {
+                    len: bytes.len(),
+                    expected: (bytes.len() / item_len + 1) * item_len,
+                });
+            }
+            bytes.len() / item_len
+        } else {
+            let first_offset = read_offset(bytes)?;
+            sanitize_offset(first_offset, None, bytes.len(), Some(first_offset))?;
+
+            if first_offset % BYTES_PER_LENGTH_OFFSET != 0 || first_offset < BYTES_PER_LENGTH_OFFSET
+            {
+                return Err(DecodeError::InvalidListFixedBytesLen(first_offset));
+            }
+
+            let len = first_offset / BYTES_PER_LENGTH_OFFSET;
+            let mut offset = first_offset;
+            for i in 1..len {
+                let next_offset = read_offset(&bytes[i * BYTES_PER_LENGTH_OFFSET..])?;
+                offset =
+                    sanitize_offset(next_offset, Some(offset), bytes.len(), Some(first_offset))?;
+            }
+            len
+        };
+
+        Ok(Self {
+            bytes,
+            len,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The number of items in the list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list has no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the SSZ bytes of the item at `index`, or `None` if `index` is out of bounds.
+    pub fn get_bytes(&self, index: usize) -> Option<&'a [u8]> {
+        if index >= self.len {
+            return None;
+        }
+
+        if T::is_ssz_fixed_len() {
+            let item_len = T::ssz_fixed_len();
+            self.bytes.get(index * item_len..(index + 1) * item_len)
+        } else {
+            // The offsets were validated when the view was created.
+            let offset = |i: usize| read_offset(&self.bytes[i * BYTES_PER_LENGTH_OFFSET..]).ok();
+            let start = offset(index)?;
+            let end = if index + 1 == self.len {
+                self.bytes.len()
+            } else {
+                offset(index + 1)?
+            };
+            self.bytes.get(start..end)
+        }
+    }
+
+    /// Decodes the item at `index`, or returns `None` if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Result<T, DecodeError>> {
+        self.get_bytes(index).map(T::from_ssz_bytes)
+    }
+
+    /// Iterates through the items of the list, decoding each one.
+    pub fn iter(&self) -> impl Iterator<Item = Result<T, DecodeError>> + '_ {
+        (0..self.len).filter_map(move |i| self.get(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_len_list() {
+        let list: Vec<u16> = vec![0, 1, 2, u16::max_value()];
+        let bytes = list.as_ssz_bytes();
+        let view = ListView::<u16>::new(&bytes).unwrap();
+
+        assert_eq!(view.len(), list.len());
+        assert_eq!(view.get(3), Some(Ok(u16::max_value())));
+        assert_eq!(view.get(4), None);
+        assert_eq!(view.iter().collect::<Result<Vec<_>, _>>(), Ok(list));
+
+        assert_eq!(
+            ListView::<u16>::new(&bytes[1..]).map(|view| view.len()),
+            Err(DecodeError::InvalidByteLength {
+                len: 7,
+                expected: 8
+            })
+        );
+    }
+
+    #[test]
+    fn variable_len_list() {
+        let list: Vec<Vec<u16>> = vec![vec![], vec![1, 2], vec![], vec![3]];
+        let bytes = list.as_ssz_bytes();
+        let view = ListView::<Vec<u16>>::new(&bytes).unwrap();
+
+        assert_eq!(view.len(), list.len());
+        assert_eq!(view.get(1), Some(Ok(vec![1, 2])));
+        assert_eq!(view.get(2), Some(Ok(vec![])));
+        assert_eq!(view.get(3), Some(Ok(vec![3])));
+        assert_eq!(view.get(4), None);
+        assert_eq!(view.iter().collect::<Result<Vec<_>, _>>(), Ok(list));
+    }
+
+    #[test]
+    fn variable_len_list_invalid_offsets() {
+        // The second offset points into the offsets.
+        let bytes = vec![8, 0, 0, 0, 4, 0, 0, 0];
+        assert_eq!(
+            ListView::<Vec<u8>>::new(&bytes).map(|view| view.len()),
+            Err(DecodeError::OffsetIntoFixedPortion(4))
+        );
+
+        // The second offset is out of bounds.
+        let bytes = vec![8, 0, 0, 0, 9, 0, 0, 0];
+        assert_eq!(
+            ListView::<Vec<u8>>::new(&bytes).map(|view| view.len()),
+            Err(DecodeError::OffsetOutOfBounds(9))
+        );
+    }
+
+    #[test]
+    fn empty_list() {
+        let view = ListView::<Vec<u8>>::new(&[]).unwrap();
+        assert!(view.is_empty());
+        assert_eq!(view.get(0), None);
+    }
+}
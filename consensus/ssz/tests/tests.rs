@@ -10,6 +10,11 @@ mod round_trip {
         for item in items {
             let encoded = &item.as_ssz_bytes();
             assert_eq!(item.ssz_bytes_len(), encoded.len());
+
+            let mut written = vec![];
+            item.ssz_write(&mut written).unwrap();
+            assert_eq!(&written, encoded);
+
             assert_eq!(T::from_ssz_bytes(&encoded), Ok(item));
         }
     }
@@ -58,6 +63,13 @@ mod round_trip {
         round_trip(items);
     }
 
+    #[test]
+    fn vec_u64_larger_than_write_batch() {
+        let items: Vec<Vec<u64>> = vec![(0..1_000).collect()];
+
+        round_trip(items);
+    }
+
     #[test]
     fn vec_of_vec_u16() {
         let items: Vec<Vec<Vec<u16>>> = vec![
@@ -378,4 +390,189 @@ mod round_trip {
 
         round_trip(vec);
     }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Newtype(u16);
+
+    #[test]
+    fn newtype_encoding() {
+        assert_eq!(Newtype(0x0102).as_ssz_bytes(), vec![02, 01]);
+        assert_eq!(<Newtype as Encode>::ssz_fixed_len(), 2);
+        assert_eq!(
+            Newtype::from_ssz_bytes(&[02]),
+            Err(DecodeError::InvalidByteLength {
+                len: 1,
+                expected: 2
+            })
+        );
+
+        round_trip(vec![Newtype(0), Newtype(42), Newtype(u16::max_value())]);
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    enum TwoVariantUnion {
+        A(u16),
+        B(Vec<u8>),
+    }
+
+    #[test]
+    fn union_encoding() {
+        assert_eq!(TwoVariantUnion::A(0x0102).as_ssz_bytes(), vec![00, 02, 01]);
+        assert_eq!(
+            TwoVariantUnion::B(vec![42, 43]).as_ssz_bytes(),
+            vec![01, 42, 43]
+        );
+        assert_eq!(TwoVariantUnion::B(vec![]).as_ssz_bytes(), vec![01]);
+    }
+
+    #[test]
+    fn union_invalid_bytes() {
+        assert_eq!(
+            TwoVariantUnion::from_ssz_bytes(&[]),
+            Err(DecodeError::InvalidByteLength {
+                len: 0,
+                expected: 1
+            })
+        );
+        assert_eq!(
+            TwoVariantUnion::from_ssz_bytes(&[02, 00, 00]),
+            Err(DecodeError::UnionSelectorInvalid(2))
+        );
+        assert_eq!(
+            TwoVariantUnion::from_ssz_bytes(&[128]),
+            Err(DecodeError::UnionSelectorInvalid(128))
+        );
+        assert_eq!(
+            TwoVariantUnion::from_ssz_bytes(&[00, 01]),
+            Err(DecodeError::InvalidByteLength {
+                len: 1,
+                expected: 2
+            })
+        );
+    }
+
+    #[test]
+    fn union_round_trip() {
+        let vec: Vec<TwoVariantUnion> = vec![
+            TwoVariantUnion::A(0),
+            TwoVariantUnion::A(u16::max_value()),
+            TwoVariantUnion::B(vec![]),
+            TwoVariantUnion::B(vec![0, 1, 2, 3]),
+        ];
+
+        round_trip(vec);
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct UnionField {
+        a: u8,
+        b: TwoVariantUnion,
+    }
+
+    #[test]
+    fn union_field_encoding() {
+        let s = UnionField {
+            a: 42,
+            b: TwoVariantUnion::A(1),
+        };
+
+        let bytes = vec![
+            //  1   2   3   4   5   6   7
+            //      | offset        | union
+            42, 05, 00, 00, 00, 00, 01, 00,
+        ];
+
+        assert_eq!(s.as_ssz_bytes(), bytes);
+        round_trip(vec![s]);
+    }
+
+    #[test]
+    fn union_is_variable_length() {
+        assert!(!<TwoVariantUnion as Encode>::is_ssz_fixed_len());
+        assert!(!<TwoVariantUnion as Decode>::is_ssz_fixed_len());
+    }
+
+    #[test]
+    fn union_list_encoding() {
+        let list = vec![TwoVariantUnion::A(0x0102), TwoVariantUnion::B(vec![42])];
+
+        let bytes = vec![
+            // | offset       | offset        | union     | union
+            08, 00, 00, 00, 11, 00, 00, 00, 00, 02, 01, 01, 42,
+        ];
+
+        assert_eq!(list.as_ssz_bytes(), bytes);
+        assert_eq!(Vec::<TwoVariantUnion>::from_ssz_bytes(&bytes), Ok(list));
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Fixed {
+        a: u8,
+        b: u16,
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    enum ContainerUnion {
+        Fixed(Fixed),
+        Variable(UnionField),
+        Nested(TwoVariantUnion),
+    }
+
+    #[test]
+    fn container_union_encoding() {
+        assert_eq!(
+            ContainerUnion::Fixed(Fixed { a: 1, b: 0x0203 }).as_ssz_bytes(),
+            vec![00, 01, 03, 02]
+        );
+        assert_eq!(
+            ContainerUnion::Nested(TwoVariantUnion::B(vec![42])).as_ssz_bytes(),
+            vec![02, 01, 42]
+        );
+        assert_eq!(
+            ContainerUnion::from_ssz_bytes(&[00, 01, 03]),
+            Err(DecodeError::InvalidByteLength {
+                len: 2,
+                expected: 3
+            })
+        );
+        assert_eq!(
+            ContainerUnion::from_ssz_bytes(&[03, 00]),
+            Err(DecodeError::UnionSelectorInvalid(3))
+        );
+    }
+
+    #[test]
+    fn container_union_round_trip() {
+        let vec: Vec<ContainerUnion> = vec![
+            ContainerUnion::Fixed(Fixed { a: 0, b: 0 }),
+            ContainerUnion::Fixed(Fixed {
+                a: u8::max_value(),
+                b: u16::max_value(),
+            }),
+            ContainerUnion::Variable(UnionField {
+                a: 42,
+                b: TwoVariantUnion::B(vec![1, 2, 3]),
+            }),
+            ContainerUnion::Nested(TwoVariantUnion::A(42)),
+            ContainerUnion::Nested(TwoVariantUnion::B(vec![])),
+        ];
+
+        round_trip(vec);
+    }
+
+    #[derive(Debug, PartialEq, Encode)]
+    #[ssz(enum_behaviour = "transparent")]
+    enum TransparentEnum {
+        A(u16),
+        B(Vec<u8>),
+    }
+
+    #[test]
+    fn transparent_enum_encoding() {
+        assert_eq!(TransparentEnum::A(0x0102).as_ssz_bytes(), vec![02, 01]);
+        assert_eq!(
+            TransparentEnum::B(vec![42, 43]).as_ssz_bytes(),
+            vec![42, 43]
+        );
+    }
 }
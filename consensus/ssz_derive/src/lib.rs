@@ -9,6 +9,9 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 
+/// The highest selector permitted in an SSZ union (`ssz::MAX_UNION_SELECTOR`).
+const MAX_UNION_SELECTOR: u8 = 127;
+
 /// Returns a Vec of `syn::Ident` for each named field in the struct, whilst filtering out fields
 /// that should not be serialized.
 ///
@@ -59,25 +62,127 @@ fn should_skip_serializing(field: &syn::Field) -> bool {
     })
 }
 
-/// Implements `ssz::Encode` for some `struct`.
+/// The encoding of an enum, set with the `#[ssz(enum_behaviour = "...")]` attribute.
+#[derive(Clone, Copy)]
+enum EnumBehaviour {
+    /// An SSZ union: a one-byte selector (the index of the variant) followed by the encoding of
+    /// the variant's value.
+    Union,
+    /// The encoding of the variant's value, without a selector.
+    Transparent,
+}
+
+/// Returns the behaviour declared by an `#[ssz(enum_behaviour = "...")]` attribute, or `Union` if
+/// there is none.
+///
+/// # Panics
+/// An unknown behaviour will raise a panic at compile time.
+fn get_enum_behaviour(attrs: &[syn::Attribute]) -> EnumBehaviour {
+    use syn::{Lit, Meta, MetaNameValue, NestedMeta};
+
+    let behaviours = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("ssz"))
+        .flat_map(|attr| attr.parse_meta())
+        .flat_map(|meta| match meta {
+            Meta::List(list) => list.nested.into_iter().collect::<Vec<_>>(),
+            _ => vec![],
+        })
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(behaviour),
+                ..
+            })) if path.is_ident("enum_behaviour") => Some(behaviour.value()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    match behaviours.as_slice() {
+        [] => EnumBehaviour::Union,
+        [behaviour] if behaviour == "union" => EnumBehaviour::Union,
+        [behaviour] if behaviour == "transparent" => EnumBehaviour::Transparent,
+        _ => panic!("ssz_derive enum_behaviour must be \"union\" or \"transparent\"."),
+    }
+}
+
+/// Returns the ident and the type of the value of each variant of an enum.
+///
+/// # Panics
+/// Any variant that does not have exactly one unnamed field will raise a panic at compile time, as
+/// will an enum with more variants than there are union selectors.
+fn get_enum_variants(enum_data: &syn::DataEnum) -> Vec<(&syn::Ident, &syn::Type)> {
+    let variants = enum_data
+        .variants
+        .iter()
+        .map(|variant| match &variant.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                (&variant.ident, &fields.unnamed[0].ty)
+            }
+            _ => panic!("ssz_derive only supports enum variants with a single unnamed field."),
+        })
+        .collect::<Vec<_>>();
+
+    if variants.len() > MAX_UNION_SELECTOR as usize + 1 {
+        panic!("ssz_derive enums may have at most 128 variants.");
+    }
+
+    variants
+}
+
+/// Returns the union selector of each variant of an enum.
+fn get_union_selectors(num_variants: usize) -> Vec<u8> {
+    (0..num_variants).map(|selector| selector as u8).collect()
+}
+
+/// Returns the type of the field of a tuple struct with a single field, or `None` if the struct
+/// is not such a newtype.
+fn get_newtype_field_type(struct_data: &syn::DataStruct) -> Option<&syn::Type> {
+    match &struct_data.fields {
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Some(&fields.unnamed[0].ty),
+        _ => None,
+    }
+}
+
+/// Implements `ssz::Encode` for some `struct` or `enum`.
+///
+/// Fields are encoded in the order they are defined. A tuple struct with a single field (a
+/// newtype) is encoded exactly as its field.
 ///
-/// Fields are encoded in the order they are defined.
+/// Each variant of an enum must have a single unnamed field. By default an enum is encoded as an
+/// SSZ union, with the index of the variant as its selector.
 ///
 /// ## Field attributes
 ///
 /// - `#[ssz(skip_serializing)]`: the field will not be serialized.
+///
+/// ## Enum attributes
+///
+/// - `#[ssz(enum_behaviour = "union")]`: encode as an SSZ union (the default).
+/// - `#[ssz(enum_behaviour = "transparent")]`: encode the value of the variant, without a
+/// selector. Such an enum cannot be decoded.
 #[proc_macro_derive(Encode, attributes(ssz))]
 pub fn ssz_encode_derive(input: TokenStream) -> TokenStream {
     let item = parse_macro_input!(input as DeriveInput);
 
+    match &item.data {
+        syn::Data::Struct(s) => match get_newtype_field_type(s) {
+            Some(field_type) => ssz_encode_derive_newtype(&item, field_type),
+            None => ssz_encode_derive_struct(&item, s),
+        },
+        syn::Data::Enum(e) => match get_enum_behaviour(&item.attrs) {
+            EnumBehaviour::Union => ssz_encode_derive_enum_union(&item, e),
+            EnumBehaviour::Transparent => ssz_encode_derive_enum_transparent(&item, e),
+        },
+        _ => panic!("ssz_derive only supports structs and enums."),
+    }
+}
+
+/// Implements `ssz::Encode` for a `struct` with named fields.
+fn ssz_encode_derive_struct(item: &DeriveInput, struct_data: &syn::DataStruct) -> TokenStream {
     let name = &item.ident;
     let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
 
-    let struct_data = match &item.data {
-        syn::Data::Struct(s) => s,
-        _ => panic!("ssz_derive only supports structs."),
-    };
-
     let field_idents = get_serializable_named_field_idents(&struct_data);
     let field_idents_a = get_serializable_named_field_idents(&struct_data);
     let field_types_a = get_serializable_field_types(&struct_data);
@@ -85,6 +190,9 @@ pub fn ssz_encode_derive(input: TokenStream) -> TokenStream {
     let field_types_d = field_types_a.clone();
     let field_types_e = field_types_a.clone();
     let field_types_f = field_types_a.clone();
+    let field_types_g = field_types_a.clone();
+    let field_idents_b = get_serializable_named_field_idents(&struct_data);
+    let field_idents_c = get_serializable_named_field_idents(&struct_data);
 
     let output = quote! {
         #[allow(clippy::integer_arithmetic)]
@@ -139,6 +247,162 @@ pub fn ssz_encode_derive(input: TokenStream) -> TokenStream {
 
                 encoder.finalize();
             }
+
+            fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                let offset = #(
+                        <#field_types_g as ssz::Encode>::ssz_fixed_len() +
+                    )*
+                        0;
+
+                let mut container = ssz::SszWriter::container(writer, offset);
+
+                #(
+                    container.write_fixed_part(&self.#field_idents_b)?;
+                )*
+
+                #(
+                    container.write_variable_part(&self.#field_idents_c)?;
+                )*
+
+                Ok(())
+            }
+        }
+    };
+    output.into()
+}
+
+/// Implements `ssz::Encode` for a tuple struct with a single field.
+fn ssz_encode_derive_newtype(item: &DeriveInput, field_type: &syn::Type) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
+
+    let output = quote! {
+        impl #impl_generics ssz::Encode for #name #ty_generics #where_clause {
+            fn is_ssz_fixed_len() -> bool {
+                <#field_type as ssz::Encode>::is_ssz_fixed_len()
+            }
+
+            fn ssz_fixed_len() -> usize {
+                <#field_type as ssz::Encode>::ssz_fixed_len()
+            }
+
+            fn ssz_bytes_len(&self) -> usize {
+                self.0.ssz_bytes_len()
+            }
+
+            fn ssz_append(&self, buf: &mut Vec<u8>) {
+                self.0.ssz_append(buf)
+            }
+
+            fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                self.0.ssz_write(writer)
+            }
+        }
+    };
+    output.into()
+}
+
+/// Implements `ssz::Encode` for an `enum`, as an SSZ union.
+fn ssz_encode_derive_enum_union(item: &DeriveInput, enum_data: &syn::DataEnum) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
+
+    let variant_idents = get_enum_variants(enum_data)
+        .into_iter()
+        .map(|(ident, _)| ident)
+        .collect::<Vec<_>>();
+    let variant_idents_a = variant_idents.clone();
+    let variant_idents_b = variant_idents.clone();
+    let selectors = get_union_selectors(variant_idents.len());
+    let selectors_a = selectors.clone();
+
+    let output = quote! {
+        #[allow(clippy::integer_arithmetic)]
+        impl #impl_generics ssz::Encode for #name #ty_generics #where_clause {
+            fn is_ssz_fixed_len() -> bool {
+                false
+            }
+
+            fn ssz_bytes_len(&self) -> usize {
+                match self {
+                    #(
+                        #name::#variant_idents(inner) => {
+                            ssz::BYTES_PER_UNION_SELECTOR + inner.ssz_bytes_len()
+                        }
+                    )*
+                }
+            }
+
+            fn ssz_append(&self, buf: &mut Vec<u8>) {
+                match self {
+                    #(
+                        #name::#variant_idents_a(inner) => {
+                            buf.push(#selectors);
+                            inner.ssz_append(buf);
+                        }
+                    )*
+                }
+            }
+
+            fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                match self {
+                    #(
+                        #name::#variant_idents_b(inner) => {
+                            writer.write_all(&[#selectors_a])?;
+                            inner.ssz_write(writer)
+                        }
+                    )*
+                }
+            }
+        }
+    };
+    output.into()
+}
+
+/// Implements `ssz::Encode` for an `enum`, as the value of each variant.
+fn ssz_encode_derive_enum_transparent(
+    item: &DeriveInput,
+    enum_data: &syn::DataEnum,
+) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
+
+    let variant_idents = get_enum_variants(enum_data)
+        .into_iter()
+        .map(|(ident, _)| ident)
+        .collect::<Vec<_>>();
+    let variant_idents_a = variant_idents.clone();
+    let variant_idents_b = variant_idents.clone();
+
+    let output = quote! {
+        impl #impl_generics ssz::Encode for #name #ty_generics #where_clause {
+            fn is_ssz_fixed_len() -> bool {
+                false
+            }
+
+            fn ssz_bytes_len(&self) -> usize {
+                match self {
+                    #(
+                        #name::#variant_idents(inner) => inner.ssz_bytes_len(),
+                    )*
+                }
+            }
+
+            fn ssz_append(&self, buf: &mut Vec<u8>) {
+                match self {
+                    #(
+                        #name::#variant_idents_a(inner) => inner.ssz_append(buf),
+                    )*
+                }
+            }
+
+            fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                match self {
+                    #(
+                        #name::#variant_idents_b(inner) => inner.ssz_write(writer),
+                    )*
+                }
+            }
         }
     };
     output.into()
@@ -154,27 +418,47 @@ fn should_skip_deserializing(field: &syn::Field) -> bool {
     })
 }
 
-/// Implements `ssz::Decode` for some `struct`.
+/// Implements `ssz::Decode` for some `struct` or `enum`.
 ///
-/// Fields are decoded in the order they are defined.
+/// Fields are decoded in the order they are defined. A tuple struct with a single field (a
+/// newtype) is decoded exactly as its field, and an enum is decoded as an SSZ union (see
+/// `ssz_encode_derive`).
 ///
 /// ## Field attributes
 ///
 /// - `#[ssz(skip_deserializing)]`: during de-serialization the field will be instantiated from a
 /// `Default` implementation. The decoder will assume that the field was not serialized at all
 /// (e.g., if it has been serialized, an error will be raised instead of `Default` overriding it).
-#[proc_macro_derive(Decode)]
+///
+/// ## Enum attributes
+///
+/// - `#[ssz(enum_behaviour = "union")]`: decode as an SSZ union (the default).
+/// - `#[ssz(enum_behaviour = "transparent")]`: not supported, as the variant cannot be determined
+/// from its encoding.
+#[proc_macro_derive(Decode, attributes(ssz))]
 pub fn ssz_decode_derive(input: TokenStream) -> TokenStream {
     let item = parse_macro_input!(input as DeriveInput);
 
+    match &item.data {
+        syn::Data::Struct(s) => match get_newtype_field_type(s) {
+            Some(field_type) => ssz_decode_derive_newtype(&item, field_type),
+            None => ssz_decode_derive_struct(&item, s),
+        },
+        syn::Data::Enum(e) => match get_enum_behaviour(&item.attrs) {
+            EnumBehaviour::Union => ssz_decode_derive_enum_union(&item, e),
+            EnumBehaviour::Transparent => {
+                panic!("ssz_derive cannot derive Decode for enum_behaviour \"transparent\".")
+            }
+        },
+        _ => panic!("ssz_derive only supports structs and enums."),
+    }
+}
+
+/// Implements `ssz::Decode` for a `struct` with named fields.
+fn ssz_decode_derive_struct(item: &DeriveInput, struct_data: &syn::DataStruct) -> TokenStream {
     let name = &item.ident;
     let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
 
-    let struct_data = match &item.data {
-        syn::Data::Struct(s) => s,
-        _ => panic!("ssz_derive only supports structs."),
-    };
-
     let mut register_types = vec![];
     let mut field_names = vec![];
     let mut fixed_decodes = vec![];
@@ -308,3 +592,60 @@ pub fn ssz_decode_derive(input: TokenStream) -> TokenStream {
     };
     output.into()
 }
+
+/// Implements `ssz::Decode` for a tuple struct with a single field.
+fn ssz_decode_derive_newtype(item: &DeriveInput, field_type: &syn::Type) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
+
+    let output = quote! {
+        impl #impl_generics ssz::Decode for #name #ty_generics #where_clause {
+            fn is_ssz_fixed_len() -> bool {
+                <#field_type as ssz::Decode>::is_ssz_fixed_len()
+            }
+
+            fn ssz_fixed_len() -> usize {
+                <#field_type as ssz::Decode>::ssz_fixed_len()
+            }
+
+            fn from_ssz_bytes(bytes: &[u8]) -> std::result::Result<Self, ssz::DecodeError> {
+                <#field_type as ssz::Decode>::from_ssz_bytes(bytes).map(Self)
+            }
+        }
+    };
+    output.into()
+}
+
+/// Implements `ssz::Decode` for an `enum`, as an SSZ union.
+fn ssz_decode_derive_enum_union(item: &DeriveInput, enum_data: &syn::DataEnum) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
+
+    let variants = get_enum_variants(enum_data);
+    let variant_idents = variants.iter().map(|(ident, _)| ident);
+    let variant_types = variants.iter().map(|(_, ty)| ty);
+    let selectors = get_union_selectors(variants.len());
+
+    let output = quote! {
+        impl #impl_generics ssz::Decode for #name #ty_generics #where_clause {
+            fn is_ssz_fixed_len() -> bool {
+                false
+            }
+
+            fn from_ssz_bytes(bytes: &[u8]) -> std::result::Result<Self, ssz::DecodeError> {
+                let (selector, body) = ssz::split_union_bytes(bytes)?;
+
+                match selector {
+                    #(
+                        #selectors => {
+                            <#variant_types as ssz::Decode>::from_ssz_bytes(body)
+                                .map(#name::#variant_idents)
+                        }
+                    )*
+                    other => Err(ssz::DecodeError::UnionSelectorInvalid(other)),
+                }
+            }
+        }
+    };
+    output.into()
+}
@@ -486,6 +486,10 @@ impl<N: Unsigned + Clone> Encode for Bitfield<Variable<N>> {
     fn ssz_append(&self, buf: &mut Vec<u8>) {
         buf.append(&mut self.clone().into_bytes())
     }
+
+    fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.clone().into_bytes())
+    }
 }
 
 impl<N: Unsigned + Clone> Decode for Bitfield<Variable<N>> {
@@ -516,6 +520,10 @@ impl<N: Unsigned + Clone> Encode for Bitfield<Fixed<N>> {
     fn ssz_append(&self, buf: &mut Vec<u8>) {
         buf.append(&mut self.clone().into_bytes())
     }
+
+    fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(self.as_slice())
+    }
 }
 
 impl<N: Unsigned + Clone> Decode for Bitfield<Fixed<N>> {
@@ -206,6 +206,10 @@ where
             encoder.finalize();
         }
     }
+
+    fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        ssz::ssz_write_list(&self.vec, writer)
+    }
 }
 
 impl<T, N: Unsigned> ssz::Decode for FixedVector<T, N>
@@ -216,6 +216,10 @@ where
     fn ssz_append(&self, buf: &mut Vec<u8>) {
         self.vec.ssz_append(buf)
     }
+
+    fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.vec.ssz_write(writer)
+    }
 }
 
 impl<T, N> ssz::Decode for VariableList<T, N>
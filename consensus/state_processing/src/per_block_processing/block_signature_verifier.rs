@@ -139,6 +139,15 @@ where
         }
     }
 
+    /// Moves all signatures included in `other` into `self`, so they are verified by a single
+    /// call to `Self::verify`.
+    ///
+    /// This allows signatures that were read using different `BeaconState`s (e.g., the blocks of
+    /// a chain segment that spans several epochs) to be verified in one batch.
+    pub fn append(&mut self, other: Self) {
+        self.sets.extend(other.sets);
+    }
+
     /// Includes all signatures on the block (except the deposit signatures) for verification.
     pub fn include_all_signatures(
         &mut self,
@@ -1,5 +1,5 @@
 use crate::Hash256;
-use eth2_hashing::{Context, SHA256};
+use eth2_hashing::{Context, Sha256Context};
 use std::cmp::max;
 
 /// Return `p(index)` in a pseudorandom permutation `p` of `0...list_size-1` with ``seed`` as entropy.
@@ -50,7 +50,7 @@ fn do_round(seed: &[u8], index: usize, pivot: usize, round: u8, list_size: usize
 }
 
 fn hash_with_round_and_position(seed: &[u8], round: u8, position: usize) -> Hash256 {
-    let mut context = Context::new(&SHA256);
+    let mut context = Context::new();
 
     context.update(seed);
     context.update(&[round]);
@@ -60,18 +60,16 @@ fn hash_with_round_and_position(seed: &[u8], round: u8, position: usize) -> Hash
      */
     context.update(&(position / 256).to_le_bytes()[0..4]);
 
-    let digest = context.finish();
-    Hash256::from_slice(digest.as_ref())
+    Hash256::from(context.finalize())
 }
 
 fn hash_with_round(seed: &[u8], round: u8) -> Hash256 {
-    let mut context = Context::new(&SHA256);
+    let mut context = Context::new();
 
     context.update(seed);
     context.update(&[round]);
 
-    let digest = context.finish();
-    Hash256::from_slice(digest.as_ref())
+    Hash256::from(context.finalize())
 }
 
 fn bytes_to_int64(slice: &[u8]) -> u64 {
@@ -7,12 +7,13 @@ pub use merkle_hasher::{Error, MerkleHasher};
 pub use merkleize_padded::merkleize_padded;
 pub use merkleize_standard::merkleize_standard;
 
-use eth2_hashing::{Context, SHA256};
-use eth2_hashing::{ZERO_HASHES, ZERO_HASHES_MAX_INDEX};
+use eth2_hashing::{hash_2to1, hash_fixed, ZERO_HASHES, ZERO_HASHES_MAX_INDEX};
 
 pub const BYTES_PER_CHUNK: usize = 32;
 pub const HASHSIZE: usize = 32;
 pub const MERKLE_HASH_CHUNK: usize = 2 * BYTES_PER_CHUNK;
+/// The highest selector permitted in an SSZ union.
+pub const MAX_UNION_SELECTOR: u8 = 127;
 
 pub type Hash256 = ethereum_types::H256;
 
@@ -39,11 +40,7 @@ pub fn merkle_root(bytes: &[u8], minimum_leaf_count: usize) -> Hash256 {
         let mut leaves = [0; HASHSIZE * 2];
         leaves[0..bytes.len()].copy_from_slice(bytes);
 
-        let mut context = Context::new(&SHA256);
-        context.update(&leaves);
-        let digest = context.finish();
-
-        Hash256::from_slice(digest.as_ref())
+        Hash256::from(hash_fixed(&leaves))
     } else {
         // If there are 3 or more leaves, use `MerkleHasher`.
         let mut hasher = MerkleHasher::with_leaves(leaves);
@@ -65,7 +62,22 @@ pub fn mix_in_length(root: &Hash256, length: usize) -> Hash256 {
     let mut length_bytes = [0; BYTES_PER_CHUNK];
     length_bytes[0..usize_len].copy_from_slice(&length.to_le_bytes());
 
-    Hash256::from_slice(&eth2_hashing::hash32_concat(root.as_bytes(), &length_bytes)[..])
+    Hash256::from(hash_2to1(root.as_fixed_bytes(), &length_bytes))
+}
+
+/// Returns the node created by hashing `root` and `selector`.
+///
+/// Used in `TreeHash` for inserting the selector of a union above the root of its value. Returns
+/// `None` if `selector > MAX_UNION_SELECTOR`.
+pub fn mix_in_selector(root: &Hash256, selector: u8) -> Option<Hash256> {
+    if selector > MAX_UNION_SELECTOR {
+        return None;
+    }
+
+    let mut chunk = [0; BYTES_PER_CHUNK];
+    chunk[0] = selector;
+
+    Some(Hash256::from(hash_2to1(root.as_fixed_bytes(), &chunk)))
 }
 
 /// Returns a cached padding node for a given height.
@@ -159,4 +171,18 @@ mod test {
             &hash[..]
         );
     }
+
+    #[test]
+    fn mix_selector() {
+        let hash = {
+            let mut preimage = vec![42; BYTES_PER_CHUNK];
+            preimage.append(&mut vec![7]);
+            preimage.append(&mut vec![0; BYTES_PER_CHUNK - 1]);
+            eth2_hashing::hash(&preimage)
+        };
+
+        let root = Hash256::from_slice(&[42; BYTES_PER_CHUNK]);
+        assert_eq!(mix_in_selector(&root, 7).unwrap().as_bytes(), &hash[..]);
+        assert_eq!(mix_in_selector(&root, MAX_UNION_SELECTOR + 1), None);
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{get_zero_hash, Hash256, HASHSIZE};
-use eth2_hashing::{Context, Digest, SHA256};
+use eth2_hashing::{hash32_concat, HASH_LEN};
 use smallvec::{smallvec, SmallVec};
 use std::mem;
 
@@ -15,7 +15,7 @@ pub enum Error {
 ///
 /// Should be used as a left or right value for some node.
 enum Preimage<'a> {
-    Digest(Digest),
+    Digest([u8; HASH_LEN]),
     Slice(&'a [u8]),
 }
 
@@ -23,7 +23,7 @@ impl<'a> Preimage<'a> {
     /// Returns a 32-byte slice.
     fn as_bytes(&self) -> &[u8] {
         match self {
-            Preimage::Digest(digest) => digest.as_ref(),
+            Preimage::Digest(digest) => digest,
             Preimage::Slice(slice) => slice,
         }
     }
@@ -31,8 +31,8 @@ impl<'a> Preimage<'a> {
 
 /// A node that has had a left child supplied, but not a right child.
 struct HalfNode {
-    /// The hasher context.
-    context: Context,
+    /// The value of the left child.
+    left: [u8; HASH_LEN],
     /// The tree id of the node. The root node has in id of `1` and ids increase moving down the
     /// tree from left to right.
     id: usize,
@@ -41,17 +41,16 @@ struct HalfNode {
 impl HalfNode {
     /// Create a new half-node from the given `left` value.
     fn new(id: usize, left: Preimage) -> Self {
-        let mut context = Context::new(&SHA256);
-        context.update(left.as_bytes());
+        let mut bytes = [0; HASH_LEN];
+        bytes.copy_from_slice(left.as_bytes());
 
-        Self { context, id }
+        Self { left: bytes, id }
     }
 
     /// Complete the half-node by providing a `right` value. Returns a digest of the left and right
     /// nodes.
-    fn finish(mut self, right: Preimage) -> Digest {
-        self.context.update(right.as_bytes());
-        self.context.finish()
+    fn finish(self, right: Preimage) -> [u8; HASH_LEN] {
+        hash32_concat(&self.left, right.as_bytes())
     }
 }
 
@@ -362,13 +361,13 @@ mod test {
     use super::*;
     use crate::merkleize_padded;
 
-    /// This test is just to ensure that the stack size of the `Context` remains the same. We choose
+    /// This test is just to ensure that the stack size of the `HalfNode` remains the same. We choose
     /// our smallvec size based upon this, so it's good to know if it suddenly changes in size.
     #[test]
     fn context_size() {
         assert_eq!(
             mem::size_of::<HalfNode>(),
-            216 + 8,
+            HASH_LEN + 8,
             "Halfnode size should be as expected"
         );
     }
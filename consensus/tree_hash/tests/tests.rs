@@ -0,0 +1,144 @@
+use eth2_hashing::hash;
+use tree_hash::{mix_in_selector, Hash256, TreeHash, BYTES_PER_CHUNK};
+use tree_hash_derive::TreeHash;
+
+#[derive(TreeHash)]
+struct Newtype(u64);
+
+#[test]
+fn newtype_hashes_as_field() {
+    assert_eq!(Newtype(42).tree_hash_root(), 42_u64.tree_hash_root());
+    assert_eq!(
+        Newtype::tree_hash_type(),
+        <u64 as TreeHash>::tree_hash_type()
+    );
+    assert_eq!(
+        Newtype(42).tree_hash_packed_encoding(),
+        42_u64.tree_hash_packed_encoding()
+    );
+}
+
+#[derive(TreeHash)]
+struct Container {
+    a: u64,
+    b: Hash256,
+}
+
+#[derive(TreeHash)]
+enum Union {
+    A(u64),
+    B(Container),
+}
+
+#[test]
+fn union_mixes_in_selector() {
+    let container = || Container {
+        a: 1,
+        b: Hash256::repeat_byte(42),
+    };
+
+    assert_eq!(
+        Union::A(7).tree_hash_root(),
+        mix_in_selector(&7_u64.tree_hash_root(), 0).unwrap()
+    );
+    assert_eq!(
+        Union::B(container()).tree_hash_root(),
+        mix_in_selector(&container().tree_hash_root(), 1).unwrap()
+    );
+}
+
+/// Returns `hash(root + selector)` as defined for unions in the SSZ spec, without using
+/// `mix_in_selector`.
+fn spec_union_root(root: Hash256, selector: u8) -> Hash256 {
+    let mut preimage = root.as_bytes().to_vec();
+    let mut selector_chunk = [0; BYTES_PER_CHUNK];
+    selector_chunk[0] = selector;
+    preimage.extend_from_slice(&selector_chunk);
+    Hash256::from_slice(&hash(&preimage))
+}
+
+#[test]
+fn union_root_matches_spec() {
+    let container = Container {
+        a: 1,
+        b: Hash256::repeat_byte(42),
+    };
+    let container_root = container.tree_hash_root();
+
+    assert_eq!(
+        Union::A(7).tree_hash_root(),
+        spec_union_root(7_u64.tree_hash_root(), 0)
+    );
+    assert_eq!(
+        Union::B(container).tree_hash_root(),
+        spec_union_root(container_root, 1)
+    );
+
+    // The zero value with selector zero is the hash of two zero chunks.
+    assert_eq!(
+        Union::A(0).tree_hash_root(),
+        Hash256::from_slice(&hash(&[0; 2 * BYTES_PER_CHUNK]))
+    );
+}
+
+#[test]
+fn union_selectors_are_distinct() {
+    #[derive(TreeHash)]
+    enum SameType {
+        A(u64),
+        B(u64),
+    }
+
+    assert_ne!(
+        SameType::A(7).tree_hash_root(),
+        SameType::B(7).tree_hash_root()
+    );
+    assert_eq!(
+        SameType::B(7).tree_hash_root(),
+        spec_union_root(7_u64.tree_hash_root(), 1)
+    );
+}
+
+#[test]
+fn union_field_root() {
+    #[derive(TreeHash)]
+    struct UnionField {
+        a: u64,
+        b: Union,
+    }
+
+    let field = UnionField {
+        a: 3,
+        b: Union::A(7),
+    };
+
+    let expected = Hash256::from_slice(&hash(
+        &[
+            3_u64.tree_hash_root().as_bytes(),
+            Union::A(7).tree_hash_root().as_bytes(),
+        ]
+        .concat(),
+    ));
+    assert_eq!(field.tree_hash_root(), expected);
+}
+
+#[derive(TreeHash)]
+#[tree_hash(enum_behaviour = "transparent")]
+enum Transparent {
+    A(u64),
+    B(Container),
+}
+
+#[test]
+fn transparent_enum_hashes_as_variant() {
+    let container = || Container {
+        a: 1,
+        b: Hash256::repeat_byte(42),
+    };
+
+    assert_eq!(Transparent::A(7).tree_hash_root(), 7_u64.tree_hash_root());
+    assert_eq!(
+        Transparent::B(container()).tree_hash_root(),
+        container().tree_hash_root()
+    );
+}
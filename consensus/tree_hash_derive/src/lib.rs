@@ -5,6 +5,9 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, Attribute, DeriveInput, Meta};
 
+/// The highest selector permitted in an SSZ union (`tree_hash::MAX_UNION_SELECTOR`).
+const MAX_UNION_SELECTOR: u8 = 127;
+
 /// Return a Vec of `syn::Ident` for each named field in the struct, whilst filtering out fields
 /// that should not be hashed.
 ///
@@ -78,21 +81,107 @@ fn should_skip_hashing(field: &syn::Field) -> bool {
     })
 }
 
-/// Implements `tree_hash::TreeHash` for some `struct`.
+/// The hashing of an enum, set with the `#[tree_hash(enum_behaviour = "...")]` attribute.
+#[derive(Clone, Copy)]
+enum EnumBehaviour {
+    /// An SSZ union: the root of the variant's value mixed in with its selector (the index of the
+    /// variant).
+    Union,
+    /// The root of the variant's value.
+    Transparent,
+}
+
+/// Returns the behaviour declared by a `#[tree_hash(enum_behaviour = "...")]` attribute, or
+/// `Union` if there is none.
+///
+/// # Panics
+/// An unknown behaviour will raise a panic at compile time.
+fn get_enum_behaviour(attrs: &[Attribute]) -> EnumBehaviour {
+    use syn::{Lit, MetaNameValue, NestedMeta};
+
+    let behaviours = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("tree_hash"))
+        .flat_map(|attr| attr.parse_meta())
+        .flat_map(|meta| match meta {
+            Meta::List(list) => list.nested.into_iter().collect::<Vec<_>>(),
+            _ => vec![],
+        })
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(behaviour),
+                ..
+            })) if path.is_ident("enum_behaviour") => Some(behaviour.value()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    match behaviours.as_slice() {
+        [] => EnumBehaviour::Union,
+        [behaviour] if behaviour == "union" => EnumBehaviour::Union,
+        [behaviour] if behaviour == "transparent" => EnumBehaviour::Transparent,
+        _ => panic!("tree_hash_derive enum_behaviour must be \"union\" or \"transparent\"."),
+    }
+}
+
+/// Returns the ident of each variant of an enum.
+///
+/// # Panics
+/// Any variant that does not have exactly one unnamed field will raise a panic at compile time, as
+/// will an enum with more variants than there are union selectors.
+fn get_enum_variant_idents(enum_data: &syn::DataEnum) -> Vec<&syn::Ident> {
+    let idents = enum_data
+        .variants
+        .iter()
+        .map(|variant| match &variant.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &variant.ident,
+            _ => {
+                panic!("tree_hash_derive only supports enum variants with a single unnamed field.")
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if idents.len() > MAX_UNION_SELECTOR as usize + 1 {
+        panic!("tree_hash_derive enums may have at most 128 variants.");
+    }
+
+    idents
+}
+
+/// Implements `tree_hash::TreeHash` for some `struct` or `enum`.
 ///
-/// Fields are hashed in the order they are defined.
+/// Fields are hashed in the order they are defined. A tuple struct with a single field (a newtype)
+/// is hashed exactly as its field.
+///
+/// Each variant of an enum must have a single unnamed field. By default an enum is hashed as an
+/// SSZ union, with the index of the variant as its selector.
+///
+/// ## Enum attributes
+///
+/// - `#[tree_hash(enum_behaviour = "union")]`: hash as an SSZ union (the default).
+/// - `#[tree_hash(enum_behaviour = "transparent")]`: hash as the value of the variant.
 #[proc_macro_derive(TreeHash, attributes(tree_hash))]
 pub fn tree_hash_derive(input: TokenStream) -> TokenStream {
     let item = parse_macro_input!(input as DeriveInput);
 
+    match &item.data {
+        syn::Data::Struct(s) => match &s.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                tree_hash_derive_newtype(&item, &fields.unnamed[0].ty)
+            }
+            _ => tree_hash_derive_struct(&item, s),
+        },
+        syn::Data::Enum(e) => tree_hash_derive_enum(&item, e, get_enum_behaviour(&item.attrs)),
+        _ => panic!("tree_hash_derive only supports structs and enums."),
+    }
+}
+
+/// Implements `tree_hash::TreeHash` for a `struct` with named fields.
+fn tree_hash_derive_struct(item: &DeriveInput, struct_data: &syn::DataStruct) -> TokenStream {
     let name = &item.ident;
     let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
 
-    let struct_data = match &item.data {
-        syn::Data::Struct(s) => s,
-        _ => panic!("tree_hash_derive only supports structs."),
-    };
-
     let idents = get_hashable_fields(&struct_data);
     let num_leaves = idents.len();
 
@@ -124,3 +213,79 @@ pub fn tree_hash_derive(input: TokenStream) -> TokenStream {
     };
     output.into()
 }
+
+/// Implements `tree_hash::TreeHash` for a tuple struct with a single field.
+fn tree_hash_derive_newtype(item: &DeriveInput, field_type: &syn::Type) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
+
+    let output = quote! {
+        impl #impl_generics tree_hash::TreeHash for #name #ty_generics #where_clause {
+            fn tree_hash_type() -> tree_hash::TreeHashType {
+                <#field_type as tree_hash::TreeHash>::tree_hash_type()
+            }
+
+            fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+                self.0.tree_hash_packed_encoding()
+            }
+
+            fn tree_hash_packing_factor() -> usize {
+                <#field_type as tree_hash::TreeHash>::tree_hash_packing_factor()
+            }
+
+            fn tree_hash_root(&self) -> tree_hash::Hash256 {
+                self.0.tree_hash_root()
+            }
+        }
+    };
+    output.into()
+}
+
+/// Implements `tree_hash::TreeHash` for an `enum`.
+fn tree_hash_derive_enum(
+    item: &DeriveInput,
+    enum_data: &syn::DataEnum,
+    behaviour: EnumBehaviour,
+) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
+
+    let variant_idents = get_enum_variant_idents(enum_data);
+    let roots = (0..variant_idents.len())
+        .map(|selector| match behaviour {
+            EnumBehaviour::Union => {
+                let selector = selector as u8;
+                quote! {
+                    tree_hash::mix_in_selector(&inner.tree_hash_root(), #selector)
+                        .expect("tree hash derive should not permit out-of-bounds selectors")
+                }
+            }
+            EnumBehaviour::Transparent => quote! { inner.tree_hash_root() },
+        })
+        .collect::<Vec<_>>();
+
+    let output = quote! {
+        impl #impl_generics tree_hash::TreeHash for #name #ty_generics #where_clause {
+            fn tree_hash_type() -> tree_hash::TreeHashType {
+                tree_hash::TreeHashType::Container
+            }
+
+            fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+                unreachable!("Enum should never be packed.")
+            }
+
+            fn tree_hash_packing_factor() -> usize {
+                unreachable!("Enum should never be packed.")
+            }
+
+            fn tree_hash_root(&self) -> tree_hash::Hash256 {
+                match self {
+                    #(
+                        #name::#variant_idents(inner) => #roots,
+                    )*
+                }
+            }
+        }
+    };
+    output.into()
+}
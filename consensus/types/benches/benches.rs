@@ -1,7 +1,9 @@
 use criterion::Criterion;
 use criterion::{black_box, criterion_group, criterion_main, Benchmark};
 use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use ssz::{Decode, Encode};
+use std::sync::Arc;
 use types::{
     test_utils::generate_deterministic_keypair, BeaconState, Epoch, Eth1Data, EthSpec, Hash256,
     MainnetEthSpec, Validator,
@@ -169,5 +171,93 @@ fn all_benches(c: &mut Criterion) {
     );
 }
 
-criterion_group!(benches, all_benches,);
+/// Compares hashing the validators and balances in parallel against hashing them on a single
+/// thread, both when building the tree hash cache from scratch and when a large range of the
+/// balances have changed (e.g., at an epoch boundary).
+fn tree_hash_benches(c: &mut Criterion) {
+    let validator_count = 16_384;
+    let spec = &MainnetEthSpec::default_spec();
+
+    let mut state = get_state::<MainnetEthSpec>(validator_count);
+    state.build_all_caches(spec).expect("should build caches");
+
+    // All of the rayon work happens on a single thread when run inside this pool.
+    let single_thread_pool: Arc<ThreadPool> = Arc::new(
+        ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .expect("should build thread pool"),
+    );
+
+    for &(name, pool) in &[
+        ("parallel", None),
+        ("single_thread", Some(&single_thread_pool)),
+    ] {
+        let mut inner_state = state.clone();
+        inner_state.drop_all_caches();
+        let pool = pool.cloned();
+        c.bench(
+            &format!("{}_validators", validator_count),
+            Benchmark::new(
+                format!("non_initialized_cached_tree_hash_{}/beacon_state", name),
+                move |b| {
+                    b.iter_batched_ref(
+                        || inner_state.clone(),
+                        |state| {
+                            let root = match &pool {
+                                Some(pool) => pool.install(|| state.update_tree_hash_cache()),
+                                None => state.update_tree_hash_cache(),
+                            };
+                            black_box(root.expect("should update tree hash"))
+                        },
+                        criterion::BatchSize::SmallInput,
+                    )
+                },
+            )
+            .sample_size(10),
+        );
+    }
+
+    for &(name, pool) in &[
+        ("parallel", None),
+        ("single_thread", Some(&single_thread_pool)),
+    ] {
+        let mut inner_state = state.clone();
+        inner_state
+            .update_tree_hash_cache()
+            .expect("should build tree hash cache");
+        let pool = pool.cloned();
+        c.bench(
+            &format!("{}_validators", validator_count),
+            Benchmark::new(
+                format!(
+                    "cached_tree_hash_with_changed_balances_{}/beacon_state",
+                    name
+                ),
+                move |b| {
+                    b.iter_batched_ref(
+                        || {
+                            let mut state = inner_state.clone();
+                            for balance in state.balances.iter_mut() {
+                                *balance += 1;
+                            }
+                            state
+                        },
+                        |state| {
+                            let root = match &pool {
+                                Some(pool) => pool.install(|| state.update_tree_hash_cache()),
+                                None => state.update_tree_hash_cache(),
+                            };
+                            black_box(root.expect("should update tree hash"))
+                        },
+                        criterion::BatchSize::SmallInput,
+                    )
+                },
+            )
+            .sample_size(10),
+        );
+    }
+}
+
+criterion_group!(benches, all_benches, tree_hash_benches);
 criterion_main!(benches);
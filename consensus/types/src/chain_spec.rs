@@ -72,6 +72,8 @@ pub struct ChainSpec {
     pub genesis_fork_version: [u8; 4],
     #[serde(deserialize_with = "u8_from_hex_str", serialize_with = "u8_to_hex_str")]
     pub bls_withdrawal_prefix_byte: u8,
+    #[serde(deserialize_with = "u8_from_hex_str", serialize_with = "u8_to_hex_str")]
+    pub eth1_address_withdrawal_prefix_byte: u8,
 
     /*
      * Time parameters
@@ -283,6 +285,7 @@ impl ChainSpec {
              */
             genesis_fork_version: [0; 4],
             bls_withdrawal_prefix_byte: 0,
+            eth1_address_withdrawal_prefix_byte: 1,
 
             /*
              * Time parameters
@@ -473,6 +476,13 @@ pub struct YamlConfig {
     genesis_fork_version: [u8; 4],
     #[serde(deserialize_with = "u8_from_hex_str", serialize_with = "u8_to_hex_str")]
     bls_withdrawal_prefix: u8,
+    // Not defined in the v0.12.3 configs, so defaults to the value from later specs.
+    #[serde(
+        default = "default_eth1_address_withdrawal_prefix",
+        deserialize_with = "u8_from_hex_str",
+        serialize_with = "u8_to_hex_str"
+    )]
+    eth1_address_withdrawal_prefix: u8,
     seconds_per_slot: u64,
     min_attestation_inclusion_delay: u64,
     min_seed_lookahead: u64,
@@ -554,6 +564,10 @@ fn default_proportional_slashing_multiplier() -> u64 {
     3
 }
 
+fn default_eth1_address_withdrawal_prefix() -> u8 {
+    1
+}
+
 impl Default for YamlConfig {
     fn default() -> Self {
         let chain_spec = MainnetEthSpec::default_spec();
@@ -585,6 +599,7 @@ impl YamlConfig {
             hysteresis_upward_multiplier: spec.hysteresis_upward_multiplier,
             proportional_slashing_multiplier: spec.proportional_slashing_multiplier,
             bls_withdrawal_prefix: spec.bls_withdrawal_prefix_byte,
+            eth1_address_withdrawal_prefix: spec.eth1_address_withdrawal_prefix_byte,
             seconds_per_slot: spec.milliseconds_per_slot / 1000,
             min_attestation_inclusion_delay: spec.min_attestation_inclusion_delay,
             min_seed_lookahead: spec.min_seed_lookahead.into(),
@@ -697,6 +712,7 @@ impl YamlConfig {
              */
             genesis_fork_version: self.genesis_fork_version,
             bls_withdrawal_prefix_byte: self.bls_withdrawal_prefix,
+            eth1_address_withdrawal_prefix_byte: self.eth1_address_withdrawal_prefix,
             /*
              * Time parameters
              */
@@ -40,6 +40,7 @@ pub mod signed_beacon_block;
 pub mod signed_beacon_block_header;
 pub mod signed_voluntary_exit;
 pub mod signing_data;
+pub mod ssz_view;
 pub mod utils;
 pub mod validator;
 pub mod voluntary_exit;
@@ -86,6 +87,7 @@ pub use crate::signed_beacon_block_header::SignedBeaconBlockHeader;
 pub use crate::signed_voluntary_exit::SignedVoluntaryExit;
 pub use crate::signing_data::{SignedRoot, SigningData};
 pub use crate::slot_epoch::{Epoch, Slot};
+pub use crate::ssz_view::{BeaconStateView, SignedBeaconBlockView};
 pub use crate::subnet_id::SubnetId;
 pub use crate::validator::Validator;
 pub use crate::voluntary_exit::VoluntaryExit;
@@ -0,0 +1,223 @@
+//! Views of the SSZ encodings of large containers, which read individual fields without decoding
+//! (and allocating) the whole container.
+use crate::*;
+use ssz::{ContainerView, DecodeError, ListView, SszDecoderBuilder};
+use std::marker::PhantomData;
+
+/// A view of the SSZ encoding of a `SignedBeaconBlock`, which reads the fields of the block
+/// without decoding its body.
+#[derive(Debug, Clone)]
+pub struct SignedBeaconBlockView<'a, E: EthSpec> {
+    message: ContainerView<'a>,
+    _phantom: PhantomData<E>,
+}
+
+impl<'a, E: EthSpec> SignedBeaconBlockView<'a, E> {
+    /// Create a view of the SSZ bytes of a `SignedBeaconBlock`.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut builder = SszDecoderBuilder::new(bytes);
+        builder.register_type::<BeaconBlock<E>>()?;
+        builder.register_type::<Signature>()?;
+        let signed_block = builder.build_view()?;
+
+        let mut builder = SszDecoderBuilder::new(signed_block.field_bytes(0));
+        builder.register_type::<Slot>()?;
+        builder.register_type::<u64>()?;
+        builder.register_type::<Hash256>()?;
+        builder.register_type::<Hash256>()?;
+        builder.register_type::<BeaconBlockBody<E>>()?;
+        let message = builder.build_view()?;
+
+        Ok(Self {
+            message,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn slot(&self) -> Result<Slot, DecodeError> {
+        self.message.decode_field(0)
+    }
+
+    pub fn proposer_index(&self) -> Result<u64, DecodeError> {
+        self.message.decode_field(1)
+    }
+
+    pub fn parent_root(&self) -> Result<Hash256, DecodeError> {
+        self.message.decode_field(2)
+    }
+
+    pub fn state_root(&self) -> Result<Hash256, DecodeError> {
+        self.message.decode_field(3)
+    }
+}
+
+/// A view of the SSZ encoding of a `BeaconState`, which reads individual fields (or the items of
+/// its lists, like `validators[i]`) without decoding the whole state.
+///
+/// The lengths of lists are not checked against their limits.
+#[derive(Debug, Clone)]
+pub struct BeaconStateView<'a, E: EthSpec> {
+    state: ContainerView<'a>,
+    _phantom: PhantomData<E>,
+}
+
+impl<'a, E: EthSpec> BeaconStateView<'a, E> {
+    /// Create a view of the SSZ bytes of a `BeaconState`.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut builder = SszDecoderBuilder::new(bytes);
+
+        builder.register_type::<u64>()?;
+        builder.register_type::<Hash256>()?;
+        builder.register_type::<Slot>()?;
+        builder.register_type::<Fork>()?;
+        builder.register_type::<BeaconBlockHeader>()?;
+        builder.register_type::<FixedVector<Hash256, E::SlotsPerHistoricalRoot>>()?;
+        builder.register_type::<FixedVector<Hash256, E::SlotsPerHistoricalRoot>>()?;
+        builder.register_type::<VariableList<Hash256, E::HistoricalRootsLimit>>()?;
+        builder.register_type::<Eth1Data>()?;
+        builder.register_type::<VariableList<Eth1Data, E::SlotsPerEth1VotingPeriod>>()?;
+        builder.register_type::<u64>()?;
+        builder.register_type::<VariableList<Validator, E::ValidatorRegistryLimit>>()?;
+        builder.register_type::<VariableList<u64, E::ValidatorRegistryLimit>>()?;
+        builder.register_type::<FixedVector<Hash256, E::EpochsPerHistoricalVector>>()?;
+        builder.register_type::<FixedVector<u64, E::EpochsPerSlashingsVector>>()?;
+        builder
+            .register_type::<VariableList<PendingAttestation<E>, E::MaxPendingAttestations>>()?;
+        builder
+            .register_type::<VariableList<PendingAttestation<E>, E::MaxPendingAttestations>>()?;
+        builder.register_type::<BitVector<E::JustificationBitsLength>>()?;
+        builder.register_type::<Checkpoint>()?;
+        builder.register_type::<Checkpoint>()?;
+        builder.register_type::<Checkpoint>()?;
+
+        Ok(Self {
+            state: builder.build_view()?,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn genesis_time(&self) -> Result<u64, DecodeError> {
+        self.state.decode_field(0)
+    }
+
+    pub fn genesis_validators_root(&self) -> Result<Hash256, DecodeError> {
+        self.state.decode_field(1)
+    }
+
+    pub fn slot(&self) -> Result<Slot, DecodeError> {
+        self.state.decode_field(2)
+    }
+
+    pub fn fork(&self) -> Result<Fork, DecodeError> {
+        self.state.decode_field(3)
+    }
+
+    pub fn latest_block_header(&self) -> Result<BeaconBlockHeader, DecodeError> {
+        self.state.decode_field(4)
+    }
+
+    pub fn block_roots(&self) -> Result<ListView<'a, Hash256>, DecodeError> {
+        self.state.list_field(5)
+    }
+
+    pub fn state_roots(&self) -> Result<ListView<'a, Hash256>, DecodeError> {
+        self.state.list_field(6)
+    }
+
+    pub fn eth1_deposit_index(&self) -> Result<u64, DecodeError> {
+        self.state.decode_field(10)
+    }
+
+    pub fn validators(&self) -> Result<ListView<'a, Validator>, DecodeError> {
+        self.state.list_field(11)
+    }
+
+    pub fn balances(&self) -> Result<ListView<'a, u64>, DecodeError> {
+        self.state.list_field(12)
+    }
+
+    pub fn previous_justified_checkpoint(&self) -> Result<Checkpoint, DecodeError> {
+        self.state.decode_field(18)
+    }
+
+    pub fn current_justified_checkpoint(&self) -> Result<Checkpoint, DecodeError> {
+        self.state.decode_field(19)
+    }
+
+    pub fn finalized_checkpoint(&self) -> Result<Checkpoint, DecodeError> {
+        self.state.decode_field(20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use ssz::Encode;
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn signed_beacon_block_view() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let block = SignedBeaconBlock::<E>::random_for_test(&mut rng);
+        let bytes = block.as_ssz_bytes();
+        let view = SignedBeaconBlockView::<E>::new(&bytes).unwrap();
+
+        assert_eq!(view.slot(), Ok(block.message.slot));
+        assert_eq!(view.proposer_index(), Ok(block.message.proposer_index));
+        assert_eq!(view.parent_root(), Ok(block.message.parent_root));
+        assert_eq!(view.state_root(), Ok(block.message.state_root));
+
+        assert!(SignedBeaconBlockView::<E>::new(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn beacon_state_view() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let state = BeaconState::<E>::random_for_test(&mut rng);
+        let bytes = state.as_ssz_bytes();
+        let view = BeaconStateView::<E>::new(&bytes).unwrap();
+
+        assert_eq!(view.genesis_time(), Ok(state.genesis_time));
+        assert_eq!(
+            view.genesis_validators_root(),
+            Ok(state.genesis_validators_root)
+        );
+        assert_eq!(view.slot(), Ok(state.slot));
+        assert_eq!(view.fork(), Ok(state.fork));
+        assert_eq!(view.latest_block_header(), Ok(state.latest_block_header));
+        assert_eq!(view.eth1_deposit_index(), Ok(state.eth1_deposit_index));
+        assert_eq!(
+            view.previous_justified_checkpoint(),
+            Ok(state.previous_justified_checkpoint)
+        );
+        assert_eq!(
+            view.current_justified_checkpoint(),
+            Ok(state.current_justified_checkpoint)
+        );
+        assert_eq!(view.finalized_checkpoint(), Ok(state.finalized_checkpoint));
+
+        let block_roots = view.block_roots().unwrap();
+        assert_eq!(block_roots.len(), state.block_roots.len());
+        assert_eq!(block_roots.get(3), Some(Ok(state.block_roots[3])));
+        assert_eq!(
+            view.state_roots().unwrap().get(5),
+            Some(Ok(state.state_roots[5]))
+        );
+
+        let validators = view.validators().unwrap();
+        assert_eq!(validators.len(), state.validators.len());
+        assert_eq!(
+            validators.iter().collect::<Result<Vec<_>, _>>(),
+            Ok(state.validators.to_vec())
+        );
+        assert_eq!(
+            view.balances()
+                .unwrap()
+                .iter()
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(state.balances.to_vec())
+        );
+    }
+}
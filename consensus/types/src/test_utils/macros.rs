@@ -14,7 +14,7 @@ macro_rules! ssz_tests {
         #[test]
         pub fn test_ssz_round_trip() {
             use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
-            use ssz::{ssz_encode, Decode};
+            use ssz::{ssz_encode, Decode, Encode};
 
             let mut rng = XorShiftRng::from_seed([42; 16]);
             let original = <$type>::random_for_test(&mut rng);
@@ -24,6 +24,10 @@ macro_rules! ssz_tests {
             let decoded = <$type>::from_ssz_bytes(&bytes).unwrap();
 
             assert_eq!(original, decoded);
+
+            let mut written = vec![];
+            original.ssz_write(&mut written).unwrap();
+            assert_eq!(written, bytes);
         }
     };
 }
@@ -0,0 +1,244 @@
+use crate::{
+    generic_aggregate_signature::{GenericAggregateSignature, TAggregateSignature},
+    generic_public_key::{GenericPublicKey, TPublicKey},
+    generic_secret_key::{GenericSecretKey, TSecretKey},
+    generic_signature::{GenericSignature, TSignature},
+    Error, Hash256, ZeroizeHash, SECRET_KEY_BYTES_LEN,
+};
+use num_bigint_dig::BigUint;
+use zeroize::Zeroize;
+
+/// The order of the BLS 12-381 curve. All secret key arithmetic is performed modulo this value.
+const CURVE_ORDER: &str =
+    "52435875175126190479447740508185965837690552500527637822603658699938581184513";
+
+/// A share of a `GenericSecretKey` that has been split using Shamir's secret sharing.
+///
+/// Signatures from any `threshold` shares of a key may be combined into a signature from the
+/// original key (see `combine_signature_shares`), whilst fewer than `threshold` shares reveal
+/// nothing about the original key.
+#[derive(Clone)]
+pub struct GenericSecretKeyShare<Sig, Pub, Sec> {
+    /// The point at which the secret sharing polynomial was evaluated. Never zero.
+    index: u64,
+    secret_key: GenericSecretKey<Sig, Pub, Sec>,
+}
+
+impl<Sig, Pub, Sec> GenericSecretKeyShare<Sig, Pub, Sec>
+where
+    Sig: TSignature<Pub>,
+    Pub: TPublicKey,
+    Sec: TSecretKey<Sig, Pub>,
+{
+    /// Splits `secret_key` into `share_count` shares, where signatures from any `threshold` of
+    /// those shares can be combined into a signature from `secret_key`.
+    ///
+    /// The shares are given the indices `1..=share_count`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if `threshold == 0` or `threshold > share_count`.
+    pub fn split(
+        secret_key: &GenericSecretKey<Sig, Pub, Sec>,
+        threshold: usize,
+        share_count: usize,
+    ) -> Result<Vec<Self>, Error> {
+        if threshold == 0 || threshold > share_count {
+            return Err(Error::InvalidThreshold {
+                threshold,
+                share_count,
+            });
+        }
+
+        let r = curve_order();
+
+        // The coefficients of the polynomial `f(x) = a_0 + a_1 * x + ... + a_{t-1} * x^{t-1}`,
+        // where `a_0` is the secret key and all other coefficients are random.
+        let mut coefficients = Vec::with_capacity(threshold);
+        coefficients.push(BigUint::from_bytes_be(secret_key.serialize().as_bytes()));
+        for _ in 1..threshold {
+            let random = GenericSecretKey::<Sig, Pub, Sec>::random();
+            coefficients.push(BigUint::from_bytes_be(random.serialize().as_bytes()));
+        }
+
+        let shares = (1..=share_count as u64)
+            .map(|index| {
+                let x = BigUint::from(index);
+                // Evaluate `f(index)` using Horner's method.
+                let mut y = coefficients
+                    .iter()
+                    .rev()
+                    .fold(BigUint::from(0_u64), |acc, a| (acc * &x + a) % &r);
+                let share_secret_key = secret_key_from_biguint(&y);
+                y.zeroize();
+
+                Ok(Self {
+                    index,
+                    secret_key: share_secret_key?,
+                })
+            })
+            .collect();
+
+        coefficients.iter_mut().for_each(Zeroize::zeroize);
+
+        shares
+    }
+
+    /// Instantiates `Self` from a share that was previously generated by `Self::split`.
+    pub fn from_components(index: u64, secret_key: GenericSecretKey<Sig, Pub, Sec>) -> Self {
+        Self { index, secret_key }
+    }
+
+    /// Returns the index of this share.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Returns the secret key of this share.
+    ///
+    /// ## Note
+    ///
+    /// A signature from this key is only useful when combined with the signatures of other shares.
+    pub fn secret_key(&self) -> &GenericSecretKey<Sig, Pub, Sec> {
+        &self.secret_key
+    }
+
+    /// Returns the public key of this share, which may be used to verify its signature shares.
+    pub fn public_key(&self) -> GenericPublicKey<Pub> {
+        self.secret_key.public_key()
+    }
+
+    /// Signs `msg`, returning a signature share that may be combined with the signature shares of
+    /// other shares.
+    pub fn sign(&self, msg: Hash256) -> GenericSignatureShare<Pub, Sig> {
+        GenericSignatureShare {
+            index: self.index,
+            signature: self.secret_key.sign(msg),
+        }
+    }
+}
+
+/// A signature produced by a `GenericSecretKeyShare`.
+#[derive(Clone, PartialEq)]
+pub struct GenericSignatureShare<Pub, Sig> {
+    /// The index of the `GenericSecretKeyShare` that produced the signature.
+    pub index: u64,
+    pub signature: GenericSignature<Pub, Sig>,
+}
+
+/// Combines signature shares across the same message into a signature from the original secret
+/// key, using Lagrange interpolation at zero.
+///
+/// The result is only valid if at least `threshold` shares of the same key are supplied.
+///
+/// ## Errors
+///
+/// - `shares` is empty.
+/// - Any share has an index of zero or the same index as another share.
+/// - Any share has an empty signature.
+pub fn combine_signature_shares<Pub, AggPub, Sig, AggSig>(
+    shares: &[GenericSignatureShare<Pub, Sig>],
+) -> Result<GenericSignature<Pub, Sig>, Error>
+where
+    Pub: Clone,
+    AggPub: Clone,
+    Sig: TSignature<Pub>,
+    AggSig: TAggregateSignature<Pub, AggPub, Sig>,
+{
+    if shares.is_empty() {
+        return Err(Error::NoSignatureShares);
+    }
+
+    for (i, share) in shares.iter().enumerate() {
+        if share.index == 0 || shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(Error::InvalidShareIndex(share.index));
+        }
+
+        if share.signature.is_empty() {
+            return Err(Error::EmptySignatureShare(share.index));
+        }
+    }
+
+    let r = curve_order();
+
+    let mut combined = GenericAggregateSignature::<Pub, AggPub, Sig, AggSig>::infinity();
+    for share in shares {
+        let coefficient =
+            lagrange_coefficient(share.index, shares.iter().map(|share| share.index), &r);
+        combined.add_assign_aggregate(&mul_signature(&share.signature, &coefficient));
+    }
+
+    GenericSignature::deserialize(&combined.serialize())
+}
+
+fn curve_order() -> BigUint {
+    BigUint::parse_bytes(CURVE_ORDER.as_bytes(), 10).expect("must be able to parse curve order")
+}
+
+/// Returns the secret key with the value `int`, which must be less than the curve order.
+fn secret_key_from_biguint<Sig, Pub, Sec>(
+    int: &BigUint,
+) -> Result<GenericSecretKey<Sig, Pub, Sec>, Error>
+where
+    Sig: TSignature<Pub>,
+    Pub: TPublicKey,
+    Sec: TSecretKey<Sig, Pub>,
+{
+    let mut bytes = int.to_bytes_be();
+    let mut secret = ZeroizeHash::zero();
+    secret.as_mut_bytes()[SECRET_KEY_BYTES_LEN - bytes.len()..].copy_from_slice(&bytes);
+    bytes.zeroize();
+
+    GenericSecretKey::deserialize(secret.as_bytes())
+}
+
+/// Returns the Lagrange basis polynomial for the share at `index`, evaluated at zero.
+///
+/// `indices` must contain distinct, non-zero values.
+fn lagrange_coefficient(index: u64, indices: impl Iterator<Item = u64>, r: &BigUint) -> BigUint {
+    let x_i = BigUint::from(index);
+
+    let (numerator, denominator) = indices.filter(|j| *j != index).fold(
+        (BigUint::from(1_u64), BigUint::from(1_u64)),
+        |(numerator, denominator), j| {
+            let x_j = BigUint::from(j);
+            let difference = (&x_j + r - &x_i) % r;
+            ((numerator * &x_j) % r, (denominator * difference) % r)
+        },
+    );
+
+    // Since `r` is prime, the inverse of the denominator is `denominator^(r - 2)`.
+    let inverse = denominator.modpow(&(r - BigUint::from(2_u64)), r);
+
+    (numerator * inverse) % r
+}
+
+/// Returns `signature * scalar`.
+///
+/// The backends only expose point addition, so this is implemented with double-and-add.
+fn mul_signature<Pub, AggPub, Sig, AggSig>(
+    signature: &GenericSignature<Pub, Sig>,
+    scalar: &BigUint,
+) -> GenericAggregateSignature<Pub, AggPub, Sig, AggSig>
+where
+    Pub: Clone,
+    AggPub: Clone,
+    Sig: TSignature<Pub>,
+    AggSig: TAggregateSignature<Pub, AggPub, Sig>,
+{
+    let mut result = GenericAggregateSignature::infinity();
+    let mut addend = GenericAggregateSignature::infinity();
+    addend.add_assign(signature);
+
+    for byte in scalar.to_bytes_le() {
+        for bit in 0..8 {
+            if (byte >> bit) & 1 == 1 {
+                result.add_assign_aggregate(&addend);
+            }
+            let double = addend.clone();
+            addend.add_assign_aggregate(&double);
+        }
+    }
+
+    result
+}
@@ -12,3 +12,15 @@ pub fn get_withdrawal_credentials(pubkey: &PublicKey, prefix_byte: u8) -> Vec<u8
 
     prefixed
 }
+
+/// Returns the eth1 address withdrawal credentials for a given eth1 `address`.
+///
+/// The credentials are `prefix_byte`, followed by 11 zero bytes, followed by the 20 bytes of
+/// `address`.
+pub fn get_eth1_withdrawal_credentials(address: &[u8; 20], prefix_byte: u8) -> Vec<u8> {
+    let mut prefixed = vec![prefix_byte];
+    prefixed.extend_from_slice(&[0; 11]);
+    prefixed.extend_from_slice(address);
+
+    prefixed
+}
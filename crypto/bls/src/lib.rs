@@ -30,6 +30,7 @@ mod generic_secret_key;
 mod generic_signature;
 mod generic_signature_bytes;
 mod generic_signature_set;
+mod generic_threshold;
 mod get_withdrawal_credentials;
 mod zeroize_hash;
 
@@ -38,7 +39,7 @@ pub mod impls;
 pub use generic_public_key::{INFINITY_PUBLIC_KEY, PUBLIC_KEY_BYTES_LEN};
 pub use generic_secret_key::SECRET_KEY_BYTES_LEN;
 pub use generic_signature::{INFINITY_SIGNATURE, SIGNATURE_BYTES_LEN};
-pub use get_withdrawal_credentials::get_withdrawal_credentials;
+pub use get_withdrawal_credentials::{get_eth1_withdrawal_credentials, get_withdrawal_credentials};
 pub use zeroize_hash::ZeroizeHash;
 
 use blst::BLST_ERROR as BlstError;
@@ -56,6 +57,17 @@ pub enum Error {
     InvalidByteLength { got: usize, expected: usize },
     /// The provided secret key bytes were an incorrect length.
     InvalidSecretKeyLength { got: usize, expected: usize },
+    /// The threshold for splitting a secret key was zero or greater than the number of shares.
+    InvalidThreshold {
+        threshold: usize,
+        share_count: usize,
+    },
+    /// No signature shares were provided to combine.
+    NoSignatureShares,
+    /// A signature share had an index of zero or the same index as another share.
+    InvalidShareIndex(u64),
+    /// A signature share had an empty signature.
+    EmptySignatureShare(u64),
 }
 
 impl From<AmclError> for Error {
@@ -79,6 +91,7 @@ pub mod generics {
     pub use crate::generic_secret_key::GenericSecretKey;
     pub use crate::generic_signature::GenericSignature;
     pub use crate::generic_signature_bytes::GenericSignatureBytes;
+    pub use crate::generic_threshold::{GenericSecretKeyShare, GenericSignatureShare};
 }
 
 /// Defines all the fundamental BLS points which should be exported by this crate by making
@@ -114,6 +127,26 @@ macro_rules! define_mod {
                 bls_variant::SecretKey,
                 bls_variant::Signature,
             >;
+            pub type SecretKeyShare = GenericSecretKeyShare<
+                bls_variant::Signature,
+                bls_variant::PublicKey,
+                bls_variant::SecretKey,
+            >;
+            pub type SignatureShare =
+                GenericSignatureShare<bls_variant::PublicKey, bls_variant::Signature>;
+
+            /// Combines signature shares across the same message into a signature from the
+            /// secret key that was split into `SecretKeyShare`s.
+            pub fn combine_signature_shares(
+                shares: &[SignatureShare],
+            ) -> Result<Signature, crate::Error> {
+                crate::generic_threshold::combine_signature_shares::<
+                    _,
+                    bls_variant::AggregatePublicKey,
+                    _,
+                    bls_variant::AggregateSignature,
+                >(shares)
+            }
         }
     };
 }
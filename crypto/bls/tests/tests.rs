@@ -601,6 +601,108 @@ macro_rules! test_suite {
                 .push_valid_set(2)
                 .run_checks()
         }
+
+        fn signature_shares(shares: &[SecretKeyShare], msg: Hash256) -> Vec<SignatureShare> {
+            shares.iter().map(|share| share.sign(msg)).collect()
+        }
+
+        #[test]
+        fn threshold_signature_from_any_subset_of_shares() {
+            let msg = Hash256::from_low_u64_be(42);
+            let secret = secret_from_u64(42);
+            let public = secret.public_key();
+            let expected = secret.sign(msg);
+
+            let shares = SecretKeyShare::split(&secret, 3, 5).unwrap();
+            assert_eq!(shares.len(), 5);
+            let sig_shares = signature_shares(&shares, msg);
+
+            for share in &sig_shares {
+                assert!(share
+                    .signature
+                    .verify(&shares[share.index as usize - 1].public_key(), msg));
+                assert!(!share.signature.verify(&public, msg));
+            }
+
+            for subset in &[[0, 1, 2], [0, 2, 4], [4, 3, 1]] {
+                let subset = subset
+                    .iter()
+                    .map(|i| sig_shares[*i].clone())
+                    .collect::<Vec<_>>();
+                let combined = combine_signature_shares(&subset).unwrap();
+                assert!(combined.verify(&public, msg));
+                assert_eq!(combined, expected);
+            }
+
+            let combined = combine_signature_shares(&sig_shares).unwrap();
+            assert!(combined.verify(&public, msg));
+        }
+
+        #[test]
+        fn threshold_signature_with_too_few_shares() {
+            let msg = Hash256::from_low_u64_be(42);
+            let secret = secret_from_u64(42);
+            let shares = SecretKeyShare::split(&secret, 3, 5).unwrap();
+            let sig_shares = signature_shares(&shares[..2], msg);
+
+            let combined = combine_signature_shares(&sig_shares).unwrap();
+            assert!(!combined.verify(&secret.public_key(), msg));
+        }
+
+        #[test]
+        fn threshold_signature_one_of_one() {
+            let msg = Hash256::from_low_u64_be(42);
+            let secret = secret_from_u64(42);
+            let shares = SecretKeyShare::split(&secret, 1, 1).unwrap();
+
+            assert_eq!(
+                shares[0].public_key(),
+                secret.public_key(),
+                "a 1-of-1 share is the original key"
+            );
+            assert_eq!(
+                combine_signature_shares(&signature_shares(&shares, msg)).unwrap(),
+                secret.sign(msg)
+            );
+        }
+
+        #[test]
+        fn threshold_split_invalid_threshold() {
+            let secret = secret_from_u64(42);
+            assert!(SecretKeyShare::split(&secret, 0, 3).is_err());
+            assert!(SecretKeyShare::split(&secret, 4, 3).is_err());
+        }
+
+        #[test]
+        fn threshold_combine_invalid_shares() {
+            let msg = Hash256::from_low_u64_be(42);
+            let secret = secret_from_u64(42);
+            let shares = SecretKeyShare::split(&secret, 2, 3).unwrap();
+            let sig_shares = signature_shares(&shares, msg);
+
+            assert_eq!(
+                combine_signature_shares(&[]),
+                Err(bls::Error::NoSignatureShares)
+            );
+            assert_eq!(
+                combine_signature_shares(&[sig_shares[0].clone(), sig_shares[0].clone()]),
+                Err(bls::Error::InvalidShareIndex(1))
+            );
+
+            let mut zero_index = sig_shares[1].clone();
+            zero_index.index = 0;
+            assert_eq!(
+                combine_signature_shares(&[sig_shares[0].clone(), zero_index]),
+                Err(bls::Error::InvalidShareIndex(0))
+            );
+
+            let mut empty = sig_shares[1].clone();
+            empty.signature = Signature::empty();
+            assert_eq!(
+                combine_signature_shares(&[sig_shares[0].clone(), empty]),
+                Err(bls::Error::EmptySignatureShare(2))
+            );
+        }
     };
 }
 
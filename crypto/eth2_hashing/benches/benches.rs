@@ -0,0 +1,46 @@
+use criterion::{black_box, criterion_group, criterion_main, Benchmark, Criterion, Throughput};
+use eth2_hashing::{DynamicImpl, RingImpl, Sha256, Sha2CrateImpl};
+
+/// The input sizes to benchmark, with the name of each benchmark group.
+///
+/// 64 bytes is the size of a merkle tree node preimage (two concatenated 32-byte hashes).
+const INPUTS: &[(&str, usize)] = &[
+    ("hash_64_bytes", 64),
+    ("hash_1_kib", 1024),
+    ("hash_1_mib", 1024 * 1024),
+];
+
+fn bench_impl<T: Sha256 + 'static>(
+    c: &mut Criterion,
+    group: &str,
+    input_len: usize,
+    name: &str,
+    hasher: T,
+) {
+    let input = vec![42; input_len];
+
+    c.bench(
+        group,
+        Benchmark::new(name, move |b| {
+            b.iter(|| black_box(hasher.hash_fixed(black_box(&input))))
+        })
+        .throughput(Throughput::Bytes(input_len as u64)),
+    );
+}
+
+fn all_benches(c: &mut Criterion) {
+    for &(group, input_len) in INPUTS {
+        bench_impl(c, group, input_len, "sha2", Sha2CrateImpl);
+        bench_impl(c, group, input_len, "ring", RingImpl);
+        bench_impl(
+            c,
+            group,
+            input_len,
+            &format!("dynamic_{:?}", DynamicImpl::best()),
+            DynamicImpl::best(),
+        );
+    }
+}
+
+criterion_group!(benches, all_benches);
+criterion_main!(benches);
@@ -1,63 +1,299 @@
-//! Provides a simple hash function utilizing `ring::digest::SHA256`.
+//! Provides the SHA256 hash function used in Ethereum 2.0.
 //!
-//! The purpose of this crate is to provide an abstraction to whatever hash function Ethereum
-//! 2.0 is using. The hash function has been subject to change during the specification process, so
-//! defining it once in this crate makes it easy to replace.
-
-#[cfg(not(target_arch = "wasm32"))]
-pub use ring::digest::{digest, Context, Digest, SHA256};
+//! The original purpose of this crate was to provide an abstraction over whatever hash function
+//! Ethereum 2.0 is using. The hash function was subject to change during the specification process,
+//! so defining it once in this crate makes it easy to replace.
+//!
+//! The crate now also selects the fastest SHA256 implementation at runtime:
+//!
+//! - `sha2`: used when the CPU has SHA extensions (x86 SHA-NI or the ARMv8 crypto extensions),
+//!   which the `sha2` crate is able to take advantage of.
+//! - `ring`: used otherwise, since its assembly implementation is the fastest without SHA
+//!   extensions.
+//!
+//! On `wasm32` only the `sha2` implementation is available.
 
-#[cfg(target_arch = "wasm32")]
-use sha2::{Digest, Sha256};
+use std::convert::TryInto;
 
-#[cfg(feature = "zero_hash_cache")]
 use lazy_static::lazy_static;
 
+/// The length of a SHA256 digest, in bytes.
+pub const HASH_LEN: usize = 32;
+
 /// Returns the digest of `input`.
 ///
-/// Uses `ring::digest::SHA256`.
+/// Uses the implementation given by `DynamicImpl::best`.
 pub fn hash(input: &[u8]) -> Vec<u8> {
-    #[cfg(not(target_arch = "wasm32"))]
-    let h = digest(&SHA256, input).as_ref().into();
+    DynamicImpl::best().hash(input)
+}
 
-    #[cfg(target_arch = "wasm32")]
-    let h = Sha256::digest(input).as_ref().into();
+/// Returns the digest of `input` as a fixed-size array, avoiding an allocation.
+///
+/// Uses the implementation given by `DynamicImpl::best`.
+pub fn hash_fixed(input: &[u8]) -> [u8; HASH_LEN] {
+    DynamicImpl::best().hash_fixed(input)
+}
 
-    h
+/// Compute the hash of two 32-byte Merkle tree nodes concatenated, returning the parent node.
+///
+/// This is specialised for the 64-byte preimage of a Merkle node: the preimage is built on the
+/// stack and hashed in a single call, instead of being streamed through a `Context`.
+#[inline]
+pub fn hash_2to1(left: &[u8; HASH_LEN], right: &[u8; HASH_LEN]) -> [u8; HASH_LEN] {
+    let mut preimage = [0; HASH_LEN * 2];
+    preimage[..HASH_LEN].copy_from_slice(left);
+    preimage[HASH_LEN..].copy_from_slice(right);
+    hash_fixed(&preimage)
 }
 
 /// Compute the hash of two slices concatenated.
 ///
+/// See `hash_2to1`.
+///
 /// # Panics
 ///
 /// Will panic if either `h1` or `h2` are not 32 bytes in length.
+pub fn hash32_concat(h1: &[u8], h2: &[u8]) -> [u8; HASH_LEN] {
+    hash_2to1(
+        h1.try_into().expect("h1 must be 32 bytes"),
+        h2.try_into().expect("h2 must be 32 bytes"),
+    )
+}
+
+/// A context for incrementally hashing some input.
+pub trait Sha256Context {
+    /// Returns a new, empty context.
+    fn new() -> Self;
+
+    /// Adds `bytes` to the input of the hash.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the digest of all the bytes provided to `update`.
+    fn finalize(self) -> [u8; HASH_LEN];
+}
+
+/// An implementation of the SHA256 hash function.
+pub trait Sha256 {
+    type Context: Sha256Context;
+
+    /// Returns the digest of `input`.
+    fn hash(&self, input: &[u8]) -> Vec<u8>;
+
+    /// Returns the digest of `input` as a fixed-size array.
+    fn hash_fixed(&self, input: &[u8]) -> [u8; HASH_LEN];
+}
+
+/// SHA256 using the `sha2` crate, which is the fastest implementation on CPUs with SHA extensions.
+pub struct Sha2CrateImpl;
+
+impl Sha256Context for sha2::Sha256 {
+    fn new() -> Self {
+        sha2::Digest::new()
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        sha2::Digest::update(self, bytes)
+    }
+
+    fn finalize(self) -> [u8; HASH_LEN] {
+        sha2::Digest::finalize(self).into()
+    }
+}
+
+impl Sha256 for Sha2CrateImpl {
+    type Context = sha2::Sha256;
+
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        self.hash_fixed(input).to_vec()
+    }
+
+    fn hash_fixed(&self, input: &[u8]) -> [u8; HASH_LEN] {
+        <sha2::Sha256 as sha2::Digest>::digest(input).into()
+    }
+}
+
+/// SHA256 using the `ring` crate, which is the fastest implementation on CPUs without SHA
+/// extensions.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct RingImpl;
+
 #[cfg(not(target_arch = "wasm32"))]
-pub fn hash32_concat(h1: &[u8], h2: &[u8]) -> [u8; 32] {
-    let mut context = Context::new(&SHA256);
-    context.update(h1);
-    context.update(h2);
+impl Sha256Context for ring::digest::Context {
+    fn new() -> Self {
+        Self::new(&ring::digest::SHA256)
+    }
 
-    let mut output = [0; 32];
-    output[..].copy_from_slice(context.finish().as_ref());
-    output
+    fn update(&mut self, bytes: &[u8]) {
+        self.update(bytes)
+    }
+
+    fn finalize(self) -> [u8; HASH_LEN] {
+        let mut output = [0; HASH_LEN];
+        output.copy_from_slice(self.finish().as_ref());
+        output
+    }
 }
 
-/// Compute the hash of two slices concatenated.
+#[cfg(not(target_arch = "wasm32"))]
+impl Sha256 for RingImpl {
+    type Context = ring::digest::Context;
+
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        ring::digest::digest(&ring::digest::SHA256, input)
+            .as_ref()
+            .into()
+    }
+
+    fn hash_fixed(&self, input: &[u8]) -> [u8; HASH_LEN] {
+        let mut output = [0; HASH_LEN];
+        output.copy_from_slice(ring::digest::digest(&ring::digest::SHA256, input).as_ref());
+        output
+    }
+}
+
+/// Returns `true` if the CPU supports SHA extensions that are used by the `sha2` crate.
 ///
-/// # Panics
+/// Detection happens at runtime; the standard library caches the result of the CPUID query.
+/// These are the same features that `sha2` (since 0.9.9) checks at runtime before using its
+/// SHA-NI backend.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn have_sha_extensions() -> bool {
+    is_x86_feature_detected!("sha")
+        && is_x86_feature_detected!("sse2")
+        && is_x86_feature_detected!("ssse3")
+        && is_x86_feature_detected!("sse4.1")
+}
+
+/// Returns `true` if the CPU supports the ARMv8 SHA2 instructions that are used by the `sha2`
+/// crate.
 ///
-/// Will panic if either `h1` or `h2` are not 32 bytes in length.
-#[cfg(target_arch = "wasm32")]
-pub fn hash32_concat(h1: &[u8], h2: &[u8]) -> [u8; 32] {
-    let mut preimage = [0; 64];
-    preimage[0..32].copy_from_slice(h1);
-    preimage[32..64].copy_from_slice(h2);
+/// Detection happens at runtime by reading the hardware capabilities provided by the kernel.
+#[cfg(all(target_arch = "aarch64", target_os = "linux"))]
+pub fn have_sha_extensions() -> bool {
+    /// The `HWCAP_SHA2` bit from `arch/arm64/include/uapi/asm/hwcap.h` in the Linux kernel.
+    const HWCAP_SHA2: libc::c_ulong = 1 << 6;
+
+    unsafe { libc::getauxval(libc::AT_HWCAP) & HWCAP_SHA2 != 0 }
+}
 
-    let mut output = [0; 32];
-    output[..].copy_from_slice(&hash(&preimage));
-    output
+/// Returns `true` if the CPU supports SHA extensions that are used by the `sha2` crate.
+///
+/// Runtime detection is not supported on this platform, so this is determined at compile time.
+#[cfg(not(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_os = "linux")
+)))]
+pub fn have_sha_extensions() -> bool {
+    cfg!(all(target_arch = "aarch64", target_feature = "crypto"))
+}
+
+/// A SHA256 implementation that is chosen at runtime.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DynamicImpl {
+    Sha2,
+    #[cfg(not(target_arch = "wasm32"))]
+    Ring,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+lazy_static! {
+    /// The fastest implementation for this CPU, only detected once.
+    static ref BEST_IMPL: DynamicImpl = DynamicImpl::detect();
+}
+
+impl DynamicImpl {
+    /// Detects the fastest implementation for this CPU.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn detect() -> Self {
+        if have_sha_extensions() {
+            Self::Sha2
+        } else {
+            Self::Ring
+        }
+    }
+
+    /// Detects the fastest implementation for this CPU.
+    #[cfg(target_arch = "wasm32")]
+    pub fn detect() -> Self {
+        Self::Sha2
+    }
+
+    /// Returns the fastest implementation for this CPU.
+    ///
+    /// This is the result of `Self::detect`, which is only run once.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[inline(always)]
+    pub fn best() -> Self {
+        *BEST_IMPL
+    }
+
+    /// Returns the fastest implementation for this CPU.
+    #[cfg(target_arch = "wasm32")]
+    #[inline(always)]
+    pub fn best() -> Self {
+        Self::Sha2
+    }
+}
+
+impl Sha256 for DynamicImpl {
+    type Context = DynamicContext;
+
+    #[inline(always)]
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha2 => Sha2CrateImpl.hash(input),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Ring => RingImpl.hash(input),
+        }
+    }
+
+    #[inline(always)]
+    fn hash_fixed(&self, input: &[u8]) -> [u8; HASH_LEN] {
+        match self {
+            Self::Sha2 => Sha2CrateImpl.hash_fixed(input),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Ring => RingImpl.hash_fixed(input),
+        }
+    }
+}
+
+/// A context for the implementation given by `DynamicImpl::best`.
+pub enum DynamicContext {
+    Sha2(sha2::Sha256),
+    #[cfg(not(target_arch = "wasm32"))]
+    Ring(ring::digest::Context),
 }
 
+impl Sha256Context for DynamicContext {
+    fn new() -> Self {
+        match DynamicImpl::best() {
+            DynamicImpl::Sha2 => Self::Sha2(Sha256Context::new()),
+            #[cfg(not(target_arch = "wasm32"))]
+            DynamicImpl::Ring => Self::Ring(Sha256Context::new()),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Sha2(ctxt) => Sha256Context::update(ctxt, bytes),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Ring(ctxt) => Sha256Context::update(ctxt, bytes),
+        }
+    }
+
+    fn finalize(self) -> [u8; HASH_LEN] {
+        match self {
+            Self::Sha2(ctxt) => Sha256Context::finalize(ctxt),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Ring(ctxt) => Sha256Context::finalize(ctxt),
+        }
+    }
+}
+
+/// The default hashing context, see `DynamicContext`.
+pub use self::DynamicContext as Context;
+
 /// The max index that can be used with `ZERO_HASHES`.
 #[cfg(feature = "zero_hash_cache")]
 pub const ZERO_HASHES_MAX_INDEX: usize = 48;
@@ -95,6 +331,39 @@ mod tests {
         assert_eq!(expected, output);
     }
 
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn implementations_agree() {
+        let inputs: Vec<Vec<u8>> = vec![vec![], vec![42; 31], vec![7; 64], vec![255; 1_000]];
+
+        for input in &inputs {
+            let expected = Sha2CrateImpl.hash_fixed(input);
+
+            #[cfg(not(target_arch = "wasm32"))]
+            assert_eq!(RingImpl.hash_fixed(input), expected);
+            assert_eq!(DynamicImpl::best().hash(input), expected.to_vec());
+
+            let mut context = Context::new();
+            for chunk in input.chunks(7) {
+                context.update(chunk);
+            }
+            assert_eq!(context.finalize(), expected);
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn hash_2to1_matches_concatenation() {
+        let left = [1; HASH_LEN];
+        let right = [2; HASH_LEN];
+
+        let mut preimage = left.to_vec();
+        preimage.extend_from_slice(&right);
+
+        assert_eq!(hash_2to1(&left, &right).to_vec(), hash(&preimage));
+        assert_eq!(hash32_concat(&left, &right), hash_2to1(&left, &right));
+    }
+
     #[cfg(feature = "zero_hash_cache")]
     mod zero_hash {
         use super::*;
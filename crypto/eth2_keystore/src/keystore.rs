@@ -4,7 +4,7 @@
 use crate::derived_key::DerivedKey;
 use crate::json_keystore::{
     Aes128Ctr, ChecksumModule, Cipher, CipherModule, Crypto, EmptyMap, EmptyString, JsonKeystore,
-    Kdf, KdfModule, Scrypt, Sha256Checksum, Version,
+    Kdf, KdfModule, Pbkdf2, Prf, Scrypt, Sha256Checksum, Version,
 };
 use crate::Uuid;
 use aes_ctr::stream_cipher::generic_array::GenericArray;
@@ -81,6 +81,7 @@ pub struct KeystoreBuilder<'a> {
     cipher: Cipher,
     uuid: Uuid,
     path: String,
+    description: Option<String>,
 }
 
 impl<'a> KeystoreBuilder<'a> {
@@ -105,6 +106,7 @@ impl<'a> KeystoreBuilder<'a> {
                 cipher: Cipher::Aes128Ctr(Aes128Ctr { iv }),
                 uuid: Uuid::new_v4(),
                 path,
+                description: None,
             })
         }
     }
@@ -115,16 +117,25 @@ impl<'a> KeystoreBuilder<'a> {
         self
     }
 
+    /// Build the keystore with the supplied human-readable `description`.
+    pub fn description(mut self, description: String) -> Self {
+        self.description = Some(description);
+        self
+    }
+
     /// Consumes `self`, returning a `Keystore`.
     pub fn build(self) -> Result<Keystore, Error> {
-        Keystore::encrypt(
+        let mut keystore = Keystore::encrypt(
             self.keypair,
             self.password,
             self.kdf,
             self.cipher,
             self.uuid,
             self.path,
-        )
+        )?;
+        keystore.json.description = self.description;
+
+        Ok(keystore)
     }
 }
 
@@ -211,6 +222,40 @@ impl Keystore {
         Ok(keypair)
     }
 
+    /// Returns a new keystore containing the same secret key as `self`, re-encrypted with
+    /// `new_password` and `kdf`.
+    ///
+    /// A new AES `IV` is generated using `rand::thread_rng()`. The UUID, path, pubkey,
+    /// description and name of `self` are preserved.
+    ///
+    /// ## Errors
+    ///
+    /// - The provided `old_password` is incorrect.
+    /// - `new_password` is empty.
+    /// - The `kdf` is badly formed (e.g., has some values set to zero).
+    pub fn rekey(&self, old_password: &[u8], new_password: &[u8], kdf: Kdf) -> Result<Self, Error> {
+        if new_password.is_empty() {
+            return Err(Error::EmptyPassword);
+        }
+
+        let keypair = self.decrypt_keypair(old_password)?;
+        let iv = rand::thread_rng().gen::<[u8; IV_SIZE]>().to_vec().into();
+
+        let mut keystore = Self::encrypt(
+            &keypair,
+            new_password,
+            kdf,
+            Cipher::Aes128Ctr(Aes128Ctr { iv }),
+            self.json.uuid,
+            String::new(),
+        )?;
+        keystore.json.path = self.json.path.clone();
+        keystore.json.description = self.json.description.clone();
+        keystore.json.name = self.json.name.clone();
+
+        Ok(keystore)
+    }
+
     /// Returns the UUID for the keystore.
     pub fn uuid(&self) -> &Uuid {
         &self.json.uuid
@@ -223,6 +268,11 @@ impl Keystore {
         self.json.path.clone()
     }
 
+    /// Returns the description for the keystore, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.json.description.as_deref()
+    }
+
     /// Returns the pubkey for the keystore.
     pub fn pubkey(&self) -> &str {
         &self.json.pubkey
@@ -295,6 +345,19 @@ pub fn default_kdf(salt: Vec<u8>) -> Kdf {
     })
 }
 
+/// Returns the PBKDF2 `Kdf` used when a keystore is created with PBKDF2 instead of
+/// `crate::default_kdf`.
+///
+/// Uses the same iteration count as the PBKDF2 test vectors of EIP-2335.
+pub fn default_pbkdf2_kdf(salt: Vec<u8>) -> Kdf {
+    Kdf::Pbkdf2(Pbkdf2 {
+        dklen: DKLEN,
+        c: 262144,
+        prf: Prf::HmacSha256,
+        salt: salt.into(),
+    })
+}
+
 /// Returns `(cipher_text, checksum)` for the given `plain_text` encrypted with `Cipher` using a
 /// key derived from `password` via the `Kdf` (key derivation function).
 ///
@@ -375,7 +438,7 @@ fn generate_checksum(derived_key: &DerivedKey, cipher_message: &[u8]) -> [u8; HA
 }
 
 /// Derive a private key from the given `password` using the given `kdf` (key derivation function).
-fn derive_key(password: &[u8], kdf: &Kdf) -> Result<DerivedKey, Error> {
+pub fn derive_key(password: &[u8], kdf: &Kdf) -> Result<DerivedKey, Error> {
     let mut dk = DerivedKey::zero();
 
     match &kdf {
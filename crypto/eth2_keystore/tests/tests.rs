@@ -3,7 +3,7 @@
 
 use bls::Keypair;
 use eth2_keystore::{
-    default_kdf,
+    default_kdf, default_pbkdf2_kdf,
     json_keystore::{Kdf, Pbkdf2, Prf, Scrypt},
     Error, Keystore, KeystoreBuilder, DKLEN,
 };
@@ -160,3 +160,60 @@ fn custom_pbkdf2_kdf() {
 
     assert_eq!(keystore.kdf(), &my_kdf);
 }
+
+#[test]
+fn rekey() {
+    let keypair = Keypair::random();
+    let path = "m/12381/3600/0/0/0".to_string();
+
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, path.clone())
+        .unwrap()
+        .description("my key".into())
+        .build()
+        .unwrap();
+    assert_eq!(keystore.description(), Some("my key"));
+
+    let kdf = default_pbkdf2_kdf(vec![42]);
+    let rekeyed = keystore
+        .rekey(GOOD_PASSWORD, BAD_PASSWORD, kdf.clone())
+        .unwrap();
+
+    assert_eq!(rekeyed.uuid(), keystore.uuid(), "uuid should be preserved");
+    assert_eq!(rekeyed.path(), Some(path), "path should be preserved");
+    assert_eq!(
+        rekeyed.description(),
+        keystore.description(),
+        "description should be preserved"
+    );
+    assert_eq!(
+        rekeyed.pubkey(),
+        keystore.pubkey(),
+        "pubkey should be preserved"
+    );
+    assert_eq!(rekeyed.kdf(), &kdf);
+
+    assert_eq!(
+        rekeyed.decrypt_keypair(GOOD_PASSWORD).err().unwrap(),
+        Error::InvalidPassword,
+        "should not decrypt with old password"
+    );
+    assert_eq!(
+        rekeyed.decrypt_keypair(BAD_PASSWORD).unwrap().pk,
+        keypair.pk,
+        "should decrypt with new password"
+    );
+
+    assert_eq!(
+        keystore
+            .rekey(BAD_PASSWORD, GOOD_PASSWORD, kdf.clone())
+            .err()
+            .unwrap(),
+        Error::InvalidPassword,
+        "should not rekey with bad password"
+    );
+    assert_eq!(
+        keystore.rekey(GOOD_PASSWORD, &[], kdf).err().unwrap(),
+        Error::EmptyPassword,
+        "should not rekey to an empty password"
+    );
+}
@@ -4,8 +4,9 @@ mod wallet;
 pub mod json_wallet;
 
 pub use bip39;
-pub use validator_path::{KeyType, ValidatorPath, COIN_TYPE, PURPOSE};
+pub use validator_path::{KeyType, ValidatorPath, COIN_TYPE, MIN_PATH_NODES, PURPOSE};
 pub use wallet::{
-    recover_validator_secret, recover_validator_secret_from_mnemonic, DerivedKey, Error,
-    KeystoreError, PlainText, Uuid, ValidatorKeystores, Wallet, WalletBuilder,
+    recover_validator_secret, recover_validator_secret_from_mnemonic,
+    recover_validator_secret_from_path, DerivedKey, EncryptedMnemonic, Error, KeystoreError,
+    PlainText, Uuid, ValidatorKeystores, Wallet, WalletBuilder,
 };
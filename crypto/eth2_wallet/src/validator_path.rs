@@ -1,14 +1,20 @@
 use std::fmt;
 use std::iter::Iterator;
+use std::str::FromStr;
 
 pub const PURPOSE: u32 = 12381;
 pub const COIN_TYPE: u32 = 3600;
 
+/// The minimum number of nodes in an EIP-2334 path: `purpose`, `coin_type`, `account` and `use`.
+pub const MIN_PATH_NODES: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum KeyType {
     Voting,
     Withdrawal,
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct ValidatorPath(Vec<u32>);
 
 impl ValidatorPath {
@@ -23,11 +29,69 @@ impl ValidatorPath {
         Self(vec)
     }
 
+    /// Instantiates `Self` from the nodes of an arbitrary EIP-2334 path (i.e., the nodes that
+    /// follow `m`).
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the path does not start with `PURPOSE` and `COIN_TYPE` or has fewer than
+    /// `MIN_PATH_NODES` nodes.
+    pub fn from_nodes(nodes: Vec<u32>) -> Result<Self, String> {
+        if nodes.len() < MIN_PATH_NODES {
+            return Err(format!(
+                "An EIP-2334 path must have at least {} nodes after m",
+                MIN_PATH_NODES
+            ));
+        }
+
+        if nodes[0] != PURPOSE || nodes[1] != COIN_TYPE {
+            return Err(format!(
+                "An EIP-2334 path must start with m/{}/{}",
+                PURPOSE, COIN_TYPE
+            ));
+        }
+
+        Ok(Self(nodes))
+    }
+
+    /// Returns the path of the withdrawal key for the voting key at `self`.
+    ///
+    /// EIP-2334 defines the voting key as the child of the withdrawal key, so this is the parent
+    /// of `self`. Returns `None` if the parent would have fewer than `MIN_PATH_NODES` nodes.
+    pub fn withdrawal_path(&self) -> Option<Self> {
+        if self.0.len() > MIN_PATH_NODES {
+            Some(Self(self.0[..self.0.len() - 1].to_vec()))
+        } else {
+            None
+        }
+    }
+
     pub fn iter_nodes(&self) -> impl Iterator<Item = &u32> {
         self.0.iter()
     }
 }
 
+impl FromStr for ValidatorPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('/');
+
+        if parts.next() != Some("m") {
+            return Err(format!("Path {:?} must start with m", s));
+        }
+
+        let nodes = parts
+            .map(|node| {
+                node.parse::<u32>()
+                    .map_err(|e| format!("Invalid node {:?} in path {:?}: {}", node, s, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_nodes(nodes)
+    }
+}
+
 impl fmt::Display for ValidatorPath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "m")?;
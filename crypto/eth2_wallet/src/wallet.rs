@@ -13,7 +13,7 @@ use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::io::{Read, Write};
 
-pub use bip39::{Mnemonic, Seed as Bip39Seed};
+pub use bip39::{Language, Mnemonic, Seed as Bip39Seed};
 pub use eth2_key_derivation::DerivedKey;
 pub use eth2_keystore::{Error as KeystoreError, PlainText};
 pub use uuid::Uuid;
@@ -24,6 +24,8 @@ pub enum Error {
     PathExhausted,
     EmptyPassword,
     EmptySeed,
+    InvalidMnemonic(String),
+    MnemonicMismatch,
 }
 
 impl From<KeystoreError> for Error {
@@ -131,27 +133,9 @@ impl Wallet {
         name: String,
         nextaccount: u32,
     ) -> Result<Self, Error> {
-        let (cipher_text, checksum) = encrypt(&seed, &password, &kdf, &cipher)?;
-
         Ok(Self {
             json: JsonWallet {
-                crypto: Crypto {
-                    kdf: KdfModule {
-                        function: kdf.function(),
-                        params: kdf,
-                        message: EmptyString,
-                    },
-                    checksum: ChecksumModule {
-                        function: Sha256Checksum::function(),
-                        params: EmptyMap,
-                        message: checksum.to_vec().into(),
-                    },
-                    cipher: CipherModule {
-                        function: cipher.function(),
-                        params: cipher,
-                        message: cipher_text.into(),
-                    },
-                },
+                crypto: encrypt_crypto(seed, password, kdf, cipher)?,
                 uuid,
                 nextaccount,
                 version: Version::one(),
@@ -208,6 +192,90 @@ impl Wallet {
         Ok(keystores)
     }
 
+    /// Returns a new wallet containing the same seed as `self`, re-encrypted with `new_password`.
+    ///
+    /// A new KDF `salt` and AES `IV` are generated using `rand::thread_rng()`. The `uuid`, `name`
+    /// and `nextaccount` of `self` are preserved.
+    ///
+    /// ## Errors
+    ///
+    /// - If `old_password` is unable to decrypt `self`.
+    /// - If `new_password.is_empty()`.
+    pub fn change_password(&self, old_password: &[u8], new_password: &[u8]) -> Result<Self, Error> {
+        if new_password.is_empty() {
+            return Err(Error::EmptyPassword);
+        }
+
+        let seed = self.decrypt_seed(old_password)?;
+
+        let salt = rand::thread_rng().gen::<[u8; SALT_SIZE]>();
+        let iv = rand::thread_rng().gen::<[u8; IV_SIZE]>().to_vec().into();
+
+        Self::encrypt(
+            seed.as_bytes(),
+            new_password,
+            default_kdf(salt.to_vec()),
+            Cipher::Aes128Ctr(Aes128Ctr { iv }),
+            self.json.uuid,
+            self.json.name.clone(),
+            self.json.nextaccount,
+        )
+    }
+
+    /// Returns the `mnemonic` of `self`, encrypted with `password`, so that it can be stored
+    /// alongside `self` and later recovered with `Self::decrypt_mnemonic`.
+    ///
+    /// A new KDF `salt` and AES `IV` are generated using `rand::thread_rng()`.
+    ///
+    /// ## Errors
+    ///
+    /// - If `password` is unable to decrypt `self`.
+    /// - If the seed of `mnemonic` is not the seed of `self`.
+    pub fn encrypt_mnemonic(
+        &self,
+        mnemonic: &Mnemonic,
+        password: &[u8],
+    ) -> Result<EncryptedMnemonic, Error> {
+        self.check_mnemonic(mnemonic, password)?;
+
+        EncryptedMnemonic::encrypt(mnemonic.entropy(), password, self.json.uuid)
+    }
+
+    /// Returns the mnemonic of `self` from `encrypted` (see `Self::encrypt_mnemonic`).
+    ///
+    /// ## Errors
+    ///
+    /// - If `password` is unable to decrypt `self` or `encrypted`.
+    /// - If `encrypted` does not contain the mnemonic of `self`.
+    pub fn decrypt_mnemonic(
+        &self,
+        encrypted: &EncryptedMnemonic,
+        password: &[u8],
+    ) -> Result<Mnemonic, Error> {
+        if encrypted.uuid != self.json.uuid {
+            return Err(Error::MnemonicMismatch);
+        }
+
+        let entropy = decrypt(password, &encrypted.crypto)?;
+        let mnemonic = Mnemonic::from_entropy(entropy.as_bytes(), Language::English)
+            .map_err(|e| Error::InvalidMnemonic(format!("{:?}", e)))?;
+
+        self.check_mnemonic(&mnemonic, password)?;
+
+        Ok(mnemonic)
+    }
+
+    /// Returns `Ok(())` if the seed of `mnemonic` is the seed of `self`.
+    fn check_mnemonic(&self, mnemonic: &Mnemonic, password: &[u8]) -> Result<(), Error> {
+        let seed = self.decrypt_seed(password)?;
+
+        if Bip39Seed::new(mnemonic, "").as_bytes() == seed.as_bytes() {
+            Ok(())
+        } else {
+            Err(Error::MnemonicMismatch)
+        }
+    }
+
     /// Returns the value of the JSON wallet `nextaccount` field.
     ///
     /// This will be the index of the next wallet generated with `Self::next_validator`.
@@ -264,6 +332,100 @@ impl Wallet {
     }
 }
 
+/// The BIP-39 mnemonic of a `Wallet`, stored as its entropy encrypted with the wallet password.
+///
+/// An EIP-2386 wallet only contains the seed derived from its mnemonic, from which the mnemonic
+/// cannot be recovered. This is stored alongside the wallet so its mnemonic can be displayed again.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptedMnemonic {
+    crypto: Crypto,
+    uuid: Uuid,
+}
+
+impl EncryptedMnemonic {
+    /// Instantiates `Self`, encrypting the mnemonic `entropy` of the wallet with `uuid` using
+    /// `password`.
+    fn encrypt(entropy: &[u8], password: &[u8], uuid: Uuid) -> Result<Self, Error> {
+        if password.is_empty() {
+            return Err(Error::EmptyPassword);
+        }
+
+        let salt = rand::thread_rng().gen::<[u8; SALT_SIZE]>();
+        let iv = rand::thread_rng().gen::<[u8; IV_SIZE]>().to_vec().into();
+
+        Ok(Self {
+            crypto: encrypt_crypto(
+                entropy,
+                password,
+                default_kdf(salt.to_vec()),
+                Cipher::Aes128Ctr(Aes128Ctr { iv }),
+            )?,
+            uuid,
+        })
+    }
+
+    /// Returns the same mnemonic as `self`, re-encrypted with `new_password`.
+    ///
+    /// ## Errors
+    ///
+    /// - If `old_password` is unable to decrypt `self`.
+    /// - If `new_password.is_empty()`.
+    pub fn change_password(&self, old_password: &[u8], new_password: &[u8]) -> Result<Self, Error> {
+        let entropy = decrypt(old_password, &self.crypto)?;
+
+        Self::encrypt(entropy.as_bytes(), new_password, self.uuid)
+    }
+
+    /// Returns the UUID of the wallet to which this mnemonic belongs.
+    pub fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    /// Encodes self as a JSON object to the given `writer`.
+    pub fn to_json_writer<W: Write>(&self, writer: W) -> Result<(), Error> {
+        serde_json::to_writer(writer, self)
+            .map_err(|e| KeystoreError::WriteError(format!("{}", e)))
+            .map_err(Into::into)
+    }
+
+    /// Instantiates `self` from a JSON `reader`.
+    pub fn from_json_reader<R: Read>(reader: R) -> Result<Self, Error> {
+        serde_json::from_reader(reader)
+            .map_err(|e| KeystoreError::ReadError(format!("{}", e)))
+            .map_err(Into::into)
+    }
+}
+
+/// Returns the EIP-2386 `Crypto` object for `secret`, encrypted using `password` (via `kdf` and
+/// `cipher`).
+fn encrypt_crypto(
+    secret: &[u8],
+    password: &[u8],
+    kdf: Kdf,
+    cipher: Cipher,
+) -> Result<Crypto, Error> {
+    let (cipher_text, checksum) = encrypt(secret, password, &kdf, &cipher)?;
+
+    Ok(Crypto {
+        kdf: KdfModule {
+            function: kdf.function(),
+            params: kdf,
+            message: EmptyString,
+        },
+        checksum: ChecksumModule {
+            function: Sha256Checksum::function(),
+            params: EmptyMap,
+            message: checksum.to_vec().into(),
+        },
+        cipher: CipherModule {
+            function: cipher.function(),
+            params: cipher,
+            message: cipher_text.into(),
+        },
+    })
+}
+
 /// Returns `(secret, path)` for the `key_type` for the validator at `index`.
 ///
 /// This function should only be used for recovering lost keys, not creating new ones because it
@@ -295,9 +457,21 @@ pub fn recover_validator_secret_from_mnemonic(
     key_type: KeyType,
 ) -> Result<(PlainText, ValidatorPath), Error> {
     let path = ValidatorPath::new(index, key_type);
+    let secret = recover_validator_secret_from_path(secret, &path)?;
+
+    Ok((secret, path))
+}
+
+/// Returns the secret for the key at an arbitrary EIP-2334 `path`.
+///
+/// This function should only be used for key recovery since it can easily lead to key duplication.
+pub fn recover_validator_secret_from_path(
+    secret: &[u8],
+    path: &ValidatorPath,
+) -> Result<PlainText, Error> {
     let master = DerivedKey::from_seed(secret).map_err(|()| Error::EmptyPassword)?;
 
     let destination = path.iter_nodes().fold(master, |dk, i| dk.child(*i));
 
-    Ok((destination.secret().to_vec().into(), path))
+    Ok(destination.secret().to_vec().into())
 }
@@ -1,8 +1,9 @@
 #![cfg(not(debug_assertions))]
 
 use eth2_wallet::{
-    bip39::{Language, Mnemonic, Seed},
-    recover_validator_secret, DerivedKey, Error, KeyType, KeystoreError, Wallet, WalletBuilder,
+    bip39::{Language, Mnemonic, MnemonicType, Seed},
+    recover_validator_secret, recover_validator_secret_from_path, DerivedKey, Error, KeyType,
+    KeystoreError, ValidatorPath, Wallet, WalletBuilder,
 };
 use std::fs::OpenOptions;
 use tempfile::tempdir;
@@ -279,3 +280,158 @@ fn key_derivation_from_seed() {
         assert_eq!(wallet.nextaccount(), i + 1, "updated nextaccount");
     }
 }
+
+#[test]
+fn change_password() {
+    let new_password: &[u8] = &[46; 46];
+
+    let mut wallet = wallet_from_seed();
+    wallet
+        .next_validator(
+            WALLET_PASSWORD,
+            VOTING_KEYSTORE_PASSWORD,
+            WITHDRAWAL_KEYSTORE_PASSWORD,
+        )
+        .unwrap();
+
+    let changed = wallet
+        .change_password(WALLET_PASSWORD, new_password)
+        .unwrap();
+
+    assert_eq!(changed.uuid(), wallet.uuid(), "uuid should be preserved");
+    assert_eq!(changed.name(), wallet.name(), "name should be preserved");
+    assert_eq!(
+        changed.nextaccount(),
+        wallet.nextaccount(),
+        "nextaccount should be preserved"
+    );
+    assert_eq!(
+        changed.decrypt_seed(new_password).unwrap().as_bytes(),
+        SEED,
+        "should decrypt with new password"
+    );
+    assert_eq!(
+        changed.decrypt_seed(WALLET_PASSWORD).err().unwrap(),
+        Error::KeystoreError(KeystoreError::InvalidPassword),
+        "should not decrypt with old password"
+    );
+
+    assert_eq!(
+        wallet.change_password(new_password, WALLET_PASSWORD).err(),
+        Some(Error::KeystoreError(KeystoreError::InvalidPassword)),
+        "should not change password with bad password"
+    );
+    assert_eq!(
+        wallet.change_password(WALLET_PASSWORD, &[]).err(),
+        Some(Error::EmptyPassword),
+        "should not change to an empty password"
+    );
+}
+
+#[test]
+fn mnemonic_encryption() {
+    let new_password: &[u8] = &[46; 46];
+    let m = Mnemonic::from_phrase(MNEMONIC, Language::English).unwrap();
+
+    let wallet = WalletBuilder::from_mnemonic(&m, WALLET_PASSWORD, NAME.into())
+        .expect("should init builder")
+        .build()
+        .expect("should build wallet");
+
+    let encrypted = wallet.encrypt_mnemonic(&m, WALLET_PASSWORD).unwrap();
+    assert_eq!(encrypted.uuid(), wallet.uuid());
+    assert_eq!(
+        wallet
+            .decrypt_mnemonic(&encrypted, WALLET_PASSWORD)
+            .unwrap()
+            .phrase(),
+        MNEMONIC,
+        "should decrypt mnemonic"
+    );
+
+    let other = Mnemonic::new(MnemonicType::Words12, Language::English);
+    assert_eq!(
+        wallet.encrypt_mnemonic(&other, WALLET_PASSWORD).err(),
+        Some(Error::MnemonicMismatch),
+        "should not encrypt the mnemonic of another wallet"
+    );
+    assert_eq!(
+        wallet_from_seed()
+            .decrypt_mnemonic(&encrypted, WALLET_PASSWORD)
+            .err(),
+        Some(Error::MnemonicMismatch),
+        "should not decrypt the mnemonic of another wallet"
+    );
+
+    let changed_wallet = wallet
+        .change_password(WALLET_PASSWORD, new_password)
+        .unwrap();
+    let changed = encrypted
+        .change_password(WALLET_PASSWORD, new_password)
+        .unwrap();
+    assert_eq!(
+        changed_wallet
+            .decrypt_mnemonic(&changed, new_password)
+            .unwrap()
+            .phrase(),
+        MNEMONIC,
+        "should decrypt mnemonic with new password"
+    );
+    assert_eq!(
+        wallet.decrypt_mnemonic(&changed, WALLET_PASSWORD).err(),
+        Some(Error::KeystoreError(KeystoreError::InvalidPassword)),
+        "should not decrypt mnemonic with old password"
+    );
+}
+
+#[test]
+fn arbitrary_paths() {
+    let path: ValidatorPath = "m/12381/3600/7/42/1".parse().expect("should parse path");
+    assert_eq!(format!("{}", path), "m/12381/3600/7/42/1");
+
+    let secret = recover_validator_secret_from_path(SEED, &path).expect("should recover secret");
+    let expected = DerivedKey::from_seed(SEED)
+        .expect("should derive master key")
+        .child(12381)
+        .child(3600)
+        .child(7)
+        .child(42)
+        .child(1);
+    assert_eq!(secret.as_bytes(), expected.secret());
+
+    assert_eq!(
+        path.withdrawal_path(),
+        Some("m/12381/3600/7/42".parse().unwrap()),
+        "withdrawal path should be the parent"
+    );
+    assert_eq!(
+        ValidatorPath::new(7, KeyType::Voting).withdrawal_path(),
+        Some(ValidatorPath::new(7, KeyType::Withdrawal)),
+        "standard withdrawal path should be the parent"
+    );
+    assert_eq!(
+        ValidatorPath::new(7, KeyType::Withdrawal).withdrawal_path(),
+        None
+    );
+
+    let standard: ValidatorPath = "m/12381/3600/3/0/0".parse().unwrap();
+    assert_eq!(standard, ValidatorPath::new(3, KeyType::Voting));
+
+    for invalid in &[
+        "",
+        "m",
+        "12381/3600/0/0",
+        "m/12381/3600/0",
+        "m/12381/60/0/0",
+        "m/44/3600/0/0",
+        "m/12381/3600/0/x",
+        "m/12381/3600/0/-1",
+        "m/12381/3600/0/4294967296",
+    ] {
+        assert!(
+            invalid.parse::<ValidatorPath>().is_err(),
+            "{:?} should not parse",
+            invalid
+        );
+    }
+}
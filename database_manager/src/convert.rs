@@ -0,0 +1,216 @@
+use crate::DatabasePaths;
+use clap::{App, Arg, ArgMatches};
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
+use store::{detect_backend, DatabaseBackend, DiskStore, Error, KeyValueStore, KeyValueStoreOp};
+use types::EthSpec;
+
+pub const CMD: &str = "convert";
+pub const TO_FLAG: &str = "to";
+
+/// The approximate size of each write batch, large enough to hold several full beacon states.
+const BATCH_BYTES: usize = 64 * 1024 * 1024;
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Converts the hot and freezer databases to another key-value store backend. The \
+             original databases are kept alongside the converted ones until removed.",
+        )
+        .arg(
+            Arg::with_name(TO_FLAG)
+                .long(TO_FLAG)
+                .value_name("BACKEND")
+                .help("The backend to convert the databases to.")
+                .possible_values(&["leveldb", "lmdb"])
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+pub fn cli_run<E: EthSpec>(matches: &ArgMatches, paths: &DatabasePaths) -> Result<(), String> {
+    let to: DatabaseBackend = clap_utils::parse_required(matches, TO_FLAG)?;
+
+    for path in &[&paths.hot, &paths.cold] {
+        match convert_db::<E>(path, to)? {
+            Some(conversion) => println!(
+                "Converted {} entries in {:?} from {} to {}. The original database was moved to \
+                 {:?} and may be deleted.",
+                conversion.entries, path, conversion.from, to, conversion.backup_path
+            ),
+            None => println!("{:?} already uses {}", path, to),
+        }
+    }
+
+    println!(
+        "Start the beacon node with --db-backend {} to use the converted databases.",
+        to
+    );
+
+    Ok(())
+}
+
+/// The outcome of converting a single database.
+#[derive(Debug)]
+pub struct Conversion {
+    pub from: DatabaseBackend,
+    pub entries: usize,
+    pub backup_path: PathBuf,
+}
+
+/// Converts the database at `path` to the `to` backend.
+///
+/// The entries are copied into a new database beside `path`, which replaces the original once
+/// its contents have been verified. The original is kept at `Conversion::backup_path`. Returns
+/// `None` if the database already uses `to`.
+pub fn convert_db<E: EthSpec>(
+    path: &Path,
+    to: DatabaseBackend,
+) -> Result<Option<Conversion>, String> {
+    let from = match detect_backend(path) {
+        Some(from) if from == to => return Ok(None),
+        Some(from) => from,
+        None => return Err(format!("No database found at {:?}", path)),
+    };
+
+    let new_path = path.with_extension(format!("{}.tmp", to));
+    let backup_path = path.with_extension(format!("{}.bak", from));
+
+    if backup_path.exists() {
+        return Err(format!(
+            "{:?} already exists, remove it before converting again",
+            backup_path
+        ));
+    }
+    // Discard the remains of an interrupted conversion.
+    if new_path.exists() {
+        fs::remove_dir_all(&new_path)
+            .map_err(|e| format!("Unable to remove {:?}: {:?}", new_path, e))?;
+    }
+
+    let entries = {
+        let source = DiskStore::<E>::open(path, from)
+            .map_err(|e| format!("Unable to open {:?}: {:?}", path, e))?;
+        let target = DiskStore::<E>::open(&new_path, to)
+            .map_err(|e| format!("Unable to create {:?}: {:?}", new_path, e))?;
+
+        let copied = copy_entries(&source, &target)
+            .map_err(|e| format!("Unable to copy {:?}: {:?}", path, e))?;
+        let written = count_entries(&target)
+            .map_err(|e| format!("Unable to read {:?}: {:?}", new_path, e))?;
+        if written != copied {
+            return Err(format!(
+                "Copied {} entries but the converted database holds {}, the original is \
+                 unchanged",
+                copied, written
+            ));
+        }
+        // Syncing leveldb writes a marker entry, so only sync once the count is verified.
+        target
+            .sync()
+            .map_err(|e| format!("Unable to sync {:?}: {:?}", new_path, e))?;
+        copied
+    };
+
+    fs::rename(path, &backup_path)
+        .map_err(|e| format!("Unable to move {:?} to {:?}: {:?}", path, backup_path, e))?;
+    fs::rename(&new_path, path)
+        .map_err(|e| format!("Unable to move {:?} to {:?}: {:?}", new_path, path, e))?;
+
+    Ok(Some(Conversion {
+        from,
+        entries,
+        backup_path,
+    }))
+}
+
+/// Copies every entry of `source` into `target` in batches, returning the number copied.
+fn copy_entries<E: EthSpec>(source: &DiskStore<E>, target: &DiskStore<E>) -> Result<usize, Error> {
+    let mut batch = vec![];
+    let mut batch_bytes = 0;
+    let mut entries = 0;
+
+    source.for_each_key_value(|key, value| {
+        batch.push(KeyValueStoreOp::PutKeyValue(key.to_vec(), value.to_vec()));
+        batch_bytes += key.len() + value.len();
+        entries += 1;
+
+        if batch_bytes >= BATCH_BYTES {
+            target.do_atomically(mem::take(&mut batch))?;
+            batch_bytes = 0;
+        }
+        Ok(())
+    })?;
+    target.do_atomically(batch)?;
+
+    Ok(entries)
+}
+
+fn count_entries<E: EthSpec>(store: &DiskStore<E>) -> Result<usize, Error> {
+    let mut entries = 0;
+    store.for_each_key_value(|_, _| {
+        entries += 1;
+        Ok(())
+    })?;
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store::{DBColumn, MinimalEthSpec};
+    use tempfile::tempdir;
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("chain_db");
+        let column: &str = DBColumn::BeaconBlock.into();
+
+        {
+            let store = DiskStore::<E>::open(&path, DatabaseBackend::LevelDb).unwrap();
+            for i in 0..64u64 {
+                store
+                    .put_bytes(column, &i.to_le_bytes(), &i.to_be_bytes())
+                    .unwrap();
+            }
+        }
+
+        let conversion = convert_db::<E>(&path, DatabaseBackend::Lmdb)
+            .unwrap()
+            .expect("should convert");
+        assert_eq!(conversion.from, DatabaseBackend::LevelDb);
+        assert_eq!(conversion.entries, 64);
+        assert_eq!(
+            detect_backend(&conversion.backup_path),
+            Some(DatabaseBackend::LevelDb)
+        );
+        assert_eq!(detect_backend(&path), Some(DatabaseBackend::Lmdb));
+        assert!(convert_db::<E>(&path, DatabaseBackend::Lmdb)
+            .unwrap()
+            .is_none());
+
+        {
+            let store = DiskStore::<E>::open(&path, DatabaseBackend::Lmdb).unwrap();
+            for i in 0..64u64 {
+                assert_eq!(
+                    store.get_bytes(column, &i.to_le_bytes()).unwrap(),
+                    Some(i.to_be_bytes().to_vec())
+                );
+            }
+        }
+
+        let conversion = convert_db::<E>(&path, DatabaseBackend::LevelDb)
+            .unwrap()
+            .expect("should convert");
+        assert_eq!(conversion.entries, 64);
+        assert_eq!(detect_backend(&path), Some(DatabaseBackend::LevelDb));
+
+        // A second backup of the same backend is refused rather than overwritten.
+        assert!(convert_db::<E>(&path, DatabaseBackend::Lmdb).is_err());
+        assert_eq!(detect_backend(&path), Some(DatabaseBackend::LevelDb));
+    }
+}
@@ -0,0 +1,235 @@
+use clap::{App, Arg, ArgMatches};
+use ssz::Decode;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use store::{DBColumn, Error, HotColdDB, ItemStore};
+use types::{EthSpec, Hash256, SignedBeaconBlock};
+
+pub const CMD: &str = "dump";
+pub const COLUMN_FLAG: &str = "column";
+pub const FORMAT_FLAG: &str = "format";
+pub const FREEZER_FLAG: &str = "freezer";
+pub const OUTPUT_DIR_FLAG: &str = "output-dir";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Writes each value of a database column to a file in the output directory, named by \
+             its key in hex.",
+        )
+        .arg(
+            Arg::with_name(COLUMN_FLAG)
+                .long(COLUMN_FLAG)
+                .value_name("COLUMN")
+                .help("The column to dump, by its name in the database, e.g. blk.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(FORMAT_FLAG)
+                .long(FORMAT_FLAG)
+                .value_name("FORMAT")
+                .help(
+                    "The format of the dumped values. JSON is only supported for the blk and ste \
+                     columns.",
+                )
+                .possible_values(&["ssz", "json"])
+                .default_value("ssz")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(FREEZER_FLAG)
+                .long(FREEZER_FLAG)
+                .help("Dump the column from the freezer database rather than the hot database."),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_DIR_FLAG)
+                .long(OUTPUT_DIR_FLAG)
+                .value_name("DIR")
+                .help("The directory to write the values to, which is created if necessary.")
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+/// The formats values may be dumped in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// The value as stored in the database, after decompression.
+    Ssz,
+    /// The JSON encoding of a block or full state.
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ssz" => Ok(Format::Ssz),
+            "json" => Ok(Format::Json),
+            other => Err(format!(
+                "Unknown format \"{}\", expected ssz or json",
+                other
+            )),
+        }
+    }
+}
+
+pub fn cli_run<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    matches: &ArgMatches,
+    store: &HotColdDB<E, Hot, Cold>,
+) -> Result<(), String> {
+    let column: DBColumn = clap_utils::parse_required(matches, COLUMN_FLAG)?;
+    let format: Format = clap_utils::parse_required(matches, FORMAT_FLAG)?;
+    let freezer = matches.is_present(FREEZER_FLAG);
+    let output_dir: PathBuf = clap_utils::parse_required(matches, OUTPUT_DIR_FLAG)?;
+
+    let count = dump_column(store, column, format, freezer, &output_dir)?;
+    println!("Wrote {} values to {:?}", count, output_dir);
+
+    Ok(())
+}
+
+/// Write every value of `column` to a file in `output_dir`, returning the number written.
+pub fn dump_column<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: &HotColdDB<E, Hot, Cold>,
+    column: DBColumn,
+    format: Format,
+    freezer: bool,
+    output_dir: &Path,
+) -> Result<usize, String> {
+    if format == Format::Json && column != DBColumn::BeaconBlock && column != DBColumn::BeaconState
+    {
+        return Err(format!("JSON is not supported for the {:?} column", column));
+    }
+
+    fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", output_dir, e))?;
+
+    let prefix: &str = column.into();
+    let mut count = 0;
+    let mut write_value = |key: &[u8], value: &[u8]| -> Result<(), Error> {
+        if !key.starts_with(prefix.as_bytes()) {
+            return Ok(());
+        }
+        let key = &key[prefix.len()..];
+        let extension = match format {
+            Format::Ssz => "ssz",
+            Format::Json => "json",
+        };
+        let path = output_dir.join(format!("0x{}.{}", hex::encode(key), extension));
+
+        let result = match format {
+            Format::Ssz => fs::write(&path, value).map_err(|e| format!("{:?}", e)),
+            Format::Json => write_json(store, column, key, value, &path),
+        };
+        result.map_err(|message| Error::DBError {
+            message: format!("Unable to write {:?}: {}", path, message),
+        })?;
+
+        count += 1;
+        Ok(())
+    };
+
+    if freezer {
+        store.for_each_cold_key_value(&mut write_value)
+    } else {
+        store.for_each_hot_key_value(&mut write_value)
+    }
+    .map_err(|e| format!("Unable to dump {:?}: {:?}", column, e))?;
+
+    Ok(count)
+}
+
+/// Write the JSON encoding of the block or state stored at `key` to `path`.
+///
+/// States are stored as summaries or partial states, so the full state is loaded from the store.
+fn write_json<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: &HotColdDB<E, Hot, Cold>,
+    column: DBColumn,
+    key: &[u8],
+    value: &[u8],
+    path: &Path,
+) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("{:?}", e))?;
+    match column {
+        DBColumn::BeaconBlock => {
+            let block = SignedBeaconBlock::<E>::from_ssz_bytes(value)
+                .map_err(|e| format!("Invalid block: {:?}", e))?;
+            serde_json::to_writer_pretty(file, &block).map_err(|e| format!("{:?}", e))
+        }
+        DBColumn::BeaconState => {
+            let state = store
+                .get_state(&Hash256::from_slice(key), None)
+                .map_err(|e| format!("Unable to load state: {:?}", e))?
+                .ok_or_else(|| "State not found".to_string())?;
+            serde_json::to_writer_pretty(file, &state).map_err(|e| format!("{:?}", e))
+        }
+        other => Err(format!("JSON is not supported for the {:?} column", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::{MemoryStore, MinimalEthSpec, StoreConfig};
+    use tempfile::tempdir;
+    use types::{BeaconBlock, Signature};
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn dump_blocks() {
+        let spec = E::default_spec();
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: HotColdDB<E, MemoryStore<E>, MemoryStore<E>> =
+            HotColdDB::open_ephemeral(StoreConfig::default(), spec.clone(), log).unwrap();
+
+        let block = SignedBeaconBlock {
+            message: BeaconBlock::empty(&spec),
+            signature: Signature::empty(),
+        };
+        let block_root = Hash256::from_low_u64_be(1);
+        store.put_block(&block_root, block.clone()).unwrap();
+
+        let dir = tempdir().unwrap();
+        let file_name = format!("0x{}", hex::encode(block_root.as_bytes()));
+
+        let count = dump_column(
+            &store,
+            DBColumn::BeaconBlock,
+            Format::Ssz,
+            false,
+            dir.path(),
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+        let bytes = fs::read(dir.path().join(format!("{}.ssz", file_name))).unwrap();
+        assert_eq!(
+            SignedBeaconBlock::<E>::from_ssz_bytes(&bytes).unwrap(),
+            block
+        );
+
+        let count = dump_column(
+            &store,
+            DBColumn::BeaconBlock,
+            Format::Json,
+            false,
+            dir.path(),
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+        let file = File::open(dir.path().join(format!("{}.json", file_name))).unwrap();
+        let decoded: SignedBeaconBlock<E> = serde_json::from_reader(file).unwrap();
+        assert_eq!(decoded, block);
+
+        assert!(
+            dump_column(&store, DBColumn::BeaconBlock, Format::Ssz, true, dir.path())
+                .map(|count| count == 0)
+                .unwrap()
+        );
+        assert!(dump_column(&store, DBColumn::OpPool, Format::Json, false, dir.path()).is_err());
+    }
+}
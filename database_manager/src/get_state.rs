@@ -0,0 +1,116 @@
+use clap::{App, Arg, ArgMatches};
+use ssz::Encode;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use store::{HotColdDB, ItemStore};
+use types::{BeaconState, EthSpec, Hash256, Slot};
+
+pub const CMD: &str = "get-state";
+pub const SLOT_FLAG: &str = "slot";
+pub const STATE_ROOT_FLAG: &str = "state-root";
+pub const OUTPUT_FLAG: &str = "output";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about("Writes the SSZ encoding of the state at a slot to a file.")
+        .arg(
+            Arg::with_name(SLOT_FLAG)
+                .long(SLOT_FLAG)
+                .value_name("SLOT")
+                .help("The slot of the state.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(STATE_ROOT_FLAG)
+                .long(STATE_ROOT_FLAG)
+                .value_name("ROOT")
+                .help(
+                    "The 0x-prefixed root of the state, required if there are several \
+                     unfinalized states at the slot.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_FLAG)
+                .long(OUTPUT_FLAG)
+                .value_name("FILE")
+                .help("The file to write the state to.")
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+pub fn cli_run<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    matches: &ArgMatches,
+    store: &HotColdDB<E, Hot, Cold>,
+) -> Result<(), String> {
+    let slot = Slot::new(clap_utils::parse_required(matches, SLOT_FLAG)?);
+    let state_root: Option<Hash256> = clap_utils::parse_ssz_optional(matches, STATE_ROOT_FLAG)?;
+    let output: PathBuf = clap_utils::parse_required(matches, OUTPUT_FLAG)?;
+
+    let state = get_state(store, slot, state_root)?;
+    let mut file = File::create(&output)
+        .map(BufWriter::new)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", output, e))?;
+    state
+        .ssz_write(&mut file)
+        .and_then(|_| file.flush())
+        .map_err(|e| format!("Unable to write {:?}: {:?}", output, e))?;
+    println!("Wrote the state at slot {} to {:?}", slot, output);
+
+    Ok(())
+}
+
+/// Load the state at `slot`.
+///
+/// Finalized states are reconstructed from the freezer. Unfinalized states may lie on several
+/// forks, in which case `state_root` selects between them.
+pub fn get_state<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: &HotColdDB<E, Hot, Cold>,
+    slot: Slot,
+    state_root: Option<Hash256>,
+) -> Result<BeaconState<E>, String> {
+    if slot < store.get_split_slot() {
+        let state = store
+            .load_cold_state_by_slot(slot)
+            .map_err(|e| format!("Unable to load the finalized state: {:?}", e))?;
+        return match state_root {
+            Some(state_root) if state_root != state.canonical_root() => Err(format!(
+                "The finalized state at slot {} has root {:?}, not {:?}",
+                slot,
+                state.canonical_root(),
+                state_root
+            )),
+            _ => Ok(state),
+        };
+    }
+
+    let state_root = match state_root {
+        Some(state_root) => state_root,
+        None => {
+            let state_roots = store
+                .get_hot_state_roots_at_slot(slot)
+                .map_err(|e| format!("Unable to read state summaries: {:?}", e))?;
+            match state_roots.as_slice() {
+                [] => return Err(format!("No state found at slot {}", slot)),
+                [state_root] => *state_root,
+                _ => {
+                    return Err(format!(
+                        "There are {} states at slot {}, select one with --{}: {:?}",
+                        state_roots.len(),
+                        slot,
+                        STATE_ROOT_FLAG,
+                        state_roots
+                    ))
+                }
+            }
+        }
+    };
+
+    store
+        .get_state(&state_root, Some(slot))
+        .map_err(|e| format!("Unable to load state {:?}: {:?}", state_root, e))?
+        .ok_or_else(|| format!("No state {:?} found at slot {}", state_root, slot))
+}
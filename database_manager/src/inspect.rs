@@ -0,0 +1,145 @@
+use beacon_chain::{
+    HeadTracker, PersistedBeaconChain, PersistedForkChoice, BEACON_CHAIN_DB_KEY, FORK_CHOICE_DB_KEY,
+};
+use clap::App;
+use std::collections::BTreeMap;
+use store::{DBColumn, Error, HotColdDB, ItemStore};
+use types::{EthSpec, Hash256};
+
+pub const CMD: &str = "inspect";
+
+/// The label of keys which do not begin with a known column, such as leveldb's sync marker.
+const UNKNOWN_COLUMN: &str = "unknown";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD).about(
+        "Prints the split slot, restore point interval, heads and checkpoints of the database, \
+         and the number of keys and value bytes in each column.",
+    )
+}
+
+/// The number of entries in a column and the total length of their (decompressed) values.
+#[derive(Debug, Default, PartialEq)]
+pub struct ColumnStats {
+    pub keys: usize,
+    pub bytes: usize,
+}
+
+pub fn cli_run<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: &HotColdDB<E, Hot, Cold>,
+) -> Result<(), String> {
+    println!("Split slot: {}", store.get_split_slot());
+    println!(
+        "Slots per restore point: {}",
+        store.get_slots_per_restore_point()
+    );
+
+    let persisted_chain: Option<PersistedBeaconChain> = store
+        .get_item(&Hash256::from_slice(&BEACON_CHAIN_DB_KEY))
+        .map_err(|e| format!("Unable to read the persisted beacon chain: {:?}", e))?;
+    match persisted_chain {
+        Some(persisted_chain) => {
+            println!(
+                "Genesis block root: {:?}",
+                persisted_chain.genesis_block_root
+            );
+            let head_tracker = HeadTracker::from_ssz_container(&persisted_chain.ssz_head_tracker)
+                .map_err(|e| format!("Invalid head tracker: {:?}", e))?;
+            let mut heads = head_tracker.heads();
+            heads.sort_by_key(|(_, slot)| *slot);
+            for (root, slot) in heads {
+                println!("Head: {:?} at slot {}", root, slot);
+            }
+        }
+        None => println!("No persisted beacon chain"),
+    }
+
+    let persisted_fork_choice: Option<PersistedForkChoice> = store
+        .get_item(&Hash256::from_slice(&FORK_CHOICE_DB_KEY))
+        .map_err(|e| format!("Unable to read the persisted fork choice: {:?}", e))?;
+    match persisted_fork_choice {
+        Some(persisted_fork_choice) => {
+            let fc_store = &persisted_fork_choice.fork_choice_store;
+            for (name, checkpoint) in &[
+                ("Justified", fc_store.justified_checkpoint()),
+                ("Finalized", fc_store.finalized_checkpoint()),
+            ] {
+                println!(
+                    "{} checkpoint: epoch {}, root {:?}",
+                    name, checkpoint.epoch, checkpoint.root
+                );
+            }
+        }
+        None => println!("No persisted fork choice"),
+    }
+
+    let hot_stats = column_stats(|f| store.for_each_hot_key_value(f))
+        .map_err(|e| format!("Unable to read the hot database: {:?}", e))?;
+    print_column_stats("Hot database", &hot_stats);
+    let cold_stats = column_stats(|f| store.for_each_cold_key_value(f))
+        .map_err(|e| format!("Unable to read the freezer database: {:?}", e))?;
+    print_column_stats("Freezer database", &cold_stats);
+
+    Ok(())
+}
+
+/// Tally the keys and value bytes of each column, visiting entries with `for_each_key_value`.
+pub fn column_stats<F>(for_each_key_value: F) -> Result<BTreeMap<&'static str, ColumnStats>, Error>
+where
+    F: FnOnce(&mut dyn FnMut(&[u8], &[u8]) -> Result<(), Error>) -> Result<(), Error>,
+{
+    let mut stats = BTreeMap::<&'static str, ColumnStats>::new();
+    for_each_key_value(&mut |key, value| {
+        let column = DBColumn::from_key(key).map_or(UNKNOWN_COLUMN, Into::into);
+        let column_stats = stats.entry(column).or_default();
+        column_stats.keys += 1;
+        column_stats.bytes += value.len();
+        Ok(())
+    })?;
+    Ok(stats)
+}
+
+fn print_column_stats(name: &str, stats: &BTreeMap<&'static str, ColumnStats>) {
+    println!("{}:", name);
+    println!("  {:<22} {:>12} {:>16}", "column", "keys", "bytes");
+    for (column, column_stats) in stats {
+        let label = match column.parse::<DBColumn>() {
+            Ok(db_column) => format!("{} ({:?})", column, db_column),
+            Err(_) => column.to_string(),
+        };
+        println!(
+            "  {:<22} {:>12} {:>16}",
+            label, column_stats.keys, column_stats.bytes
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store::{KeyValueStore, MemoryStore, MinimalEthSpec};
+
+    #[test]
+    fn column_stats_by_prefix() {
+        let db = MemoryStore::<MinimalEthSpec>::open();
+        let blocks: &str = DBColumn::BeaconBlock.into();
+        let states: &str = DBColumn::BeaconState.into();
+        db.put_bytes(blocks, &[1; 32], &[0; 10]).unwrap();
+        db.put_bytes(blocks, &[2; 32], &[0; 20]).unwrap();
+        db.put_bytes(states, &[1; 32], &[0; 100]).unwrap();
+        db.put_bytes("syn", b"c", b"sync").unwrap();
+
+        let stats = column_stats(|f| db.for_each_key_value(f)).unwrap();
+
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[blocks], ColumnStats { keys: 2, bytes: 30 });
+        assert_eq!(
+            stats[states],
+            ColumnStats {
+                keys: 1,
+                bytes: 100
+            }
+        );
+        assert_eq!(stats[UNKNOWN_COLUMN], ColumnStats { keys: 1, bytes: 4 });
+    }
+}
@@ -0,0 +1,221 @@
+pub mod convert;
+pub mod dump;
+pub mod get_state;
+pub mod inspect;
+pub mod reconfigure;
+
+use beacon_node::{get_data_dir, ClientConfig};
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use slog::Logger;
+use std::path::PathBuf;
+use store::config::DEFAULT_SLOTS_PER_RESTORE_POINT;
+use store::{detect_backend, DiskStore, HotColdDB, StoreConfig};
+use types::{ChainSpec, EthSpec};
+
+pub const CMD: &str = "database_manager";
+pub const FREEZER_DIR_FLAG: &str = "freezer-dir";
+
+/// The store opened by the subcommands which read the databases.
+pub type DiskHotColdDB<E> = HotColdDB<E, DiskStore<E>, DiskStore<E>>;
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .visible_alias("db")
+        .about("Utilities for managing the beacon node database. The beacon node must be stopped.")
+        .arg(
+            Arg::with_name(FREEZER_DIR_FLAG)
+                .long(FREEZER_DIR_FLAG)
+                .value_name("DIR")
+                .help("Data directory for the freezer database, if it was set for the beacon node.")
+                .takes_value(true)
+                .global(true),
+        )
+        .subcommand(convert::cli_app())
+        .subcommand(inspect::cli_app())
+        .subcommand(dump::cli_app())
+        .subcommand(get_state::cli_app())
+        .subcommand(reconfigure::cli_app())
+}
+
+/// The paths of the hot and freezer databases, as the beacon node would resolve them.
+pub struct DatabasePaths {
+    pub hot: PathBuf,
+    pub cold: PathBuf,
+}
+
+impl DatabasePaths {
+    pub fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        let mut client_config = ClientConfig::default();
+        client_config.data_dir = get_data_dir(matches);
+        client_config.freezer_db_path = clap_utils::parse_optional(matches, FREEZER_DIR_FLAG)?;
+
+        Ok(Self {
+            hot: client_config
+                .get_db_path()
+                .ok_or_else(|| "Unable to determine the database path".to_string())?,
+            cold: client_config
+                .get_freezer_db_path()
+                .ok_or_else(|| "Unable to determine the freezer database path".to_string())?,
+        })
+    }
+}
+
+/// Open the existing databases at `paths` for reading, without writing anything to them.
+///
+/// The databases are read with the backend and slots per restore point they were written with.
+/// Returns an error if either database does not exist.
+pub fn open_store_read_only<E: EthSpec>(
+    paths: &DatabasePaths,
+    spec: ChainSpec,
+    log: Logger,
+) -> Result<DiskHotColdDB<E>, String> {
+    let config = StoreConfig {
+        slots_per_restore_point: std::cmp::min(
+            E::slots_per_historical_root() as u64,
+            DEFAULT_SLOTS_PER_RESTORE_POINT,
+        ),
+        ..StoreConfig::default()
+    };
+    HotColdDB::open_read_only(&paths.hot, &paths.cold, config, spec, log)
+        .map_err(|e| format!("Unable to open database: {:?}", e))
+}
+
+/// Open the existing databases at `paths` with the backend they were written with.
+///
+/// The freezer is read with the slots per restore point it was written with, unless
+/// `slots_per_restore_point` is provided, in which case its restore points are reconstructed at
+/// that interval.
+pub fn open_store<E: EthSpec>(
+    paths: &DatabasePaths,
+    slots_per_restore_point: Option<u64>,
+    spec: ChainSpec,
+    log: Logger,
+) -> Result<DiskHotColdDB<E>, String> {
+    let backend = detect_backend(&paths.hot)
+        .ok_or_else(|| format!("No database found at {:?}", paths.hot))?;
+
+    let config = StoreConfig {
+        slots_per_restore_point: slots_per_restore_point.unwrap_or_else(|| {
+            std::cmp::min(
+                E::slots_per_historical_root() as u64,
+                DEFAULT_SLOTS_PER_RESTORE_POINT,
+            )
+        }),
+        slots_per_restore_point_set_explicitly: slots_per_restore_point.is_some(),
+        backend,
+        ..StoreConfig::default()
+    };
+    HotColdDB::open(&paths.hot, &paths.cold, config, spec, log)
+        .map_err(|e| format!("Unable to open database: {:?}", e))
+}
+
+/// Run the database manager, returning an error if the operation did not succeed.
+///
+/// Global flags such as `--datadir` are read from the subcommand's matches, which hold them
+/// wherever they appeared on the command line.
+pub fn run<T: EthSpec>(matches: &ArgMatches<'_>, mut env: Environment<T>) -> Result<(), String> {
+    let spec = env.eth2_config.spec.clone();
+    let log = env.core_context().log().clone();
+
+    match matches.subcommand() {
+        (convert::CMD, Some(matches)) => {
+            convert::cli_run::<T>(matches, &DatabasePaths::from_cli(matches)?)?
+        }
+        (inspect::CMD, Some(matches)) => {
+            let store = open_store_read_only::<T>(&DatabasePaths::from_cli(matches)?, spec, log)?;
+            inspect::cli_run(&store)?
+        }
+        (dump::CMD, Some(matches)) => {
+            let store = open_store_read_only::<T>(&DatabasePaths::from_cli(matches)?, spec, log)?;
+            dump::cli_run(matches, &store)?
+        }
+        (get_state::CMD, Some(matches)) => {
+            let store = open_store_read_only::<T>(&DatabasePaths::from_cli(matches)?, spec, log)?;
+            get_state::cli_run(matches, &store)?
+        }
+        (reconfigure::CMD, Some(matches)) => {
+            reconfigure::cli_run::<T>(matches, &DatabasePaths::from_cli(matches)?, spec, log)?
+        }
+        (unknown, _) => {
+            return Err(format!(
+                "{} is not a valid {} command. See --help.",
+                unknown, CMD
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::hot_cold_store::CONFIG_DB_KEY;
+    use store::{DBColumn, DatabaseBackend, KeyValueStore, MinimalEthSpec};
+    use tempfile::tempdir;
+
+    type E = MinimalEthSpec;
+
+    fn paths(dir: &std::path::Path) -> DatabasePaths {
+        DatabasePaths {
+            hot: dir.join("chain_db"),
+            cold: dir.join("freezer_db"),
+        }
+    }
+
+    #[test]
+    fn read_only_requires_existing_database() {
+        let dir = tempdir().unwrap();
+        let paths = paths(dir.path());
+        let log = NullLoggerBuilder.build().unwrap();
+
+        assert!(open_store_read_only::<E>(&paths, E::default_spec(), log).is_err());
+        assert!(!paths.hot.exists());
+        assert!(!paths.cold.exists());
+    }
+
+    #[test]
+    fn read_only_does_not_write() {
+        for backend in &[DatabaseBackend::LevelDb, DatabaseBackend::Lmdb] {
+            let dir = tempdir().unwrap();
+            let paths = paths(dir.path());
+            let log = NullLoggerBuilder.build().unwrap();
+
+            let config = StoreConfig {
+                backend: *backend,
+                ..StoreConfig::default()
+            };
+            drop(
+                HotColdDB::<E, DiskStore<E>, DiskStore<E>>::open(
+                    &paths.hot,
+                    &paths.cold,
+                    config,
+                    E::default_spec(),
+                    log.clone(),
+                )
+                .unwrap(),
+            );
+
+            // Forget the freezer's configuration, which opening for writing would store again.
+            let config_column: &str = DBColumn::BeaconMeta.into();
+            DiskStore::<E>::open(&paths.cold, *backend)
+                .unwrap()
+                .key_delete(config_column, CONFIG_DB_KEY.as_bytes())
+                .unwrap();
+
+            let store = open_store_read_only::<E>(&paths, E::default_spec(), log).unwrap();
+            assert_eq!(
+                store.get_slots_per_restore_point(),
+                StoreConfig::default().slots_per_restore_point
+            );
+            drop(store);
+
+            assert!(!DiskStore::<E>::open_read_only(&paths.cold)
+                .unwrap()
+                .key_exists(config_column, CONFIG_DB_KEY.as_bytes())
+                .unwrap());
+        }
+    }
+}
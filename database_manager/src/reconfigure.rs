@@ -0,0 +1,45 @@
+use crate::{open_store, DatabasePaths};
+use clap::{App, Arg, ArgMatches};
+use slog::Logger;
+use types::{ChainSpec, EthSpec};
+
+pub const CMD: &str = "reconfigure";
+pub const SLOTS_PER_RESTORE_POINT_FLAG: &str = "slots-per-restore-point";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Changes the number of slots between restore points in the freezer database, \
+             replaying finalized blocks to create any new restore points. The beacon node does \
+             the same on startup when its --slots-per-restore-point changes.",
+        )
+        .arg(
+            Arg::with_name(SLOTS_PER_RESTORE_POINT_FLAG)
+                .long(SLOTS_PER_RESTORE_POINT_FLAG)
+                .value_name("SLOT_COUNT")
+                .help(
+                    "The new number of slots between restore points. Must be a divisor of \
+                     SLOTS_PER_HISTORICAL_ROOT.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+pub fn cli_run<E: EthSpec>(
+    matches: &ArgMatches,
+    paths: &DatabasePaths,
+    spec: ChainSpec,
+    log: Logger,
+) -> Result<(), String> {
+    let slots_per_restore_point =
+        clap_utils::parse_required(matches, SLOTS_PER_RESTORE_POINT_FLAG)?;
+
+    let store = open_store::<E>(paths, Some(slots_per_restore_point), spec, log)?;
+    println!(
+        "The freezer database has {} slots per restore point",
+        store.get_slots_per_restore_point()
+    );
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use clap::ArgMatches;
 use ssz::{Decode, Encode};
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::{BufWriter, Read, Write};
 use std::path::PathBuf;
 use types::{BeaconState, EthSpec};
 
@@ -31,9 +31,13 @@ pub fn run<T: EthSpec>(matches: &ArgMatches) -> Result<(), String> {
 
     state.genesis_time = genesis_time;
 
-    let mut file = File::create(path).map_err(|e| format!("Unable to create file: {}", e))?;
+    let mut file = File::create(path)
+        .map(BufWriter::new)
+        .map_err(|e| format!("Unable to create file: {}", e))?;
 
-    file.write_all(&state.as_ssz_bytes())
+    state
+        .ssz_write(&mut file)
+        .and_then(|_| file.flush())
         .map_err(|e| format!("Unable to write to file: {}", e))?;
 
     Ok(())
@@ -1,10 +1,22 @@
 use clap::ArgMatches;
-use clap_utils::{parse_required, parse_ssz_required};
+use clap_utils::{parse_optional, parse_required, parse_ssz_required};
 use deposit_contract::{decode_eth1_tx_data, DEPOSIT_DATA_LEN};
+use eth2_testnet_config::Eth2TestnetConfig;
+use std::fs::File;
+use std::path::PathBuf;
 use tree_hash::TreeHash;
-use types::EthSpec;
+use types::{ChainSpec, EthSpec};
+use validator_dir::deposit_data_json::{read_deposit_data_json, verify_withdrawal_credentials};
 
 pub fn run<T: EthSpec>(matches: &ArgMatches) -> Result<(), String> {
+    if let Some(path) = parse_optional::<PathBuf>(matches, "deposit-data-json")? {
+        let spec = match parse_optional::<PathBuf>(matches, "testnet-dir")? {
+            Some(testnet_dir) => testnet_spec::<T>(testnet_dir)?,
+            None => T::default_spec(),
+        };
+        return check_deposit_data_json(path, &spec);
+    }
+
     let rlp_bytes = parse_ssz_required::<Vec<u8>>(matches, "deposit-data")?;
     let amount = parse_required(matches, "deposit-amount")?;
 
@@ -27,5 +39,39 @@ pub fn run<T: EthSpec>(matches: &ArgMatches) -> Result<(), String> {
         ));
     }
 
+    verify_withdrawal_credentials(&deposit_data.withdrawal_credentials, &T::default_spec())
+        .map_err(|e| format!("Invalid withdrawal credentials: {:?}", e))?;
+
+    Ok(())
+}
+
+/// Returns the spec of the testnet in `testnet_dir`.
+fn testnet_spec<T: EthSpec>(testnet_dir: PathBuf) -> Result<ChainSpec, String> {
+    let testnet_config: Eth2TestnetConfig<T> = Eth2TestnetConfig::load(testnet_dir)?;
+    testnet_config
+        .yaml_config
+        .as_ref()
+        .ok_or_else(|| "The testnet directory must contain a spec config".to_string())?
+        .apply_to_chain_spec::<T>(&T::default_spec())
+        .ok_or_else(|| "The testnet spec config is not compatible with --spec".to_string())
+}
+
+/// Checks that each deposit in a `deposit_data-*.json` file is for the genesis fork version of
+/// `spec`, with consistent roots and a valid signature.
+fn check_deposit_data_json(path: PathBuf, spec: &ChainSpec) -> Result<(), String> {
+    let file = File::open(&path).map_err(|e| format!("Unable to open {:?}: {:?}", path, e))?;
+    let deposits = read_deposit_data_json(file)
+        .map_err(|e| format!("Invalid deposit data JSON in {:?}: {:?}", path, e))?;
+
+    if deposits.is_empty() {
+        return Err(format!("No deposits in {:?}", path));
+    }
+
+    for (i, deposit) in deposits.iter().enumerate() {
+        deposit
+            .verify(spec)
+            .map_err(|e| format!("Deposit {} ({:?}) is invalid: {:?}", i, deposit.pubkey, e))?;
+    }
+
     Ok(())
 }
@@ -386,14 +386,15 @@ fn main() {
         .subcommand(
             SubCommand::with_name("check-deposit-data")
                 .about(
-                    "Checks the integrity of some deposit data.",
+                    "Checks the integrity of some deposit data, given either as the eth1 \
+                    transaction data or as a deposit_data-*.json file.",
                 )
                 .arg(
                     Arg::with_name("deposit-amount")
                         .index(1)
                         .value_name("GWEI")
                         .takes_value(true)
-                        .required(true)
+                        .required_unless("deposit-data-json")
                         .help("The amount (in Gwei) that was deposited"),
                 )
                 .arg(
@@ -401,10 +402,21 @@ fn main() {
                         .index(2)
                         .value_name("HEX")
                         .takes_value(true)
-                        .required(true)
+                        .required_unless("deposit-data-json")
                         .help("A 0x-prefixed hex string of the deposit data. Should include the
                             function signature."),
                 )
+                .arg(
+                    Arg::with_name("deposit-data-json")
+                        .long("deposit-data-json")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .conflicts_with_all(&["deposit-amount", "deposit-data"])
+                        .help("Path to a deposit_data-*.json file, as used by the staking \
+                            launchpad. Checks the fork version, roots and signature of each \
+                            deposit against the network of --testnet-dir, or of --spec if it \
+                            is not given."),
+                )
         )
         .subcommand(
             SubCommand::with_name("generate-bootnode-enr")
@@ -3,7 +3,7 @@ use clap::ArgMatches;
 use ssz::Encode;
 use state_processing::per_slot_processing;
 use std::fs::File;
-use std::io::prelude::*;
+use std::io::{prelude::*, BufWriter};
 use std::path::PathBuf;
 use types::{BeaconState, EthSpec};
 
@@ -44,11 +44,13 @@ pub fn run<T: EthSpec>(matches: &ArgMatches) -> Result<(), String> {
             .map_err(|e| format!("Failed to advance slot on iteration {}: {:?}", i, e))?;
     }
 
-    let mut output_file =
-        File::create(output_path).map_err(|e| format!("Unable to create output file: {:?}", e))?;
+    let mut output_file = File::create(output_path)
+        .map(BufWriter::new)
+        .map_err(|e| format!("Unable to create output file: {:?}", e))?;
 
-    output_file
-        .write_all(&state.as_ssz_bytes())
+    state
+        .ssz_write(&mut output_file)
+        .and_then(|_| output_file.flush())
         .map_err(|e| format!("Unable to write to output file: {:?}", e))?;
 
     Ok(())
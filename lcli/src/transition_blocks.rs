@@ -2,7 +2,7 @@ use clap::ArgMatches;
 use ssz::{Decode, Encode};
 use state_processing::{per_block_processing, per_slot_processing, BlockSignatureStrategy};
 use std::fs::File;
-use std::io::prelude::*;
+use std::io::{prelude::*, BufWriter};
 use std::path::PathBuf;
 use types::{BeaconState, EthSpec, SignedBeaconBlock};
 
@@ -34,11 +34,13 @@ pub fn run_transition_blocks<T: EthSpec>(matches: &ArgMatches) -> Result<(), Str
 
     let post_state = do_transition(pre_state, block)?;
 
-    let mut output_file =
-        File::create(output_path).map_err(|e| format!("Unable to create output file: {:?}", e))?;
+    let mut output_file = File::create(output_path)
+        .map(BufWriter::new)
+        .map_err(|e| format!("Unable to create output file: {:?}", e))?;
 
-    output_file
-        .write_all(&post_state.as_ssz_bytes())
+    post_state
+        .ssz_write(&mut output_file)
+        .and_then(|_| output_file.flush())
         .map_err(|e| format!("Unable to write to output file: {:?}", e))?;
 
     Ok(())
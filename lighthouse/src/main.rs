@@ -122,6 +122,7 @@ fn main() {
         .subcommand(boot_node::cli_app())
         .subcommand(validator_client::cli_app())
         .subcommand(account_manager::cli_app())
+        .subcommand(database_manager::cli_app())
         .get_matches();
 
     // boot node subcommand circumvents the environment
@@ -244,6 +245,14 @@ fn run<E: EthSpec>(
         return Ok(());
     };
 
+    if let Some(sub_matches) = matches.subcommand_matches(database_manager::CMD) {
+        eprintln!("Running database manager for {} testnet", testnet_name);
+        database_manager::run(sub_matches, environment)?;
+
+        // Exit as soon as database manager returns control.
+        return Ok(());
+    };
+
     warn!(
         log,
         "Ethereum 2.0 is pre-release. This software is experimental."
@@ -3,21 +3,39 @@
 use account_manager::{
     validator::{
         create::*,
+        export_deposit_data::{self, CMD as EXPORT_DEPOSIT_DATA_CMD},
         import::{self, CMD as IMPORT_CMD},
+        keystore::{
+            rekey::{self, CMD as REKEY_CMD},
+            CMD as KEYSTORE_CMD,
+        },
+        recover::{self, CMD as RECOVER_CMD},
+        split_key::{self, CMD as SPLIT_KEY_CMD},
+        vault::{
+            add::{self, CMD as ADD_CMD},
+            CMD as VAULT_CMD,
+        },
         CMD as VALIDATOR_CMD,
     },
     wallet::{
+        change_password::{self, CMD as CHANGE_PASSWORD_CMD},
         create::{CMD as CREATE_CMD, *},
+        delete::{self, CMD as DELETE_CMD},
+        export::{self, CMD as EXPORT_CMD},
         list::CMD as LIST_CMD,
+        show_mnemonic::{self, CMD as SHOW_MNEMONIC_CMD},
         CMD as WALLET_CMD,
     },
     BASE_DIR_FLAG, CMD as ACCOUNT_CMD, *,
 };
 use account_utils::{
-    eth2_keystore::KeystoreBuilder,
+    eth2_keystore::{json_keystore::Kdf, Keystore, KeystoreBuilder},
+    password_vault::{default_vault_path, PasswordVault},
     validator_definitions::{SigningDefinition, ValidatorDefinition, ValidatorDefinitions},
     ZeroizeString,
 };
+use bls::{combine_signature_shares, Hash256, SecretKeyShare};
+use eth2_wallet::Wallet;
 use std::env;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, Write};
@@ -25,8 +43,11 @@ use std::path::{Path, PathBuf};
 use std::process::{Command, Output, Stdio};
 use std::str::from_utf8;
 use tempfile::{tempdir, TempDir};
-use types::Keypair;
-use validator_dir::ValidatorDir;
+use types::{EthSpec, Keypair, MainnetEthSpec};
+use validator_dir::{
+    deposit_data_json::read_deposit_data_json, ValidatorDir, VOTING_KEYSTORE_FILE,
+    WITHDRAWAL_KEYSTORE_FILE,
+};
 
 // TODO: create tests for the `lighthouse account validator deposit` command. This involves getting
 // access to an IPC endpoint during testing or adding support for deposit submission via HTTP and
@@ -202,6 +223,171 @@ fn wallet_create_and_list() {
     assert_eq!(list_wallets(wally.base_dir()).len(), 2);
 }
 
+#[test]
+fn wallet_change_password_export_and_delete() {
+    const NEW_PASSWORD: &str = "a much longer new password";
+
+    let base_temp_dir = tempdir().unwrap();
+    let base_dir: PathBuf = base_temp_dir.path().into();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let output_dir = tempdir().unwrap();
+
+    let wally = TestWallet::new(&base_dir, "wally");
+    wally.create_expect_success();
+    let wally2 = TestWallet::new(&base_dir, "wally2");
+    wally2.create_expect_success();
+
+    /*
+     * Change the password of a wallet.
+     */
+
+    let new_password_path = output_dir.path().join("new.pass");
+    fs::write(&new_password_path, NEW_PASSWORD).unwrap();
+
+    output_result(
+        wallet_cmd()
+            .arg(format!("--{}", BASE_DIR_FLAG))
+            .arg(base_dir.as_os_str())
+            .arg(CHANGE_PASSWORD_CMD)
+            .arg(format!("--{}", NAME_FLAG))
+            .arg(&wally.name)
+            .arg(format!("--{}", PASSWORD_FLAG))
+            .arg(wally.password_path().as_os_str())
+            .arg(format!("--{}", change_password::NEW_PASSWORD_FLAG))
+            .arg(new_password_path.as_os_str()),
+    )
+    .unwrap();
+
+    /*
+     * Export the wallet.
+     */
+
+    let export_path = output_dir.path().join("wally.json");
+    let mut export_cmd = wallet_cmd();
+    export_cmd
+        .arg(format!("--{}", BASE_DIR_FLAG))
+        .arg(base_dir.as_os_str())
+        .arg(EXPORT_CMD)
+        .arg(format!("--{}", NAME_FLAG))
+        .arg(&wally.name)
+        .arg(format!("--{}", export::OUTPUT_FLAG))
+        .arg(export_path.as_os_str());
+    output_result(&mut export_cmd).unwrap();
+
+    let exported = Wallet::from_json_str(&fs::read_to_string(&export_path).unwrap()).unwrap();
+    assert_eq!(exported.name(), wally.name);
+    exported
+        .decrypt_seed(NEW_PASSWORD.as_bytes())
+        .expect("exported wallet should use the new password");
+    exported
+        .decrypt_seed(&fs::read(wally.password_path()).unwrap())
+        .expect_err("exported wallet should not use the old password");
+
+    // The export must not overwrite an existing file.
+    output_result(&mut export_cmd).unwrap_err();
+
+    /*
+     * Delete the wallets.
+     */
+
+    let delete_cmd = |wallet: &TestWallet, password_path: &Path| {
+        let mut cmd = wallet_cmd();
+        cmd.arg(format!("--{}", BASE_DIR_FLAG))
+            .arg(base_dir.as_os_str())
+            .arg(DELETE_CMD)
+            .arg(format!("--{}", STDIN_INPUTS_FLAG)) // Using tty does not work well with tests.
+            .arg(format!("--{}", NAME_FLAG))
+            .arg(&wallet.name)
+            .arg(format!("--{}", PASSWORD_FLAG))
+            .arg(password_path.as_os_str())
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.path().as_os_str());
+        cmd
+    };
+
+    // A wallet that has derived validators in the validator dir cannot be deleted.
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wally2);
+    validator.create_expect_success(COUNT_FLAG, 1, false);
+    let err = output_result(&mut delete_cmd(
+        &validator.wallet,
+        &validator.wallet.password_path(),
+    ))
+    .unwrap_err();
+    assert!(err.contains("Refusing to delete"));
+
+    let mut child = delete_cmd(&wally, &new_password_path)
+        .stderr(Stdio::piped())
+        .stdin(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stderr = child.stderr.as_mut().map(BufReader::new).unwrap().lines();
+    let stdin = child.stdin.as_mut().unwrap();
+
+    loop {
+        if stderr.next().unwrap().unwrap() == delete::CONFIRMATION_PROMPT {
+            break;
+        }
+    }
+
+    stdin.write(format!("{}\n", wally.name).as_bytes()).unwrap();
+
+    assert!(child.wait().unwrap().success());
+    assert_eq!(list_wallets(&base_dir), vec![validator.wallet.name.clone()]);
+}
+
+#[test]
+fn wallet_show_mnemonic() {
+    let base_temp_dir = tempdir().unwrap();
+    let base_dir: PathBuf = base_temp_dir.path().into();
+
+    let wally = TestWallet::new(&base_dir, "wally");
+    wally.create_expect_success();
+    let phrase = fs::read_to_string(wally.mnemonic_path()).unwrap();
+
+    let run_show_mnemonic = |confirmation: &str| {
+        let mut child = wallet_cmd()
+            .arg(format!("--{}", BASE_DIR_FLAG))
+            .arg(base_dir.as_os_str())
+            .arg(SHOW_MNEMONIC_CMD)
+            .arg(format!("--{}", STDIN_INPUTS_FLAG)) // Using tty does not work well with tests.
+            .arg(format!("--{}", NAME_FLAG))
+            .arg(&wally.name)
+            .arg(format!("--{}", PASSWORD_FLAG))
+            .arg(wally.password_path().as_os_str())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        // Answer the confirmation prompt, then the prompt to clear the terminal.
+        child
+            .stdin
+            .as_mut()
+            .unwrap()
+            .write_all(format!("{}\n\n", confirmation).as_bytes())
+            .unwrap();
+
+        child.wait_with_output().unwrap()
+    };
+
+    // The mnemonic must not be shown without confirmation.
+    let output = run_show_mnemonic("no");
+    assert!(!output.status.success());
+    assert!(!from_utf8(&output.stdout).unwrap().contains(&phrase));
+
+    let output = run_show_mnemonic(show_mnemonic::CONFIRMATION);
+    assert!(output.status.success());
+    let stdout = from_utf8(&output.stdout).unwrap();
+    assert!(stdout.contains(&phrase), "should show the mnemonic");
+    assert!(
+        stdout.ends_with(show_mnemonic::CLEAR_TERMINAL),
+        "should clear the terminal after the mnemonic"
+    );
+}
+
 /// Returns the `lighthouse account validator` command.
 fn validator_cmd() -> Command {
     let mut cmd = account_cmd();
@@ -375,6 +561,45 @@ fn validator_create() {
     assert_eq!(dir_child_count(validator_dir.path()), 6);
 }
 
+#[test]
+fn validator_export_deposit_data() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let output_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let deposits = validator
+        .create_expect_success(COUNT_FLAG, 2, false)
+        .into_iter()
+        .map(|dir| dir.eth1_deposit_data().unwrap().unwrap().deposit_data)
+        .collect::<Vec<_>>();
+
+    let output = output_dir.path().join("deposit_data.json");
+    let mut cmd = validator_cmd();
+    cmd.arg(EXPORT_DEPOSIT_DATA_CMD)
+        .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+        .arg(validator_dir.path().as_os_str())
+        .arg(format!("--{}", export_deposit_data::VALIDATOR_FLAG))
+        .arg("all")
+        .arg(format!("--{}", export_deposit_data::OUTPUT_FLAG))
+        .arg(output.as_os_str());
+    output_result(&mut cmd).unwrap();
+
+    let exported = read_deposit_data_json(File::open(&output).unwrap()).unwrap();
+    assert_eq!(exported.len(), deposits.len());
+    for deposit in &exported {
+        assert!(deposits.contains(&deposit.deposit_data()));
+        deposit.verify(&MainnetEthSpec::default_spec()).unwrap();
+    }
+
+    // The output file must not be overwritten.
+    output_result(&mut cmd).unwrap_err();
+}
+
 /// Returns the `lighthouse account validator import` command.
 fn validator_import_cmd() -> Command {
     let mut cmd = validator_cmd();
@@ -468,3 +693,494 @@ fn validator_import_launchpad() {
         "validator defs file should be accurate"
     );
 }
+
+/// Returns the `lighthouse account validator keystore rekey` command.
+fn validator_rekey_cmd() -> Command {
+    let mut cmd = validator_cmd();
+    cmd.arg(KEYSTORE_CMD).arg(REKEY_CMD);
+    cmd
+}
+
+#[test]
+fn validator_keystore_rekey() {
+    const NEW_PASSWORD: &str = "a new password";
+    const IMPORTED_PASSWORD: &str = "cats";
+
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let password_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    /*
+     * Rekey a validator created by Lighthouse, with a password file in the secrets dir.
+     */
+
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let keystore_path = validator
+        .create_expect_success(COUNT_FLAG, 1, false)
+        .pop()
+        .unwrap()
+        .dir()
+        .join(VOTING_KEYSTORE_FILE);
+    let keystore = Keystore::from_json_file(&keystore_path).unwrap();
+    let pubkey = format!("0x{}", keystore.pubkey());
+
+    let new_password_path = password_dir.path().join("new.pass");
+    fs::write(&new_password_path, format!("{}\n", NEW_PASSWORD)).unwrap();
+
+    output_result(
+        validator_rekey_cmd()
+            .arg(format!("--{}", rekey::PUBKEY_FLAG))
+            .arg(&pubkey)
+            .arg(format!("--{}", rekey::NEW_PASSWORD_FLAG))
+            .arg(new_password_path.as_os_str())
+            .arg(format!("--{}", rekey::KDF_FLAG))
+            .arg(rekey::PBKDF2_KDF)
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.path().as_os_str())
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(secrets_dir.path().as_os_str()),
+    )
+    .unwrap();
+
+    let rekeyed = Keystore::from_json_file(&keystore_path).unwrap();
+    assert_eq!(rekeyed.uuid(), keystore.uuid());
+    assert_eq!(rekeyed.path(), keystore.path());
+    assert_eq!(rekeyed.pubkey(), keystore.pubkey());
+    assert!(matches!(rekeyed.kdf(), Kdf::Pbkdf2(_)));
+    rekeyed.decrypt_keypair(NEW_PASSWORD.as_bytes()).unwrap();
+    assert_eq!(
+        fs::read(secrets_dir.path().join(&pubkey)).unwrap(),
+        NEW_PASSWORD.as_bytes(),
+        "password file should be updated"
+    );
+    assert!(
+        !keystore_path
+            .with_file_name(format!("{}.lock", VOTING_KEYSTORE_FILE))
+            .exists(),
+        "lockfile should be removed"
+    );
+
+    /*
+     * Rekey an imported validator, with a password in the validator definitions.
+     */
+
+    let keypair = Keypair::random();
+    let keystore = KeystoreBuilder::new(&keypair, IMPORTED_PASSWORD.as_bytes(), "".into())
+        .unwrap()
+        .build()
+        .unwrap();
+    let pubkey = format!("0x{}", keystore.pubkey());
+    let keystore_dir = validator_dir.path().join(&pubkey);
+    fs::create_dir_all(&keystore_dir).unwrap();
+    let keystore_path = keystore_dir.join("keystore-m_12381_3600_0_0_0-1595406747.json");
+    File::create(&keystore_path)
+        .map(|mut file| keystore.to_json_writer(&mut file).unwrap())
+        .unwrap();
+
+    let mut defs = ValidatorDefinitions::open_or_create(&validator_dir).unwrap();
+    defs.push(
+        ValidatorDefinition::new_keystore_with_password(
+            &keystore_path,
+            Some(ZeroizeString::from(IMPORTED_PASSWORD.to_string())),
+        )
+        .unwrap(),
+    );
+    defs.save(&validator_dir).unwrap();
+
+    output_result(
+        validator_rekey_cmd()
+            .arg(format!("--{}", rekey::PUBKEY_FLAG))
+            .arg(&pubkey)
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.path().as_os_str())
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(secrets_dir.path().as_os_str()),
+    )
+    .unwrap();
+
+    let defs = ValidatorDefinitions::open(&validator_dir).unwrap();
+    let password = match &defs.as_slice()[0].signing_definition {
+        SigningDefinition::LocalKeystore {
+            voting_keystore_password: Some(password),
+            ..
+        } => password.clone(),
+        _ => panic!("definition should have a password"),
+    };
+    assert!(
+        password != ZeroizeString::from(IMPORTED_PASSWORD.to_string()),
+        "a random password should be generated"
+    );
+
+    let rekeyed = Keystore::from_json_file(&keystore_path).unwrap();
+    assert_eq!(rekeyed.uuid(), keystore.uuid());
+    assert!(matches!(rekeyed.kdf(), Kdf::Scrypt(_)));
+    assert_eq!(
+        rekeyed.decrypt_keypair(password.as_ref()).unwrap().pk,
+        keypair.pk
+    );
+
+    // An unknown validator cannot be rekeyed.
+    output_result(
+        validator_rekey_cmd()
+            .arg(format!("--{}", rekey::PUBKEY_FLAG))
+            .arg(Keypair::random().pk.to_hex_string())
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.path().as_os_str())
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(secrets_dir.path().as_os_str()),
+    )
+    .unwrap_err();
+}
+
+/// Returns the `lighthouse account validator split-key` command.
+fn validator_split_key_cmd() -> Command {
+    let mut cmd = validator_cmd();
+    cmd.arg(SPLIT_KEY_CMD);
+    cmd
+}
+
+#[test]
+fn validator_split_key() {
+    const PASSWORD: &str = "cats";
+
+    let keystore_dir = tempdir().unwrap();
+    let output_dir = tempdir().unwrap();
+
+    let keypair = Keypair::random();
+    let keystore = KeystoreBuilder::new(&keypair, PASSWORD.as_bytes(), "".into())
+        .unwrap()
+        .build()
+        .unwrap();
+    let keystore_path = keystore_dir.path().join("keystore.json");
+    File::create(&keystore_path)
+        .map(|mut file| keystore.to_json_writer(&mut file).unwrap())
+        .unwrap();
+    let password_path = keystore_dir.path().join("keystore.pass");
+    fs::write(&password_path, format!("{}\n", PASSWORD)).unwrap();
+
+    let split = |threshold: usize, shares: usize| {
+        output_result(
+            validator_split_key_cmd()
+                .arg(format!("--{}", split_key::KEYSTORE_FLAG))
+                .arg(keystore_path.as_os_str())
+                .arg(format!("--{}", split_key::PASSWORD_FLAG))
+                .arg(password_path.as_os_str())
+                .arg(format!("--{}", split_key::THRESHOLD_FLAG))
+                .arg(threshold.to_string())
+                .arg(format!("--{}", split_key::SHARES_FLAG))
+                .arg(shares.to_string())
+                .arg(format!("--{}", split_key::OUTPUT_DIR_FLAG))
+                .arg(output_dir.path().as_os_str()),
+        )
+    };
+
+    split(4, 3).unwrap_err();
+    assert_eq!(
+        dir_child_count(output_dir.path()),
+        0,
+        "should not create shares with an invalid threshold"
+    );
+
+    split(2, 3).unwrap();
+    assert_eq!(dir_child_count(output_dir.path()), 3);
+
+    let shares = (1..=3)
+        .map(|index| {
+            let dir = output_dir.path().join(split_key::share_dir_name(index));
+            let share_keystore =
+                Keystore::from_json_file(dir.join(split_key::share_keystore_file_name(index)))
+                    .unwrap();
+            let share_password =
+                fs::read(dir.join(split_key::share_password_file_name(index))).unwrap();
+            assert!(share_keystore.description().is_some());
+
+            let share_keypair = share_keystore.decrypt_keypair(&share_password).unwrap();
+            assert!(
+                share_keypair.pk != keypair.pk,
+                "shares should not contain the original key"
+            );
+
+            let definition = split_key::ShareDefinition::from_json_file(
+                dir.join(split_key::SHARE_DEFINITION_FILE),
+            )
+            .unwrap();
+            assert_eq!(
+                definition,
+                split_key::ShareDefinition {
+                    index,
+                    threshold: 2,
+                    share_count: 3,
+                    share_pubkey: share_keypair.pk.clone(),
+                    pubkey: keypair.pk.clone(),
+                }
+            );
+
+            SecretKeyShare::from_components(definition.index, share_keypair.sk)
+        })
+        .collect::<Vec<_>>();
+
+    let msg = Hash256::from_low_u64_be(1337);
+    let signature = combine_signature_shares(&[shares[0].sign(msg), shares[2].sign(msg)]).unwrap();
+    assert!(signature.verify(&keypair.pk, msg));
+    assert_eq!(signature, keypair.sk.sign(msg));
+
+    let signature = combine_signature_shares(&[shares[1].sign(msg)]).unwrap();
+    assert!(
+        !signature.verify(&keypair.pk, msg),
+        "a single share should not produce a valid signature"
+    );
+
+    split(2, 3).unwrap_err();
+}
+
+/// Returns the `lighthouse account validator vault add` command.
+fn validator_vault_add_cmd() -> Command {
+    let mut cmd = validator_cmd();
+    cmd.arg(VAULT_CMD).arg(ADD_CMD);
+    cmd
+}
+
+#[test]
+fn validator_vault_add() {
+    const PASSPHRASE: &str = "a long vault passphrase";
+
+    let keystore_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+
+    let passphrase_path = keystore_dir.path().join("vault.pass");
+    fs::write(&passphrase_path, format!("{}\n", PASSPHRASE)).unwrap();
+
+    let create_keystore = |name: &str, password: &str| {
+        let keypair = Keypair::random();
+        let keystore = KeystoreBuilder::new(&keypair, password.as_bytes(), "".into())
+            .unwrap()
+            .build()
+            .unwrap();
+        let keystore_path = keystore_dir.path().join(format!("{}.json", name));
+        File::create(&keystore_path)
+            .map(|mut file| keystore.to_json_writer(&mut file).unwrap())
+            .unwrap();
+        let password_path = keystore_dir.path().join(format!("{}.pass", name));
+        fs::write(&password_path, format!("{}\n", password)).unwrap();
+        (keypair, keystore_path, password_path)
+    };
+
+    let add = |keystore_path: &Path, password_path: &Path, delete: bool| {
+        let mut cmd = validator_vault_add_cmd();
+        cmd.arg(format!("--{}", add::KEYSTORE_FLAG))
+            .arg(keystore_path.as_os_str())
+            .arg(format!("--{}", add::PASSWORD_FLAG))
+            .arg(password_path.as_os_str())
+            .arg(format!("--{}", add::PASSPHRASE_FLAG))
+            .arg(passphrase_path.as_os_str())
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(secrets_dir.path().as_os_str());
+        if delete {
+            cmd.arg(format!("--{}", add::DELETE_PASSWORD_FLAG));
+        }
+        output_result(&mut cmd)
+    };
+
+    let (a, a_keystore_path, a_password_path) = create_keystore("a", "cats");
+    let (b, b_keystore_path, b_password_path) = create_keystore("b", "dogs");
+
+    add(&a_keystore_path, &b_password_path, false).unwrap_err();
+    assert_eq!(
+        dir_child_count(secrets_dir.path()),
+        0,
+        "should not create a vault with an incorrect password"
+    );
+
+    add(&a_keystore_path, &a_password_path, false).unwrap();
+    assert!(a_password_path.exists());
+    add(&b_keystore_path, &b_password_path, true).unwrap();
+    assert!(!b_password_path.exists(), "password file should be deleted");
+
+    let vault_path = default_vault_path(secrets_dir.path());
+    let vault = PasswordVault::open(&vault_path, PASSPHRASE.as_bytes()).unwrap();
+    assert_eq!(vault.get(&a.pk).unwrap().unwrap().as_bytes(), b"cats");
+    assert_eq!(vault.get(&b.pk).unwrap().unwrap().as_bytes(), b"dogs");
+    assert!(
+        !fs::read_to_string(&vault_path).unwrap().contains("cats"),
+        "passwords should not be stored in plain-text"
+    );
+
+    fs::write(&passphrase_path, "an incorrect passphrase").unwrap();
+    add(&a_keystore_path, &a_password_path, false).unwrap_err();
+}
+
+/// Returns the `lighthouse account validator recover` command.
+fn validator_recover_cmd() -> Command {
+    let mut cmd = validator_cmd();
+    cmd.arg(RECOVER_CMD);
+    cmd
+}
+
+#[test]
+fn validator_recover_by_path_and_pubkeys() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let recovered_dir = tempdir().unwrap();
+    let recovered_secrets_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let pubkeys = validator
+        .create_expect_success(COUNT_FLAG, 3, false)
+        .iter()
+        .map(|dir| dir.dir().file_name().unwrap().to_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+
+    let recover = |args: &[&str]| {
+        let mut cmd = validator_recover_cmd();
+        cmd.arg(format!("--{}", recover::MNEMONIC_FLAG))
+            .arg(validator.wallet.mnemonic_path().into_os_string())
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(recovered_dir.path().as_os_str())
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(recovered_secrets_dir.path().as_os_str())
+            .args(args);
+        output_result(&mut cmd)
+    };
+    let keystore_path = |pubkey: &str, file: &str| {
+        Keystore::from_json_file(recovered_dir.path().join(pubkey).join(file))
+            .unwrap()
+            .path()
+            .unwrap()
+    };
+
+    /*
+     * Find a validator by its public key.
+     */
+
+    recover(&[
+        &format!("--{}", recover::PUBKEYS_FLAG),
+        &pubkeys[2],
+        &format!("--{}", COUNT_FLAG),
+        "4",
+    ])
+    .unwrap();
+    assert_eq!(dir_child_count(recovered_dir.path()), 1);
+    assert_eq!(
+        keystore_path(&pubkeys[2], VOTING_KEYSTORE_FILE),
+        "m/12381/3600/2/0/0"
+    );
+
+    let unknown_pubkey = Keypair::random().pk.to_hex_string();
+    let err = recover(&[
+        &format!("--{}", recover::PUBKEYS_FLAG),
+        &format!("{},{}", pubkeys[1], unknown_pubkey),
+        &format!("--{}", COUNT_FLAG),
+        "4",
+    ])
+    .unwrap_err();
+    assert!(
+        err.contains(&unknown_pubkey),
+        "should name the missing key: {}",
+        err
+    );
+    assert_eq!(
+        dir_child_count(recovered_dir.path()),
+        1,
+        "no keys should be recovered if any are missing"
+    );
+
+    /*
+     * Recover a validator from an arbitrary path.
+     */
+
+    let output = recover(&[
+        &format!("--{}", recover::PATH_FLAG),
+        "m/12381/3600/5/1/0",
+        &format!("--{}", STORE_WITHDRAW_FLAG),
+    ])
+    .unwrap();
+    let stdout = from_utf8(&output.stdout).unwrap();
+    let pubkey = stdout.trim().rsplit('\t').next().unwrap();
+    assert_eq!(
+        keystore_path(pubkey, VOTING_KEYSTORE_FILE),
+        "m/12381/3600/5/1/0"
+    );
+    assert_eq!(
+        keystore_path(pubkey, WITHDRAWAL_KEYSTORE_FILE),
+        "m/12381/3600/5/1"
+    );
+
+    recover(&[&format!("--{}", recover::PATH_FLAG), "m/12381/3600/5"]).unwrap_err();
+    recover(&[
+        &format!("--{}", recover::PATH_FLAG),
+        "m/12381/3600/5/1/0",
+        &format!("--{}", COUNT_FLAG),
+        "2",
+    ])
+    .unwrap_err();
+}
+
+#[test]
+fn validator_recover_eth1_withdrawal_address() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let recovered_dir = tempdir().unwrap();
+    let recovered_secrets_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let created = validator.create_expect_success(COUNT_FLAG, 2, false);
+
+    let address = "0x0000000000000000000000000000000000000001";
+    let deposit_gwei = 1_000_000_000;
+
+    let mut cmd = validator_recover_cmd();
+    cmd.arg(format!("--{}", recover::MNEMONIC_FLAG))
+        .arg(validator.wallet.mnemonic_path().into_os_string())
+        .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+        .arg(recovered_dir.path().as_os_str())
+        .arg(format!("--{}", SECRETS_DIR_FLAG))
+        .arg(recovered_secrets_dir.path().as_os_str())
+        .arg(format!("--{}", COUNT_FLAG))
+        .arg("2")
+        .arg(format!("--{}", ETH1_WITHDRAWAL_ADDRESS_FLAG))
+        .arg(address)
+        .arg(format!("--{}", DEPOSIT_GWEI_FLAG))
+        .arg(deposit_gwei.to_string());
+    output_result(&mut cmd).unwrap();
+
+    assert_eq!(dir_child_count(recovered_dir.path()), created.len());
+
+    let spec = MainnetEthSpec::default_spec();
+    let mut expected_credentials = [0; 32];
+    expected_credentials[0] = spec.eth1_address_withdrawal_prefix_byte;
+    expected_credentials[31] = 1;
+
+    for created_dir in created {
+        let pubkey = created_dir.dir().file_name().unwrap();
+        let dir = ValidatorDir::open(recovered_dir.path().join(pubkey)).unwrap();
+
+        assert!(
+            !dir.dir().join(WITHDRAWAL_KEYSTORE_FILE).exists(),
+            "should not store withdrawal keystore"
+        );
+
+        let deposit_data = dir.eth1_deposit_data().unwrap().unwrap().deposit_data;
+        assert_eq!(
+            deposit_data.withdrawal_credentials,
+            Hash256::from(expected_credentials),
+            "should use eth1 address withdrawal credentials"
+        );
+        assert_eq!(deposit_data.amount, deposit_gwei);
+
+        dir.deposit_data_json(&spec)
+            .unwrap()
+            .unwrap()
+            .verify(&spec)
+            .unwrap();
+    }
+}
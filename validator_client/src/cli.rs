@@ -44,6 +44,44 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 Eth2 mainnet, this flag will likely be removed and its behaviour will become default."
             )
         )
+        .arg(
+            Arg::with_name("password-vault")
+            .long("password-vault")
+            .help(
+                "If present, unlock the password vault in the secrets-dir at startup and use it \
+                to unlock any keystores that do not have a password in validator_definitions.yml. \
+                The vault passphrase is read from --password-vault-passphrase-file, then the \
+                LIGHTHOUSE_PASSWORD_VAULT_PASSPHRASE environment variable, then the terminal."
+            )
+        )
+        .arg(
+            Arg::with_name("password-vault-passphrase-file")
+            .long("password-vault-passphrase-file")
+            .value_name("PASSPHRASE_PATH")
+            .help("A path to a file containing the passphrase of the password vault.")
+            .requires("password-vault")
+            .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("prompt-for-passwords")
+            .long("prompt-for-passwords")
+            .help(
+                "If present, prompt on the terminal for the password of any keystore that does not \
+                have a password in validator_definitions.yml, a password file or the password \
+                vault. Prompted passwords are never written to disk. Without this flag, such \
+                keystores cause the validator client to exit with an error."
+            )
+        )
+        .arg(
+            Arg::with_name("reuse-prompted-password")
+            .long("reuse-prompted-password")
+            .help(
+                "If present, try each prompted password on the remaining keystores before \
+                prompting again, so that keystores which share a password only require it to be \
+                entered once."
+            )
+            .requires("prompt-for-passwords")
+        )
         .arg(
             Arg::with_name("disable-auto-discover")
             .long("disable-auto-discover")
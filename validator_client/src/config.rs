@@ -28,6 +28,14 @@ pub struct Config {
     pub strict_lockfiles: bool,
     /// If true, don't scan the validators dir for new keystores.
     pub disable_auto_discover: bool,
+    /// If true, unlock the password vault in the `secrets_dir` at startup.
+    pub password_vault: bool,
+    /// The path to a file containing the password vault passphrase.
+    pub password_vault_passphrase_path: Option<PathBuf>,
+    /// If true, prompt for the passwords of keystores that have no other password available.
+    pub prompt_for_passwords: bool,
+    /// If true, try each prompted password on the other keystores before prompting again.
+    pub reuse_prompted_password: bool,
     /// Graffiti to be inserted everytime we create a block.
     pub graffiti: Option<Graffiti>,
 }
@@ -48,6 +56,10 @@ impl Default for Config {
             allow_unsynced_beacon_node: false,
             strict_lockfiles: false,
             disable_auto_discover: false,
+            password_vault: false,
+            password_vault_passphrase_path: None,
+            prompt_for_passwords: false,
+            reuse_prompted_password: false,
             graffiti: None,
         }
     }
@@ -79,6 +91,11 @@ impl Config {
         config.allow_unsynced_beacon_node = cli_args.is_present("allow-unsynced");
         config.strict_lockfiles = cli_args.is_present("strict-lockfiles");
         config.disable_auto_discover = cli_args.is_present("disable-auto-discover");
+        config.password_vault = cli_args.is_present("password-vault");
+        config.password_vault_passphrase_path =
+            parse_optional(cli_args, "password-vault-passphrase-file")?;
+        config.prompt_for_passwords = cli_args.is_present("prompt-for-passwords");
+        config.reuse_prompted_password = cli_args.is_present("reuse-prompted-password");
 
         if let Some(secrets_dir) = parse_optional(cli_args, "secrets-dir")? {
             config.secrets_dir = secrets_dir;
@@ -7,21 +7,29 @@
 //! validators are managed by this validator client.
 
 use account_utils::{
+    password_vault::{self, PasswordVault},
     read_password, read_password_from_user,
     validator_definitions::{
         self, SigningDefinition, ValidatorDefinition, ValidatorDefinitions, CONFIG_FILENAME,
     },
+    ZeroizeString,
 };
 use eth2_keystore::Keystore;
 use slog::{error, info, warn, Logger};
 use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use types::{Keypair, PublicKey};
 
 // Use TTY instead of stdin to capture passwords from users.
-const USE_STDIN: bool = false;
+pub const USE_STDIN: bool = false;
+/// The terminal which is used to prompt for passwords when `USE_STDIN == false`.
+const TTY_PATH: &str = "/dev/tty";
+/// Explains how to provide a password for a keystore when `Error::PasswordUnknown` is raised.
+pub const PASSWORD_UNKNOWN_HINT: &str = "Add the password to the validator definitions file, \
+    a password file or the password vault (--password-vault), or start the validator client with \
+    --prompt-for-passwords to enter it on the terminal";
 
 #[derive(Debug)]
 pub enum Error {
@@ -50,10 +58,32 @@ pub enum Error {
     UnableToSaveDefinitions(validator_definitions::Error),
     /// It is not legal to try and initialize a disabled validator definition.
     UnableToInitializeDisabledValidator,
-    /// It is not legal to try and initialize a disabled validator definition.
+    /// There is no password for the keystore at the given path and prompting for passwords is
+    /// disabled. See `PASSWORD_UNKNOWN_HINT`.
     PasswordUnknown(PathBuf),
+    /// A password is required for the keystore at the given path, however there is no terminal
+    /// to prompt on.
+    UnableToOpenTerminal {
+        keystore_path: PathBuf,
+        error: io::Error,
+    },
     /// There was an error reading from stdin.
     UnableToReadPasswordFromUser(String),
+    /// There was an error reading the keystore password from the password vault.
+    UnableToReadPasswordFromVault(password_vault::Error),
+}
+
+/// Determines whether the user is prompted for the password of a keystore when no password is
+/// available in the validator definition, the password vault or a password file.
+///
+/// Prompted passwords are never written to disk.
+pub enum PasswordPrompt {
+    /// Do not prompt, fail to initialize the validator instead.
+    Disabled,
+    /// Prompt for the password of each keystore.
+    EachKeystore,
+    /// Prompt for a password and try it on each subsequent keystore before prompting again.
+    SharedPassword { password: Option<ZeroizeString> },
 }
 
 /// A method used by a validator to sign messages.
@@ -78,8 +108,11 @@ pub struct InitializedValidator {
 impl InitializedValidator {
     /// Instantiate `self` from a `ValidatorDefinition`.
     ///
-    /// If `stdin.is_some()` any missing passwords will result in a prompt requesting input on
-    /// stdin (prompts published to stderr).
+    /// If the definition does not contain a password, the password is read from `password_vault`
+    /// (if supplied) in favour of the password path in the definition.
+    ///
+    /// If no password is available, the user is prompted for one according to `password_prompt`
+    /// (prompts published to stderr).
     ///
     /// ## Errors
     ///
@@ -87,6 +120,8 @@ impl InitializedValidator {
     pub fn from_definition(
         def: ValidatorDefinition,
         strict_lockfiles: bool,
+        password_vault: Option<&PasswordVault>,
+        password_prompt: &mut PasswordPrompt,
         log: &Logger,
     ) -> Result<Self, Error> {
         if !def.enabled {
@@ -106,14 +141,28 @@ impl InitializedValidator {
                 let voting_keystore = Keystore::from_json_reader(keystore_file)
                     .map_err(Error::UnableToParseVotingKeystore)?;
 
-                let voting_keypair = match (voting_keystore_password_path, voting_keystore_password)
-                {
+                let vault_password = match (&voting_keystore_password, password_vault) {
+                    (None, Some(vault)) => vault
+                        .get(&def.voting_public_key)
+                        .map_err(Error::UnableToReadPasswordFromVault)?,
+                    _ => None,
+                };
+
+                let voting_keypair = match (
+                    voting_keystore_password_path,
+                    voting_keystore_password,
+                    vault_password,
+                ) {
                     // If the password is supplied, use it and ignore the path (if supplied).
-                    (_, Some(password)) => voting_keystore
+                    (_, Some(password), _) => voting_keystore
                         .decrypt_keypair(password.as_ref())
                         .map_err(Error::UnableToDecryptKeystore)?,
+                    // If the password is in the vault, use it and ignore the path (if supplied).
+                    (_, None, Some(password)) => voting_keystore
+                        .decrypt_keypair(password.as_bytes())
+                        .map_err(Error::UnableToDecryptKeystore)?,
                     // If only the path is supplied, use the path.
-                    (Some(path), None) => {
+                    (Some(path), None, None) => {
                         let password = read_password(path)
                             .map_err(Error::UnableToReadVotingKeystorePassword)?;
 
@@ -122,9 +171,12 @@ impl InitializedValidator {
                             .map_err(Error::UnableToDecryptKeystore)?
                     }
                     // If there is no password available, maybe prompt for a password.
-                    (None, None) => {
-                        unlock_keystore_via_stdin_password(&voting_keystore, &voting_keystore_path)?
-                    }
+                    (None, None, None) => unlock_keystore_via_stdin_password(
+                        &voting_keystore,
+                        &voting_keystore_path,
+                        password_prompt,
+                        Path::new(TTY_PATH),
+                    )?,
                 };
 
                 if voting_keypair.pk != def.voting_public_key {
@@ -220,11 +272,45 @@ impl Drop for InitializedValidator {
     }
 }
 
-/// Try to unlock `keystore` at `keystore_path` by prompting the user via `stdin`.
+/// Try to unlock `keystore` at `keystore_path` by prompting the user via `stdin`, as permitted by
+/// `password_prompt`.
+///
+/// A shared password from a previous prompt is tried before prompting. Unless `USE_STDIN == true`,
+/// the prompt fails if the terminal at `tty_path` cannot be opened.
 fn unlock_keystore_via_stdin_password(
     keystore: &Keystore,
     keystore_path: &PathBuf,
+    password_prompt: &mut PasswordPrompt,
+    tty_path: &Path,
 ) -> Result<Keypair, Error> {
+    match password_prompt {
+        PasswordPrompt::Disabled => return Err(Error::PasswordUnknown(keystore_path.clone())),
+        PasswordPrompt::EachKeystore => (),
+        PasswordPrompt::SharedPassword { password } => {
+            if let Some(password) = password {
+                match keystore.decrypt_keypair(password.as_ref()) {
+                    Ok(keypair) => return Ok(keypair),
+                    // The shared password is not for this keystore, prompt instead.
+                    Err(eth2_keystore::Error::InvalidPassword) => (),
+                    Err(e) => return Err(Error::UnableToDecryptKeystore(e)),
+                }
+            }
+        }
+    }
+
+    // Check for a terminal before printing the prompt, so that a validator client running as a
+    // service fails with a clear error rather than waiting for input that will never arrive.
+    if !USE_STDIN {
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(tty_path)
+            .map_err(|error| Error::UnableToOpenTerminal {
+                keystore_path: keystore_path.clone(),
+                error,
+            })?;
+    }
+
     eprintln!("");
     eprintln!(
         "The {} file does not contain either of the following fields for {:?}:",
@@ -240,6 +326,12 @@ fn unlock_keystore_via_stdin_password(
                             will be raised next time the validator is started.",
         CONFIG_FILENAME
     );
+    if let PasswordPrompt::SharedPassword { .. } = password_prompt {
+        eprintln!("");
+        eprintln!(
+            "The password will also be tried on any other keystores that are missing a password."
+        );
+    }
     eprintln!("");
     eprintln!("Enter password (or press Ctrl+c to exit):");
 
@@ -250,7 +342,15 @@ fn unlock_keystore_via_stdin_password(
         eprintln!("");
 
         match keystore.decrypt_keypair(password.as_ref()) {
-            Ok(keystore) => break Ok(keystore),
+            Ok(keystore) => {
+                if let PasswordPrompt::SharedPassword {
+                    password: shared_password,
+                } = password_prompt
+                {
+                    *shared_password = Some(password);
+                }
+                break Ok(keystore);
+            }
             Err(eth2_keystore::Error::InvalidPassword) => {
                 eprintln!("Invalid password, try again (or press Ctrl+c to exit):");
             }
@@ -273,6 +373,10 @@ pub struct InitializedValidators {
     validators_dir: PathBuf,
     /// The canonical set of validators.
     validators: HashMap<PublicKey, InitializedValidator>,
+    /// An unlocked vault containing the passwords of voting keystores, if any.
+    password_vault: Option<PasswordVault>,
+    /// Determines whether the user is prompted for missing keystore passwords.
+    password_prompt: PasswordPrompt,
     /// For logging via `slog`.
     log: Logger,
 }
@@ -283,6 +387,8 @@ impl InitializedValidators {
         definitions: ValidatorDefinitions,
         validators_dir: PathBuf,
         strict_lockfiles: bool,
+        password_vault: Option<PasswordVault>,
+        password_prompt: PasswordPrompt,
         log: Logger,
     ) -> Result<Self, Error> {
         let mut this = Self {
@@ -290,9 +396,17 @@ impl InitializedValidators {
             validators_dir,
             definitions,
             validators: HashMap::default(),
+            password_vault,
+            password_prompt,
             log,
         };
         this.update_validators()?;
+
+        // Do not hold a shared password in memory once the validators have been initialized.
+        if let PasswordPrompt::SharedPassword { password } = &mut this.password_prompt {
+            *password = None;
+        }
+
         Ok(this)
     }
 
@@ -374,6 +488,8 @@ impl InitializedValidators {
                         match InitializedValidator::from_definition(
                             def.clone(),
                             self.strict_lockfiles,
+                            self.password_vault.as_ref(),
+                            &mut self.password_prompt,
                             &self.log,
                         ) {
                             Ok(init) => {
@@ -393,6 +509,14 @@ impl InitializedValidators {
                                     "validator" => format!("{:?}", def.voting_public_key)
                                 );
 
+                                if let Error::PasswordUnknown(_) = e {
+                                    error!(
+                                        self.log,
+                                        "No password available for keystore";
+                                        "hint" => PASSWORD_UNKNOWN_HINT,
+                                    );
+                                }
+
                                 // Exit on an invalid validator.
                                 return Err(e);
                             }
@@ -411,3 +535,146 @@ impl InitializedValidators {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth2_keystore::{
+        json_keystore::{Kdf, Pbkdf2, Prf},
+        KeystoreBuilder, DKLEN,
+    };
+    use slog::{o, Discard};
+    use tempdir::TempDir;
+
+    const PASSWORD: &str = "shared password";
+    const OTHER_PASSWORD: &str = "another password";
+    /// Tests must never block on a prompt, so they use a terminal which does not exist.
+    const MISSING_TTY_PATH: &str = "/nonexistent/tty";
+
+    /// A KDF which is cheap enough to run many times in tests.
+    fn insecure_kdf() -> Kdf {
+        Kdf::Pbkdf2(Pbkdf2 {
+            dklen: DKLEN,
+            c: 2,
+            prf: Prf::HmacSha256,
+            salt: vec![42; 32].into(),
+        })
+    }
+
+    fn keystore(password: &str) -> (Keypair, Keystore) {
+        let keypair = Keypair::random();
+        let keystore = KeystoreBuilder::new(&keypair, password.as_bytes(), "".into())
+            .unwrap()
+            .kdf(insecure_kdf())
+            .build()
+            .unwrap();
+        (keypair, keystore)
+    }
+
+    fn shared_password(password: &str) -> PasswordPrompt {
+        PasswordPrompt::SharedPassword {
+            password: Some(ZeroizeString::from(password.to_string())),
+        }
+    }
+
+    #[test]
+    fn disabled_prompt_is_password_unknown() {
+        let (_, keystore) = keystore(PASSWORD);
+        let path = PathBuf::from("keystore.json");
+
+        match unlock_keystore_via_stdin_password(
+            &keystore,
+            &path,
+            &mut PasswordPrompt::Disabled,
+            Path::new(MISSING_TTY_PATH),
+        ) {
+            Err(Error::PasswordUnknown(p)) => assert_eq!(p, path),
+            other => panic!("expected PasswordUnknown, got {:?}", other.map(|kp| kp.pk)),
+        }
+        assert!(PASSWORD_UNKNOWN_HINT.contains("--prompt-for-passwords"));
+        assert!(PASSWORD_UNKNOWN_HINT.contains("password vault"));
+    }
+
+    #[test]
+    fn missing_terminal_is_an_error() {
+        let (_, keystore) = keystore(PASSWORD);
+        let path = PathBuf::from("keystore.json");
+
+        for mut prompt in vec![
+            PasswordPrompt::EachKeystore,
+            PasswordPrompt::SharedPassword { password: None },
+        ] {
+            match unlock_keystore_via_stdin_password(
+                &keystore,
+                &path,
+                &mut prompt,
+                Path::new(MISSING_TTY_PATH),
+            ) {
+                Err(Error::UnableToOpenTerminal { keystore_path, .. }) => {
+                    assert_eq!(keystore_path, path)
+                }
+                other => panic!(
+                    "expected UnableToOpenTerminal, got {:?}",
+                    other.map(|kp| kp.pk)
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn shared_password_unlocks_keystore() {
+        let dir = TempDir::new("initialized_validators").unwrap();
+        let (keypair, keystore) = keystore(PASSWORD);
+        let keystore_path = dir.path().join("keystore.json");
+        keystore
+            .to_json_writer(File::create(&keystore_path).unwrap())
+            .unwrap();
+
+        let def = ValidatorDefinition::new_keystore_with_password(&keystore_path, None).unwrap();
+        let mut prompt = shared_password(PASSWORD);
+        let log = Logger::root(Discard, o!());
+
+        let validator =
+            InitializedValidator::from_definition(def, true, None, &mut prompt, &log).unwrap();
+
+        assert_eq!(validator.voting_public_key(), &keypair.pk);
+        match prompt {
+            PasswordPrompt::SharedPassword {
+                password: Some(password),
+            } => assert_eq!(password.as_ref(), PASSWORD.as_bytes()),
+            _ => panic!("shared password should be retained"),
+        }
+    }
+
+    #[test]
+    fn non_matching_shared_password_prompts() {
+        let (_, keystore) = keystore(PASSWORD);
+        let path = PathBuf::from("keystore.json");
+        let mut prompt = shared_password(OTHER_PASSWORD);
+
+        // The shared password is rejected, so the user must be prompted. There is no terminal in
+        // tests, so the prompt fails.
+        match unlock_keystore_via_stdin_password(
+            &keystore,
+            &path,
+            &mut prompt,
+            Path::new(MISSING_TTY_PATH),
+        ) {
+            Err(Error::UnableToOpenTerminal { keystore_path, .. }) => {
+                assert_eq!(keystore_path, path)
+            }
+            other => panic!(
+                "expected UnableToOpenTerminal, got {:?}",
+                other.map(|kp| kp.pk)
+            ),
+        }
+
+        // The shared password is kept for the remaining keystores.
+        match prompt {
+            PasswordPrompt::SharedPassword {
+                password: Some(password),
+            } => assert_eq!(password.as_ref(), OTHER_PASSWORD.as_bytes()),
+            _ => panic!("shared password should be retained"),
+        }
+    }
+}
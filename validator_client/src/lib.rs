@@ -12,7 +12,10 @@ mod validator_store;
 pub use cli::cli_app;
 pub use config::Config;
 
-use account_utils::validator_definitions::ValidatorDefinitions;
+use account_utils::{
+    password_vault::{default_vault_path, read_vault_passphrase, PasswordVault},
+    validator_definitions::ValidatorDefinitions,
+};
 use attestation_service::{AttestationService, AttestationServiceBuilder};
 use block_service::{BlockService, BlockServiceBuilder};
 use clap::ArgMatches;
@@ -20,7 +23,7 @@ use duties_service::{DutiesService, DutiesServiceBuilder};
 use environment::RuntimeContext;
 use fork_service::{ForkService, ForkServiceBuilder};
 use futures::channel::mpsc;
-use initialized_validators::InitializedValidators;
+use initialized_validators::{InitializedValidators, PasswordPrompt, USE_STDIN};
 use notifier::spawn_notifier;
 use remote_beacon_node::RemoteBeaconNode;
 use slog::{error, info, Logger};
@@ -87,10 +90,37 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             );
         }
 
+        let password_vault = if config.password_vault {
+            let vault_path = default_vault_path(&config.secrets_dir);
+            let passphrase =
+                read_vault_passphrase(config.password_vault_passphrase_path.as_deref(), USE_STDIN)?;
+            let vault = PasswordVault::open(&vault_path, passphrase.as_bytes()).map_err(|e| {
+                format!("Unable to unlock password vault {:?}: {:?}", vault_path, e)
+            })?;
+
+            info!(
+                log,
+                "Unlocked password vault";
+                "path" => format!("{:?}", vault_path),
+            );
+
+            Some(vault)
+        } else {
+            None
+        };
+
+        let password_prompt = match (config.prompt_for_passwords, config.reuse_prompted_password) {
+            (false, _) => PasswordPrompt::Disabled,
+            (true, false) => PasswordPrompt::EachKeystore,
+            (true, true) => PasswordPrompt::SharedPassword { password: None },
+        };
+
         let validators = InitializedValidators::from_definitions(
             validator_defs,
             config.data_dir.clone(),
             config.strict_lockfiles,
+            password_vault,
+            password_prompt,
             log.clone(),
         )
         .map_err(|e| format!("Unable to initialize validators: {:?}", e))?;